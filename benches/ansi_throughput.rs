@@ -0,0 +1,28 @@
+//! Parser throughput: a few MiB of representative output fed through a headless
+//! `TerminalRenderer` (VTE parser, ANSI handler and grid, no PTY)
+//! Run with `cargo bench --bench ansi_throughput`
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+use term39::sample_output::sample_output;
+use term39::terminal_renderer::TerminalRenderer;
+
+fn ansi_throughput(c: &mut Criterion) {
+    let data = sample_output(4 * 1024 * 1024);
+    let chunks: Vec<Vec<u8>> = data.chunks(4096).map(<[u8]>::to_vec).collect();
+
+    let mut group = c.benchmark_group("ansi");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    group.sample_size(20);
+    group.bench_function("feed_4mib_120x40", |b| {
+        b.iter(|| {
+            let mut renderer = TerminalRenderer::new(120, 40, 1000);
+            renderer.feed_output_chunks(black_box(&chunks));
+            renderer
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, ansi_throughput);
+criterion_main!(benches);
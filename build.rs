@@ -3,6 +3,12 @@ fn main() {
     // No build-time linking required - works for both native and cross-compilation
     println!("cargo:rerun-if-changed=build.rs");
 
+    // Record the git commit for --version-info (absent when building from a crates.io tarball)
+    if let Some(hash) = git_commit_hash() {
+        println!("cargo:rustc-env=TERM39_GIT_HASH={}", hash);
+        println!("cargo:rerun-if-changed=.git/HEAD");
+    }
+
     // Embed icon and metadata into Windows executable
     #[cfg(target_os = "windows")]
     {
@@ -23,3 +29,16 @@ fn main() {
         res.compile().expect("Failed to compile Windows resources");
     }
 }
+
+/// Short hash of the current git commit, if building from a git checkout
+fn git_commit_hash() -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let hash = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!hash.is_empty()).then_some(hash)
+}
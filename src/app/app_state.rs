@@ -1,18 +1,22 @@
-use super::config_manager::AppConfig;
+use super::config_manager::{AppConfig, SystemMenuCommand};
 use crate::input::keyboard_mode::{KeyboardMode, MovementState};
 use crate::lockscreen::{LockScreen, PinSetupDialog};
 use crate::rendering::Charset;
+use crate::term_emu::ForegroundSignal;
 use crate::ui::button::Button;
 use crate::ui::config_window::ConfigWindow;
 use crate::ui::context_menu::ContextMenu;
+use crate::ui::debug_overlay::DebugOverlay;
 use crate::ui::error_dialog::ErrorDialog;
 use crate::ui::info_window::InfoWindow;
 use crate::ui::prompt::Prompt;
+use crate::ui::screensaver::Screensaver;
 use crate::ui::slight_input::SlightInput;
 use crate::ui::toast::Toast;
 use crate::ui::ui_render::CalendarState;
 use crate::ui::widgets::TopBar;
-use std::time::Instant;
+use crate::window::animation::AnimationTracker;
+use std::time::{Duration, Instant};
 
 /// Direction for auto-scrolling during text selection
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -21,6 +25,75 @@ pub enum AutoScrollDirection {
     Down,
 }
 
+/// A window duplicate waiting for the "rerun command?" prompt to be answered
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DuplicateRequest {
+    /// Directory the new window starts in
+    pub working_dir: Option<String>,
+    /// Command to rerun (cleared when the user picks a plain shell)
+    pub command: Option<String>,
+}
+
+/// A signal for a window's foreground job waiting on its confirmation prompt (SIGKILL)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignalRequest {
+    pub window_id: u32,
+    pub signal: ForegroundSignal,
+}
+
+/// A window to kill and close, waiting on its confirmation prompt
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForceCloseRequest {
+    pub window_id: u32,
+}
+
+/// A launcher command the launcher doesn't know, waiting on its confirmation prompt
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LaunchRequest {
+    pub command: String,
+}
+
+/// Shell offered when new windows keep failing to start
+#[cfg(unix)]
+pub const FALLBACK_SHELL: &str = "/bin/sh";
+#[cfg(windows)]
+pub const FALLBACK_SHELL: &str = "cmd.exe";
+
+/// A window with FALLBACK_SHELL waiting on the repeated spawn failure prompt
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FallbackShellRequest {
+    /// Directory the failed window was to start in
+    pub working_dir: Option<String>,
+}
+
+/// How pasted text is sent (the paste, paste-literal and paste-and-run shortcuts)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PasteKind {
+    Normal,
+    Literal,
+    AndRun,
+}
+
+/// A paste into a window at a hidden prompt, waiting on its confirmation prompt
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PasteRequest {
+    pub window_id: u32,
+    pub text: String,
+    pub kind: PasteKind,
+}
+
+/// A request held until the user answers its confirmation prompt
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PendingConfirmation {
+    /// Duplicate a window (the prompt asks whether to rerun its command)
+    Duplicate(DuplicateRequest),
+    Signal(SignalRequest),
+    ForceClose(ForceCloseRequest),
+    Launch(LaunchRequest),
+    Paste(PasteRequest),
+    FallbackShell(FallbackShellRequest),
+}
+
 /// Centralizes all mutable application state
 pub struct AppState {
     // Dialog/Popup State
@@ -37,6 +110,11 @@ pub struct AppState {
     pub taskbar_menu: ContextMenu,
     pub taskbar_menu_window_id: Option<u32>,
     pub system_menu: ContextMenu,
+    pub desktop_menu: ContextMenu,
+    /// Launcher commands listed in the desktop menu (indexed by MenuAction::RunCommand)
+    pub desktop_menu_commands: Vec<String>,
+    /// Configured System menu entries (indexed by MenuAction::UserItem)
+    pub system_menu_items: Vec<SystemMenuCommand>,
 
     // Top Bar Buttons (legacy - will be replaced by TopBar)
     #[allow(dead_code)]
@@ -59,6 +137,7 @@ pub struct AppState {
     // Application Settings
     pub auto_tiling_enabled: bool,
     pub tint_terminal: bool,
+    pub tint_strength: f32,
 
     // Selection State
     pub selection_active: bool,
@@ -66,15 +145,47 @@ pub struct AppState {
     pub last_click_pos: Option<(u16, u16)>,
     pub click_count: u32,
 
+    // Last mouse position in buffer coordinates (None until the mouse is used)
+    pub pointer_position: Option<(u16, u16)>,
+
     // Auto-scroll state during selection
     pub auto_scroll_direction: Option<AutoScrollDirection>,
     pub last_auto_scroll_time: Option<Instant>,
 
+    // Mouse tracking state of the focused window (as of the last frame)
+    pub focused_mouse_tracking: bool,
+
+    // Key pass-through: forward every key to the focused terminal, bypassing shortcuts
+    pub key_passthrough: bool,
+
+    // Mouse capture: off while the user hands the mouse back to the host terminal
+    pub mouse_capture_enabled: bool,
+
+    // Mouse pointer hidden by typing (auto_hide_cursor) until the mouse moves again
+    pub mouse_cursor_hidden: bool,
+
+    // Charset mode was cycled with the shortcut; the new mode is saved to config on exit
+    pub charset_mode_changed: bool,
+
+    // Night dim: percent every color is scaled toward black (0 = off)
+    pub dim_percent: u8,
+
     // Exit flags
     pub should_exit: bool,
     /// When true, kill the persist daemon on exit (instead of just detaching)
     pub should_kill_daemon: bool,
 
+    // Request waiting on the active prompt, and whether the user confirmed it
+    // (a prompt dismissed any other way drops the request)
+    pub pending_confirmation: Option<PendingConfirmation>,
+    pub pending_confirmed: bool,
+    // Window and time of the last hidden prompt paste warning (paste_guard = Warn);
+    // pasting into that window again while the warning shows sends the paste
+    pub hidden_paste_warning: Option<(u32, Instant)>,
+    // Window spawns that failed in a row, and how many bring up that prompt (0 = never)
+    pub spawn_failures: u32,
+    pub spawn_failure_prompt_after: u32,
+
     // Keyboard Mode State (vim-like window control)
     pub keyboard_mode: KeyboardMode,
     pub move_state: MovementState,
@@ -83,10 +194,26 @@ pub struct AppState {
     // Double-backtick detection for literal backtick input
     pub last_backtick_time: Option<Instant>,
 
+    // Paste from clipboard history (CTRL+SHIFT+Y): the next digit picks the copy
+    pub pending_history_paste: bool,
+
     // Window number overlay (F10 toggle, Option+1-9 selection)
     /// Whether to show window number overlay
     pub show_window_number_overlay: bool,
 
+    // Expose overview (F9 toggle): all windows shown as tiles
+    pub show_expose: bool,
+
+    // Minimize/maximize animations (only used when enabled in config)
+    pub window_animations: AnimationTracker,
+
+    // Emulator metrics overlay (toggle allowed with --debug-overlay)
+    pub debug_overlay: DebugOverlay,
+
+    // Idle screensaver (shown after screensaver_idle_secs without key or mouse input)
+    pub last_input_time: Instant,
+    pub screensaver: Option<Screensaver>,
+
     // Lockscreen
     pub lockscreen: LockScreen,
     pub active_pin_setup: Option<PinSetupDialog>,
@@ -135,8 +262,24 @@ impl AppState {
         // Taskbar context menu (initially at 0, 0, not visible)
         let taskbar_menu = ContextMenu::new_taskbar_menu(0, 0);
 
+        // Configured System menu entries; malformed ones are skipped and reported in a toast
+        let mut system_menu_items = Vec::new();
+        let mut system_menu_labels = Vec::new();
+        let mut skipped = Vec::new();
+        for (index, item) in config.system_menu_items.iter().enumerate() {
+            match item.menu_command() {
+                Ok(command) => {
+                    system_menu_items.push(command);
+                    system_menu_labels.push(item.label.trim().to_string());
+                }
+                Err(reason) => skipped.push(format!("#{}: {}", index + 1, reason)),
+            }
+        }
+        let active_toast = (!skipped.is_empty())
+            .then(|| Toast::new(format!("Skipped System menu items {}", skipped.join("; "))));
+
         // System menu (width matches the button: "[ System ]" = 10 chars)
-        let system_menu = ContextMenu::new_system_menu(0, 1, 10, charset);
+        let system_menu = ContextMenu::new_system_menu(0, 1, 10, charset, &system_menu_labels);
 
         Self {
             // Dialog/Popup State
@@ -148,11 +291,14 @@ impl AppState {
             active_winmode_help_window: None,
             active_slight_input: None,
             active_error_dialog: None,
-            active_toast: None,
+            active_toast,
             context_menu,
             taskbar_menu,
             taskbar_menu_window_id: None,
             system_menu,
+            desktop_menu: ContextMenu::new_desktop_menu(0, 0, &[]),
+            desktop_menu_commands: Vec::new(),
+            system_menu_items,
 
             // Top Bar Buttons (legacy)
             new_terminal_button,
@@ -178,6 +324,7 @@ impl AppState {
             // Application Settings
             auto_tiling_enabled: auto_tiling_on_startup,
             tint_terminal,
+            tint_strength: config.tint_strength.clamp(0.0, 1.0),
 
             // Selection State
             selection_active: false,
@@ -185,14 +332,35 @@ impl AppState {
             last_click_pos: None,
             click_count: 0,
 
+            // Pointer position (unknown until the first mouse event)
+            pointer_position: None,
+
             // Auto-scroll state during selection
             auto_scroll_direction: None,
             last_auto_scroll_time: None,
 
+            // Mouse tracking state
+            focused_mouse_tracking: false,
+
+            // Key pass-through (off until toggled)
+            key_passthrough: false,
+
+            // Mouse capture (on until toggled)
+            mouse_capture_enabled: true,
+            mouse_cursor_hidden: false,
+            charset_mode_changed: false,
+            dim_percent: config.dim_factor.min(90),
+
             // Exit flags
             should_exit: false,
             should_kill_daemon: false,
 
+            pending_confirmation: None,
+            pending_confirmed: false,
+            hidden_paste_warning: None,
+            spawn_failures: 0,
+            spawn_failure_prompt_after: config.spawn_failure_prompt_after,
+
             // Keyboard Mode State
             keyboard_mode: KeyboardMode::Normal,
             move_state: MovementState::new(),
@@ -201,9 +369,25 @@ impl AppState {
             // Double-backtick detection
             last_backtick_time: None,
 
+            // Clipboard history paste
+            pending_history_paste: false,
+
             // Window number overlay (F10 toggle)
             show_window_number_overlay: false,
 
+            // Expose overview (F9 toggle)
+            show_expose: false,
+
+            // Minimize/maximize animations
+            window_animations: AnimationTracker::new(),
+
+            // Debug metrics overlay (hidden until toggled)
+            debug_overlay: DebugOverlay::new(),
+
+            // Idle screensaver
+            last_input_time: Instant::now(),
+            screensaver: None,
+
             // Lockscreen - initialize with config settings
             lockscreen: LockScreen::new_with_mode(
                 config.lockscreen_auth_mode,
@@ -228,6 +412,55 @@ impl AppState {
         );
     }
 
+    /// Record key or mouse input: restarts the idle timer and dismisses the screensaver
+    pub fn note_user_input(&mut self) {
+        self.last_input_time = Instant::now();
+        self.screensaver = None;
+    }
+
+    /// Start the screensaver once the configured idle time has passed (never over the lockscreen)
+    pub fn update_screensaver(&mut self, config: &AppConfig) {
+        if !config.screensaver_enabled || self.lockscreen.is_active() {
+            self.screensaver = None;
+            return;
+        }
+        let idle = Duration::from_secs(config.screensaver_idle_secs.max(1));
+        if self.screensaver.is_none() && self.last_input_time.elapsed() >= idle {
+            self.screensaver = Some(Screensaver::new(config.screensaver_effect));
+        }
+    }
+
+    /// Show a confirmation prompt holding `request` until it is answered
+    pub fn ask_confirmation(&mut self, prompt: Prompt, request: PendingConfirmation) {
+        self.active_prompt = Some(prompt);
+        self.pending_confirmation = Some(request);
+        self.pending_confirmed = false;
+    }
+
+    /// Confirm the request waiting on the active prompt
+    pub fn confirm_pending(&mut self) {
+        self.pending_confirmed = self.pending_confirmation.is_some();
+        self.active_prompt = None;
+    }
+
+    /// Answer the duplicate prompt with a plain shell instead of rerunning the command
+    pub fn confirm_duplicate_shell(&mut self) {
+        if let Some(PendingConfirmation::Duplicate(request)) = self.pending_confirmation.as_mut() {
+            request.command = None;
+        }
+        self.confirm_pending();
+    }
+
+    /// Take the confirmed request once its prompt is gone (a dismissed prompt discards the
+    /// request, and with it any pasted text)
+    pub fn take_confirmed(&mut self) -> Option<PendingConfirmation> {
+        if self.active_prompt.is_some() {
+            return None;
+        }
+        let confirmed = std::mem::take(&mut self.pending_confirmed);
+        self.pending_confirmation.take().filter(|_| confirmed)
+    }
+
     /// Updates button positions and states based on current clipboard and selection state
     pub fn update_button_states(
         &mut self,
@@ -281,6 +514,46 @@ impl AppState {
         }
     }
 
+    /// Records the focused window's current mouse tracking state
+    /// Returns true when the focused application just enabled mouse tracking
+    /// (it now receives mouse events)
+    pub fn update_mouse_tracking(&mut self, enabled: bool) -> bool {
+        let grabbed = enabled && !self.focused_mouse_tracking;
+        self.focused_mouse_tracking = enabled;
+        grabbed
+    }
+
+    /// Effective tint strength for rendering terminal content (0.0 when tinting is off)
+    pub fn effective_tint_strength(&self) -> f32 {
+        if self.tint_terminal {
+            self.tint_strength
+        } else {
+            0.0
+        }
+    }
+
+    /// X position where the bottom bar window buttons start (after the mode indicator and
+    /// the auto-tiling button). Format: "[label ]" + 2 spaces
+    pub fn window_buttons_start(&self) -> u16 {
+        let mode_indicator_width = crate::ui::ui_render::mode_indicator_width(&self.keyboard_mode);
+        let mode_offset = if mode_indicator_width > 0 {
+            mode_indicator_width + 1
+        } else {
+            0
+        };
+        mode_offset + 1 + 1 + self.auto_tiling_button.label.len() as u16 + 1 + 2
+    }
+
+    /// X position where the bottom bar window buttons end (before the indicators and help text)
+    pub fn window_buttons_end(&self, cols: u16) -> u16 {
+        crate::ui::ui_render::window_buttons_end(
+            cols,
+            self.focused_mouse_tracking,
+            self.key_passthrough,
+            !self.mouse_capture_enabled,
+        )
+    }
+
     /// Updates the auto-tiling button position (call after resize)
     pub fn update_auto_tiling_button_position(&mut self, rows: u16) {
         self.auto_tiling_button.y = rows - 1;
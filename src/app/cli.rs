@@ -1,5 +1,7 @@
 use clap::Parser;
 
+use crate::lockscreen::auth::{create_authenticator, is_os_auth_available};
+
 const LONG_ABOUT: &str = "\
 A modern, retro-styled terminal multiplexer with a classic MS-DOS aesthetic.
 Features a full-screen text-based interface with authentic DOS-style rendering.
@@ -22,8 +24,27 @@ KEYBOARD SHORTCUTS:
   'c'         - Show calendar
   CTRL+Space  - Command launcher (Slight)
   CTRL+L      - Clear terminal
+  CTRL+SHIFT+K - Clear scrollback
+  CTRL+SHIFT+D - Duplicate window (same directory)
+  CTRL+SHIFT+B - Interrupt foreground job (SIGINT)
+  CTRL+SHIFT+T - Terminate foreground job (SIGTERM)
+  CTRL+SHIFT+X - Kill foreground job (SIGKILL, asks first)
+  CTRL+SHIFT+W - Force close window: SIGKILL its processes, no unsaved-work check (asks first)
+  CTRL+SHIFT+Y, 1-9 - Paste one of the last 9 copies (1 = the last one)
+  CTRL+SHIFT+L - Lock/unlock window input
+  CTRL+SHIFT+R - Show control characters and escape sequences literally (inspector)
+  CTRL+SHIFT+U - Always show the cursor, even if the application hid it
+  CTRL+SHIFT+G - Cycle window color tag (also :tag <color> in the launcher)
+  CTRL+SHIFT+P - Pass all keys to terminal
+  CTRL+SHIFT+O - Toggle mouse capture (host terminal gets the mouse)
+  CTRL+SHIFT+A - Cycle charset: ASCII, Unicode, single-line Unicode (saved on exit)
+  CTRL+SHIFT+N - Cycle night dim: off, 25%, 50%, 75% darker
+  CTRL+SHIFT+S - Show/hide the drop-down scratchpad window
+  F9          - Window overview (expose)
+  F11         - Hide/show top and bottom bars
   CTRL+S      - Save session manually
-  ALT+TAB     - Switch between windows
+  ALT+TAB     - Switch between windows (ALT+SHIFT+TAB for previous)
+  ALT+`       - Back to the last focused window (press again to return)
 
 MOUSE CONTROLS:
   Click title bar     - Drag window
@@ -63,7 +84,8 @@ pub struct Cli {
     ///
     /// Available themes:
     ///   - classic:        DOS-inspired blue and cyan colors (default)
-    ///   - monochrome:     Grayscale theme with black and white
+    ///   - monochrome:     Grayscale theme with black and white (alias: paper_black)
+    ///   - paper_white:    Dark ink on a light page for bright rooms (alias: paper)
     ///   - dark:           Dark theme inspired by modern dark schemes
     ///   - dracu:          IDE-inspired dark theme (aliases: darcula, intellij)
     ///   - green_phosphor: Green monochrome CRT style (aliases: green)
@@ -86,6 +108,18 @@ pub struct Cli {
     #[arg(long, help = "Apply theme-based tinting to terminal content")]
     pub tint_terminal: bool,
 
+    /// Strength of the terminal tint (0.0 - 1.0)
+    ///
+    /// Controls how far terminal colors move toward the theme palette when tinting is
+    /// enabled: 0.0 keeps native colors, 1.0 maps them fully to the theme. Values in
+    /// between give a subtle cohesion without washing out application colors.
+    #[arg(
+        long,
+        value_name = "STRENGTH",
+        help = "Tint strength from 0.0 (off) to 1.0 (full)"
+    )]
+    pub tint_strength: Option<f32>,
+
     /// Don't restore previous session on startup
     ///
     /// By default, term39 automatically restores your previous session (window layouts
@@ -94,6 +128,15 @@ pub struct Cli {
     #[arg(long, help = "Don't restore previous session on startup")]
     pub no_restore: bool,
 
+    /// Start with a maximized window
+    ///
+    /// Opens a terminal window maximized at startup (respecting tiling gaps), or
+    /// maximizes the focused window when a session was restored. Un-maximizing
+    /// returns the window to its regular size. Combine with `--exec` to run a
+    /// single full-screen program.
+    #[arg(long, help = "Start with a maximized window")]
+    pub maximized: bool,
+
     /// Don't save session (disables both auto-save on exit and manual save)
     ///
     /// By default, term39 saves your session when you exit and allows manual saving
@@ -307,6 +350,14 @@ pub struct Cli {
     )]
     pub no_exit: bool,
 
+    /// Allow toggling the debug metrics overlay with CTRL+SHIFT+M
+    ///
+    /// The overlay shows frame time and, per window, the grid size, scrollback
+    /// length and PTY output throughput. Useful for diagnosing render performance
+    /// and input/output issues; metrics are only gathered while it is shown.
+    #[arg(long, help = "Allow toggling the debug metrics overlay (CTRL+SHIFT+M)")]
+    pub debug_overlay: bool,
+
     /// Set the keybinding profile
     ///
     /// Available profiles:
@@ -337,6 +388,38 @@ pub struct Cli {
     )]
     pub shell: Option<String>,
 
+    /// Run a program instead of a shell in every terminal window
+    ///
+    /// Like `xterm -e`, each window (restored windows, new terminals opened
+    /// with 't' or F7) launches CMD directly instead of the login shell.
+    /// Commands started from the launcher still run as typed. CMD is split
+    /// on whitespace; use quotes to keep arguments together. When CMD exits,
+    /// its window closes.
+    ///
+    /// Examples:
+    ///   --exec htop
+    ///   --exec "tail -f /var/log/syslog"
+    #[arg(
+        long,
+        short = 'e',
+        value_name = "CMD",
+        help = "Run CMD instead of a shell in every terminal window"
+    )]
+    pub exec: Option<String>,
+
+    /// Directory new terminal windows start in
+    ///
+    /// Applies to windows opened with 't' or F7 and to startup windows;
+    /// duplicated windows still start in the directory of their original.
+    /// Overrides the default_cwd config option. `~/` expands to the home
+    /// directory; a path that is not a directory falls back to the home
+    /// directory with a warning.
+    ///
+    /// Example:
+    ///   --cwd ~/projects/term39
+    #[arg(long, value_name = "PATH", help = "Start new terminal windows in PATH")]
+    pub cwd: Option<String>,
+
     /// Lock a running term39 instance (Unix only)
     ///
     /// Sends a lock signal (SIGUSR1) to a running term39 process,
@@ -373,6 +456,63 @@ pub struct Cli {
     #[cfg(unix)]
     #[arg(long, help = "Force-attach, kicking any existing client")]
     pub force_attach: bool,
+
+    /// Print version, build and platform details and exit
+    ///
+    /// Prints the crate version, git commit (when built from a git checkout),
+    /// enabled cargo features, target OS/architecture and the detected
+    /// lockscreen authentication backend and the selected clipboard backend.
+    /// Include this output in bug reports.
+    #[arg(
+        long,
+        alias = "print-version-info",
+        help = "Print build details (version, features, platform) and exit"
+    )]
+    pub version_info: bool,
+
+    /// Print every action and its key bindings and exit
+    ///
+    /// Lists the bindings of the resolved keybinding profile (--keybindings or
+    /// the config file, with clipboard_shortcuts applied), grouped by where they
+    /// work: globally, on the desktop, in a focused window and in Window Mode.
+    #[arg(long, help = "Print the active key bindings and exit")]
+    pub dump_keymap: bool,
+
+    /// Write the config, theme colors and key bindings to one file and exit
+    ///
+    /// The TOML file holds the complete config under [config], plus the resolved
+    /// colors of the active theme and the active key bindings for reference.
+    /// The lock screen PIN is left out. Share it and load it with --import-setup.
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Export config, theme and key bindings to FILE and exit"
+    )]
+    pub export_setup: Option<String>,
+
+    /// Replace the config with the one in a file written by --export-setup and exit
+    ///
+    /// The file is validated first (theme, keybinding profile, startup windows and
+    /// System menu entries); the current config.toml is backed up to config.toml.bak
+    /// and the lock screen PIN is kept.
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Import a setup file written by --export-setup and exit"
+    )]
+    pub import_setup: Option<String>,
+
+    /// Append the window list and focus to a file for an external status bar
+    ///
+    /// Writes one JSON line (focused window id, then each window's id, title,
+    /// focused and minimized state) whenever windows open, close, get renamed or
+    /// change focus, at most every 150ms. Overrides `status_file` in the config.
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Stream window list and focus as JSON lines to PATH"
+    )]
+    pub status_file: Option<String>,
 }
 
 impl Cli {
@@ -381,3 +521,50 @@ impl Cli {
         Self::parse()
     }
 }
+
+/// Cargo features this binary was compiled with
+fn enabled_features() -> Vec<&'static str> {
+    let features = [
+        ("clipboard", cfg!(feature = "clipboard")),
+        ("framebuffer-backend", cfg!(feature = "framebuffer-backend")),
+        ("battery", cfg!(feature = "battery")),
+        ("lockscreen", cfg!(feature = "lockscreen")),
+    ];
+    features
+        .into_iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// Build and platform details for bug reports (--version-info)
+/// `clipboard_backend` names the clipboard mechanism selected by the config
+pub fn version_info(clipboard_backend: &str) -> String {
+    let features = enabled_features();
+    let auth_backend = if is_os_auth_available() {
+        create_authenticator().system_name()
+    } else {
+        "not available (PIN only)"
+    };
+
+    format!(
+        "term39 {}\n\
+         git commit: {}\n\
+         features:   {}\n\
+         target:     {}-{} ({})\n\
+         os auth:    {}\n\
+         clipboard:  {}",
+        env!("CARGO_PKG_VERSION"),
+        option_env!("TERM39_GIT_HASH").unwrap_or("unknown"),
+        if features.is_empty() {
+            "none".to_string()
+        } else {
+            features.join(", ")
+        },
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        std::env::consts::FAMILY,
+        auth_backend,
+        clipboard_backend,
+    )
+}
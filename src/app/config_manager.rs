@@ -1,6 +1,14 @@
+use crate::input::keyboard_mode::SnapPosition;
+use crate::rendering::CharsetMode;
+use crate::term_emu::{
+    ControlNotation, CursorShape, PasteControls, PasteTransform, parse_color_spec,
+};
+use crate::ui::prompt::PromptType;
+use crossterm::style::Color;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// Authentication mode for lockscreen
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -10,6 +18,442 @@ pub enum LockscreenAuthMode {
     Pin, // Alphanumeric PIN with local hash (default - always available)
 }
 
+/// Action performed when clicking on an empty area of the desktop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum DesktopClickAction {
+    #[default]
+    Focus, // Only focus the desktop (default)
+    SystemMenu,  // Open the System menu at the click position
+    NewTerminal, // Create a new terminal window
+    Menu,        // Show the desktop menu (new terminal + recent commands)
+}
+
+/// Action performed by the mouse wheel over the empty desktop or the top bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ScrollAction {
+    #[default]
+    None, // Ignore the wheel (default)
+    CycleWindows, // Wheel up/down focuses the previous/next window
+}
+
+/// How Alt+letter combinations are dispatched when a terminal window is focused
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum AltKeyMode {
+    #[default]
+    Intercept, // Window manager shortcuts take precedence (default)
+    Forward, // Alt+letter always goes to the focused application (as ESC + letter)
+}
+
+/// What the Enter key sends to terminal applications (LNM, mode 20, always sends CR LF)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum EnterSends {
+    #[default]
+    Cr, // Carriage return, what terminal line disciplines expect (default)
+    Lf,   // Line feed only
+    CrLf, // Carriage return and line feed
+}
+
+/// What a bell (BEL) from a terminal application does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum BellMode {
+    #[default]
+    Indicator, // Mark the bottom bar button of a background window with `!` (default)
+    Audible, // Ring the host terminal bell
+    Both,    // Mark background windows and ring the host terminal bell
+    Off,     // Ignore bells
+}
+
+impl BellMode {
+    /// Whether bells from background windows mark their bottom bar button
+    pub fn indicator(self) -> bool {
+        matches!(self, BellMode::Indicator | BellMode::Both)
+    }
+
+    /// Whether bells ring the host terminal bell
+    pub fn audible(self) -> bool {
+        matches!(self, BellMode::Audible | BellMode::Both)
+    }
+}
+
+/// Extra emphasis on the focused window beyond its title bar and border colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum FocusHighlight {
+    #[default]
+    None, // Only the theme's focused/unfocused colors (default)
+    Border,    // Draw the focused window's side and bottom borders in its title bar colors
+    DimOthers, // Mute the frames of unfocused windows by focus_dim_percent
+    Both,      // Border and DimOthers
+}
+
+impl FocusHighlight {
+    /// Whether the focused window's borders take its title bar colors
+    pub fn border(self) -> bool {
+        matches!(self, FocusHighlight::Border | FocusHighlight::Both)
+    }
+
+    /// Whether the frames of unfocused windows are muted
+    pub fn dim_others(self) -> bool {
+        matches!(self, FocusHighlight::DimOthers | FocusHighlight::Both)
+    }
+}
+
+/// Which mouse events are forwarded to the focused terminal's application
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum MouseForwardMode {
+    Always, // Forward clicks and wheel even if the app didn't request mouse tracking
+    #[default]
+    WhenAppRequests, // Forward only while the app has mouse tracking enabled (default)
+    Never,  // Keep the mouse for window management and selection only
+}
+
+/// Order of the key and mouse events handled in one frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum InputPriority {
+    #[default]
+    Keys, // Handle pending keys (and flush them to the PTYs) before pointer motion (default)
+    Arrival, // Handle key and mouse events in the order they arrived
+}
+
+/// When the window scrollbar is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ScrollbarMode {
+    #[default]
+    Auto, // Show the scrollbar once there is scrollback (default)
+    Always, // Always show the scrollbar
+    Never,  // Never show it; the content area takes over its column
+}
+
+/// Effect drawn by the idle screensaver
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ScreensaverEffect {
+    #[default]
+    Starfield, // Stars flying out of the centre of the screen (default)
+    Matrix,  // Falling columns of glyphs
+    Bouncer, // The focused window's title bouncing off the edges
+}
+
+/// Where new floating windows open (auto-tiling places windows itself)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum NewWindowPosition {
+    #[default]
+    Cascade, // Offset from the last new window, back to the centre at the edge (default)
+    Center,     // Always centred on the screen
+    UnderMouse, // Title bar under the mouse pointer
+    Smart,      // Where it overlaps the visible windows least
+}
+
+/// What submitting an empty query in the launcher does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum LauncherEmptyAction {
+    #[default]
+    Nothing, // Close the launcher without doing anything (default)
+    NewTerminal,   // Open a plain new terminal window
+    TopSuggestion, // Run the selected suggestion (most frequent at first)
+}
+
+/// When exiting term39 asks for confirmation first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ConfirmExit {
+    Always, // Always show the exit confirmation dialog
+    #[default]
+    MultipleWindows, // Confirm only when more than one window is open (default)
+    Never,  // Exit immediately
+}
+
+impl ConfirmExit {
+    /// Whether exiting with `window_count` open windows needs confirmation
+    pub fn applies(self, window_count: usize) -> bool {
+        match self {
+            ConfirmExit::Always => true,
+            ConfirmExit::MultipleWindows => window_count > 1,
+            ConfirmExit::Never => false,
+        }
+    }
+}
+
+/// What Enter does in the close confirmation dialog ('y' closes and 'n' cancels in every mode)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum CloseConfirmEnter {
+    #[default]
+    Selected, // Activate the selected button, Cancel is preselected (default)
+    Cancel, // Always cancel; closing needs 'y' or a click on Close
+    Ignore, // Do nothing; closing needs 'y', cancelling 'n' or Esc
+}
+
+/// What pasting into a window at a hidden (password) prompt does
+/// At such a prompt paste-and-run doesn't press Enter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum PasteGuard {
+    #[default]
+    Warn, // Warn and hold the paste; pasting again while the warning shows sends it (default)
+    Confirm, // Ask before pasting
+    Off,     // Paste silently
+}
+
+/// How bottom bar button titles longer than the maximum are shortened
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ButtonTitleTruncation {
+    #[default]
+    Cut, // Cut the title at the maximum length (default)
+    Ellipsis, // End the shortened title with "..."
+}
+
+/// How bottom bar buttons of windows sharing a title are told apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ButtonTitleDuplicates {
+    #[default]
+    Number, // Append " #1", " #2", ... in window order (default)
+    Process, // Append the running program, numbering any that still match
+    Off,     // Show the titles as they are
+}
+
+/// Whether windows start out showing control characters literally (toggled with CTRL+SHIFT+R)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ControlCharDisplay {
+    #[default]
+    Off, // Interpret control characters and escape sequences (default)
+    Pictures, // Show them as Unicode control pictures: ␀ ␁ … ␛
+    Caret,    // Show them in caret notation: ^@ ^A … ^[
+}
+
+/// What happens to windows that no longer fit when the screen becomes very small
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SmallScreenPolicy {
+    #[default]
+    Clamp, // Shrink and move windows to stay partially visible (default)
+    Minimize, // Minimize windows that don't fit, keeping the focused window
+    Stack,    // Center all windows on top of each other, shrunk to the screen
+}
+
+/// Characters used for borders, shadows and icons (`--ascii` and `--single-line` override it)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum CharsetStyle {
+    #[default]
+    Unicode, // Double-line box drawing (default)
+    UnicodeSingleLine, // Single-line box drawing
+    Ascii,             // Plain ASCII for terminals and links that mangle box drawing
+}
+
+impl From<CharsetStyle> for CharsetMode {
+    fn from(style: CharsetStyle) -> Self {
+        match style {
+            CharsetStyle::Unicode => CharsetMode::Unicode,
+            CharsetStyle::UnicodeSingleLine => CharsetMode::UnicodeSingleLine,
+            CharsetStyle::Ascii => CharsetMode::Ascii,
+        }
+    }
+}
+
+impl From<CharsetMode> for CharsetStyle {
+    fn from(mode: CharsetMode) -> Self {
+        match mode {
+            CharsetMode::Unicode => CharsetStyle::Unicode,
+            CharsetMode::UnicodeSingleLine => CharsetStyle::UnicodeSingleLine,
+            CharsetMode::Ascii => CharsetStyle::Ascii,
+        }
+    }
+}
+
+/// Clipboard mechanism used for copy and paste
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ClipboardBackend {
+    #[default]
+    Auto, // The platform clipboard when available, else the internal buffer (default)
+    X11,      // The X11 clipboard (the platform clipboard outside Linux and the BSDs)
+    Wayland,  // wl-copy/wl-paste from wl-clipboard
+    Osc52,    // Copy through the host terminal's OSC 52 escape (e.g. over SSH)
+    Internal, // Process-local buffer only
+}
+
+/// What the mouse wheel does over a window showing the alternate screen (full-screen apps
+/// without mouse support such as less or man), which has no scrollback
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum AltScreenScroll {
+    #[default]
+    Scrollback, // Scroll term39's scrollback as on the main screen (default)
+    SendArrows, // Up/Down key presses to the application
+    Forward,    // Wheel reports to apps that track the mouse, Up/Down key presses otherwise
+    None,       // Ignore the wheel
+}
+
+/// First column of the calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum WeekStart {
+    #[default]
+    Sunday, // Su Mo Tu ... (default)
+    Monday, // Mo Tu We ... (ISO 8601)
+}
+
+/// Commands run on window events (the `[hooks]` table); empty commands are skipped
+/// See `app::hooks` for the environment each command receives
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HookConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub on_window_open: String,
+    #[serde(default)]
+    pub on_window_close: String,
+    #[serde(default)]
+    pub on_focus_change: String,
+    #[serde(default)]
+    pub on_lock: String,
+    #[serde(default)]
+    pub on_unlock: String,
+}
+
+/// A window opened at startup when no session was restored (`[[startup_windows]]` entries)
+/// Placed in a named `zone`, or at `x`/`y` with `width`/`height`; unset values use the
+/// defaults of a new window
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StartupWindow {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub title: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub command: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub zone: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u16>,
+}
+
+/// Where a startup window goes, once its entry has been validated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StartupPlacement {
+    Maximized,
+    Snap(SnapPosition),
+    /// Explicit geometry; unset values are filled in like for any new window
+    Rect {
+        x: Option<u16>,
+        y: Option<u16>,
+        width: Option<u16>,
+        height: Option<u16>,
+    },
+}
+
+impl StartupPlacement {
+    /// Whether the entry asked for a position (a zone, maximized or any geometry)
+    pub fn is_placed(&self) -> bool {
+        !matches!(
+            self,
+            StartupPlacement::Rect {
+                x: None,
+                y: None,
+                width: None,
+                height: None,
+            }
+        )
+    }
+}
+
+impl StartupWindow {
+    /// Validate the entry, explaining what is wrong with a malformed one
+    pub fn placement(&self) -> Result<StartupPlacement, String> {
+        let has_geometry =
+            self.x.is_some() || self.y.is_some() || self.width.is_some() || self.height.is_some();
+        if self.width == Some(0) || self.height == Some(0) {
+            return Err("width and height must be at least 1".to_string());
+        }
+        let zone = self.zone.trim();
+        if zone.is_empty() {
+            return Ok(StartupPlacement::Rect {
+                x: self.x,
+                y: self.y,
+                width: self.width,
+                height: self.height,
+            });
+        }
+        if has_geometry {
+            return Err("set either zone or x/y/width/height, not both".to_string());
+        }
+        if zone.eq_ignore_ascii_case("maximized") {
+            return Ok(StartupPlacement::Maximized);
+        }
+        SnapPosition::from_name(zone)
+            .map(StartupPlacement::Snap)
+            .ok_or_else(|| format!("unknown zone '{}'", zone))
+    }
+}
+
+/// An entry listed at the top of the System menu (`[[system_menu_items]]` entries)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SystemMenuItem {
+    #[serde(default)]
+    pub label: String,
+    #[serde(default)]
+    pub action: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub command: String,
+}
+
+/// What a System menu entry does, once its entry has been validated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SystemMenuCommand {
+    /// Open a terminal window running the command
+    Run(String),
+    NewTerminal,
+    Settings,
+    Lock,
+    Help,
+    About,
+    Exit,
+}
+
+impl SystemMenuItem {
+    /// Validate the entry, explaining what is wrong with a malformed one
+    pub fn menu_command(&self) -> Result<SystemMenuCommand, String> {
+        if self.label.trim().is_empty() {
+            return Err("missing label".to_string());
+        }
+        let command = self.command.trim();
+        let action = self.action.trim().to_ascii_lowercase();
+        match action.as_str() {
+            "run" if command.is_empty() => Err("run needs a command".to_string()),
+            "run" => Ok(SystemMenuCommand::Run(command.to_string())),
+            "new_terminal" => Ok(SystemMenuCommand::NewTerminal),
+            "settings" => Ok(SystemMenuCommand::Settings),
+            "lock" => Ok(SystemMenuCommand::Lock),
+            "help" => Ok(SystemMenuCommand::Help),
+            "about" => Ok(SystemMenuCommand::About),
+            "exit" => Ok(SystemMenuCommand::Exit),
+            _ => Err(format!("unknown action '{}'", self.action.trim())),
+        }
+    }
+}
+
+/// Which kinds of unsaved work make closing a window ask for confirmation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CloseConfirmPolicy {
+    /// Ask while a non-shell process runs in the foreground
+    pub if_process_running: bool,
+    /// Ask once the user has typed into the window
+    pub if_typed: bool,
+}
+
+impl Default for CloseConfirmPolicy {
+    fn default() -> Self {
+        Self {
+            if_process_running: true,
+            if_typed: true,
+        }
+    }
+}
+
+/// Calendar layout options (from the `calendar_*` config values)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CalendarOptions {
+    /// Show ISO week numbers in front of each week
+    pub week_numbers: bool,
+    /// Start weeks on Monday instead of Sunday
+    pub monday_first: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
     #[serde(default = "default_auto_tiling_on_startup")]
@@ -24,6 +468,10 @@ pub struct AppConfig {
     pub background_char_index: usize,
     #[serde(default = "default_tint_terminal")]
     pub tint_terminal: bool,
+    #[serde(default = "default_tint_strength")]
+    pub tint_strength: f32,
+    #[serde(default)]
+    pub minimum_contrast: f32,
     #[serde(default = "default_auto_save")]
     pub auto_save: bool,
     #[serde(default = "default_persist_enabled")]
@@ -36,18 +484,217 @@ pub struct AppConfig {
     pub lockscreen_pin_hash: Option<String>,
     #[serde(default)]
     pub lockscreen_salt: Option<String>,
+    #[serde(default = "default_lockscreen_animation")]
+    pub lockscreen_animation: bool,
     #[serde(default)]
     pub network_widget_enabled: bool,
     #[serde(default)]
     pub network_interface: String,
     #[serde(default = "default_keybinding_profile")]
     pub keybinding_profile: String,
+    #[serde(default = "default_clipboard_shortcuts")]
+    pub clipboard_shortcuts: bool,
+    #[serde(default)]
+    pub desktop_click_action: DesktopClickAction,
+    #[serde(default)]
+    pub desktop_right_click_action: DesktopClickAction,
+    #[serde(default)]
+    pub default_cursor_shape: CursorShape,
+    #[serde(default)]
+    pub answerback: String,
+    #[serde(default)]
+    pub notify_on_command_complete: bool,
+    #[serde(default)]
+    pub alt_key_mode: AltKeyMode,
+    #[serde(default)]
+    pub enter_sends: EnterSends,
+    #[serde(default)]
+    pub mouse_forward_mode: MouseForwardMode,
+    #[serde(default)]
+    pub confirm_exit: ConfirmExit,
+    #[serde(default)]
+    pub scrollbar_mode: ScrollbarMode,
+    #[serde(default = "default_exit_label")]
+    pub exit_label: String,
+    #[serde(default = "default_shift_wheel_scrollback")]
+    pub shift_wheel_scrollback: bool,
+    #[serde(default)]
+    pub launcher_empty_action: LauncherEmptyAction,
+    #[serde(default)]
+    pub screensaver_enabled: bool,
+    #[serde(default = "default_screensaver_idle_secs")]
+    pub screensaver_idle_secs: u64,
+    #[serde(default)]
+    pub screensaver_effect: ScreensaverEffect,
+    #[serde(default = "default_launcher_confirm_unknown")]
+    pub launcher_confirm_unknown: bool,
+    #[serde(default = "default_prompt_timeout_info_secs")]
+    pub prompt_timeout_info_secs: u64,
+    #[serde(default = "default_prompt_timeout_success_secs")]
+    pub prompt_timeout_success_secs: u64,
+    #[serde(default)]
+    pub prompt_timeout_warning_secs: u64,
+    #[serde(default = "default_session_scrollback_lines")]
+    pub session_scrollback_lines: usize,
+    #[serde(default = "default_session_trim_trailing_blanks")]
+    pub session_trim_trailing_blanks: bool,
+    #[serde(default)]
+    pub animations: bool,
+    #[serde(default = "default_animation_duration_ms")]
+    pub animation_duration_ms: u64,
+    #[serde(default)]
+    pub small_screen_policy: SmallScreenPolicy,
+    #[serde(default = "default_small_screen_min_cols")]
+    pub small_screen_min_cols: u16,
+    #[serde(default = "default_small_screen_min_rows")]
+    pub small_screen_min_rows: u16,
+    #[serde(default)]
+    pub bars_hidden: bool,
+    #[serde(default = "default_paste_crlf_to_lf")]
+    pub paste_crlf_to_lf: bool,
+    #[serde(default)]
+    pub paste_strip_cr: bool,
+    #[serde(default)]
+    pub paste_expand_tabs: bool,
+    #[serde(default)]
+    pub paste_control_chars: PasteControls,
+    #[serde(default)]
+    pub paste_guard: PasteGuard,
+    #[serde(default = "default_button_title_max_len")]
+    pub button_title_max_len: usize,
+    #[serde(default)]
+    pub button_title_truncation: ButtonTitleTruncation,
+    #[serde(default)]
+    pub button_title_duplicates: ButtonTitleDuplicates,
+    #[serde(default)]
+    pub close_confirm_enter: CloseConfirmEnter,
+    #[serde(default = "default_confirm_close_if_process_running")]
+    pub confirm_close_if_process_running: bool,
+    #[serde(default = "default_confirm_close_if_typed")]
+    pub confirm_close_if_typed: bool,
+    #[serde(default)]
+    pub new_window_position: NewWindowPosition,
+    #[serde(default)]
+    pub output_log: String,
+    #[serde(default = "default_output_log_max_kb")]
+    pub output_log_max_kb: u64,
+    #[serde(default = "default_keyboard_step")]
+    pub keyboard_move_step: u16,
+    #[serde(default = "default_keyboard_step")]
+    pub keyboard_resize_step: u16,
+    #[serde(default = "default_keyboard_large_step_multiplier")]
+    pub keyboard_large_step_multiplier: u16,
+    #[serde(default)]
+    pub charset: CharsetStyle,
+    #[serde(default)]
+    pub clipboard_backend: ClipboardBackend,
+    #[serde(default)]
+    pub join_borders: bool,
+    #[serde(default)]
+    pub calendar_week_numbers: bool,
+    #[serde(default)]
+    pub calendar_week_start: WeekStart,
+    #[serde(default = "default_max_bytes_per_frame")]
+    pub max_bytes_per_frame: usize,
+    #[serde(default = "default_word_chars")]
+    pub word_chars: String,
+    #[serde(default)]
+    pub alt_screen_scroll: AltScreenScroll,
+    #[serde(default)]
+    pub cursor_color: String,
+    #[serde(default)]
+    pub auto_hide_cursor: bool,
+    #[serde(default)]
+    pub desktop_scroll_action: ScrollAction,
+    #[serde(default)]
+    pub top_bar_scroll_action: ScrollAction,
+    #[serde(default)]
+    pub status_file: String,
+    #[serde(default)]
+    pub scrollback_compression: bool,
+    #[serde(default = "default_scrollback_compress_after")]
+    pub scrollback_compress_after: usize,
+    #[serde(default)]
+    pub ghost_unfocused_windows: bool,
+    #[serde(default)]
+    pub show_control_chars: ControlCharDisplay,
+    #[serde(default)]
+    pub dim_factor: u8,
+    #[serde(default = "default_scroll_indicator")]
+    pub scroll_indicator: bool,
+    #[serde(default = "default_spawn_failure_prompt_after")]
+    pub spawn_failure_prompt_after: u32,
+    #[serde(default)]
+    pub default_cwd: String,
+    #[serde(default = "default_resize_grip_size")]
+    pub resize_grip_size: u16,
+    #[serde(default)]
+    pub bell: BellMode,
+    #[serde(default)]
+    pub force_cursor_visible: bool,
+    #[serde(default)]
+    pub protected_process_names: Vec<String>,
+    #[serde(default)]
+    pub focus_highlight: FocusHighlight,
+    #[serde(default = "default_focus_dim_percent")]
+    pub focus_dim_percent: u8,
+    #[serde(default)]
+    pub maximized_tab_strip: bool,
+    #[serde(default)]
+    pub input_priority: InputPriority,
+    #[serde(default = "default_events_per_frame")]
+    pub key_events_per_frame: usize,
+    #[serde(default = "default_events_per_frame")]
+    pub mouse_events_per_frame: usize,
+    // Tables go last so they serialize after the plain values
+    #[serde(default)]
+    pub startup_windows: Vec<StartupWindow>,
+    #[serde(default)]
+    pub system_menu_items: Vec<SystemMenuItem>,
+    #[serde(default)]
+    pub hooks: HookConfig,
 }
 
 fn default_keybinding_profile() -> String {
     "term39".to_string()
 }
 
+fn default_prompt_timeout_info_secs() -> u64 {
+    5 // Default to 5 seconds (0 keeps the prompt until dismissed)
+}
+
+fn default_prompt_timeout_success_secs() -> u64 {
+    3 // Default to 3 seconds (0 keeps the prompt until dismissed)
+}
+
+fn default_session_scrollback_lines() -> usize {
+    1000 // Default to 1000 lines per window (0 saves only the visible screen)
+}
+
+fn default_session_trim_trailing_blanks() -> bool {
+    true // Default to leaving trailing blank cells out of saved and restored lines
+}
+
+fn default_small_screen_min_cols() -> u16 {
+    60 // Default to applying the small screen policy below 60 columns
+}
+
+fn default_small_screen_min_rows() -> u16 {
+    16 // Default to applying the small screen policy below 16 rows
+}
+
+fn default_button_title_max_len() -> usize {
+    14 // Default to 14 characters ("[ " + title + " ]" is at most 18 wide)
+}
+
+fn default_paste_crlf_to_lf() -> bool {
+    true // Default to converting CRLF to LF (avoids stray ^M in Unix shells)
+}
+
+fn default_animation_duration_ms() -> u64 {
+    150 // Default to 150ms (capped at MAX_ANIMATION_DURATION_MS)
+}
+
 fn default_auto_tiling_on_startup() -> bool {
     false // Default to false (disabled at startup)
 }
@@ -72,6 +719,10 @@ fn default_tint_terminal() -> bool {
     false // Default to false (preserve native ANSI colors)
 }
 
+fn default_tint_strength() -> f32 {
+    1.0 // Default to full tint (colors fully mapped to the theme)
+}
+
 fn default_auto_save() -> bool {
     true // Default to true (auto-save session on exit)
 }
@@ -84,6 +735,82 @@ fn default_lockscreen_enabled() -> bool {
     true // Default to true (maintains existing behavior)
 }
 
+fn default_screensaver_idle_secs() -> u64 {
+    600 // Default to 10 minutes without input
+}
+
+fn default_scroll_indicator() -> bool {
+    true // Default to showing [↑ n] in the title bar while scrolled back
+}
+
+fn default_spawn_failure_prompt_after() -> u32 {
+    3 // Default to offering the fallback shell after 3 failed spawns in a row (0 = never)
+}
+
+fn default_focus_dim_percent() -> u8 {
+    50 // Default to muting unfocused frames halfway to black (with focus_highlight DimOthers)
+}
+
+fn default_events_per_frame() -> usize {
+    50 // Default to handling up to 50 key and 50 mouse events per frame, the rest waits
+}
+
+fn default_resize_grip_size() -> u16 {
+    1 // Default to resizing from the border cells only
+}
+
+fn default_launcher_confirm_unknown() -> bool {
+    true // Default to asking before running a command the launcher doesn't know
+}
+
+fn default_output_log_max_kb() -> u64 {
+    10 * 1024 // Default to rotating the output log at 10 MiB
+}
+
+fn default_scrollback_compress_after() -> usize {
+    200 // Default to keeping the newest 200 scrollback lines unpacked
+}
+
+fn default_max_bytes_per_frame() -> usize {
+    256 * 1024 // Default to parsing 256 KiB per window per frame (0 = unlimited)
+}
+
+fn default_word_chars() -> String {
+    "_-".to_string() // Default to identifiers and dashed names (add "./~" for paths)
+}
+
+fn default_keyboard_step() -> u16 {
+    1 // Default to one cell per Window Mode move/resize press (before acceleration)
+}
+
+fn default_keyboard_large_step_multiplier() -> u16 {
+    5 // Default to Ctrl moving/resizing five times as far
+}
+
+fn default_confirm_close_if_process_running() -> bool {
+    true // Default to asking before closing a window with a program running
+}
+
+fn default_confirm_close_if_typed() -> bool {
+    true // Default to asking before closing a window the user typed into
+}
+
+fn default_exit_label() -> String {
+    "Exit".to_string() // Default to "Exit" (System menu item and exit dialog button)
+}
+
+fn default_shift_wheel_scrollback() -> bool {
+    true // Default to true (Shift+wheel scrolls scrollback even when the app tracks the mouse)
+}
+
+fn default_clipboard_shortcuts() -> bool {
+    true // Default to true (Ctrl+Shift+C/V and Shift+Insert copy and paste)
+}
+
+fn default_lockscreen_animation() -> bool {
+    true // Default to true (starfield behind the lock dialog)
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -93,25 +820,134 @@ impl Default for AppConfig {
             theme: default_theme(),
             background_char_index: default_background_char_index(),
             tint_terminal: default_tint_terminal(),
+            tint_strength: default_tint_strength(),
+            minimum_contrast: 0.0,
             auto_save: default_auto_save(),
             persist_enabled: default_persist_enabled(),
             lockscreen_enabled: default_lockscreen_enabled(),
             lockscreen_auth_mode: LockscreenAuthMode::default(),
             lockscreen_pin_hash: None,
             lockscreen_salt: None,
+            lockscreen_animation: default_lockscreen_animation(),
             network_widget_enabled: false,
             network_interface: String::new(),
             keybinding_profile: default_keybinding_profile(),
+            clipboard_shortcuts: default_clipboard_shortcuts(),
+            desktop_click_action: DesktopClickAction::default(),
+            desktop_right_click_action: DesktopClickAction::default(),
+            default_cursor_shape: CursorShape::default(),
+            answerback: String::new(),
+            notify_on_command_complete: false,
+            alt_key_mode: AltKeyMode::default(),
+            enter_sends: EnterSends::default(),
+            mouse_forward_mode: MouseForwardMode::default(),
+            confirm_exit: ConfirmExit::default(),
+            scrollbar_mode: ScrollbarMode::default(),
+            exit_label: default_exit_label(),
+            shift_wheel_scrollback: default_shift_wheel_scrollback(),
+            launcher_empty_action: LauncherEmptyAction::default(),
+            screensaver_enabled: false,
+            screensaver_idle_secs: default_screensaver_idle_secs(),
+            screensaver_effect: ScreensaverEffect::default(),
+            launcher_confirm_unknown: default_launcher_confirm_unknown(),
+            prompt_timeout_info_secs: default_prompt_timeout_info_secs(),
+            prompt_timeout_success_secs: default_prompt_timeout_success_secs(),
+            prompt_timeout_warning_secs: 0,
+            session_scrollback_lines: default_session_scrollback_lines(),
+            session_trim_trailing_blanks: default_session_trim_trailing_blanks(),
+            animations: false,
+            animation_duration_ms: default_animation_duration_ms(),
+            small_screen_policy: SmallScreenPolicy::default(),
+            small_screen_min_cols: default_small_screen_min_cols(),
+            small_screen_min_rows: default_small_screen_min_rows(),
+            bars_hidden: false,
+            paste_crlf_to_lf: default_paste_crlf_to_lf(),
+            paste_strip_cr: false,
+            paste_expand_tabs: false,
+            paste_control_chars: PasteControls::Strip,
+            paste_guard: PasteGuard::default(),
+            button_title_max_len: default_button_title_max_len(),
+            button_title_truncation: ButtonTitleTruncation::default(),
+            button_title_duplicates: ButtonTitleDuplicates::default(),
+            close_confirm_enter: CloseConfirmEnter::default(),
+            confirm_close_if_process_running: default_confirm_close_if_process_running(),
+            confirm_close_if_typed: default_confirm_close_if_typed(),
+            new_window_position: NewWindowPosition::default(),
+            output_log: String::new(),
+            output_log_max_kb: default_output_log_max_kb(),
+            keyboard_move_step: default_keyboard_step(),
+            keyboard_resize_step: default_keyboard_step(),
+            keyboard_large_step_multiplier: default_keyboard_large_step_multiplier(),
+            charset: CharsetStyle::default(),
+            clipboard_backend: ClipboardBackend::default(),
+            join_borders: false,
+            calendar_week_numbers: false,
+            calendar_week_start: WeekStart::default(),
+            max_bytes_per_frame: default_max_bytes_per_frame(),
+            word_chars: default_word_chars(),
+            alt_screen_scroll: AltScreenScroll::default(),
+            cursor_color: String::new(),
+            auto_hide_cursor: false,
+            desktop_scroll_action: ScrollAction::default(),
+            top_bar_scroll_action: ScrollAction::default(),
+            status_file: String::new(),
+            scrollback_compression: false,
+            scrollback_compress_after: default_scrollback_compress_after(),
+            ghost_unfocused_windows: false,
+            show_control_chars: ControlCharDisplay::Off,
+            dim_factor: 0,
+            scroll_indicator: default_scroll_indicator(),
+            spawn_failure_prompt_after: default_spawn_failure_prompt_after(),
+            default_cwd: String::new(),
+            resize_grip_size: default_resize_grip_size(),
+            bell: BellMode::Indicator,
+            force_cursor_visible: false,
+            protected_process_names: Vec::new(),
+            focus_highlight: FocusHighlight::None,
+            focus_dim_percent: default_focus_dim_percent(),
+            maximized_tab_strip: false,
+            input_priority: InputPriority::Keys,
+            key_events_per_frame: default_events_per_frame(),
+            mouse_events_per_frame: default_events_per_frame(),
+            startup_windows: Vec::new(),
+            system_menu_items: Vec::new(),
+            hooks: HookConfig::default(),
         }
     }
 }
 
 impl AppConfig {
+    /// Clean-up applied to plain (non-bracketed) pastes
+    pub fn paste_transform(&self) -> PasteTransform {
+        PasteTransform {
+            crlf_to_lf: self.paste_crlf_to_lf,
+            strip_cr: self.paste_strip_cr,
+            expand_tabs: self.paste_expand_tabs,
+            controls: self.paste_control_chars,
+        }
+    }
+
+    /// Whether a screen of this size is below the small screen threshold
+    pub fn is_small_screen(&self, cols: u16, rows: u16) -> bool {
+        cols < self.small_screen_min_cols || rows < self.small_screen_min_rows
+    }
+
+    /// Duration of minimize/maximize animations, or None when animations are off
+    pub fn animation_duration(&self) -> Option<Duration> {
+        if !self.animations || self.animation_duration_ms == 0 {
+            return None;
+        }
+        let ms = self
+            .animation_duration_ms
+            .min(crate::window::animation::MAX_ANIMATION_DURATION_MS);
+        Some(Duration::from_millis(ms))
+    }
+
     /// Get the configuration file path
     /// Returns ~/Library/Application Support/term39/config.toml on macOS
     /// Returns ~/.config/term39/config.toml on Linux
     /// Returns %APPDATA%\term39\config.toml on Windows
-    fn config_path() -> Option<PathBuf> {
+    pub fn config_path() -> Option<PathBuf> {
         let config_dir = dirs::config_dir()?;
         let app_config_dir = config_dir.join("term39");
         Some(app_config_dir.join("config.toml"))
@@ -154,6 +990,63 @@ impl AppConfig {
         Ok(())
     }
 
+    /// Get the auto-dismiss timeout for notification prompts of the given type
+    /// Danger prompts always stay until answered; a timeout of 0 disables auto-dismiss
+    pub fn prompt_auto_dismiss(&self, prompt_type: PromptType) -> Option<Duration> {
+        let secs = match prompt_type {
+            PromptType::Info => self.prompt_timeout_info_secs,
+            PromptType::Success => self.prompt_timeout_success_secs,
+            PromptType::Warning => self.prompt_timeout_warning_secs,
+            PromptType::Danger => 0,
+        };
+        (secs > 0).then(|| Duration::from_secs(secs))
+    }
+
+    /// Which kinds of unsaved work make closing a window ask first
+    pub fn close_confirm_policy(&self) -> CloseConfirmPolicy {
+        CloseConfirmPolicy {
+            if_process_running: self.confirm_close_if_process_running,
+            if_typed: self.confirm_close_if_typed,
+        }
+    }
+
+    /// How the calendar lays out its weeks
+    pub fn calendar_options(&self) -> CalendarOptions {
+        CalendarOptions {
+            week_numbers: self.calendar_week_numbers,
+            monday_first: self.calendar_week_start == WeekStart::Monday,
+        }
+    }
+
+    /// Configured text cursor color (None when unset or not a valid color spec)
+    pub fn cursor_color(&self) -> Option<Color> {
+        parse_color_spec(self.cursor_color.as_bytes()).map(|(r, g, b)| Color::Rgb { r, g, b })
+    }
+
+    /// Notation windows start out showing control characters in (None = interpreted)
+    pub fn control_notation(&self) -> Option<ControlNotation> {
+        match self.show_control_chars {
+            ControlCharDisplay::Off => None,
+            ControlCharDisplay::Pictures => Some(ControlNotation::Pictures),
+            ControlCharDisplay::Caret => Some(ControlNotation::Caret),
+        }
+    }
+
+    /// Newest scrollback lines kept unpacked when compression is on (None when it is off)
+    pub fn scrollback_compression(&self) -> Option<usize> {
+        self.scrollback_compression
+            .then_some(self.scrollback_compress_after)
+    }
+
+    /// Minimum contrast ratio clamped to the WCAG range (0.0 when disabled)
+    pub fn minimum_contrast(&self) -> f32 {
+        if self.minimum_contrast > 1.0 {
+            self.minimum_contrast.min(21.0)
+        } else {
+            0.0
+        }
+    }
+
     /// Toggle auto tiling on startup setting and save
     pub fn toggle_auto_tiling_on_startup(&mut self) {
         self.auto_tiling_on_startup = !self.auto_tiling_on_startup;
@@ -427,3 +1320,54 @@ impl AppConfig {
         let _ = self.save();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_startup_window_placement() {
+        let zone = |zone: &str| StartupWindow {
+            zone: zone.to_string(),
+            ..StartupWindow::default()
+        };
+        assert_eq!(
+            zone("top_left").placement(),
+            Ok(StartupPlacement::Snap(SnapPosition::TopLeft))
+        );
+        assert_eq!(
+            zone(" Maximized ").placement(),
+            Ok(StartupPlacement::Maximized)
+        );
+        assert!(zone("middle").placement().is_err());
+
+        // No zone: the geometry given, the rest left to the defaults
+        let rect = StartupWindow {
+            x: Some(4),
+            width: Some(60),
+            ..StartupWindow::default()
+        };
+        assert_eq!(
+            rect.placement(),
+            Ok(StartupPlacement::Rect {
+                x: Some(4),
+                y: None,
+                width: Some(60),
+                height: None,
+            })
+        );
+        assert!(rect.placement().unwrap().is_placed());
+        assert!(!StartupWindow::default().placement().unwrap().is_placed());
+
+        let both = StartupWindow {
+            y: Some(2),
+            ..zone("left")
+        };
+        assert!(both.placement().is_err());
+        let empty = StartupWindow {
+            height: Some(0),
+            ..StartupWindow::default()
+        };
+        assert!(empty.placement().is_err());
+    }
+}
@@ -1,17 +1,21 @@
+use crate::app::app_state::PendingConfirmation;
 use crate::app::{AppConfig, AppState};
+use crate::input::event_queue::{InputQueue, QueuedEvent};
+use crate::input::keybinding_profile::matches_any;
 use crate::input::mouse_handlers::{
     ModalMouseResult, SystemMenuResult, TopBarClickResult, handle_about_window_mouse,
     handle_auto_tiling_click, handle_calendar_mouse, handle_config_window_mouse,
-    handle_context_menu_mouse, handle_error_dialog_mouse, handle_help_window_mouse,
-    handle_pin_setup_mouse, handle_prompt_mouse, handle_selection_mouse, handle_system_menu_mouse,
-    handle_taskbar_menu_mouse, handle_topbar_click, handle_winmode_help_window_mouse,
-    show_context_menu, show_taskbar_menu, update_bar_button_hover_states,
+    handle_context_menu_mouse, handle_desktop_click, handle_desktop_menu_mouse,
+    handle_desktop_scroll, handle_error_dialog_mouse, handle_expose_mouse,
+    handle_help_window_mouse, handle_pin_setup_mouse, handle_prompt_mouse, handle_selection_mouse,
+    handle_system_menu_mouse, handle_taskbar_menu_mouse, handle_topbar_click,
+    handle_winmode_help_window_mouse, show_context_menu, show_taskbar_menu,
+    update_bar_button_hover_states,
 };
 use crate::lockscreen::PinSetupState;
 use crate::rendering::RenderBackend;
 use crate::ui::config_action_handler::{apply_config_result, process_config_action};
 use crate::ui::config_window::ConfigWindow;
-use crate::ui::prompt::{Prompt, PromptAction, PromptButton, PromptType};
 use crate::ui::slight_input::SlightInput;
 use crate::utils::{ClipboardManager, CommandHistory, CommandIndexer};
 use crate::window::{FocusState, WindowManager};
@@ -25,7 +29,7 @@ use std::time::{Duration, Instant};
 /// Windows: Dedicated input thread to prevent event loss
 /// Reads events continuously and sends via channel
 #[cfg(target_os = "windows")]
-use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::mpsc::{self, Receiver};
 
 #[cfg(target_os = "windows")]
 fn spawn_input_thread() -> Receiver<Event> {
@@ -87,8 +91,26 @@ pub fn run(
     #[cfg(target_os = "windows")]
     let input_rx = spawn_input_thread();
 
+    // Watches the window list and lock state for user hooks
+    let mut window_events = crate::app::hooks::WindowEventWatcher::new();
+
+    // Window list and focus lines for external status bars
+    let mut status_file = crate::app::status_file::StatusFile::from_config(&app_config.status_file);
+
+    // Key and mouse events waiting to be handled, in per-frame batches
+    let mut input_queue = InputQueue::new(
+        app_config.input_priority,
+        app_config.key_events_per_frame,
+        app_config.mouse_events_per_frame,
+    );
+
     // Main loop
     loop {
+        // Exit cleanly on SIGTERM so the session is saved like a normal exit
+        if crate::app::signal_handler::check_and_clear() {
+            break;
+        }
+
         // Check for external lock request (via SIGUSR1 signal)
         if crate::lockscreen::signal_handler::check_and_clear() {
             if app_state.lockscreen.is_available() {
@@ -98,6 +120,102 @@ pub fn run(
 
         // Update lockscreen state (check lockout timer)
         app_state.lockscreen.update();
+
+        // Start the idle screensaver when enabled
+        app_state.update_screensaver(app_config);
+
+        // One window list per frame, shared by the hooks and the status file, and only
+        // gathered when one of them needs it
+        let now = Instant::now();
+        let status_due = status_file.as_ref().is_some_and(|s| s.is_due(now));
+        if app_config.hooks.enabled || status_due {
+            let windows = window_manager.get_window_list();
+
+            // Run user hooks for windows opened, closed or focused and lock changes since last frame
+            if app_config.hooks.enabled {
+                let events = window_events.observe(&windows, app_state.lockscreen.is_active());
+                crate::app::hooks::run_hooks(&app_config.hooks, &events);
+            }
+
+            // A status file that can't be written is dropped rather than retried every frame
+            if status_due
+                && let Some(status) = &mut status_file
+                && status
+                    .update(&windows, window_manager.get_focus(), now)
+                    .is_err()
+            {
+                status_file = None;
+            }
+        }
+        if !app_config.hooks.enabled {
+            // Start from a fresh baseline if hooks are enabled later
+            window_events = crate::app::hooks::WindowEventWatcher::new();
+        }
+
+        // Auto-dismiss notification prompts once their timeout has elapsed
+        if app_state
+            .active_prompt
+            .as_ref()
+            .is_some_and(|p| p.is_expired(app_config.prompt_auto_dismiss(p.prompt_type)))
+        {
+            app_state.active_prompt = None;
+        }
+
+        // Carry out a request once its confirmation prompt has been answered
+        match app_state.take_confirmed() {
+            Some(PendingConfirmation::Duplicate(request)) => {
+                let (cols, rows) = backend.dimensions();
+                crate::input::keyboard_handlers::create_duplicate_window(
+                    app_state,
+                    window_manager,
+                    cols,
+                    rows,
+                    app_config.tiling_gaps,
+                    request,
+                );
+            }
+            Some(PendingConfirmation::Signal(request))
+                if window_manager.get_focus() == FocusState::Window(request.window_id) =>
+            {
+                crate::input::keyboard_handlers::send_signal_to_focused(
+                    app_state,
+                    window_manager,
+                    backend.as_ref(),
+                    request.signal,
+                );
+            }
+            Some(PendingConfirmation::ForceClose(request)) => {
+                window_manager.force_close_window(request.window_id);
+            }
+            Some(PendingConfirmation::Launch(request)) => {
+                command_history.record_command(&request.command);
+                crate::ui::dialog_handlers::launch_command(
+                    app_state,
+                    window_manager,
+                    backend.as_ref(),
+                    app_config.tiling_gaps,
+                    request.command,
+                );
+            }
+            Some(PendingConfirmation::FallbackShell(request)) => {
+                let (cols, rows) = backend.dimensions();
+                crate::input::keyboard_handlers::create_terminal_window_in_dir(
+                    app_state,
+                    window_manager,
+                    cols,
+                    rows,
+                    false,
+                    app_config.tiling_gaps,
+                    Some(crate::app::app_state::FALLBACK_SHELL.to_string()),
+                    request.working_dir,
+                );
+            }
+            Some(PendingConfirmation::Paste(request)) => {
+                crate::input::keyboard_handlers::send_paste_request(window_manager, &request, true);
+            }
+            _ => {}
+        }
+
         // Check if backend was resized and recreate buffer if needed
         if let Some((new_cols, new_rows)) = backend.check_resize()? {
             // Clear the terminal screen to remove artifacts
@@ -106,21 +224,44 @@ pub fn run(
             *video_buffer = crate::app::initialization::initialize_video_buffer(backend.as_ref());
             app_state.update_auto_tiling_button_position(new_rows);
 
-            // Update mouse input manager bounds for the new size
-            mouse_input_manager.set_bounds(new_cols, new_rows);
+            // Update mouse input manager bounds for the new size (visible rows only)
+            mouse_input_manager
+                .set_bounds(new_cols, new_rows.saturating_sub(2 * backend.hidden_rows()));
 
             // Reposition windows to fit the new screen dimensions
             if app_state.auto_tiling_enabled {
                 window_manager.auto_position_windows(new_cols, new_rows, app_config.tiling_gaps);
             } else {
-                // Clamp windows to new screen bounds
-                window_manager.clamp_windows_to_bounds(new_cols, new_rows);
+                // Maximized windows follow the new size (e.g. bars hidden or shown)
+                window_manager.refit_maximized_windows(new_cols, new_rows, app_config.tiling_gaps);
+
+                if app_config.is_small_screen(new_cols, new_rows) {
+                    // Below the small screen threshold: apply the configured policy
+                    window_manager.fit_windows_to_small_screen(
+                        new_cols,
+                        new_rows,
+                        app_config.small_screen_policy,
+                    );
+                } else {
+                    // Clamp windows to new screen bounds
+                    window_manager.clamp_windows_to_bounds(new_cols, new_rows);
+                }
             }
         }
 
         // Get current dimensions from backend
         let (cols, _rows) = backend.dimensions();
 
+        // Detect the focused app grabbing the mouse
+        if app_state.update_mouse_tracking(window_manager.focused_receives_mouse())
+            && app_state.selection_active
+        {
+            // The app now owns mouse events - abandon any in-progress drag selection
+            app_state.selection_active = false;
+            app_state.auto_scroll_direction = None;
+            app_state.last_auto_scroll_time = None;
+        }
+
         // Update clipboard buttons state and position
         let has_clipboard_content = clipboard_manager.has_content();
         let has_selection = window_manager.focused_window_has_meaningful_selection();
@@ -140,129 +281,158 @@ pub fn run(
             has_selection,
         )?;
 
+        // Send a queued OSC 52 copy now that the frame is flushed
+        if let Some(escape) = clipboard_manager.take_osc52() {
+            backend.send_host_escape(&escape);
+        }
+
+        // Notify when a command finishes in a background window
+        let finished = window_manager.take_finished_background_commands();
+        if app_config.notify_on_command_complete {
+            if let Some((title, command)) = finished.last() {
+                app_state.active_toast = Some(crate::ui::toast::Toast::new(format!(
+                    "{}: {} finished",
+                    title, command
+                )));
+                // Ring the host terminal bell
+                backend.bell();
+            }
+        }
+
+        // Bells: mark background windows and/or ring the host terminal bell
+        if app_config.bell != crate::app::config_manager::BellMode::Off
+            && window_manager.take_bells(app_config.bell.indicator())
+            && app_config.bell.audible()
+        {
+            backend.bell();
+        }
+
         // Auto-reposition remaining windows if any were closed
         if windows_closed && app_state.auto_tiling_enabled {
             let (cols, rows) = backend.dimensions();
             window_manager.auto_position_windows(cols, rows, app_config.tiling_gaps);
         }
 
-        // Poll unified mouse input manager for raw input events (TTY mode only)
+        // Queue raw mouse events from the unified mouse input manager (TTY mode only)
         // Skip this for framebuffer mode - it has its own native mouse input
-        let raw_mouse_event =
-            if mouse_input_manager.uses_raw_input() && !backend.has_native_mouse_input() {
-                if let Ok(Some(event)) = mouse_input_manager.poll_event() {
-                    // Update TTY cursor position for display
-                    let (cursor_col, cursor_row) = mouse_input_manager.cursor_position();
-                    backend.set_tty_cursor(cursor_col, cursor_row);
-                    Some(Event::Mouse(event))
-                } else {
-                    None
-                }
-            } else if !backend.has_native_mouse_input() {
-                // In terminal emulator mode, clear any TTY cursor
-                backend.clear_tty_cursor();
-                None
-            } else {
-                // Framebuffer mode handles mouse input natively
-                None
-            };
-
-        // Process raw mouse event if available (from MouseInputManager)
-        #[cfg(target_os = "linux")]
-        let mut injected_event: Option<Event> = raw_mouse_event;
-        #[cfg(not(target_os = "linux"))]
-        let _injected_event: Option<Event> = raw_mouse_event;
-
-        // Process framebuffer mouse events if available
-        #[cfg(all(target_os = "linux", feature = "framebuffer-backend"))]
-        if injected_event.is_none() {
-            if let Some((event_type, button_id, col, row)) = backend.get_mouse_button_event() {
-                injected_event = Some(crate::input::mouse_handlers::map_fb_button_event(
-                    event_type,
-                    button_id,
-                    col,
-                    row,
-                    fb_config.mouse.swap_buttons,
-                ));
+        if mouse_input_manager.uses_raw_input() && !backend.has_native_mouse_input() {
+            for _ in 0..input_queue.frame_capacity() {
+                let Ok(Some(event)) = mouse_input_manager.poll_event() else {
+                    break;
+                };
+                // Update TTY cursor position for display (mouse input reveals it again)
+                app_state.mouse_cursor_hidden = false;
+                let (cursor_col, cursor_row) = mouse_input_manager.cursor_position();
+                backend.set_tty_cursor(cursor_col, cursor_row);
+                // Only Linux handles raw events (elsewhere they just move the TTY cursor)
+                #[cfg(target_os = "linux")]
+                input_queue.push(Event::Mouse(event), true);
+                #[cfg(not(target_os = "linux"))]
+                let _ = event;
             }
+        } else if !backend.has_native_mouse_input() {
+            // In terminal emulator mode, clear any TTY cursor
+            backend.clear_tty_cursor();
         }
 
+        // Queue framebuffer mouse events
         #[cfg(all(target_os = "linux", feature = "framebuffer-backend"))]
-        if injected_event.is_none() {
-            if let Some((scroll_direction, col, row)) = backend.get_mouse_scroll_event() {
-                injected_event = Some(crate::input::mouse_handlers::map_fb_scroll_event(
-                    scroll_direction,
-                    col,
-                    row,
-                ));
+        {
+            while let Some((event_type, button_id, col, row)) = backend.get_mouse_button_event() {
+                input_queue.push(
+                    crate::input::mouse_handlers::map_fb_button_event(
+                        event_type,
+                        button_id,
+                        col,
+                        row,
+                        fb_config.mouse.swap_buttons,
+                    ),
+                    true,
+                );
+            }
+            while let Some((scroll_direction, col, row)) = backend.get_mouse_scroll_event() {
+                input_queue.push(
+                    crate::input::mouse_handlers::map_fb_scroll_event(scroll_direction, col, row),
+                    true,
+                );
             }
         }
 
-        // Process all available events before next frame (batch processing for responsiveness)
-        const MAX_EVENTS_PER_FRAME: usize = 50;
-        let mut events_processed = 0;
-        let mut should_break_main_loop = false;
-
-        while events_processed < MAX_EVENTS_PER_FRAME {
-            // Windows: read from dedicated input thread via channel
-            #[cfg(target_os = "windows")]
-            let current_event = {
-                if events_processed == 0 {
-                    // First iteration: wait briefly for an event
-                    match input_rx.recv_timeout(Duration::from_millis(16)) {
-                        Ok(evt) => evt,
-                        Err(_) => break, // No event within timeout
-                    }
-                } else {
-                    // Subsequent: non-blocking check for more events
-                    match input_rx.try_recv() {
-                        Ok(evt) => evt,
-                        Err(TryRecvError::Empty) => break,
-                        Err(TryRecvError::Disconnected) => break,
-                    }
-                }
+        // Queue pending crossterm events, waiting up to a frame (~60fps) when nothing is queued
+        #[cfg(not(target_os = "windows"))]
+        {
+            let mut timeout = if input_queue.is_empty() {
+                Duration::from_millis(16)
+            } else {
+                Duration::ZERO
             };
-            #[cfg(target_os = "windows")]
-            let is_injected = false;
-            #[cfg(target_os = "windows")]
-            {
-                events_processed += 1;
+            for _ in 0..input_queue.frame_capacity() {
+                if !event::poll(timeout)? {
+                    break;
+                }
+                input_queue.push(event::read()?, false);
+                timeout = Duration::ZERO;
             }
+        }
 
-            // Non-Windows: use standard poll/read
-            #[cfg(not(target_os = "windows"))]
-            let poll_timeout = if events_processed == 0 {
-                Duration::from_millis(16) // ~60fps frame timing
+        // Windows: read from dedicated input thread via channel
+        #[cfg(target_os = "windows")]
+        {
+            let mut next = if input_queue.is_empty() {
+                input_rx.recv_timeout(Duration::from_millis(16)).ok()
             } else {
-                Duration::from_millis(0) // Non-blocking for subsequent events
+                input_rx.try_recv().ok()
             };
+            let mut read = 0;
+            while let Some(evt) = next {
+                input_queue.push(evt, false);
+                read += 1;
+                next = if read < input_queue.frame_capacity() {
+                    input_rx.try_recv().ok()
+                } else {
+                    None
+                };
+            }
+        }
 
-            #[cfg(all(not(target_os = "windows"), target_os = "linux"))]
-            let has_event = injected_event.is_some() || event::poll(poll_timeout)?;
-            #[cfg(all(not(target_os = "windows"), not(target_os = "linux")))]
-            let has_event = event::poll(poll_timeout)?;
+        // Handle this frame's events: with the default `Keys` priority every pending key
+        // comes before the mouse events, and typed input is flushed to the PTYs before the
+        // first mouse event so a click or drag never overtakes a keystroke
+        let mut should_break_main_loop = false;
+        let mut unflushed_keys = false;
 
-            #[cfg(not(target_os = "windows"))]
-            if !has_event {
-                break; // No more events available
+        for QueuedEvent {
+            event: current_event,
+            injected: is_injected,
+            ..
+        } in input_queue.next_batch()
+        {
+            if !matches!(current_event, Event::Mouse(_)) {
+                unflushed_keys = true;
+            } else if unflushed_keys {
+                window_manager.flush_all_terminal_input();
+                unflushed_keys = false;
             }
 
-            events_processed += 1;
+            // Mouse capture off: the host terminal owns the mouse, drop anything still in flight
+            if !app_state.mouse_capture_enabled && matches!(current_event, Event::Mouse(_)) {
+                continue;
+            }
 
-            // Track whether this event is injected (raw/FB) to avoid double-scaling
-            #[cfg(all(not(target_os = "windows"), target_os = "linux"))]
-            let is_injected = injected_event.is_some();
-            #[cfg(all(not(target_os = "windows"), not(target_os = "linux")))]
-            let is_injected = false;
+            // Any key or mouse input dismisses the screensaver and is still handled below
+            if matches!(
+                current_event,
+                Event::Key(_) | Event::Mouse(_) | Event::Paste(_)
+            ) {
+                app_state.note_user_input();
+            }
 
-            #[cfg(all(not(target_os = "windows"), target_os = "linux"))]
-            let current_event = if let Some(evt) = injected_event.take() {
-                evt
-            } else {
-                event::read()?
-            };
-            #[cfg(all(not(target_os = "windows"), not(target_os = "linux")))]
-            let current_event = event::read()?;
+            // Typing hides the mouse pointer drawn by term39 until the mouse moves again
+            // (only the drawing: mouse reporting to applications is unaffected)
+            if app_config.auto_hide_cursor && matches!(current_event, Event::Key(_)) {
+                app_state.mouse_cursor_hidden = true;
+                backend.clear_tty_cursor();
+            }
 
             match current_event {
                 Event::Key(key_event) => {
@@ -317,6 +487,102 @@ pub fn run(
                         continue;
                     }
 
+                    // CTRL+SHIFT+O - Global toggle handing the mouse back to the host terminal
+                    // (e.g. for its native text selection); works even inside a terminal window
+                    if matches_any(
+                        &keybinding_profile.toggle_mouse_capture,
+                        key_event.code,
+                        key_event.modifiers,
+                    ) {
+                        let enabled = !app_state.mouse_capture_enabled;
+                        app_state.mouse_capture_enabled = enabled;
+                        crate::app::initialization::set_mouse_capture(stdout, enabled)?;
+                        mouse_input_manager.set_paused(!enabled);
+                        if !enabled {
+                            // Abandon any drag selection the mouse was in the middle of
+                            app_state.selection_active = false;
+                            app_state.auto_scroll_direction = None;
+                            app_state.last_auto_scroll_time = None;
+                        }
+                        let message = if enabled {
+                            "Mouse capture on"
+                        } else {
+                            "Mouse capture off: the host terminal gets the mouse"
+                        };
+                        app_state.active_toast = Some(crate::ui::toast::Toast::new(message));
+                        continue;
+                    }
+
+                    // CTRL+SHIFT+A - Global cycle through the charset modes, e.g. to drop to
+                    // ASCII over a link that mangles box drawing characters
+                    if matches_any(
+                        &keybinding_profile.cycle_charset,
+                        key_event.code,
+                        key_event.modifiers,
+                    ) {
+                        let mode = charset.mode.next();
+                        let background = charset.background;
+                        *charset = crate::rendering::Charset::from_mode(mode);
+                        charset.set_background(background);
+                        app_config.charset = mode.into();
+                        app_state.charset_mode_changed = true;
+
+                        // Repaint every cell, leftovers of mangled glyphs included
+                        use crossterm::execute;
+                        execute!(stdout, terminal::Clear(ClearType::All))?;
+                        *video_buffer =
+                            crate::app::initialization::initialize_video_buffer(backend.as_ref());
+                        app_state.active_toast = Some(crate::ui::toast::Toast::new(format!(
+                            "Charset: {}",
+                            mode.name()
+                        )));
+                        continue;
+                    }
+
+                    // CTRL+SHIFT+N - Global night dim, cycling through a few brightness levels
+                    if matches_any(
+                        &keybinding_profile.cycle_dim,
+                        key_event.code,
+                        key_event.modifiers,
+                    ) {
+                        app_state.dim_percent =
+                            crate::rendering::next_dim_level(app_state.dim_percent);
+                        let message = match app_state.dim_percent {
+                            0 => "Dim off".to_string(),
+                            percent => format!("Dim: {}%", percent),
+                        };
+                        app_state.active_toast = Some(crate::ui::toast::Toast::new(message));
+                        continue;
+                    }
+
+                    // CTRL+SHIFT+S - Global drop-down scratchpad (created on first use)
+                    if matches_any(
+                        &keybinding_profile.toggle_scratchpad,
+                        key_event.code,
+                        key_event.modifiers,
+                    ) {
+                        let (cols, rows) = backend.dimensions();
+                        if let Err(error_msg) = window_manager.toggle_scratchpad(cols, rows) {
+                            app_state.active_error_dialog = Some(
+                                crate::ui::error_dialog::ErrorDialog::new(cols, rows, error_msg),
+                            );
+                        }
+                        continue;
+                    }
+
+                    // F11 - Global shortcut to hide/show the top and bottom bars
+                    // The resulting size change re-tiles or clamps the windows
+                    if matches_any(
+                        &keybinding_profile.toggle_bars,
+                        key_event.code,
+                        key_event.modifiers,
+                    ) {
+                        app_config.bars_hidden = !app_config.bars_hidden;
+                        backend.set_bars_hidden(app_config.bars_hidden);
+                        let _ = app_config.save();
+                        continue;
+                    }
+
                     // Dismiss toast on any key press (if active and not just created)
                     // Check if toast was created more than 100ms ago to avoid dismissing
                     // toasts that were just created by the same key press
@@ -339,9 +605,11 @@ pub fn run(
 
                     // Handle close confirmation keyboard events (window-specific modal)
                     if let FocusState::Window(window_id) = current_focus {
-                        if let Some(should_close) =
-                            window_manager.handle_close_confirmation_key(window_id, key_event)
-                        {
+                        if let Some(should_close) = window_manager.handle_close_confirmation_key(
+                            window_id,
+                            key_event,
+                            app_config.close_confirm_enter,
+                        ) {
                             if should_close {
                                 // User confirmed close
                                 window_manager.close_window(window_id);
@@ -365,6 +633,11 @@ pub fn run(
                         continue;
                     }
 
+                    // Handle Escape on open popup menus
+                    if crate::ui::dialog_handlers::handle_menu_keyboard(app_state, key_event) {
+                        continue;
+                    }
+
                     // Handle PIN setup dialog keyboard events
                     if let Some(ref mut pin_setup) = app_state.active_pin_setup {
                         pin_setup.handle_key(key_event);
@@ -391,7 +664,7 @@ pub fn run(
                         command_history,
                         window_manager,
                         backend.as_ref(),
-                        app_config.tiling_gaps,
+                        app_config,
                     ) {
                         continue;
                     }
@@ -431,6 +704,29 @@ pub fn run(
                         continue;
                     }
 
+                    // Handle expose overview keyboard events (number selects, ESC returns)
+                    if crate::ui::dialog_handlers::handle_expose_keyboard(
+                        app_state,
+                        key_event,
+                        window_manager,
+                    ) {
+                        continue;
+                    }
+
+                    // Handle key pass-through and forwarded Alt+letter (bypass shortcuts)
+                    if !app_state.keyboard_mode.is_window_mode()
+                        && crate::input::keyboard_handlers::handle_key_passthrough(
+                            app_state,
+                            key_event,
+                            current_focus,
+                            window_manager,
+                            app_config,
+                            keybinding_profile,
+                        )
+                    {
+                        continue;
+                    }
+
                     // Handle Window Mode keyboard events (vim-like window control)
                     if crate::window::mode_handlers::handle_window_mode_keyboard(
                         app_state,
@@ -498,6 +794,9 @@ pub fn run(
                         mouse_event.column = scaled_col;
                         mouse_event.row = scaled_row;
                     }
+                    // Screen rows to buffer rows (the top bar row is hidden with the bars)
+                    mouse_event.row += backend.hidden_rows();
+                    app_state.pointer_position = Some((mouse_event.column, mouse_event.row));
 
                     let (_, rows) = backend.dimensions();
                     let bar_y = rows - 1;
@@ -566,6 +865,13 @@ pub fn run(
                         handled = true;
                     }
 
+                    // Handle expose overview mouse events
+                    if !handled
+                        && handle_expose_mouse(app_state, window_manager, &mouse_event, cols, rows)
+                    {
+                        handled = true;
+                    }
+
                     // Update button hover states (always active)
                     if !handled {
                         // Get clipboard and selection state for hover updates
@@ -618,40 +924,82 @@ pub fn run(
                         && app_state.active_prompt.is_none()
                         && mouse_event.kind == MouseEventKind::Down(MouseButton::Left)
                     {
-                        // Calculate where window buttons start (after auto-tiling button)
-                        // Format: "[label ]" + 2 spaces
-                        let window_buttons_start =
-                            1 + 1 + app_state.auto_tiling_button.label.len() as u16 + 1 + 2;
-
+                        // Area of the window buttons (after the auto-tiling button)
                         handled = window_manager
                             .button_bar_click(
                                 mouse_event.column,
                                 bar_y,
                                 mouse_event.row,
-                                window_buttons_start,
+                                app_state.window_buttons_start(),
+                                app_state.window_buttons_end(cols),
                             )
                             .is_some();
                     }
 
                     // Handle right-click on button bar for taskbar context menu
                     if !handled && app_state.active_prompt.is_none() {
-                        let window_buttons_start =
-                            1 + 1 + app_state.auto_tiling_button.label.len() as u16 + 1 + 2;
+                        let window_buttons = (
+                            app_state.window_buttons_start(),
+                            app_state.window_buttons_end(cols),
+                        );
                         if show_taskbar_menu(
                             app_state,
                             window_manager,
                             &mouse_event,
                             bar_y,
-                            window_buttons_start,
+                            window_buttons,
                         ) {
                             handled = true;
                         }
                     }
 
+                    // Handle desktop menu interactions
+                    if !handled
+                        && handle_desktop_menu_mouse(
+                            app_state,
+                            window_manager,
+                            &mouse_event,
+                            cols,
+                            rows,
+                            app_config.tiling_gaps,
+                        )
+                    {
+                        handled = true;
+                    }
+
+                    // Handle configurable click actions on the empty desktop
+                    if !handled
+                        && app_state.active_prompt.is_none()
+                        && !app_state.context_menu.visible
+                        && !app_state.taskbar_menu.visible
+                        && handle_desktop_click(
+                            app_state,
+                            window_manager,
+                            clipboard_manager,
+                            command_history,
+                            app_config,
+                            &mouse_event,
+                            cols,
+                            rows,
+                        )
+                    {
+                        handled = true;
+                    }
+
+                    // Handle configurable wheel actions on the empty desktop and the top bar
+                    if !handled
+                        && app_state.active_prompt.is_none()
+                        && !app_state.context_menu.visible
+                        && !app_state.taskbar_menu.visible
+                        && handle_desktop_scroll(window_manager, app_config, &mouse_event, rows)
+                    {
+                        handled = true;
+                    }
+
                     // Handle right-click for context menu (inside windows)
                     if !handled
                         && app_state.active_prompt.is_none()
-                        && show_context_menu(app_state, window_manager, &mouse_event)
+                        && show_context_menu(app_state, window_manager, &mouse_event, cols, rows)
                     {
                         handled = true;
                     }
@@ -663,6 +1011,8 @@ pub fn run(
                             window_manager,
                             clipboard_manager,
                             &mouse_event,
+                            cols,
+                            rows,
                         )
                     {
                         handled = true;
@@ -689,6 +1039,8 @@ pub fn run(
                             window_manager,
                             clipboard_manager,
                             &mouse_event,
+                            cols,
+                            rows,
                         ) {
                             SystemMenuResult::Handled => handled = true,
                             SystemMenuResult::ShowSettings => {
@@ -715,46 +1067,38 @@ pub fn run(
                                 handled = true;
                             }
                             SystemMenuResult::ShowExitPrompt => {
-                                // Build exit confirmation message
-                                let window_count = window_manager.window_count();
-                                let message = if window_count > 0 {
-                                    format!(
-                                        "You have {} open terminal{}. Are you sure you want to exit?",
-                                        window_count,
-                                        if window_count == 1 { "" } else { "s" }
-                                    )
-                                } else {
-                                    "Are you sure you want to exit?".to_string()
-                                };
-
-                                let mut buttons = vec![
-                                    PromptButton::new(
-                                        "Cancel".to_string(),
-                                        PromptAction::Cancel,
-                                        false,
-                                    ),
-                                    PromptButton::new(
-                                        "Exit".to_string(),
-                                        PromptAction::Confirm,
-                                        true,
-                                    ),
-                                ];
-                                // Add "Exit & Kill Daemon" option when persist mode is active and enabled
-                                #[cfg(unix)]
-                                if app_config.persist_enabled && window_manager.has_persist_client()
+                                crate::input::keyboard_handlers::show_exit_prompt(
+                                    app_state,
+                                    window_manager,
+                                    backend.as_ref(),
+                                    app_config,
+                                );
+                                handled = true;
+                            }
+                            SystemMenuResult::OpenTerminal(command) => {
+                                let maximized = app_state.auto_tiling_enabled
+                                    && window_manager.window_count() == 0;
+                                crate::input::keyboard_handlers::create_terminal_window_with_command(
+                                    app_state,
+                                    window_manager,
+                                    cols,
+                                    rows,
+                                    maximized,
+                                    app_config.tiling_gaps,
+                                    command,
+                                );
+                                handled = true;
+                            }
+                            SystemMenuResult::LockScreen => {
+                                if app_config.lockscreen_enabled
+                                    && app_state.lockscreen.is_available()
                                 {
-                                    buttons.push(PromptButton::new(
-                                        "Exit & Kill Daemon".to_string(),
-                                        PromptAction::Custom(1),
-                                        true,
+                                    app_state.lockscreen.lock();
+                                } else {
+                                    app_state.active_toast = Some(crate::ui::toast::Toast::new(
+                                        "To lock the screen, configure in Settings",
                                     ));
                                 }
-
-                                app_state.active_prompt = Some(
-                                    Prompt::new(PromptType::Danger, message, buttons, cols, rows)
-                                        .with_selection_indicators(true)
-                                        .with_selected_button(0),
-                                );
                                 handled = true;
                             }
                             SystemMenuResult::NotHandled => {}
@@ -812,7 +1156,7 @@ pub fn run(
                 }
                 _ => {}
             }
-        } // End of while events loop
+        } // End of events loop
 
         // Process auto-scroll during selection (frame-based timing)
         if app_state.selection_active {
@@ -857,6 +1201,12 @@ pub fn run(
                         app_state.active_toast =
                             Some(crate::ui::toast::Toast::new("Daemon connection lost"));
                     }
+                    crate::window::manager::PersistEvent::SignalSent(signal) => {
+                        app_state.active_toast = Some(crate::ui::toast::Toast::new(format!(
+                            "Sent {} to foreground job",
+                            signal.name()
+                        )));
+                    }
                     crate::window::manager::PersistEvent::Error(msg) => {
                         app_state.active_toast =
                             Some(crate::ui::toast::Toast::new(format!("Daemon: {msg}")));
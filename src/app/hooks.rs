@@ -0,0 +1,317 @@
+//! User commands run on window events (the `[hooks]` table in config.toml)
+//! Each command runs through the shell, detached so it never blocks rendering, with
+//! the event described in `TERM39_EVENT`, `TERM39_WINDOW_ID` and `TERM39_WINDOW_TITLE`
+
+use crate::app::config_manager::HookConfig;
+use std::process::{Command, Stdio};
+
+/// A lifecycle event hooks can react to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowEvent {
+    Open {
+        id: u32,
+        title: String,
+    },
+    Close {
+        id: u32,
+        title: String,
+    },
+    /// Focus moved to another window (None when the desktop took focus)
+    FocusChange {
+        window: Option<(u32, String)>,
+    },
+    Lock,
+    Unlock,
+}
+
+impl WindowEvent {
+    /// Value of `TERM39_EVENT`
+    pub fn name(&self) -> &'static str {
+        match self {
+            WindowEvent::Open { .. } => "window-open",
+            WindowEvent::Close { .. } => "window-close",
+            WindowEvent::FocusChange { .. } => "focus-change",
+            WindowEvent::Lock => "lock",
+            WindowEvent::Unlock => "unlock",
+        }
+    }
+
+    /// Window the event is about, if any
+    fn window(&self) -> Option<(u32, &str)> {
+        match self {
+            WindowEvent::Open { id, title } | WindowEvent::Close { id, title } => {
+                Some((*id, title.as_str()))
+            }
+            WindowEvent::FocusChange { window } => {
+                window.as_ref().map(|(id, title)| (*id, title.as_str()))
+            }
+            WindowEvent::Lock | WindowEvent::Unlock => None,
+        }
+    }
+
+    /// Command configured for this event (empty when unset)
+    fn command<'a>(&self, config: &'a HookConfig) -> &'a str {
+        match self {
+            WindowEvent::Open { .. } => &config.on_window_open,
+            WindowEvent::Close { .. } => &config.on_window_close,
+            WindowEvent::FocusChange { .. } => &config.on_focus_change,
+            WindowEvent::Lock => &config.on_lock,
+            WindowEvent::Unlock => &config.on_unlock,
+        }
+    }
+}
+
+/// Turns snapshots of the window list into events by comparing each one with the last
+/// The first snapshot only records a baseline, so restored windows don't fire "open"
+#[derive(Debug, Default)]
+pub struct WindowEventWatcher {
+    /// Window ids and titles of the last snapshot, in snapshot order
+    windows: Vec<(u32, String)>,
+    focused: Option<u32>,
+    locked: bool,
+    primed: bool,
+}
+
+impl WindowEventWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compare `windows` (id, title, is_focused, is_minimized) and the lock state with the
+    /// previous snapshot and return what changed, in the order hooks should run
+    pub fn observe(
+        &mut self,
+        windows: &[(u32, &str, bool, bool)],
+        locked: bool,
+    ) -> Vec<WindowEvent> {
+        let focused = windows.iter().find(|w| w.2).map(|w| w.0);
+        // Most frames change nothing: compare in place and keep the stored snapshot
+        let unchanged = self.primed
+            && locked == self.locked
+            && focused == self.focused
+            && windows.len() == self.windows.len()
+            && windows
+                .iter()
+                .zip(&self.windows)
+                .all(|(w, (id, title))| w.0 == *id && w.1 == title);
+        if unchanged {
+            return Vec::new();
+        }
+        let mut events = Vec::new();
+
+        if self.primed {
+            if locked && !self.locked {
+                events.push(WindowEvent::Lock);
+            }
+            for (id, title, ..) in windows {
+                if !self.windows.iter().any(|(known, _)| known == id) {
+                    events.push(WindowEvent::Open {
+                        id: *id,
+                        title: title.to_string(),
+                    });
+                }
+            }
+            let mut closed: Vec<&(u32, String)> = self
+                .windows
+                .iter()
+                .filter(|(id, _)| !windows.iter().any(|w| w.0 == *id))
+                .collect();
+            closed.sort_unstable_by_key(|(id, _)| *id);
+            for (id, title) in closed {
+                events.push(WindowEvent::Close {
+                    id: *id,
+                    title: title.clone(),
+                });
+            }
+            if focused != self.focused {
+                let window = focused.and_then(|id| {
+                    windows
+                        .iter()
+                        .find(|w| w.0 == id)
+                        .map(|w| (id, w.1.to_string()))
+                });
+                events.push(WindowEvent::FocusChange { window });
+            }
+            if !locked && self.locked {
+                events.push(WindowEvent::Unlock);
+            }
+        }
+
+        self.windows = windows
+            .iter()
+            .map(|(id, title, ..)| (*id, title.to_string()))
+            .collect();
+        self.focused = focused;
+        self.locked = locked;
+        self.primed = true;
+        events
+    }
+}
+
+/// Run the configured command for each event (nothing when hooks are disabled)
+pub fn run_hooks(config: &HookConfig, events: &[WindowEvent]) {
+    if !config.enabled {
+        return;
+    }
+    for event in events {
+        let command = event.command(config).trim();
+        if !command.is_empty() {
+            spawn_detached(command, event);
+        }
+    }
+}
+
+/// Start `command` through the shell with the event in its environment, without waiting
+fn spawn_detached(command: &str, event: &WindowEvent) {
+    #[cfg(unix)]
+    let mut cmd = {
+        use std::os::unix::process::CommandExt;
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(command);
+        // Own process group so keys meant for term39 windows never reach the hook
+        cmd.process_group(0);
+        cmd
+    };
+    #[cfg(windows)]
+    let mut cmd = {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    };
+
+    let (id, title) = event
+        .window()
+        .map(|(id, title)| (id.to_string(), title.to_string()))
+        .unwrap_or_default();
+    cmd.env("TERM39_EVENT", event.name())
+        .env("TERM39_WINDOW_ID", id)
+        .env("TERM39_WINDOW_TITLE", title)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+
+    // Reap the child on a background thread so finished hooks don't linger as zombies
+    if let Ok(mut child) = cmd.spawn() {
+        std::thread::spawn(move || {
+            let _ = child.wait();
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_snapshot_is_baseline() {
+        let mut watcher = WindowEventWatcher::new();
+        assert!(
+            watcher
+                .observe(&[(1, "Terminal 1", true, false)], false)
+                .is_empty()
+        );
+        assert!(
+            watcher
+                .observe(&[(1, "Terminal 1", true, false)], false)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_open_close_and_focus_events() {
+        let mut watcher = WindowEventWatcher::new();
+        watcher.observe(&[(1, "Terminal 1", true, false)], false);
+
+        let events = watcher.observe(
+            &[
+                (1, "Terminal 1", false, false),
+                (2, "Terminal 2", true, false),
+            ],
+            false,
+        );
+        assert_eq!(
+            events,
+            vec![
+                WindowEvent::Open {
+                    id: 2,
+                    title: "Terminal 2".to_string()
+                },
+                WindowEvent::FocusChange {
+                    window: Some((2, "Terminal 2".to_string()))
+                },
+            ]
+        );
+
+        // Closing the focused window reports its last title and the desktop taking focus
+        let events = watcher.observe(&[(1, "Terminal 1", false, false)], false);
+        assert_eq!(
+            events,
+            vec![
+                WindowEvent::Close {
+                    id: 2,
+                    title: "Terminal 2".to_string()
+                },
+                WindowEvent::FocusChange { window: None },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_retitled_window_closes_with_new_title() {
+        let mut watcher = WindowEventWatcher::new();
+        watcher.observe(&[(1, "bash", false, false)], false);
+        // A new title alone fires nothing, but is what the close event reports
+        assert!(
+            watcher
+                .observe(&[(1, "vim", false, false)], false)
+                .is_empty()
+        );
+        assert_eq!(
+            watcher.observe(&[], false),
+            vec![WindowEvent::Close {
+                id: 1,
+                title: "vim".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_lock_and_unlock_events() {
+        let mut watcher = WindowEventWatcher::new();
+        watcher.observe(&[], false);
+        assert_eq!(watcher.observe(&[], true), vec![WindowEvent::Lock]);
+        assert!(watcher.observe(&[], true).is_empty());
+        assert_eq!(watcher.observe(&[], false), vec![WindowEvent::Unlock]);
+    }
+
+    #[test]
+    fn test_hook_commands_per_event() {
+        let config = HookConfig {
+            enabled: true,
+            on_window_open: "notify-send open".to_string(),
+            on_lock: "pause-music".to_string(),
+            ..HookConfig::default()
+        };
+        let open = WindowEvent::Open {
+            id: 3,
+            title: "vim".to_string(),
+        };
+        assert_eq!(open.command(&config), "notify-send open");
+        assert_eq!(open.window(), Some((3, "vim")));
+        assert_eq!(WindowEvent::Lock.command(&config), "pause-music");
+        assert_eq!(WindowEvent::Unlock.command(&config), "");
+        assert_eq!(WindowEvent::Lock.window(), None);
+    }
+
+    #[test]
+    fn test_hooks_table_round_trips() {
+        let mut config = crate::app::AppConfig::default();
+        config.hooks.enabled = true;
+        config.hooks.on_focus_change = "echo $TERM39_WINDOW_ID".to_string();
+        let toml_string = toml::to_string_pretty(&config).unwrap();
+        assert!(toml_string.contains("[hooks]"));
+        let parsed: crate::app::AppConfig = toml::from_str(&toml_string).unwrap();
+        assert!(parsed.hooks.enabled);
+        assert_eq!(parsed.hooks.on_focus_change, config.hooks.on_focus_change);
+    }
+}
@@ -1,5 +1,6 @@
+use super::app_state::AppState;
 use super::cli::Cli;
-use super::config_manager::AppConfig;
+use super::config_manager::{AppConfig, StartupPlacement};
 use super::platform::is_console_environment;
 #[cfg(all(target_os = "linux", feature = "framebuffer-backend"))]
 use crate::framebuffer::fb_config::FramebufferConfig;
@@ -97,8 +98,19 @@ pub fn initialize_backend(
             invert_y,
             sensitivity,
         ) {
-            Ok(fb_backend) => {
+            Ok(mut fb_backend) => {
                 println!("Framebuffer backend initialized: {}", mode_kind);
+
+                // Load the smaller font used by dense windows (per-window font override)
+                let dense_mode = TextModeKind::from_str(&fb_config.font.dense_mode)
+                    .unwrap_or(TextModeKind::Mode80x50);
+                fb_backend.load_dense_font(
+                    TextMode::new(dense_mode),
+                    fb_config.font.dense_name.as_deref(),
+                );
+                fb_backend
+                    .set_crt_effect(fb_config.display.scanlines, fb_config.display.phosphor_glow);
+                fb_backend.set_cursor_trail(fb_config.display.cursor_trail);
                 return Ok(Box::new(fb_backend));
             }
             Err(e) => {
@@ -114,7 +126,9 @@ pub fn initialize_backend(
 }
 
 /// Sets up terminal modes and mouse capture
-pub fn setup_terminal(stdout: &mut io::Stdout) -> io::Result<()> {
+/// `init_mouse` initializes mouse input (and GPM) at the point the ordering requires;
+/// its result is returned
+pub fn setup_terminal<T>(stdout: &mut io::Stdout, init_mouse: impl FnOnce() -> T) -> io::Result<T> {
     // Enter raw mode for low-level terminal control
     terminal::enable_raw_mode()?;
 
@@ -137,40 +151,53 @@ pub fn setup_terminal(stdout: &mut io::Stdout) -> io::Result<()> {
         }
     }
 
-    // Enter alternate screen buffer to prevent scrolling the parent terminal
-    // Hide cursor and enable mouse capture
-    // Skip EnableMouseCapture on console/TTY since raw device input handles mouse there
-    let is_console = is_console_environment();
+    let keyboard_enhancement = terminal::supports_keyboard_enhancement().unwrap_or(false);
+    write_terminal_setup(
+        stdout,
+        is_console_environment(),
+        keyboard_enhancement,
+        init_mouse,
+    )
+}
 
-    if is_console {
-        // On console/TTY, raw input provides mouse support - don't send ANSI mouse escape sequences
-        execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
-    } else {
-        // On terminal emulators, enable mouse capture via ANSI escape sequences
-        execute!(
-            stdout,
-            terminal::EnterAlternateScreen,
-            cursor::Hide,
-            event::EnableMouseCapture
-        )?;
+/// Write the setup sequences in their required order, calling `init_mouse` (GPM and raw
+/// mouse devices) in between:
+///
+/// 1. Enter the alternate screen and hide the cursor
+/// 2. `init_mouse`: GPM must be set up before mouse reporting is requested, or the
+///    console and xterm-style reporting fight over the mouse
+/// 3. Mouse capture: DECSET 1000 (press/release), 1002 (drag), 1003 (all motion), then
+///    the 1015/1006 coordinate encodings; skipped on console/TTY, where raw device input
+///    handles the mouse
+/// 4. Keyboard enhancement (kitty protocol) when supported, so Shift+Enter and other
+///    modified keys can be told apart
+/// 5. Clear the screen
+fn write_terminal_setup<W: Write, T>(
+    out: &mut W,
+    is_console: bool,
+    keyboard_enhancement: bool,
+    init_mouse: impl FnOnce() -> T,
+) -> io::Result<T> {
+    execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let mouse = init_mouse();
+    if !is_console {
+        execute!(out, event::EnableMouseCapture)?;
     }
 
-    // Enable keyboard enhancement protocol if supported (kitty keyboard protocol)
-    // This allows distinguishing Shift+Enter from plain Enter, and other modified keys
-    if terminal::supports_keyboard_enhancement().unwrap_or(false) {
+    if keyboard_enhancement {
         queue!(
-            stdout,
+            out,
             event::PushKeyboardEnhancementFlags(
                 event::KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
             )
         )?;
-        stdout.flush()?;
+        out.flush()?;
     }
 
-    // Clear the screen
-    execute!(stdout, terminal::Clear(terminal::ClearType::All))?;
+    execute!(out, terminal::Clear(terminal::ClearType::All))?;
 
-    Ok(())
+    Ok(mouse)
 }
 
 /// Loads and configures charset based on CLI and config
@@ -180,7 +207,7 @@ pub fn initialize_charset(cli_args: &Cli, app_config: &AppConfig) -> Charset {
     } else if cli_args.single_line {
         Charset::unicode_single_line()
     } else {
-        Charset::unicode()
+        Charset::from_mode(app_config.charset.into())
     };
 
     // Set the background character from config
@@ -199,6 +226,7 @@ pub fn initialize_keybinding_profile(
         .as_ref()
         .unwrap_or(&app_config.keybinding_profile);
     crate::input::keybinding_profile::KeybindingProfile::from_name(profile_name)
+        .with_clipboard_shortcuts(app_config.clipboard_shortcuts)
 }
 
 /// Loads theme from CLI or config
@@ -209,23 +237,98 @@ pub fn initialize_theme(cli_args: &Cli, app_config: &AppConfig) -> Theme {
 }
 
 /// Validate shell configuration early (before terminal setup)
-/// This allows the warning to be visible to the user
+/// Problems are added to `warnings` so they can be shown before the terminal is taken over
 /// Returns the validated ShellConfig
-pub fn validate_shell_config(cli_args: &Cli) -> ShellConfig {
-    if let Some(ref shell_path) = cli_args.shell {
+pub fn validate_shell_config(
+    cli_args: &Cli,
+    app_config: &AppConfig,
+    warnings: &mut Vec<String>,
+) -> ShellConfig {
+    let config = if let Some(ref shell_path) = cli_args.shell {
         let config = ShellConfig::custom_shell(shell_path.clone());
         // Validate shell path exists and is executable
         if let Err(msg) = config.validate() {
-            eprintln!("Warning: {}, using system default shell", msg);
-            // Give user time to see the warning
-            std::thread::sleep(std::time::Duration::from_secs(2));
+            warnings.push(format!("{}, using system default shell", msg));
             ShellConfig::default()
         } else {
             config
         }
     } else {
         ShellConfig::default()
+    };
+    config
+        .with_exec_command(cli_args.exec.clone())
+        .with_working_dir(default_working_dir(cli_args, app_config, warnings))
+}
+
+/// Directory new windows start in: --cwd, else the default_cwd option (None when neither
+/// is set). A path that is not a directory falls back to the home directory with a warning
+fn default_working_dir(
+    cli_args: &Cli,
+    app_config: &AppConfig,
+    warnings: &mut Vec<String>,
+) -> Option<String> {
+    let path = cli_args
+        .cwd
+        .as_deref()
+        .unwrap_or(&app_config.default_cwd)
+        .trim();
+    if path.is_empty() {
+        return None;
+    }
+    let dir = match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().map(|home| home.join(rest)),
+        None if path == "~" => dirs::home_dir(),
+        None => Some(std::path::PathBuf::from(path)),
+    };
+    match dir {
+        Some(dir) if dir.is_dir() => Some(dir.to_string_lossy().into_owned()),
+        _ => {
+            warnings.push(format!(
+                "start directory '{}' not found, using home directory",
+                path
+            ));
+            dirs::home_dir().map(|home| home.to_string_lossy().into_owned())
+        }
+    }
+}
+
+/// Warns about malformed `[[startup_windows]]` entries when the config loads;
+/// open_startup_windows skips them
+pub fn validate_startup_windows(app_config: &AppConfig, warnings: &mut Vec<String>) {
+    for (index, entry) in app_config.startup_windows.iter().enumerate() {
+        if let Err(reason) = entry.placement() {
+            warnings.push(format!(
+                "startup window #{}: {}, skipped",
+                index + 1,
+                reason
+            ));
+        }
+    }
+}
+
+/// Warns when `cursor_color` is set but isn't a color spec; the cursor then keeps inverting
+/// the cell under it
+pub fn validate_cursor_color(app_config: &AppConfig, warnings: &mut Vec<String>) {
+    if !app_config.cursor_color.is_empty() && app_config.cursor_color().is_none() {
+        warnings.push(format!(
+            "invalid cursor_color '{}', using the default cursor",
+            app_config.cursor_color
+        ));
+    }
+}
+
+/// Print the configuration warnings collected at startup (before terminal setup, so
+/// they are visible), pausing once so the user can read them
+pub fn show_startup_warnings(warnings: &[String]) {
+    if warnings.is_empty() {
+        return;
+    }
+    for warning in warnings {
+        eprintln!("Warning: {}", warning);
     }
+    // Give user time to see the warnings
+    std::thread::sleep(std::time::Duration::from_secs(2));
 }
 
 /// Initializes or restores window manager
@@ -236,8 +339,11 @@ pub fn initialize_window_manager(
 ) -> io::Result<WindowManager> {
     let window_manager = if !cli_args.no_restore {
         // Try to restore session, fall back to new if it fails
-        let manager = WindowManager::restore_session_from_file(shell_config.clone())
-            .unwrap_or_else(|_| WindowManager::with_shell_config(shell_config));
+        let manager = WindowManager::restore_session_from_file(
+            shell_config.clone(),
+            app_config.session_trim_trailing_blanks,
+        )
+        .unwrap_or_else(|_| WindowManager::with_shell_config(shell_config));
 
         // If auto-save is disabled, clear session after loading (one-time load)
         if !app_config.auto_save {
@@ -252,6 +358,94 @@ pub fn initialize_window_manager(
     Ok(window_manager)
 }
 
+/// Opens the `[[startup_windows]]` from the config, in order, unless a session was restored
+/// Windows that can't be created are skipped and reported in a toast; with auto-tiling on,
+/// the windows are tiled unless an entry chose its own zone or geometry
+pub fn open_startup_windows(
+    app_state: &mut AppState,
+    window_manager: &mut WindowManager,
+    app_config: &AppConfig,
+    cols: u16,
+    rows: u16,
+) {
+    if app_config.startup_windows.is_empty() || window_manager.window_count() > 0 {
+        return;
+    }
+
+    let mut skipped = Vec::new();
+    let mut placed = false;
+    for (index, entry) in app_config.startup_windows.iter().enumerate() {
+        // Malformed entries were reported when the config loaded
+        let Ok(placement) = entry.placement() else {
+            continue;
+        };
+        placed |= placement.is_placed();
+
+        let (default_width, default_height) = WindowManager::calculate_window_size(cols, rows);
+        let (x, y, width, height) = match placement {
+            StartupPlacement::Snap(position) => position.calculate_rect(cols, rows, 1),
+            StartupPlacement::Maximized => {
+                let (x, y) = window_manager.next_window_position(
+                    default_width,
+                    default_height,
+                    cols,
+                    rows,
+                    None,
+                );
+                (x, y, default_width, default_height)
+            }
+            StartupPlacement::Rect {
+                x,
+                y,
+                width,
+                height,
+            } => {
+                let width = width.unwrap_or(default_width).min(cols);
+                let height = height
+                    .unwrap_or(default_height)
+                    .min(rows.saturating_sub(2).max(1));
+                let (next_x, next_y) =
+                    window_manager.next_window_position(width, height, cols, rows, None);
+                let (x, y) = crate::window::manager::clamp_window_position(
+                    x.unwrap_or(next_x),
+                    y.unwrap_or(next_y),
+                    width,
+                    height,
+                    cols,
+                    rows,
+                );
+                (x, y, width, height)
+            }
+        };
+
+        let title = match entry.title.trim() {
+            "" => format!("Terminal {}", window_manager.window_count() + 1),
+            title => title.to_string(),
+        };
+        let command = Some(entry.command.trim())
+            .filter(|command| !command.is_empty())
+            .map(str::to_string);
+        match window_manager.create_window(x, y, width, height, title, command) {
+            Ok(id) => {
+                if placement == StartupPlacement::Maximized {
+                    window_manager.maximize_window(id, cols, rows, app_config.tiling_gaps);
+                }
+            }
+            Err(error) => skipped.push(format!("#{}: {}", index + 1, error)),
+        }
+    }
+
+    if app_state.auto_tiling_enabled && !placed && window_manager.window_count() > 0 {
+        window_manager.auto_position_windows(cols, rows, app_config.tiling_gaps);
+    }
+    if !skipped.is_empty() {
+        app_state.active_toast = Some(crate::ui::toast::Toast::new(format!(
+            "Skipped startup windows {}",
+            skipped.join("; ")
+        )));
+    }
+}
+
 /// Creates a new video buffer for the given backend dimensions
 pub fn initialize_video_buffer(backend: &dyn RenderBackend) -> VideoBuffer {
     let (cols, rows) = backend.dimensions();
@@ -393,11 +587,7 @@ pub fn try_cleanup(stdout: &mut io::Stdout) -> io::Result<()> {
     execute!(stdout, terminal::LeaveAlternateScreen, cursor::Show)?;
 
     // 4. Disable mouse capture (only if not on console/TTY)
-    let is_console = is_console_environment();
-
-    if !is_console {
-        execute!(stdout, event::DisableMouseCapture)?;
-    }
+    set_mouse_capture(stdout, false)?;
 
     // 5. Final color reset
     execute!(stdout, style::ResetColor)?;
@@ -412,3 +602,133 @@ pub fn cleanup(stdout: &mut io::Stdout) {
         eprintln!("Failed to restore terminal: {err}");
     }
 }
+
+/// Turn terminal mouse reporting on or off at runtime
+/// Uses the same EnableMouseCapture sequence as setup_terminal, so re-enabling restores
+/// the mode set in the same order; no-op on console/TTY where raw device input is used
+pub fn set_mouse_capture(stdout: &mut io::Stdout, enabled: bool) -> io::Result<()> {
+    if is_console_environment() {
+        return Ok(());
+    }
+    if enabled {
+        execute!(stdout, event::EnableMouseCapture)
+    } else {
+        execute!(stdout, event::DisableMouseCapture)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Writer appending to a log shared with the stand-in mouse initialization
+    struct SharedWriter(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn position(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        haystack.windows(needle.len()).position(|w| w == needle)
+    }
+
+    #[test]
+    fn test_cwd_flag_overrides_default_cwd() {
+        use clap::Parser;
+        let tmp = std::env::temp_dir();
+        let tmp = tmp.to_string_lossy();
+        let app_config = AppConfig {
+            default_cwd: "/".to_string(),
+            ..AppConfig::default()
+        };
+
+        let mut warnings = Vec::new();
+
+        let cli_args = Cli::parse_from(["term39"]);
+        assert_eq!(
+            default_working_dir(&cli_args, &app_config, &mut warnings).as_deref(),
+            Some("/")
+        );
+        let cli_args = Cli::parse_from(["term39", "--cwd", tmp.as_ref()]);
+        assert_eq!(
+            default_working_dir(&cli_args, &app_config, &mut warnings).as_deref(),
+            Some(tmp.as_ref())
+        );
+        assert_eq!(
+            default_working_dir(
+                &Cli::parse_from(["term39"]),
+                &AppConfig::default(),
+                &mut warnings
+            ),
+            None
+        );
+        assert!(warnings.is_empty());
+
+        // A missing directory is reported instead of stalling startup on its own
+        let cli_args = Cli::parse_from(["term39", "--cwd", "/nonexistent/term39-cwd"]);
+        let _ = default_working_dir(&cli_args, &app_config, &mut warnings);
+        assert_eq!(
+            warnings,
+            ["start directory '/nonexistent/term39-cwd' not found, using home directory"]
+        );
+    }
+
+    // On Windows crossterm enables mouse capture through the console API instead
+    #[cfg(unix)]
+    #[test]
+    fn test_mouse_init_precedes_mouse_capture_sequences() {
+        const GPM_INIT: &[u8] = b"<gpm init>";
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut out = SharedWriter(log.clone());
+        let mouse = write_terminal_setup(&mut out, false, false, || {
+            log.borrow_mut().extend_from_slice(GPM_INIT);
+            "manager"
+        })
+        .unwrap();
+        assert_eq!(mouse, "manager");
+
+        let bytes = log.borrow();
+        let expected: [&[u8]; 7] = [
+            b"\x1b[?1049h", // alternate screen
+            GPM_INIT,
+            b"\x1b[?1000h",
+            b"\x1b[?1002h",
+            b"\x1b[?1003h",
+            b"\x1b[?1006h",
+            b"\x1b[2J", // clear
+        ];
+        let positions: Vec<usize> = expected
+            .iter()
+            .map(|step| {
+                position(&bytes, step)
+                    .unwrap_or_else(|| panic!("{:?} not written", String::from_utf8_lossy(step)))
+            })
+            .collect();
+        assert!(
+            positions.windows(2).all(|pair| pair[0] < pair[1]),
+            "setup order changed: {:?}",
+            String::from_utf8_lossy(&bytes)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_console_setup_leaves_mouse_to_raw_input() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut out = SharedWriter(log.clone());
+        write_terminal_setup(&mut out, true, false, || ()).unwrap();
+
+        let bytes = log.borrow();
+        assert!(position(&bytes, b"\x1b[?1049h").is_some());
+        assert!(position(&bytes, b"\x1b[?1000h").is_none());
+    }
+}
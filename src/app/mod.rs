@@ -3,10 +3,14 @@ pub mod cli;
 pub mod config;
 pub mod config_manager;
 pub mod event_loop;
+pub mod hooks;
 pub mod initialization;
 pub mod panic_handler;
 pub mod platform;
 pub mod session;
+pub mod setup_file;
+pub mod signal_handler;
+pub mod status_file;
 
 pub use app_state::AppState;
 pub use config_manager::AppConfig;
@@ -3,9 +3,19 @@
 //! This module sets up a panic hook that restores the terminal to a usable state
 //! when the application crashes. This prevents the terminal from being left in
 //! raw mode with mouse capture enabled.
+//!
+//! The hook covers panics on any thread. `TerminalGuard` additionally restores the
+//! terminal when `main` returns early with an error or unwinds.
 
 use std::io;
 use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set once the terminal has been restored, so the hook and the guard don't both run
+static TERMINAL_RESTORED: AtomicBool = AtomicBool::new(false);
+
+/// Set while a `TerminalGuard` is alive (the UI owns the terminal)
+static TERMINAL_GUARDED: AtomicBool = AtomicBool::new(false);
 
 /// Set up a panic hook that restores the terminal state on panic.
 ///
@@ -17,30 +27,66 @@ use std::panic;
 /// 4. Disable mouse capture
 /// 5. Reset colors
 /// 6. Call the default panic handler to print the panic message
+/// 7. Exit the process if the panic happened on a background thread while the UI runs
+///
+/// A background thread (PTY reader, widget updater) that panics would otherwise
+/// leave the main loop drawing into a terminal that is no longer in raw mode.
 pub fn setup_panic_hook() {
     let default_panic = panic::take_hook();
     panic::set_hook(Box::new(move |panic_info| {
-        // Attempt to restore terminal state
-        let mut stdout = io::stdout();
+        // Attempt to restore terminal state (only once)
+        if !TERMINAL_RESTORED.swap(true, Ordering::SeqCst) {
+            let mut stdout = io::stdout();
 
-        // Best-effort cleanup - ignore errors since we're already panicking
-        // Following ratatui's pattern: disable raw mode FIRST (most side effects)
-        let _ = crossterm::terminal::disable_raw_mode();
+            // Best-effort cleanup - ignore errors since we're already panicking
+            // Following ratatui's pattern: disable raw mode FIRST (most side effects)
+            let _ = crossterm::terminal::disable_raw_mode();
 
-        // Leave alternate screen and show cursor
-        let _ = crossterm::execute!(
-            stdout,
-            crossterm::terminal::LeaveAlternateScreen,
-            crossterm::cursor::Show
-        );
+            // Leave alternate screen and show cursor
+            let _ = crossterm::execute!(
+                stdout,
+                crossterm::terminal::LeaveAlternateScreen,
+                crossterm::cursor::Show
+            );
 
-        // Disable mouse capture
-        let _ = crossterm::execute!(stdout, crossterm::event::DisableMouseCapture);
+            // Disable mouse capture
+            let _ = crossterm::execute!(stdout, crossterm::event::DisableMouseCapture);
 
-        // Final color reset
-        let _ = crossterm::execute!(stdout, crossterm::style::ResetColor);
+            // Final color reset
+            let _ = crossterm::execute!(stdout, crossterm::style::ResetColor);
+        }
 
         // Call the default panic handler to print the panic message
         default_panic(panic_info);
+
+        // The UI cannot keep running on a restored terminal: stop the whole process
+        if TERMINAL_GUARDED.load(Ordering::SeqCst) && std::thread::current().name() != Some("main")
+        {
+            std::process::exit(101);
+        }
     }));
 }
+
+/// RAII guard that restores the terminal when dropped.
+///
+/// Create it in `main` right after the terminal is set up; every exit path
+/// (normal return, `?` error, unwinding panic) then restores the terminal.
+pub struct TerminalGuard {
+    _private: (),
+}
+
+impl TerminalGuard {
+    pub fn new() -> Self {
+        TERMINAL_GUARDED.store(true, Ordering::SeqCst);
+        Self { _private: () }
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        TERMINAL_GUARDED.store(false, Ordering::SeqCst);
+        if !TERMINAL_RESTORED.swap(true, Ordering::SeqCst) {
+            super::initialization::cleanup(&mut io::stdout());
+        }
+    }
+}
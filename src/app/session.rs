@@ -4,7 +4,7 @@ use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
 
-/// Maximum number of lines to save per terminal (scrollback + visible)
+/// Maximum number of scrollback lines to save per terminal
 pub const MAX_LINES_PER_TERMINAL: usize = 2000;
 
 /// Maximum session file size (10 MB) to prevent memory exhaustion attacks
@@ -54,6 +54,13 @@ pub struct WindowSnapshot {
     pub is_focused: bool,
     pub is_minimized: bool,
     pub is_maximized: bool,
+    #[serde(default)]
+    pub is_scratchpad: bool,
+    #[serde(default)]
+    pub input_locked: bool,
+    // Title bar color tag (palette name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag_color: Option<String>,
 
     // Pre-maximize state (for restore)
     pub pre_maximize_x: u16,
@@ -65,8 +72,13 @@ pub struct WindowSnapshot {
     pub scroll_offset: usize,
     pub cursor: SerializableCursor,
 
-    // Terminal content (capped at MAX_LINES_PER_TERMINAL)
+    // Terminal content: the visible screen
+    // (older sessions stored scrollback here too; restore handles both)
     pub terminal_lines: Vec<SerializableTerminalLine>,
+
+    // Scrollback history, oldest first (capped by session_scrollback_lines)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scrollback_lines: Vec<SerializableTerminalLine>,
 }
 
 /// Serializable version of a terminal line
@@ -82,6 +94,8 @@ pub struct SerializableCell {
     pub fg: SerializableColor,
     pub bg: SerializableColor,
     pub attrs: SerializableCellAttributes,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub combining: Option<String>,
 }
 
 /// Serializable version of Color
@@ -153,6 +167,7 @@ impl From<&TerminalCell> for SerializableCell {
             fg: SerializableColor::from(&cell.fg),
             bg: SerializableColor::from(&cell.bg),
             attrs: SerializableCellAttributes::from(&cell.attrs),
+            combining: cell.combining.as_deref().map(String::from),
         }
     }
 }
@@ -236,6 +251,7 @@ impl From<&SerializableCell> for TerminalCell {
             fg: Color::from(&cell.fg),
             bg: Color::from(&cell.bg),
             attrs: CellAttributes::from(&cell.attrs),
+            combining: cell.combining.as_deref().map(Box::from),
         }
     }
 }
@@ -328,10 +344,28 @@ pub fn get_session_path() -> io::Result<PathBuf> {
     Ok(config_path.join("session.json"))
 }
 
+/// Serialize session state, dropping older scrollback until it fits MAX_SESSION_FILE_SIZE
+/// (a larger file would be rejected on load)
+fn serialize_within_limit(state: &mut SessionState) -> io::Result<String> {
+    loop {
+        let json = serde_json::to_string_pretty(state).map_err(io::Error::other)?;
+        let has_scrollback = state.windows.iter().any(|w| !w.scrollback_lines.is_empty());
+        if json.len() as u64 <= MAX_SESSION_FILE_SIZE || !has_scrollback {
+            return Ok(json);
+        }
+
+        // Halve every window's scrollback, keeping the most recent lines
+        for window in &mut state.windows {
+            let drop_count = window.scrollback_lines.len().div_ceil(2);
+            window.scrollback_lines.drain(..drop_count);
+        }
+    }
+}
+
 /// Save session state to a file
-pub fn save_session(state: &SessionState, path: &Path) -> io::Result<()> {
+pub fn save_session(state: &mut SessionState, path: &Path) -> io::Result<()> {
     // Serialize to JSON
-    let json = serde_json::to_string_pretty(state).map_err(io::Error::other)?;
+    let json = serialize_within_limit(state)?;
 
     // Write to temp file first, then rename (atomic operation)
     let temp_path = path.with_extension("json.tmp");
@@ -0,0 +1,206 @@
+//! Single-file setup export and import (`--export-setup` / `--import-setup`)
+//! The TOML file holds the complete config under `[config]`, then the resolved colors of
+//! the active theme under `[theme]` and the active key bindings under `[keymap]`. Themes
+//! and keybinding profiles are built in and selected by name in `[config]`, so the last
+//! two sections are for people reading the file and are ignored on import.
+//! The lock screen PIN never leaves the machine: it is left out on export and the current
+//! one is kept on import.
+
+use crate::app::config_manager::AppConfig;
+use crate::input::keybinding_profile::KeybindingProfile;
+use crate::rendering::Theme;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Version of the file layout, bumped on incompatible changes
+const FORMAT: u32 = 1;
+
+#[derive(Serialize)]
+struct SetupExport<'a> {
+    format: u32,
+    term39_version: &'a str,
+    config: AppConfig,
+    theme: ThemeSection<'a>,
+    keymap: BTreeMap<&'a str, BTreeMap<&'a str, Vec<String>>>,
+}
+
+#[derive(Serialize)]
+struct ThemeSection<'a> {
+    name: &'a str,
+    #[serde(flatten)]
+    colors: &'a Theme,
+}
+
+/// The parts of a setup file that are read back
+#[derive(Deserialize)]
+struct SetupImport {
+    format: u32,
+    config: AppConfig,
+}
+
+/// Write `config` (without the PIN), the colors of `theme` and the bindings of `profile`
+pub fn export_setup(
+    config: &AppConfig,
+    theme: &Theme,
+    profile: &KeybindingProfile,
+    path: &Path,
+) -> io::Result<()> {
+    let contents = setup_toml(config, theme, profile).map_err(io::Error::other)?;
+    fs::write(path, contents)
+}
+
+fn setup_toml(
+    config: &AppConfig,
+    theme: &Theme,
+    profile: &KeybindingProfile,
+) -> Result<String, String> {
+    let mut config = config.clone();
+    config.lockscreen_pin_hash = None;
+    config.lockscreen_salt = None;
+
+    let keymap = profile
+        .keymap()
+        .into_iter()
+        .map(|(context, actions)| {
+            let actions = actions
+                .into_iter()
+                .map(|(action, bindings)| {
+                    (action, bindings.iter().map(|b| b.to_string()).collect())
+                })
+                .collect();
+            (context, actions)
+        })
+        .collect();
+
+    let theme_name = config.theme.clone();
+    let export = SetupExport {
+        format: FORMAT,
+        term39_version: env!("CARGO_PKG_VERSION"),
+        config,
+        theme: ThemeSection {
+            name: &theme_name,
+            colors: theme,
+        },
+        keymap,
+    };
+    toml::to_string_pretty(&export).map_err(|e| format!("Could not write the setup: {}", e))
+}
+
+/// Validate a setup file, back up the current config to `config.toml.bak` and write the
+/// imported one (keeping the current PIN). Returns the backup path, if there was a config
+pub fn import_setup(path: &Path) -> io::Result<Option<PathBuf>> {
+    let contents = fs::read_to_string(path)?;
+    let mut config =
+        parse_setup(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let config_path = AppConfig::config_path()
+        .ok_or_else(|| io::Error::other("Could not determine config path"))?;
+    let backup = if config_path.exists() {
+        let current = AppConfig::load();
+        config.lockscreen_pin_hash = current.lockscreen_pin_hash;
+        config.lockscreen_salt = current.lockscreen_salt;
+        let backup = config_path.with_extension("toml.bak");
+        fs::copy(&config_path, &backup)?;
+        Some(backup)
+    } else {
+        None
+    };
+
+    config.save().map_err(|e| io::Error::other(e.to_string()))?;
+    Ok(backup)
+}
+
+/// The config of a setup file, or every problem found in it
+fn parse_setup(contents: &str) -> Result<AppConfig, String> {
+    let setup: SetupImport =
+        toml::from_str(contents).map_err(|e| format!("Not a valid setup file: {}", e))?;
+    if setup.format > FORMAT {
+        return Err(format!(
+            "Setup file format {} is newer than this term39 supports ({})",
+            setup.format, FORMAT
+        ));
+    }
+
+    let config = setup.config;
+    let mut problems = Vec::new();
+    if Theme::try_from_name(&config.theme).is_none() {
+        problems.push(format!("unknown theme '{}'", config.theme));
+    }
+    if !KeybindingProfile::all_names().contains(&config.keybinding_profile.as_str()) {
+        problems.push(format!(
+            "unknown keybinding profile '{}'",
+            config.keybinding_profile
+        ));
+    }
+    for (index, window) in config.startup_windows.iter().enumerate() {
+        if let Err(reason) = window.placement() {
+            problems.push(format!("startup window #{}: {}", index + 1, reason));
+        }
+    }
+    for (index, item) in config.system_menu_items.iter().enumerate() {
+        if let Err(reason) = item.menu_command() {
+            problems.push(format!("System menu item #{}: {}", index + 1, reason));
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(config)
+    } else {
+        Err(format!("Invalid setup file: {}", problems.join("; ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::config_manager::SystemMenuItem;
+
+    #[test]
+    fn test_export_round_trips_without_pin() {
+        let mut config = AppConfig {
+            theme: "amber".to_string(),
+            keybinding_profile: "hyprland".to_string(),
+            lockscreen_pin_hash: Some("hash".to_string()),
+            lockscreen_salt: Some("salt".to_string()),
+            ..AppConfig::default()
+        };
+        config.system_menu_items.push(SystemMenuItem {
+            label: "htop".to_string(),
+            action: "run".to_string(),
+            command: "htop".to_string(),
+        });
+        let theme = Theme::from_name("amber");
+        let profile = KeybindingProfile::from_name("hyprland");
+
+        let contents = setup_toml(&config, &theme, &profile).unwrap();
+        assert!(contents.contains("[theme]"));
+        assert!(contents.contains("desktop_bg = "));
+        assert!(!contents.contains("hash"));
+
+        let imported = parse_setup(&contents).unwrap();
+        assert_eq!(imported.lockscreen_pin_hash, None);
+        config.lockscreen_pin_hash = None;
+        config.lockscreen_salt = None;
+        assert_eq!(
+            toml::to_string(&imported).unwrap(),
+            toml::to_string(&config).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_import_rejects_invalid_setups() {
+        let err = parse_setup("format = 1\n[config]\ntheme = \"neon\"\n").unwrap_err();
+        assert!(err.contains("unknown theme 'neon'"), "{}", err);
+        let err = parse_setup("format = 9\n[config]\n").unwrap_err();
+        assert!(err.contains("newer"), "{}", err);
+        assert!(parse_setup("theme = \"amber\"\n").is_err());
+        // Missing settings take their defaults
+        assert_eq!(
+            parse_setup("format = 1\n[config]\n").unwrap().theme,
+            "classic"
+        );
+    }
+}
@@ -0,0 +1,57 @@
+//! Graceful termination via SIGTERM (Unix only).
+//!
+//! Session managers send SIGTERM on logout; the event loop treats it like a clean
+//! exit so the session is saved (or cleared) and the terminal restored.
+
+#[cfg(unix)]
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Atomic flag set by SIGTERM signal handler
+#[cfg(unix)]
+pub static TERMINATE_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Set up the SIGTERM signal handler for graceful shutdown.
+/// Call this once during application initialization.
+#[cfg(unix)]
+pub fn setup() {
+    unsafe {
+        libc::signal(
+            libc::SIGTERM,
+            handle_sigterm as *const () as libc::sighandler_t,
+        );
+    }
+}
+
+/// Signal handler function - sets the atomic flag
+#[cfg(unix)]
+extern "C" fn handle_sigterm(_: libc::c_int) {
+    TERMINATE_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+/// Check if termination was requested via signal and clear the flag.
+/// Returns true if SIGTERM was received since last check.
+#[cfg(unix)]
+pub fn check_and_clear() -> bool {
+    TERMINATE_REQUESTED.swap(false, Ordering::SeqCst)
+}
+
+/// No-op setup for non-Unix platforms
+#[cfg(not(unix))]
+pub fn setup() {}
+
+/// Always returns false on non-Unix platforms
+#[cfg(not(unix))]
+pub fn check_and_clear() -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_and_clear_without_signal() {
+        setup();
+        assert!(!check_and_clear());
+    }
+}
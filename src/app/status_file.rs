@@ -0,0 +1,160 @@
+//! Window list and focus stream for external status bars (the `status_file` option)
+//! Each change appends one compact JSON line, e.g.
+//! `{"focus":2,"windows":[{"focused":false,"id":1,"minimized":false,"title":"bash"},…]}`,
+//! so a polybar/i3bar script can `tail -f` the file and show the last line.
+//! Changes arriving faster than `DEBOUNCE` are coalesced: only the latest state is written.
+
+use crate::window::manager::FocusState;
+use serde_json::json;
+use std::fs::{File, OpenOptions};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Minimum time between two lines
+pub const DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Append-only writer of window manager state lines
+#[derive(Debug)]
+pub struct StatusFile {
+    path: PathBuf,
+    /// Opened on the first write
+    file: Option<File>,
+    /// Fingerprint of the state last written, so unchanged frames skip serializing
+    written: Option<u64>,
+    last_write: Option<Instant>,
+}
+
+impl StatusFile {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            file: None,
+            written: None,
+            last_write: None,
+        }
+    }
+
+    /// Writer for the configured path (`~/` expands to the home directory), None when empty
+    pub fn from_config(path: &str) -> Option<Self> {
+        let path = path.trim();
+        if path.is_empty() {
+            return None;
+        }
+        Some(Self::new(match path.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir()
+                .map(|home| home.join(rest))
+                .unwrap_or_else(|| PathBuf::from(path)),
+            None => PathBuf::from(path),
+        }))
+    }
+
+    /// Whether the last write is at least `DEBOUNCE` old, so a change could be written
+    pub fn is_due(&self, now: Instant) -> bool {
+        self.last_write
+            .is_none_or(|last| now.duration_since(last) >= DEBOUNCE)
+    }
+
+    /// Record the current `windows` (id, title, is_focused, is_minimized) and focus,
+    /// writing a line when they changed and the last write is at least `DEBOUNCE` old
+    /// A change that is not due yet is picked up by a later call, which sees it again
+    pub fn update(
+        &mut self,
+        windows: &[(u32, &str, bool, bool)],
+        focus: FocusState,
+        now: Instant,
+    ) -> io::Result<()> {
+        if !self.is_due(now) {
+            return Ok(());
+        }
+        let focus = focused_id(focus);
+        let fingerprint = {
+            let mut hasher = DefaultHasher::new();
+            (windows, focus).hash(&mut hasher);
+            hasher.finish()
+        };
+        if self.written == Some(fingerprint) {
+            return Ok(());
+        }
+        let line = status_line(windows, focus);
+
+        let file = match &mut self.file {
+            Some(file) => file,
+            None => self.file.insert(
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)?,
+            ),
+        };
+        writeln!(file, "{}", line)?;
+        self.written = Some(fingerprint);
+        self.last_write = Some(now);
+        Ok(())
+    }
+}
+
+/// The focused window id (None for the desktop or top bar)
+fn focused_id(focus: FocusState) -> Option<u32> {
+    match focus {
+        FocusState::Window(id) => Some(id),
+        FocusState::Desktop | FocusState::Topbar => None,
+    }
+}
+
+/// One status line: the focused window id (null for the desktop or top bar) and every window
+fn status_line(windows: &[(u32, &str, bool, bool)], focus: Option<u32>) -> String {
+    let windows: Vec<_> = windows
+        .iter()
+        .map(|&(id, title, focused, minimized)| {
+            json!({
+                "id": id,
+                "title": title,
+                "focused": focused,
+                "minimized": minimized,
+            })
+        })
+        .collect();
+    json!({ "focus": focus, "windows": windows }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_changes_are_debounced() {
+        let path = std::env::temp_dir().join(format!("term39-status-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut status = StatusFile::new(&path);
+        let start = Instant::now();
+        let one = [(1, "bash", true, false)];
+        let two = [(1, "bash", false, false), (2, "htop", true, false)];
+
+        status.update(&one, FocusState::Window(1), start).unwrap();
+        // Unchanged state writes nothing, rapid changes wait for the interval
+        status.update(&one, FocusState::Window(1), start).unwrap();
+        status
+            .update(&two, FocusState::Window(2), start + DEBOUNCE / 3)
+            .unwrap();
+        status
+            .update(&two, FocusState::Desktop, start + DEBOUNCE / 2)
+            .unwrap();
+        status
+            .update(&two, FocusState::Desktop, start + DEBOUNCE)
+            .unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                r#"{"focus":1,"windows":[{"focused":true,"id":1,"minimized":false,"title":"bash"}]}"#,
+                r#"{"focus":null,"windows":[{"focused":false,"id":1,"minimized":false,"title":"bash"},{"focused":true,"id":2,"minimized":false,"title":"htop"}]}"#,
+            ]
+        );
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -16,6 +16,15 @@ pub struct DisplayConfig {
     /// Pixel scale factor ("auto", "1", "2", "3", "4", etc.)
     #[serde(default = "default_scale")]
     pub scale: String,
+    /// CRT scanline effect: percent every other scanline is darkened (0 = off, max 100)
+    #[serde(default)]
+    pub scanlines: u8,
+    /// Slightly brighten the lit scanlines, like phosphor glow
+    #[serde(default)]
+    pub phosphor_glow: bool,
+    /// Leave fading copies of the mouse pointer behind as it moves (off by default)
+    #[serde(default)]
+    pub cursor_trail: bool,
 }
 
 fn default_mode() -> String {
@@ -31,6 +40,9 @@ impl Default for DisplayConfig {
         Self {
             mode: default_mode(),
             scale: default_scale(),
+            scanlines: 0,
+            phosphor_glow: false,
+            cursor_trail: false,
         }
     }
 }
@@ -41,16 +53,28 @@ pub struct FontConfig {
     /// Console font name (e.g., "Unifont-APL8x16")
     #[serde(default = "default_font_name")]
     pub name: String,
+    /// Text mode whose character cell is used by dense windows (e.g., "80x50" for 8x8)
+    #[serde(default = "default_dense_mode")]
+    pub dense_mode: String,
+    /// Console font for dense windows (optional, auto-detect for dense_mode if not specified)
+    #[serde(default)]
+    pub dense_name: Option<String>,
 }
 
 fn default_font_name() -> String {
     "Unifont-APL8x16".to_string()
 }
 
+fn default_dense_mode() -> String {
+    "80x50".to_string()
+}
+
 impl Default for FontConfig {
     fn default() -> Self {
         Self {
             name: default_font_name(),
+            dense_mode: default_dense_mode(),
+            dense_name: None,
         }
     }
 }
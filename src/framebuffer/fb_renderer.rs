@@ -5,9 +5,10 @@
 
 use super::font_manager::FontManager;
 use super::text_modes::TextMode;
-use crate::rendering::{Cell, VideoBuffer};
+use crate::rendering::{Cell, DENSE_PLACEHOLDER, DenseRegion, VideoBuffer};
 use crossterm::style::Color;
 use framebuffer::Framebuffer;
+use std::collections::VecDeque;
 use std::io;
 use std::os::unix::fs::FileTypeExt;
 
@@ -37,6 +38,9 @@ const DOS_PALETTE: [(u8, u8, u8); 16] = [
 const CURSOR_WIDTH: usize = 16;
 const CURSOR_HEIGHT: usize = 16;
 
+/// Earlier pointer positions drawn as fading copies when the cursor trail is on
+const CURSOR_TRAIL_LENGTH: usize = 4;
+
 /// Cursor sprite bitmap (16x16 arrow cursor)
 /// 0 = transparent, 1 = black outline, 2 = white fill
 const CURSOR_SPRITE: [[u8; CURSOR_WIDTH]; CURSOR_HEIGHT] = [
@@ -72,12 +76,23 @@ pub struct FramebufferRenderer {
     offset_y: usize, // Y offset to center content
     cursor_visible: bool,
     cursor_saved_pixels: Vec<(usize, usize, u8, u8, u8)>, // (x, y, r, g, b)
+    // Recent pointer positions, oldest first (None while the cursor trail is off)
+    cursor_trail: Option<VecDeque<(usize, usize)>>,
     // Pixel format offsets (byte positions for RGB channels)
     r_offset: usize,
     g_offset: usize,
     b_offset: usize,
     // Previous frame buffer for dirty tracking (only render changed cells)
     prev_buffer: Vec<Cell>,
+    // Smaller font for dense windows (per-window font override)
+    dense_font: Option<FontManager>,
+    // Dense regions drawn on the previous frame (redrawn only when they change)
+    prev_dense_regions: Vec<DenseRegion>,
+    // Cells rendered during the current frame (reused to avoid allocations)
+    rendered_cells: Vec<(usize, usize)>,
+    // CRT effect: percent every other scanline is darkened (0 = off), and phosphor glow
+    scanlines: u8,
+    phosphor_glow: bool,
 }
 
 impl FramebufferRenderer {
@@ -226,13 +241,62 @@ impl FramebufferRenderer {
             offset_y,
             cursor_visible: true,
             cursor_saved_pixels: Vec::new(),
+            cursor_trail: None,
             r_offset,
             g_offset,
             b_offset,
             prev_buffer,
+            dense_font: None,
+            prev_dense_regions: Vec::new(),
+            rendered_cells: Vec::new(),
+            scanlines: 0,
+            phosphor_glow: false,
         })
     }
 
+    /// Load the font used for dense windows, whose content is drawn with smaller glyphs
+    /// If font_name is None, selects a font matching the character cell of `mode`.
+    /// The font's cell must evenly divide the main font's cell and be smaller than it;
+    /// otherwise dense windows are unavailable.
+    pub fn load_dense_font(&mut self, mode: TextMode, font_name: Option<&str>) {
+        let font = match font_name {
+            Some(name) => FontManager::load_font_by_name(name),
+            None => FontManager::load_for_dimensions(mode.char_width, mode.char_height),
+        };
+
+        match font {
+            Ok(font) => {
+                self.dense_font = Some(font);
+                if self.dense_density().is_none() {
+                    eprintln!(
+                        "Warning: Dense window font does not fit the {}x{} main font, dense windows disabled",
+                        self.font.width, self.font.height
+                    );
+                    self.dense_font = None;
+                }
+            }
+            Err(e) => eprintln!("Warning: Failed to load dense window font: {}", e),
+        }
+    }
+
+    /// Terminal cells per screen cell (columns, rows) for dense windows
+    /// Returns None when no suitable dense font is loaded
+    pub fn dense_density(&self) -> Option<(u16, u16)> {
+        let dense = self.dense_font.as_ref()?;
+        if dense.width == 0
+            || dense.height == 0
+            || !self.font.width.is_multiple_of(dense.width)
+            || !self.font.height.is_multiple_of(dense.height)
+        {
+            return None;
+        }
+        let density = (
+            (self.font.width / dense.width) as u16,
+            (self.font.height / dense.height) as u16,
+        );
+        (density != (1, 1)).then_some(density)
+    }
+
     /// Convert Color enum to RGB tuple
     #[inline(always)]
     fn color_to_rgb(&self, color: Color) -> (u8, u8, u8) {
@@ -372,7 +436,13 @@ impl FramebufferRenderer {
         let bg_color = self.color_to_rgb(cell.bg_color);
 
         // Get glyph data reference (no copy needed for scanline rendering)
-        let glyph = self.font.get_glyph(cell.character);
+        // Dense region placeholders render blank; the dense content is drawn on top
+        let character = if cell.character == DENSE_PLACEHOLDER {
+            ' '
+        } else {
+            cell.character
+        };
+        let glyph = self.font.get_glyph(character);
         let glyph_len = glyph.len();
         let is_width_8 = self.font.is_width_8;
         let bytes_per_row = self.font.bytes_per_row;
@@ -457,6 +527,31 @@ impl FramebufferRenderer {
         let copy_len = glyph_len.min(72);
         glyph_data[..copy_len].copy_from_slice(&glyph[..copy_len]);
 
+        let glyph_layout = (font_width, font_height, bytes_per_row, is_width_8);
+        self.put_glyph_pixels(
+            x_offset,
+            y_offset,
+            &glyph_data[..copy_len],
+            glyph_layout,
+            fg_color,
+            bg_color,
+        );
+    }
+
+    /// Draw a glyph bitmap pixel by pixel at logical pixel position (x_offset, y_offset)
+    /// `layout` is (width, height, bytes_per_row, is_width_8) of the glyph's font
+    fn put_glyph_pixels(
+        &mut self,
+        x_offset: usize,
+        y_offset: usize,
+        glyph_data: &[u8],
+        layout: (usize, usize, usize, bool),
+        fg_color: (u8, u8, u8),
+        bg_color: (u8, u8, u8),
+    ) {
+        let (font_width, font_height, bytes_per_row, is_width_8) = layout;
+        let copy_len = glyph_data.len();
+
         for py in 0..font_height {
             for px in 0..font_width {
                 let is_set = if is_width_8 {
@@ -473,6 +568,142 @@ impl FramebufferRenderer {
         }
     }
 
+    /// Render one cell of a dense region with the dense font at logical pixel position
+    fn render_dense_char(&mut self, x_offset: usize, y_offset: usize, cell: &Cell) {
+        let Some(font) = self.dense_font.as_ref() else {
+            return;
+        };
+        let layout = (font.width, font.height, font.bytes_per_row, font.is_width_8);
+
+        // Copy glyph data to stack buffer to avoid borrow conflicts with put_pixel
+        let glyph = font.get_glyph(cell.character);
+        let mut glyph_data = [0u8; 72];
+        let copy_len = glyph.len().min(72);
+        glyph_data[..copy_len].copy_from_slice(&glyph[..copy_len]);
+
+        let fg_color = self.color_to_rgb(cell.fg_color);
+        let bg_color = self.color_to_rgb(cell.bg_color);
+        self.put_glyph_pixels(
+            x_offset,
+            y_offset,
+            &glyph_data[..copy_len],
+            layout,
+            fg_color,
+            bg_color,
+        );
+        self.apply_crt_effect(x_offset, y_offset, layout.0, layout.1);
+    }
+
+    /// Draw a dense region over its placeholder cells
+    /// Cells covered by other windows no longer hold placeholders and are skipped
+    fn render_dense_region(&mut self, buffer: &VideoBuffer, region: &DenseRegion) {
+        let hidden_rows = buffer.hidden_rows() as usize;
+        let Some((dense_width, dense_height)) = self
+            .dense_font
+            .as_ref()
+            .map(|font| (font.width, font.height))
+        else {
+            return;
+        };
+        let (x, y, _, height) = region.rect;
+        let (density_x, density_y) = (region.density.0 as usize, region.density.1 as usize);
+
+        for row in 0..height as usize * density_y {
+            for col in 0..region.grid_width() {
+                let screen_col = x as usize + col / density_x;
+                let buffer_row = y as usize + row / density_y;
+                let Some(screen_row) = buffer_row.checked_sub(hidden_rows) else {
+                    continue;
+                };
+                if !self.mode.is_valid_position(screen_col, screen_row) {
+                    continue;
+                }
+                let is_visible = buffer
+                    .get(screen_col as u16, buffer_row as u16)
+                    .is_some_and(|cell| cell.character == DENSE_PLACEHOLDER);
+                if !is_visible {
+                    continue;
+                }
+                if let Some(cell) = region.get(col, row) {
+                    let px = screen_col * self.font.width + (col % density_x) * dense_width;
+                    let py = screen_row * self.font.height + (row % density_y) * dense_height;
+                    self.render_dense_char(px, py, cell);
+                }
+            }
+        }
+    }
+
+    /// Set the CRT effect: `scanlines` is how much (0-100%) every other scanline is
+    /// darkened, `phosphor_glow` brightens the lit scanlines slightly
+    /// Only redrawn cells get the effect, so the dirty tracking keeps working
+    pub fn set_crt_effect(&mut self, scanlines: u8, phosphor_glow: bool) {
+        self.scanlines = scanlines.min(100);
+        self.phosphor_glow = phosphor_glow;
+        self.invalidate();
+    }
+
+    /// Apply the CRT effect to a freshly drawn area (logical pixels)
+    /// Scanlines are physical pixel rows, so scaled modes get several per font row
+    fn apply_crt_effect(&mut self, x: usize, y: usize, width: usize, height: usize) {
+        if self.scanlines == 0 && !self.phosphor_glow {
+            return;
+        }
+        let (scanlines, glow) = (self.scanlines, self.phosphor_glow);
+        let x0 = x * self.scale + self.offset_x;
+        let x1 = ((x + width) * self.scale + self.offset_x).min(self.width_pixels);
+        let y0 = y * self.scale + self.offset_y;
+        let y1 = ((y + height) * self.scale + self.offset_y).min(self.height_pixels);
+        let (line_length, bytes_per_pixel) = (self.line_length, self.bytes_per_pixel);
+        let (r_offset, g_offset, b_offset) = (self.r_offset, self.g_offset, self.b_offset);
+        let frame = self.framebuffer.frame.as_mut();
+        let frame_len = frame.len();
+
+        for py in y0..y1 {
+            let dark_row = (py - self.offset_y) % 2 == 1;
+            for px in x0..x1 {
+                let offset = py * line_length + px * bytes_per_pixel;
+                match bytes_per_pixel {
+                    4 | 3 if offset + 2 < frame_len => {
+                        let (r, g, b) = crt_shade(
+                            (
+                                frame[offset + r_offset],
+                                frame[offset + g_offset],
+                                frame[offset + b_offset],
+                            ),
+                            dark_row,
+                            scanlines,
+                            glow,
+                        );
+                        frame[offset + r_offset] = r;
+                        frame[offset + g_offset] = g;
+                        frame[offset + b_offset] = b;
+                    }
+                    2 if offset + 1 < frame_len => {
+                        let color = (frame[offset] as u16) | ((frame[offset + 1] as u16) << 8);
+                        let rgb = (
+                            ((color >> 11) as u8) << 3,
+                            ((color >> 5) as u8 & 0x3F) << 2,
+                            (color as u8 & 0x1F) << 3,
+                        );
+                        let (r, g, b) = crt_shade(rgb, dark_row, scanlines, glow);
+                        let color =
+                            ((r >> 3) as u16) << 11 | ((g >> 2) as u16) << 5 | (b >> 3) as u16;
+                        frame[offset] = (color & 0xFF) as u8;
+                        frame[offset + 1] = (color >> 8) as u8;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Forget what was drawn so the next render_buffer() redraws every cell
+    pub fn invalidate(&mut self) {
+        self.prev_buffer
+            .fill(Cell::new_unchecked('\0', Color::Black, Color::Black));
+        self.prev_dense_regions.clear();
+    }
+
     /// Clear the entire screen - fills borders with black, content area with specified color
     #[allow(dead_code)]
     pub fn clear(&mut self, color: Color) {
@@ -500,13 +731,18 @@ impl FramebufferRenderer {
     /// Uses dirty tracking to only render cells that have changed
     pub fn render_buffer(&mut self, buffer: &VideoBuffer) {
         let (cols, rows) = buffer.dimensions();
+        self.rendered_cells.clear();
 
-        let max_rows = (rows as usize).min(self.mode.rows);
+        // Screen row r shows buffer row r + hidden_rows (bars hidden)
+        let hidden_rows = buffer.hidden_rows() as usize;
+        let max_rows = (rows as usize)
+            .saturating_sub(2 * hidden_rows)
+            .min(self.mode.rows);
         let max_cols = (cols as usize).min(self.mode.cols);
 
         for row in 0..max_rows {
             for col in 0..max_cols {
-                if let Some(cell) = buffer.get(col as u16, row as u16) {
+                if let Some(cell) = buffer.get(col as u16, (row + hidden_rows) as u16) {
                     // Calculate index into prev_buffer
                     let idx = row * self.mode.cols + col;
 
@@ -517,14 +753,42 @@ impl FramebufferRenderer {
                             self.render_char(col, row, cell);
                             // Update previous buffer
                             self.prev_buffer[idx] = *cell;
+                            self.rendered_cells.push((col, row));
                         }
                     } else {
                         // Index out of bounds, render anyway
                         self.render_char(col, row, cell);
+                        self.rendered_cells.push((col, row));
                     }
                 }
             }
         }
+
+        // CRT effect on what was just drawn (dense content below gets it per glyph)
+        let (font_width, font_height) = (self.font.width, self.font.height);
+        for i in 0..self.rendered_cells.len() {
+            let (col, row) = self.rendered_cells[i];
+            self.apply_crt_effect(col * font_width, row * font_height, font_width, font_height);
+        }
+
+        // Draw dense window content over its placeholders, but only when the region
+        // changed or some of its cells were just redrawn with the main font
+        let regions = buffer.dense_regions();
+        for (i, region) in regions.iter().enumerate() {
+            let (x, y, width, height) = region.rect;
+            let (x, width, height) = (x as usize, width as usize, height as usize);
+            let y = (y as usize).saturating_sub(hidden_rows);
+            let covered_redrawn = self
+                .rendered_cells
+                .iter()
+                .any(|&(col, row)| col >= x && col < x + width && row >= y && row < y + height);
+            if covered_redrawn || self.prev_dense_regions.get(i) != Some(region) {
+                self.render_dense_region(buffer, region);
+            }
+        }
+        if self.prev_dense_regions.as_slice() != regions {
+            self.prev_dense_regions = regions.to_vec();
+        }
     }
 
     /// Get current text mode
@@ -598,6 +862,11 @@ impl FramebufferRenderer {
         self.cursor_visible = visible;
     }
 
+    /// Turn the pointer trail on or off: fading copies of the sprite at its last positions
+    pub fn set_cursor_trail(&mut self, enabled: bool) {
+        self.cursor_trail = enabled.then(VecDeque::new);
+    }
+
     /// Draw cursor at specified pixel position (logical coordinates, not scaled)
     /// This should be called AFTER all other content is rendered
     pub fn draw_cursor(&mut self, x: usize, y: usize) {
@@ -608,6 +877,22 @@ impl FramebufferRenderer {
         // Save pixels under cursor before drawing
         self.cursor_saved_pixels.clear();
 
+        if let Some(trail) = self.cursor_trail.as_mut() {
+            advance_trail(trail, (x, y));
+            let ghosts: Vec<(usize, usize)> = trail.iter().copied().collect();
+            let steps = ghosts.len();
+            // The newest position is the pointer itself, the older ones fade out
+            for (age, &(gx, gy)) in ghosts[..steps - 1].iter().enumerate() {
+                let alpha = ((age + 1) * 160 / steps) as u8;
+                self.draw_sprite(gx, gy, alpha);
+            }
+        }
+        self.draw_sprite(x, y, 255);
+    }
+
+    /// Draw the pointer sprite blended over the screen by `alpha` (255 = opaque),
+    /// saving the pixels it covers for restore_cursor_area
+    fn draw_sprite(&mut self, x: usize, y: usize, alpha: u8) {
         for (cy, row) in CURSOR_SPRITE.iter().enumerate() {
             for (cx, &sprite_pixel) in row.iter().enumerate() {
                 let pixel_x = x + cx;
@@ -636,6 +921,7 @@ impl FramebufferRenderer {
                     _ => continue,
                 };
 
+                let (r, g, b) = blend(original, (r, g, b), alpha);
                 self.put_pixel(pixel_x, pixel_y, r, g, b);
             }
         }
@@ -645,8 +931,81 @@ impl FramebufferRenderer {
     pub fn restore_cursor_area(&mut self) {
         // Use std::mem::take to move ownership without cloning
         let saved = std::mem::take(&mut self.cursor_saved_pixels);
-        for (x, y, r, g, b) in saved {
+        // Newest first, so pixels covered by overlapping trail sprites end up original
+        for (x, y, r, g, b) in saved.into_iter().rev() {
             self.put_pixel(x, y, r, g, b);
         }
     }
 }
+
+/// Record the pointer position in the trail: a move adds it, a frame without one lets
+/// the oldest copy fade away, so the trail disappears once the pointer rests
+fn advance_trail(trail: &mut VecDeque<(usize, usize)>, position: (usize, usize)) {
+    if trail.back() == Some(&position) {
+        if trail.len() > 1 {
+            trail.pop_front();
+        }
+    } else {
+        trail.push_back(position);
+        if trail.len() > CURSOR_TRAIL_LENGTH + 1 {
+            trail.pop_front();
+        }
+    }
+}
+
+/// Mix `top` over `base`, `alpha` of 255 keeping only `top`
+fn blend(base: (u8, u8, u8), top: (u8, u8, u8), alpha: u8) -> (u8, u8, u8) {
+    let mix = |b: u8, t: u8| -> u8 {
+        ((b as u16 * (255 - alpha as u16) + t as u16 * alpha as u16) / 255) as u8
+    };
+    (mix(base.0, top.0), mix(base.1, top.1), mix(base.2, top.2))
+}
+
+/// CRT effect for one pixel: dark scanlines keep `100 - scanlines` percent of the color,
+/// lit ones are brightened by an eighth with `glow`
+fn crt_shade(rgb: (u8, u8, u8), dark_row: bool, scanlines: u8, glow: bool) -> (u8, u8, u8) {
+    let shade = |c: u8| -> u8 {
+        if dark_row {
+            (c as u16 * (100 - scanlines.min(100)) as u16 / 100) as u8
+        } else if glow {
+            c.saturating_add(c / 8)
+        } else {
+            c
+        }
+    };
+    (shade(rgb.0), shade(rgb.1), shade(rgb.2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crt_shade_darkens_alternate_scanlines() {
+        assert_eq!(crt_shade((200, 100, 0), true, 50, false), (100, 50, 0));
+        assert_eq!(crt_shade((200, 100, 0), false, 50, false), (200, 100, 0));
+        // Glow brightens lit scanlines only, never past white
+        assert_eq!(crt_shade((200, 100, 0), false, 50, true), (225, 112, 0));
+        assert_eq!(crt_shade((250, 250, 250), false, 0, true), (255, 255, 255));
+        assert_eq!(crt_shade((200, 100, 0), true, 100, true), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_cursor_trail_follows_and_fades() {
+        let mut trail = VecDeque::new();
+        for x in 0..7 {
+            advance_trail(&mut trail, (x, 0));
+        }
+        // The pointer plus CURSOR_TRAIL_LENGTH earlier positions
+        assert_eq!(trail, [(2, 0), (3, 0), (4, 0), (5, 0), (6, 0)]);
+        // Resting frames drop the oldest copy until only the pointer is left
+        for _ in 0..10 {
+            advance_trail(&mut trail, (6, 0));
+        }
+        assert_eq!(trail, [(6, 0)]);
+
+        assert_eq!(blend((0, 0, 0), (255, 255, 255), 255), (255, 255, 255));
+        assert_eq!(blend((200, 100, 0), (0, 0, 0), 0), (200, 100, 0));
+        assert_eq!(blend((0, 0, 0), (255, 255, 255), 51), (51, 51, 51));
+    }
+}
@@ -0,0 +1,279 @@
+//! Order of the input events handled in one frame (the `input_priority`,
+//! `key_events_per_frame` and `mouse_events_per_frame` options)
+//! Events from crossterm and the raw mouse and framebuffer readers are queued as they
+//! arrive. With `Keys` priority pending key, paste and resize events are handled before
+//! queued pointer motion, and the event loop flushes typed input to the PTYs before the
+//! first mouse event, so a drag can never overtake a keystroke. Clicks and wheel events
+//! keep their arrival order relative to keys (a click and then typing still lands the
+//! text where it was clicked). Events past a per-frame limit stay queued for the next
+//! frame instead of being dropped, and runs of pointer motion are coalesced into their
+//! latest position.
+
+use crate::app::config_manager::InputPriority;
+use crossterm::event::{Event, MouseEvent, MouseEventKind};
+use std::collections::VecDeque;
+
+/// A queued event and whether it came from the raw mouse or framebuffer readers
+/// (already in buffer coordinates) rather than from crossterm
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedEvent {
+    pub event: Event,
+    pub injected: bool,
+    /// Arrival order across both queues
+    seq: u64,
+}
+
+/// Pending key and mouse events, kept between frames
+#[derive(Debug)]
+pub struct InputQueue {
+    priority: InputPriority,
+    key_limit: usize,
+    mouse_limit: usize,
+    /// Key, paste, focus and resize events
+    keys: VecDeque<QueuedEvent>,
+    mouse: VecDeque<QueuedEvent>,
+    next_seq: u64,
+}
+
+impl InputQueue {
+    pub fn new(priority: InputPriority, key_limit: usize, mouse_limit: usize) -> Self {
+        Self {
+            priority,
+            key_limit: key_limit.max(1),
+            mouse_limit: mouse_limit.max(1),
+            keys: VecDeque::new(),
+            mouse: VecDeque::new(),
+            next_seq: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty() && self.mouse.is_empty()
+    }
+
+    /// Events a frame handles at most, the bound for reading new events in one frame
+    pub fn frame_capacity(&self) -> usize {
+        self.key_limit + self.mouse_limit
+    }
+
+    /// Queue an event; pointer motion replaces queued motion nothing else has followed
+    pub fn push(&mut self, event: Event, injected: bool) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        let queued = QueuedEvent {
+            event,
+            injected,
+            seq,
+        };
+        let Event::Mouse(mouse_event) = &queued.event else {
+            self.keys.push_back(queued);
+            return;
+        };
+        if let Some(last) = self.mouse.back_mut()
+            && last.injected == injected
+            && last.seq + 1 == seq
+            && let Event::Mouse(last_event) = &last.event
+            && same_motion(last_event, mouse_event)
+        {
+            *last = queued;
+            return;
+        }
+        self.mouse.push_back(queued);
+    }
+
+    /// Take the events to handle this frame: up to `key_events_per_frame` keys and
+    /// `mouse_events_per_frame` mouse events, keys ahead of motion or in arrival order
+    pub fn next_batch(&mut self) -> Vec<QueuedEvent> {
+        let key_count = self.keys.len().min(self.key_limit);
+        let mouse_count = self.mouse.len().min(self.mouse_limit);
+        match self.priority {
+            InputPriority::Keys => self.keys_first_batch(key_count, mouse_count),
+            InputPriority::Arrival => {
+                let mut batch: Vec<QueuedEvent> = self.keys.drain(..key_count).collect();
+                // Mouse events that arrived before the last key taken keep their place,
+                // later ones wait when the key limit cut the batch short
+                let cutoff = match self.keys.front() {
+                    Some(next_key) => next_key.seq,
+                    None => u64::MAX,
+                };
+                let taken = self
+                    .mouse
+                    .iter()
+                    .take(mouse_count)
+                    .take_while(|m| m.seq < cutoff)
+                    .count();
+                batch.extend(self.mouse.drain(..taken));
+                batch.sort_by_key(|queued| queued.seq);
+                batch
+            }
+        }
+    }
+
+    /// `Keys` priority: keys overtake pointer motion but never a click or wheel event
+    /// that arrived before them, and mouse events never overtake a key left for the
+    /// next frame, so the per-frame limits can't reorder events across frames
+    fn keys_first_batch(&mut self, key_count: usize, mouse_count: usize) -> Vec<QueuedEvent> {
+        let mut batch = Vec::with_capacity(key_count + mouse_count);
+        let (mut keys_taken, mut mouse_taken) = (0, 0);
+        loop {
+            // The first queued click or wheel event, which keys may not pass
+            let barrier = self
+                .mouse
+                .iter()
+                .find(|queued| !is_motion(&queued.event))
+                .map_or(u64::MAX, |queued| queued.seq);
+            let next_key = self.keys.front().map_or(u64::MAX, |queued| queued.seq);
+
+            if keys_taken < key_count && next_key < barrier {
+                batch.extend(self.keys.pop_front());
+                keys_taken += 1;
+            } else if mouse_taken < mouse_count
+                && self
+                    .mouse
+                    .front()
+                    .is_some_and(|queued| queued.seq < next_key)
+            {
+                batch.extend(self.mouse.pop_front());
+                mouse_taken += 1;
+            } else {
+                return batch;
+            }
+        }
+    }
+}
+
+/// Whether a queued event only moves the pointer (motion or drag)
+fn is_motion(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Moved | MouseEventKind::Drag(_),
+            ..
+        })
+    )
+}
+
+/// Whether `next` only moves the pointer on from `prev` (same drag button and modifiers)
+fn same_motion(prev: &MouseEvent, next: &MouseEvent) -> bool {
+    let motion =
+        |kind: MouseEventKind| matches!(kind, MouseEventKind::Moved | MouseEventKind::Drag(_));
+    motion(prev.kind) && prev.kind == next.kind && prev.modifiers == next.modifiers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton};
+
+    fn key(c: char) -> Event {
+        Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16) -> Event {
+        Event::Mouse(MouseEvent {
+            kind,
+            column,
+            row: 5,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    fn drain(queue: &mut InputQueue) -> Vec<Event> {
+        queue.next_batch().into_iter().map(|q| q.event).collect()
+    }
+
+    #[test]
+    fn test_mixed_events_drain_keys_first() {
+        let drag = MouseEventKind::Drag(MouseButton::Left);
+        let mut queue = InputQueue::new(InputPriority::Keys, 2, 10);
+        queue.push(mouse(MouseEventKind::Down(MouseButton::Left), 1), false);
+        queue.push(mouse(drag, 2), false);
+        queue.push(key('a'), false);
+        // Motion interrupted by a key is kept, consecutive motion collapses to the latest
+        queue.push(mouse(drag, 3), true);
+        queue.push(mouse(drag, 4), true);
+        queue.push(key('b'), false);
+        queue.push(key('c'), false);
+        queue.push(mouse(MouseEventKind::Up(MouseButton::Left), 4), false);
+
+        // Keys overtake the drag, not the press before them
+        assert_eq!(
+            drain(&mut queue),
+            [
+                mouse(MouseEventKind::Down(MouseButton::Left), 1),
+                key('a'),
+                key('b'),
+                mouse(drag, 2),
+                mouse(drag, 4),
+            ]
+        );
+        // The key over the limit waits for the next frame, and the release behind it
+        assert_eq!(
+            drain(&mut queue),
+            [key('c'), mouse(MouseEventKind::Up(MouseButton::Left), 4)]
+        );
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_click_then_type_keeps_order() {
+        let down = mouse(MouseEventKind::Down(MouseButton::Left), 3);
+        let up = mouse(MouseEventKind::Up(MouseButton::Left), 3);
+        let mut queue = InputQueue::new(InputPriority::Keys, 10, 10);
+        queue.push(down.clone(), false);
+        queue.push(up.clone(), false);
+        queue.push(key('l'), false);
+        queue.push(key('s'), false);
+        assert_eq!(
+            drain(&mut queue),
+            [down.clone(), up.clone(), key('l'), key('s')]
+        );
+
+        // Typing and then clicking elsewhere sends the text to where it was typed
+        queue.push(key('x'), false);
+        queue.push(mouse(MouseEventKind::Moved, 3), false);
+        queue.push(down.clone(), false);
+        assert_eq!(
+            drain(&mut queue),
+            [key('x'), mouse(MouseEventKind::Moved, 3), down]
+        );
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_leftover_keys_hold_back_later_clicks() {
+        let down = mouse(MouseEventKind::Down(MouseButton::Left), 3);
+        let mut queue = InputQueue::new(InputPriority::Keys, 1, 10);
+        queue.push(key('a'), false);
+        queue.push(key('b'), false);
+        queue.push(down.clone(), false);
+        // The click waits behind the key the limit left for the next frame
+        assert_eq!(drain(&mut queue), [key('a')]);
+        assert_eq!(drain(&mut queue), [key('b'), down]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_arrival_order_keeps_interleaving() {
+        let mut queue = InputQueue::new(InputPriority::Arrival, 1, 10);
+        queue.push(mouse(MouseEventKind::Moved, 1), false);
+        queue.push(key('a'), false);
+        queue.push(mouse(MouseEventKind::ScrollUp, 1), false);
+        queue.push(key('b'), false);
+        queue.push(mouse(MouseEventKind::ScrollDown, 1), false);
+
+        assert_eq!(
+            drain(&mut queue),
+            [
+                mouse(MouseEventKind::Moved, 1),
+                key('a'),
+                mouse(MouseEventKind::ScrollUp, 1),
+            ]
+        );
+        assert_eq!(
+            drain(&mut queue),
+            [key('b'), mouse(MouseEventKind::ScrollDown, 1)]
+        );
+        assert!(queue.is_empty());
+    }
+}
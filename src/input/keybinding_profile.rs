@@ -1,4 +1,5 @@
 use crossterm::event::{KeyCode, KeyModifiers};
+use std::fmt;
 
 /// A single key binding: key code + required modifiers
 #[derive(Clone, Debug)]
@@ -18,6 +19,33 @@ impl KeyBinding {
     }
 }
 
+/// Human-readable form, e.g. "Ctrl+Shift+C", "Alt+Tab", "F12"
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl"),
+            (KeyModifiers::ALT, "Alt"),
+            (KeyModifiers::SUPER, "Cmd"),
+            (KeyModifiers::SHIFT, "Shift"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{}+", name)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            // BackTab is Shift+Tab, whether or not the terminal also reports Shift
+            KeyCode::BackTab if self.modifiers.contains(KeyModifiers::SHIFT) => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::PageUp => write!(f, "PageUp"),
+            KeyCode::PageDown => write!(f, "PageDown"),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
 /// Helper: create a binding with no modifiers
 fn key(code: KeyCode) -> KeyBinding {
     KeyBinding::new(code, KeyModifiers::NONE)
@@ -33,19 +61,65 @@ fn alt_shift(code: KeyCode) -> KeyBinding {
     KeyBinding::new(code, KeyModifiers::ALT.union(KeyModifiers::SHIFT))
 }
 
+/// Helper: create a binding with CONTROL modifier
+fn ctrl(code: KeyCode) -> KeyBinding {
+    KeyBinding::new(code, KeyModifiers::CONTROL)
+}
+
+/// Helper: create a binding with CONTROL+SHIFT modifier
+fn ctrl_shift(code: KeyCode) -> KeyBinding {
+    KeyBinding::new(code, KeyModifiers::CONTROL.union(KeyModifiers::SHIFT))
+}
+
 /// Helper: create a binding with SHIFT modifier
 fn shift(code: KeyCode) -> KeyBinding {
     KeyBinding::new(code, KeyModifiers::SHIFT)
 }
 
+/// Helper: create a binding with SUPER (Cmd) modifier
+fn cmd(code: KeyCode) -> KeyBinding {
+    KeyBinding::new(code, KeyModifiers::SUPER)
+}
+
+/// Platform copy chord: Cmd+C on macOS, Ctrl+Shift+C elsewhere
+fn copy_selection_bindings() -> Vec<KeyBinding> {
+    if cfg!(target_os = "macos") {
+        vec![cmd(KeyCode::Char('c'))]
+    } else {
+        vec![ctrl_shift(KeyCode::Char('C'))]
+    }
+}
+
+/// Platform paste chords: Cmd+V on macOS, Ctrl+Shift+V elsewhere, plus Shift+Insert
+fn paste_clipboard_bindings() -> Vec<KeyBinding> {
+    let mut bindings = if cfg!(target_os = "macos") {
+        vec![cmd(KeyCode::Char('v'))]
+    } else {
+        vec![ctrl_shift(KeyCode::Char('V'))]
+    };
+    bindings.push(shift(KeyCode::Insert));
+    bindings
+}
+
 /// Check if any binding in the list matches the given code and modifiers
 pub fn matches_any(bindings: &[KeyBinding], code: KeyCode, modifiers: KeyModifiers) -> bool {
     bindings.iter().any(|b| b.matches(code, modifiers))
 }
 
+/// An action name and its bindings, as listed by `KeybindingProfile::keymap`
+pub type KeymapEntry<'a> = (&'static str, &'a [KeyBinding]);
+
 /// All available profile names
 const PROFILE_NAMES: &[&str] = &["term39", "hyprland"];
 
+/// Built-in keys that no profile changes, listed after the profile's own (--dump-keymap)
+const FIXED_BINDINGS: &[(&str, &str)] = &[
+    ("launcher", "Ctrl+Space, Alt+Space"),
+    ("lock_screen", "F12"),
+    ("select_window", "Alt+1..Alt+9"),
+    ("send_function_key", "Shift+F1..Shift+F12"),
+];
+
 /// Display names for profiles
 const PROFILE_DISPLAY_NAMES: &[&str] = &["Term39", "Hyprland"];
 
@@ -57,9 +131,21 @@ pub struct KeybindingProfile {
     // -- Desktop actions --
     pub help: Vec<KeyBinding>,
     pub cycle_window: Vec<KeyBinding>,
+    /// Checked before `cycle_window`, whose Alt+Tab also matches with Shift held
+    pub cycle_window_previous: Vec<KeyBinding>,
+    /// Jump back to the previously focused window
+    pub focus_last_window: Vec<KeyBinding>,
     pub save_session: Vec<KeyBinding>,
     pub copy: Vec<KeyBinding>,
     pub paste: Vec<KeyBinding>,
+    pub paste_literal: Vec<KeyBinding>,
+    pub paste_and_run: Vec<KeyBinding>,
+    /// Prefix for pasting a recent copy: the digit pressed next picks it (1 = last copy)
+    pub paste_from_history: Vec<KeyBinding>,
+    /// Copy chord (Ctrl+Shift+C / Cmd+C); only taken when there is a selection
+    pub copy_selection: Vec<KeyBinding>,
+    /// Paste chords (Ctrl+Shift+V / Cmd+V, Shift+Insert); only taken when the clipboard has text
+    pub paste_clipboard: Vec<KeyBinding>,
     pub new_terminal: Vec<KeyBinding>,
     pub new_terminal_maximized: Vec<KeyBinding>,
     pub toggle_window_mode: Vec<KeyBinding>,
@@ -70,12 +156,32 @@ pub struct KeybindingProfile {
     #[allow(dead_code)]
     pub launcher: Vec<KeyBinding>,
     pub lock_screen: Vec<KeyBinding>,
+    pub clear_scrollback: Vec<KeyBinding>,
+    pub duplicate_window: Vec<KeyBinding>,
+    pub signal_interrupt: Vec<KeyBinding>,
+    pub signal_terminate: Vec<KeyBinding>,
+    pub signal_kill: Vec<KeyBinding>,
+    pub force_close_window: Vec<KeyBinding>,
+    pub toggle_debug_overlay: Vec<KeyBinding>,
+    pub toggle_input_lock: Vec<KeyBinding>,
+    pub toggle_control_display: Vec<KeyBinding>,
+    pub toggle_force_cursor: Vec<KeyBinding>,
+    pub cycle_window_tag: Vec<KeyBinding>,
+    pub toggle_key_passthrough: Vec<KeyBinding>,
+    pub toggle_mouse_capture: Vec<KeyBinding>,
+    pub cycle_charset: Vec<KeyBinding>,
+    pub cycle_dim: Vec<KeyBinding>,
+    pub toggle_scratchpad: Vec<KeyBinding>,
+    pub expose: Vec<KeyBinding>,
+    pub toggle_bars: Vec<KeyBinding>,
 
     // -- Window Mode actions --
     pub wm_focus_left: Vec<KeyBinding>,
     pub wm_focus_down: Vec<KeyBinding>,
     pub wm_focus_up: Vec<KeyBinding>,
     pub wm_focus_right: Vec<KeyBinding>,
+    pub wm_cycle_next: Vec<KeyBinding>,
+    pub wm_cycle_previous: Vec<KeyBinding>,
     pub wm_snap_left: Vec<KeyBinding>,
     pub wm_snap_down: Vec<KeyBinding>,
     pub wm_snap_up: Vec<KeyBinding>,
@@ -84,8 +190,12 @@ pub struct KeybindingProfile {
     pub wm_enter_resize: Vec<KeyBinding>,
     pub wm_close: Vec<KeyBinding>,
     pub wm_maximize: Vec<KeyBinding>,
+    pub wm_maximize_vertical: Vec<KeyBinding>,
+    pub wm_maximize_horizontal: Vec<KeyBinding>,
     pub wm_minimize: Vec<KeyBinding>,
     pub wm_toggle_auto_tiling: Vec<KeyBinding>,
+    pub wm_toggle_dense_font: Vec<KeyBinding>,
+    pub wm_lower: Vec<KeyBinding>,
 
     // -- Direct-mode actions (Alt-modifier, work from any focus) --
     pub direct_close_window: Vec<KeyBinding>,
@@ -121,9 +231,16 @@ impl KeybindingProfile {
                 key(KeyCode::Char('h')),
             ],
             cycle_window: vec![key(KeyCode::F(2)), alt(KeyCode::Tab)],
+            cycle_window_previous: vec![alt(KeyCode::BackTab), alt_shift(KeyCode::Tab)],
+            focus_last_window: vec![alt(KeyCode::Char('`'))],
             save_session: vec![key(KeyCode::F(3))],
             copy: vec![key(KeyCode::F(5))],
             paste: vec![key(KeyCode::F(6))],
+            paste_literal: vec![ctrl(KeyCode::F(6))],
+            paste_and_run: vec![ctrl_shift(KeyCode::Char('E'))],
+            paste_from_history: vec![ctrl_shift(KeyCode::Char('Y'))],
+            copy_selection: copy_selection_bindings(),
+            paste_clipboard: paste_clipboard_bindings(),
             new_terminal: vec![key(KeyCode::F(7)), key(KeyCode::Char('t'))],
             new_terminal_maximized: vec![key(KeyCode::Char('T'))],
             toggle_window_mode: vec![key(KeyCode::Char('`')), key(KeyCode::F(8))],
@@ -137,12 +254,32 @@ impl KeybindingProfile {
             about: vec![key(KeyCode::Char('l'))],
             launcher: vec![], // Handled separately via Ctrl+Space
             lock_screen: vec![shift(KeyCode::Char('Q'))],
+            clear_scrollback: vec![ctrl_shift(KeyCode::Char('K'))],
+            duplicate_window: vec![ctrl_shift(KeyCode::Char('D'))],
+            signal_interrupt: vec![ctrl_shift(KeyCode::Char('B'))],
+            signal_terminate: vec![ctrl_shift(KeyCode::Char('T'))],
+            signal_kill: vec![ctrl_shift(KeyCode::Char('X'))],
+            force_close_window: vec![ctrl_shift(KeyCode::Char('W'))],
+            toggle_debug_overlay: vec![ctrl_shift(KeyCode::Char('M'))],
+            toggle_input_lock: vec![ctrl_shift(KeyCode::Char('L'))],
+            toggle_control_display: vec![ctrl_shift(KeyCode::Char('R'))],
+            toggle_force_cursor: vec![ctrl_shift(KeyCode::Char('U'))],
+            cycle_window_tag: vec![ctrl_shift(KeyCode::Char('G'))],
+            toggle_key_passthrough: vec![ctrl_shift(KeyCode::Char('P'))],
+            toggle_mouse_capture: vec![ctrl_shift(KeyCode::Char('O'))],
+            cycle_charset: vec![ctrl_shift(KeyCode::Char('A'))],
+            cycle_dim: vec![ctrl_shift(KeyCode::Char('N'))],
+            toggle_scratchpad: vec![ctrl_shift(KeyCode::Char('S'))],
+            expose: vec![key(KeyCode::F(9))],
+            toggle_bars: vec![key(KeyCode::F(11))],
 
             // Window Mode actions
             wm_focus_left: vec![key(KeyCode::Char('h')), key(KeyCode::Left)],
             wm_focus_down: vec![key(KeyCode::Char('j')), key(KeyCode::Down)],
             wm_focus_up: vec![key(KeyCode::Char('k')), key(KeyCode::Up)],
             wm_focus_right: vec![key(KeyCode::Char('l')), key(KeyCode::Right)],
+            wm_cycle_next: vec![key(KeyCode::Tab)],
+            wm_cycle_previous: vec![key(KeyCode::BackTab), shift(KeyCode::Tab)],
             wm_snap_left: vec![shift(KeyCode::Char('H')), shift(KeyCode::Left)],
             wm_snap_down: vec![shift(KeyCode::Char('J')), shift(KeyCode::Down)],
             wm_snap_up: vec![shift(KeyCode::Char('K')), shift(KeyCode::Up)],
@@ -155,8 +292,12 @@ impl KeybindingProfile {
                 key(KeyCode::Char('+')),
                 key(KeyCode::Char(' ')),
             ],
+            wm_maximize_vertical: vec![key(KeyCode::Char('v'))],
+            wm_maximize_horizontal: vec![shift(KeyCode::Char('V'))],
             wm_minimize: vec![key(KeyCode::Char('-')), key(KeyCode::Char('_'))],
             wm_toggle_auto_tiling: vec![key(KeyCode::Char('a'))],
+            wm_toggle_dense_font: vec![key(KeyCode::Char('d'))],
+            wm_lower: vec![key(KeyCode::Char('b'))],
 
             // Direct-mode: empty for term39 (all through Window Mode)
             direct_close_window: vec![],
@@ -223,9 +364,16 @@ impl KeybindingProfile {
             // Desktop actions (from desktop/topbar focus)
             help: vec![key(KeyCode::F(1)), key(KeyCode::Char('?'))],
             cycle_window: vec![alt(KeyCode::Tab), key(KeyCode::F(2))],
+            cycle_window_previous: vec![alt(KeyCode::BackTab), alt_shift(KeyCode::Tab)],
+            focus_last_window: vec![alt(KeyCode::Char('`'))],
             save_session: vec![key(KeyCode::F(3))],
             copy: vec![key(KeyCode::F(5))],
             paste: vec![key(KeyCode::F(6))],
+            paste_literal: vec![ctrl(KeyCode::F(6))],
+            paste_and_run: vec![ctrl_shift(KeyCode::Char('E'))],
+            paste_from_history: vec![ctrl_shift(KeyCode::Char('Y'))],
+            copy_selection: copy_selection_bindings(),
+            paste_clipboard: paste_clipboard_bindings(),
             new_terminal: vec![key(KeyCode::Char('t'))],
             new_terminal_maximized: vec![key(KeyCode::Char('T'))],
             toggle_window_mode: vec![key(KeyCode::F(8))], // No backtick (backtick goes to terminal)
@@ -235,12 +383,32 @@ impl KeybindingProfile {
             about: vec![key(KeyCode::Char('l'))],
             launcher: vec![alt(KeyCode::Char(' '))],
             lock_screen: vec![shift(KeyCode::Char('Q'))],
+            clear_scrollback: vec![ctrl_shift(KeyCode::Char('K'))],
+            duplicate_window: vec![ctrl_shift(KeyCode::Char('D'))],
+            signal_interrupt: vec![ctrl_shift(KeyCode::Char('B'))],
+            signal_terminate: vec![ctrl_shift(KeyCode::Char('T'))],
+            signal_kill: vec![ctrl_shift(KeyCode::Char('X'))],
+            force_close_window: vec![ctrl_shift(KeyCode::Char('W'))],
+            toggle_debug_overlay: vec![ctrl_shift(KeyCode::Char('M'))],
+            toggle_input_lock: vec![ctrl_shift(KeyCode::Char('L'))],
+            toggle_control_display: vec![ctrl_shift(KeyCode::Char('R'))],
+            toggle_force_cursor: vec![ctrl_shift(KeyCode::Char('U'))],
+            cycle_window_tag: vec![ctrl_shift(KeyCode::Char('G'))],
+            toggle_key_passthrough: vec![ctrl_shift(KeyCode::Char('P'))],
+            toggle_mouse_capture: vec![ctrl_shift(KeyCode::Char('O'))],
+            cycle_charset: vec![ctrl_shift(KeyCode::Char('A'))],
+            cycle_dim: vec![ctrl_shift(KeyCode::Char('N'))],
+            toggle_scratchpad: vec![ctrl_shift(KeyCode::Char('S'))],
+            expose: vec![key(KeyCode::F(9))],
+            toggle_bars: vec![key(KeyCode::F(11))],
 
             // Window Mode actions (same as term39 since F8 still works)
             wm_focus_left: vec![key(KeyCode::Char('h')), key(KeyCode::Left)],
             wm_focus_down: vec![key(KeyCode::Char('j')), key(KeyCode::Down)],
             wm_focus_up: vec![key(KeyCode::Char('k')), key(KeyCode::Up)],
             wm_focus_right: vec![key(KeyCode::Char('l')), key(KeyCode::Right)],
+            wm_cycle_next: vec![key(KeyCode::Tab)],
+            wm_cycle_previous: vec![key(KeyCode::BackTab), shift(KeyCode::Tab)],
             wm_snap_left: vec![shift(KeyCode::Char('H')), shift(KeyCode::Left)],
             wm_snap_down: vec![shift(KeyCode::Char('J')), shift(KeyCode::Down)],
             wm_snap_up: vec![shift(KeyCode::Char('K')), shift(KeyCode::Up)],
@@ -253,8 +421,12 @@ impl KeybindingProfile {
                 key(KeyCode::Char('+')),
                 key(KeyCode::Char(' ')),
             ],
+            wm_maximize_vertical: vec![key(KeyCode::Char('v'))],
+            wm_maximize_horizontal: vec![shift(KeyCode::Char('V'))],
             wm_minimize: vec![key(KeyCode::Char('-')), key(KeyCode::Char('_'))],
             wm_toggle_auto_tiling: vec![key(KeyCode::Char('a'))],
+            wm_toggle_dense_font: vec![key(KeyCode::Char('d'))],
+            wm_lower: vec![key(KeyCode::Char('b'))],
 
             // Direct-mode actions (Alt-modifier, work from any focus)
             direct_close_window: direct_close,
@@ -284,8 +456,16 @@ impl KeybindingProfile {
         }
     }
 
+    /// Drop the copy/paste chords so the terminal receives those keys instead
+    pub fn with_clipboard_shortcuts(mut self, enabled: bool) -> Self {
+        if !enabled {
+            self.copy_selection.clear();
+            self.paste_clipboard.clear();
+        }
+        self
+    }
+
     /// Get all available profile names
-    #[allow(dead_code)]
     pub fn all_names() -> &'static [&'static str] {
         PROFILE_NAMES
     }
@@ -318,6 +498,144 @@ impl KeybindingProfile {
         PROFILE_DISPLAY_NAMES[idx]
     }
 
+    /// Every action with its bindings, grouped by where the keys are handled
+    pub fn keymap(&self) -> Vec<(&'static str, Vec<KeymapEntry<'_>>)> {
+        vec![
+            (
+                "Global (any focus)",
+                vec![
+                    ("cycle_window", &self.cycle_window[..]),
+                    ("cycle_window_previous", &self.cycle_window_previous),
+                    ("focus_last_window", &self.focus_last_window),
+                    ("toggle_window_mode", &self.toggle_window_mode),
+                    ("exit", &self.exit),
+                    ("save_session", &self.save_session),
+                    ("expose", &self.expose),
+                    ("toggle_debug_overlay", &self.toggle_debug_overlay),
+                    ("toggle_key_passthrough", &self.toggle_key_passthrough),
+                    ("toggle_mouse_capture", &self.toggle_mouse_capture),
+                    ("cycle_charset", &self.cycle_charset),
+                    ("cycle_dim", &self.cycle_dim),
+                    ("toggle_scratchpad", &self.toggle_scratchpad),
+                    ("toggle_bars", &self.toggle_bars),
+                    ("direct_close_window", &self.direct_close_window),
+                    ("direct_focus_left", &self.direct_focus_left),
+                    ("direct_focus_down", &self.direct_focus_down),
+                    ("direct_focus_up", &self.direct_focus_up),
+                    ("direct_focus_right", &self.direct_focus_right),
+                    ("direct_snap_left", &self.direct_snap_left),
+                    ("direct_snap_down", &self.direct_snap_down),
+                    ("direct_snap_up", &self.direct_snap_up),
+                    ("direct_snap_right", &self.direct_snap_right),
+                    ("direct_maximize", &self.direct_maximize),
+                    ("direct_toggle_auto_tiling", &self.direct_toggle_auto_tiling),
+                    ("direct_new_terminal", &self.direct_new_terminal),
+                    (
+                        "direct_new_terminal_maximized",
+                        &self.direct_new_terminal_maximized,
+                    ),
+                    ("direct_settings", &self.direct_settings),
+                ],
+            ),
+            (
+                "Desktop focused",
+                vec![
+                    ("help", &self.help[..]),
+                    ("about", &self.about),
+                    ("calendar", &self.calendar),
+                    ("settings", &self.settings),
+                    ("new_terminal", &self.new_terminal),
+                    ("new_terminal_maximized", &self.new_terminal_maximized),
+                    ("lock_screen", &self.lock_screen),
+                ],
+            ),
+            (
+                "Window focused",
+                vec![
+                    ("copy", &self.copy[..]),
+                    ("paste", &self.paste),
+                    ("paste_literal", &self.paste_literal),
+                    ("paste_and_run", &self.paste_and_run),
+                    ("paste_from_history", &self.paste_from_history),
+                    ("copy_selection", &self.copy_selection),
+                    ("paste_clipboard", &self.paste_clipboard),
+                    ("clear_scrollback", &self.clear_scrollback),
+                    ("duplicate_window", &self.duplicate_window),
+                    ("signal_interrupt", &self.signal_interrupt),
+                    ("signal_terminate", &self.signal_terminate),
+                    ("signal_kill", &self.signal_kill),
+                    ("force_close_window", &self.force_close_window),
+                    ("toggle_input_lock", &self.toggle_input_lock),
+                    ("toggle_control_display", &self.toggle_control_display),
+                    ("toggle_force_cursor", &self.toggle_force_cursor),
+                    ("cycle_window_tag", &self.cycle_window_tag),
+                ],
+            ),
+            (
+                "Window Mode",
+                vec![
+                    ("wm_focus_left", &self.wm_focus_left[..]),
+                    ("wm_focus_down", &self.wm_focus_down),
+                    ("wm_focus_up", &self.wm_focus_up),
+                    ("wm_focus_right", &self.wm_focus_right),
+                    ("wm_cycle_next", &self.wm_cycle_next),
+                    ("wm_cycle_previous", &self.wm_cycle_previous),
+                    ("wm_snap_left", &self.wm_snap_left),
+                    ("wm_snap_down", &self.wm_snap_down),
+                    ("wm_snap_up", &self.wm_snap_up),
+                    ("wm_snap_right", &self.wm_snap_right),
+                    ("wm_enter_move", &self.wm_enter_move),
+                    ("wm_enter_resize", &self.wm_enter_resize),
+                    ("wm_close", &self.wm_close),
+                    ("wm_maximize", &self.wm_maximize),
+                    ("wm_maximize_vertical", &self.wm_maximize_vertical),
+                    ("wm_maximize_horizontal", &self.wm_maximize_horizontal),
+                    ("wm_minimize", &self.wm_minimize),
+                    ("wm_toggle_auto_tiling", &self.wm_toggle_auto_tiling),
+                    ("wm_toggle_dense_font", &self.wm_toggle_dense_font),
+                    ("wm_lower", &self.wm_lower),
+                ],
+            ),
+        ]
+    }
+
+    /// Plain-text listing of the keymap (--dump-keymap); unbound actions show "-"
+    pub fn dump_keymap(&self) -> String {
+        let mut out = format!("Keybinding profile: {}\n", Self::display_name(&self.name));
+        for (context, actions) in self.keymap() {
+            out.push_str(&format!("\n[{}]\n", context));
+            for (action, bindings) in actions {
+                let mut keys: Vec<String> = Vec::new();
+                for key in bindings.iter().map(|b| b.to_string()) {
+                    if !keys.contains(&key) {
+                        keys.push(key);
+                    }
+                }
+                let keys = if keys.is_empty() {
+                    "-".to_string()
+                } else {
+                    keys.join(", ")
+                };
+                out.push_str(&format!("  {:<30} {}\n", action, keys));
+            }
+        }
+        out.push_str("\n[Fixed]\n");
+        for (action, keys) in self.fixed_bindings() {
+            out.push_str(&format!("  {:<30} {}\n", action, keys));
+        }
+        out
+    }
+
+    /// The built-in keys this profile leaves in effect: F10 toggles the window number
+    /// overlay only where it isn't an exit key
+    fn fixed_bindings(&self) -> Vec<(&'static str, &'static str)> {
+        let mut fixed = FIXED_BINDINGS.to_vec();
+        if !matches_any(&self.exit, KeyCode::F(10), KeyModifiers::NONE) {
+            fixed.insert(3, ("window_number_overlay", "F10"));
+        }
+        fixed
+    }
+
     /// Check if this profile has any direct-mode bindings
     pub fn has_direct_bindings(&self) -> bool {
         !self.direct_close_window.is_empty()
@@ -325,3 +643,138 @@ impl KeybindingProfile {
             || !self.direct_new_terminal.is_empty()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clipboard_chords_leave_ctrl_c_alone() {
+        let profile = KeybindingProfile::term39();
+        let ctrl_shift_mods = KeyModifiers::CONTROL | KeyModifiers::SHIFT;
+        if !cfg!(target_os = "macos") {
+            assert!(matches_any(
+                &profile.copy_selection,
+                KeyCode::Char('C'),
+                ctrl_shift_mods
+            ));
+            assert!(matches_any(
+                &profile.paste_clipboard,
+                KeyCode::Char('V'),
+                ctrl_shift_mods
+            ));
+        }
+        assert!(matches_any(
+            &profile.paste_clipboard,
+            KeyCode::Insert,
+            KeyModifiers::SHIFT
+        ));
+
+        // Plain Ctrl+C (SIGINT) and unmodified Insert go to the terminal
+        assert!(!matches_any(
+            &profile.copy_selection,
+            KeyCode::Char('c'),
+            KeyModifiers::CONTROL
+        ));
+        assert!(!matches_any(
+            &profile.paste_clipboard,
+            KeyCode::Insert,
+            KeyModifiers::NONE
+        ));
+
+        let remapped = profile.with_clipboard_shortcuts(false);
+        assert!(remapped.copy_selection.is_empty() && remapped.paste_clipboard.is_empty());
+    }
+
+    #[test]
+    fn test_dump_keymap_lists_every_context() {
+        assert_eq!(ctrl_shift(KeyCode::Char('S')).to_string(), "Ctrl+Shift+S");
+        assert_eq!(alt_shift(KeyCode::BackTab).to_string(), "Alt+Shift+Tab");
+        assert_eq!(key(KeyCode::F(12)).to_string(), "F12");
+        assert_eq!(key(KeyCode::Char(' ')).to_string(), "Space");
+
+        let dump = KeybindingProfile::term39()
+            .with_clipboard_shortcuts(false)
+            .dump_keymap();
+        for context in [
+            "[Global",
+            "[Desktop focused]",
+            "[Window focused]",
+            "[Window Mode]",
+        ] {
+            assert!(dump.contains(context), "missing {}", context);
+        }
+        assert!(dump.contains("Ctrl+Shift+S"));
+        // Disabled clipboard chords are listed as unbound
+        assert!(
+            dump.lines()
+                .any(|l| l.trim_start().starts_with("copy_selection") && l.ends_with(" -"))
+        );
+    }
+
+    #[test]
+    fn test_f10_overlay_listed_only_where_it_is_not_exit() {
+        let overlay = |profile: &KeybindingProfile| {
+            profile
+                .fixed_bindings()
+                .contains(&("window_number_overlay", "F10"))
+        };
+        // F10 exits in the built-in profiles, so it never reaches the overlay there
+        for profile in [KeybindingProfile::term39(), KeybindingProfile::hyprland()] {
+            assert!(matches_any(
+                &profile.exit,
+                KeyCode::F(10),
+                KeyModifiers::NONE
+            ));
+            assert!(!overlay(&profile), "{} lists the overlay", profile.name);
+            let mut without_f10 = profile.clone();
+            without_f10
+                .exit
+                .retain(|b| !b.matches(KeyCode::F(10), KeyModifiers::NONE));
+            assert!(overlay(&without_f10));
+        }
+    }
+
+    #[test]
+    fn test_plain_tab_is_left_to_the_terminal() {
+        let alt_shift_mods = KeyModifiers::ALT | KeyModifiers::SHIFT;
+        for profile in [KeybindingProfile::term39(), KeybindingProfile::hyprland()] {
+            // Tab and Shift+Tab are shell completion keys outside Window Mode
+            for (code, mods) in [
+                (KeyCode::Tab, KeyModifiers::NONE),
+                (KeyCode::BackTab, KeyModifiers::SHIFT),
+            ] {
+                assert!(!matches_any(&profile.cycle_window, code, mods));
+                assert!(!matches_any(&profile.cycle_window_previous, code, mods));
+            }
+            assert!(matches_any(
+                &profile.cycle_window,
+                KeyCode::Tab,
+                KeyModifiers::ALT
+            ));
+            // Terminals report Alt+Shift+Tab either as BackTab or as Tab with Shift
+            assert!(matches_any(
+                &profile.cycle_window_previous,
+                KeyCode::BackTab,
+                alt_shift_mods
+            ));
+            assert!(matches_any(
+                &profile.cycle_window_previous,
+                KeyCode::Tab,
+                alt_shift_mods
+            ));
+
+            // In Window Mode plain Tab cycles
+            assert!(matches_any(
+                &profile.wm_cycle_next,
+                KeyCode::Tab,
+                KeyModifiers::NONE
+            ));
+            assert!(matches_any(
+                &profile.wm_cycle_previous,
+                KeyCode::BackTab,
+                KeyModifiers::SHIFT
+            ));
+        }
+    }
+}
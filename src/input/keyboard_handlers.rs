@@ -1,9 +1,13 @@
-use crate::app::app_state::AppState;
+use crate::app::app_state::{
+    AppState, DuplicateRequest, FALLBACK_SHELL, FallbackShellRequest, ForceCloseRequest, PasteKind,
+    PasteRequest, PendingConfirmation, SignalRequest,
+};
 use crate::app::cli::Cli;
 use crate::app::config;
-use crate::app::config_manager::AppConfig;
+use crate::app::config_manager::{AltKeyMode, AppConfig, EnterSends, PasteGuard};
 use crate::input::keybinding_profile::{KeybindingProfile, matches_any};
 use crate::rendering::RenderBackend;
+use crate::term_emu::ForegroundSignal;
 use crate::ui::config_window::ConfigWindow;
 use crate::ui::error_dialog::ErrorDialog;
 use crate::ui::info_window::InfoWindow;
@@ -42,6 +46,73 @@ fn get_function_key_sequence(n: u8) -> Option<&'static str> {
     }
 }
 
+/// Routes keys straight to the focused terminal, bypassing window manager shortcuts
+/// Handles the pass-through toggle itself, every key while pass-through is on,
+/// and Alt+letter when `alt_key_mode` is Forward
+/// Returns true if event was handled
+pub fn handle_key_passthrough(
+    app_state: &mut AppState,
+    key_event: KeyEvent,
+    current_focus: FocusState,
+    window_manager: &mut WindowManager,
+    app_config: &AppConfig,
+    profile: &KeybindingProfile,
+) -> bool {
+    let code = key_event.code;
+    let modifiers = key_event.modifiers;
+    if !matches!(current_focus, FocusState::Window(_)) {
+        return false;
+    }
+
+    // Toggle pass-through (CTRL+SHIFT+P) - the only key not forwarded while it is on
+    if matches_any(&profile.toggle_key_passthrough, code, modifiers) {
+        app_state.key_passthrough = !app_state.key_passthrough;
+        let message = if app_state.key_passthrough {
+            "Key pass-through on: all keys go to the terminal"
+        } else {
+            "Key pass-through off"
+        };
+        app_state.active_toast = Some(crate::ui::toast::Toast::new(message));
+        return true;
+    }
+
+    let forward_alt = app_config.alt_key_mode == AltKeyMode::Forward;
+    if app_state.key_passthrough {
+        match meta_sequence(key_event) {
+            Some(seq) if forward_alt => {
+                let _ = window_manager.send_to_focused(&seq);
+            }
+            _ => forward_to_terminal(key_event, window_manager),
+        }
+        return true;
+    }
+
+    // Alt+letter goes to the application instead of the keymap (Alt+digit, Alt+Tab, etc. still work)
+    if forward_alt && matches!(code, KeyCode::Char(c) if c.is_ascii_alphabetic()) {
+        if let Some(seq) = meta_sequence(key_event) {
+            let _ = window_manager.send_to_focused(&seq);
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Bytes Alt+character sends with `alt_key_mode` Forward: the character with an ESC
+/// prefix, like xterm's metaSendsEscape (None for other keys and Ctrl+Alt)
+fn meta_sequence(key_event: KeyEvent) -> Option<String> {
+    let modifiers = key_event.modifiers;
+    match key_event.code {
+        KeyCode::Char(c)
+            if modifiers.contains(KeyModifiers::ALT)
+                && !modifiers.contains(KeyModifiers::CONTROL) =>
+        {
+            Some(format!("\x1b{}", c))
+        }
+        _ => None,
+    }
+}
+
 /// Handles desktop keyboard shortcuts (when not in a dialog)
 /// Returns true if event was handled
 #[allow(clippy::too_many_arguments)]
@@ -60,6 +131,44 @@ pub fn handle_desktop_keyboard(
     let modifiers = key_event.modifiers;
     let on_desktop = matches!(current_focus, FocusState::Desktop | FocusState::Topbar);
 
+    // A digit after the paste-from-history prefix pastes that copy (1 = the last one);
+    // any other key drops the prefix and is handled as usual
+    if std::mem::take(&mut app_state.pending_history_paste) {
+        if let KeyCode::Char(c) = code
+            && let Some(n) = c.to_digit(10).filter(|&n| n > 0)
+        {
+            if let FocusState::Window(window_id) = current_focus {
+                match clipboard_manager
+                    .history_entry(n as usize)
+                    .map(str::to_string)
+                {
+                    Some(text) => {
+                        let (cols, rows) = backend.dimensions();
+                        paste_into_window(
+                            app_state,
+                            window_manager,
+                            clipboard_manager,
+                            window_id,
+                            text,
+                            PasteKind::Normal,
+                            cols,
+                            rows,
+                        );
+                        window_manager.clear_selection(window_id);
+                    }
+                    None => {
+                        app_state.active_toast = Some(crate::ui::toast::Toast::new(format!(
+                            "No clip {}: the history holds {}",
+                            n,
+                            clipboard_manager.history_len()
+                        )));
+                    }
+                }
+            }
+            return true;
+        }
+    }
+
     // Handle Shift+F1-F12 to send function key sequences to terminal
     // This allows users to send F-keys to terminal apps while F-keys are used for app shortcuts
     if let KeyCode::F(n) = code {
@@ -172,7 +281,17 @@ pub fn handle_desktop_keyboard(
         return true;
     }
 
-    // Handle window cycling
+    // Handle jumping back to the previously focused window
+    if matches_any(&profile.focus_last_window, code, modifiers) {
+        window_manager.focus_last_window();
+        return true;
+    }
+
+    // Handle window cycling (plain Tab is never bound here so shells keep completion)
+    if matches_any(&profile.cycle_window_previous, code, modifiers) {
+        window_manager.cycle_to_previous_window();
+        return true;
+    }
     if matches_any(&profile.cycle_window, code, modifiers) {
         window_manager.cycle_to_next_window();
         return true;
@@ -248,6 +367,13 @@ pub fn handle_desktop_keyboard(
         return true;
     }
 
+    // Handle F9 to open the expose overview (closing is handled by the overview itself)
+    if matches_any(&profile.expose, code, modifiers) && window_manager.window_count() > 0 {
+        app_state.show_expose = true;
+        app_state.show_window_number_overlay = false;
+        return true;
+    }
+
     // Handle Alt+1-9 (or Option+1-9 on macOS) for direct window selection
     if let KeyCode::Char(c) = code {
         let num: Option<u32> = match c {
@@ -295,6 +421,123 @@ pub fn handle_desktop_keyboard(
         return true;
     }
 
+    // Handle clear scrollback (CTRL+SHIFT+K) - keeps the visible screen and the shell untouched
+    if matches_any(&profile.clear_scrollback, code, modifiers)
+        && matches!(current_focus, FocusState::Window(_))
+    {
+        window_manager.clear_focused_scrollback();
+        return true;
+    }
+
+    // Handle input lock toggle (CTRL+SHIFT+L) - guards a monitoring window from stray keystrokes
+    if matches_any(&profile.toggle_input_lock, code, modifiers)
+        && matches!(current_focus, FocusState::Window(_))
+    {
+        if let Some(locked) = window_manager.toggle_focused_input_lock() {
+            let message = if locked {
+                "Input locked"
+            } else {
+                "Input unlocked"
+            };
+            app_state.active_toast = Some(crate::ui::toast::Toast::new(message));
+        }
+        return true;
+    }
+
+    // Handle control character display (CTRL+SHIFT+R) - shows what the program sends
+    if matches_any(&profile.toggle_control_display, code, modifiers)
+        && matches!(current_focus, FocusState::Window(_))
+    {
+        if let Some(shown) = window_manager.toggle_focused_control_display() {
+            let message = if shown {
+                "Showing control characters"
+            } else {
+                "Interpreting control characters"
+            };
+            app_state.active_toast = Some(crate::ui::toast::Toast::new(message));
+        }
+        return true;
+    }
+
+    // Handle forced cursor (CTRL+SHIFT+U) - shows a cursor the application hid and never restored
+    if matches_any(&profile.toggle_force_cursor, code, modifiers)
+        && matches!(current_focus, FocusState::Window(_))
+    {
+        if let Some(forced) = window_manager.toggle_focused_force_cursor() {
+            let message = if forced {
+                "Cursor always shown"
+            } else {
+                "Cursor follows the application"
+            };
+            app_state.active_toast = Some(crate::ui::toast::Toast::new(message));
+        }
+        return true;
+    }
+
+    // Handle window color tag (CTRL+SHIFT+G) - cycles the title bar through the tag palette
+    if matches_any(&profile.cycle_window_tag, code, modifiers)
+        && matches!(current_focus, FocusState::Window(_))
+    {
+        if let Some(tag) = window_manager.cycle_focused_tag() {
+            let message = match tag.and_then(crate::window::base::tag_name) {
+                Some(name) => format!("Window tag: {}", name),
+                None => "Window tag cleared".to_string(),
+            };
+            app_state.active_toast = Some(crate::ui::toast::Toast::new(message));
+        }
+        return true;
+    }
+
+    // Handle debug overlay toggle (CTRL+SHIFT+M) - only with --debug-overlay
+    if cli_args.debug_overlay && matches_any(&profile.toggle_debug_overlay, code, modifiers) {
+        app_state.debug_overlay.toggle();
+        return true;
+    }
+
+    // Handle duplicate window (CTRL+SHIFT+D) - same directory, optionally rerunning its command
+    if matches_any(&profile.duplicate_window, code, modifiers)
+        && matches!(current_focus, FocusState::Window(_))
+    {
+        let (cols, rows) = backend.dimensions();
+        duplicate_focused_window(
+            app_state,
+            window_manager,
+            cols,
+            rows,
+            app_config.tiling_gaps,
+        );
+        return true;
+    }
+
+    // Handle signals to the foreground job (CTRL+SHIFT+B/T/X); SIGKILL asks first
+    if matches!(current_focus, FocusState::Window(_)) {
+        let signal = if matches_any(&profile.signal_interrupt, code, modifiers) {
+            Some(ForegroundSignal::Interrupt)
+        } else if matches_any(&profile.signal_terminate, code, modifiers) {
+            Some(ForegroundSignal::Terminate)
+        } else if matches_any(&profile.signal_kill, code, modifiers) {
+            Some(ForegroundSignal::Kill)
+        } else {
+            None
+        };
+        if let Some(signal) = signal {
+            if signal == ForegroundSignal::Kill {
+                confirm_kill_focused(app_state, window_manager, backend);
+            } else {
+                send_signal_to_focused(app_state, window_manager, backend, signal);
+            }
+            return true;
+        }
+    }
+
+    // Handle force close (CTRL+SHIFT+W): kill the window's processes and close it, asks first
+    if matches_any(&profile.force_close_window, code, modifiers)
+        && matches!(current_focus, FocusState::Window(_))
+    {
+        confirm_force_close_focused(app_state, window_manager, backend);
+        return true;
+    }
+
     // Handle CTRL+Space / Option+Space to open Slight input popup
     let is_launcher_shortcut = (code == KeyCode::Char(' ')
         && (modifiers.contains(KeyModifiers::CONTROL) || modifiers.contains(KeyModifiers::ALT)))
@@ -308,7 +551,7 @@ pub fn handle_desktop_keyboard(
     if matches_any(&profile.copy, code, modifiers) {
         if let FocusState::Window(window_id) = current_focus {
             if let Some(text) = window_manager.get_selected_text(window_id) {
-                if clipboard_manager.copy(text).is_ok() {
+                if copy_from_window(clipboard_manager, window_manager, window_id, text).is_ok() {
                     window_manager.clear_selection(window_id);
                 }
             }
@@ -316,11 +559,63 @@ pub fn handle_desktop_keyboard(
         return true;
     }
 
+    // Handle paste and run (Ctrl+Shift+E) and paste without running (Ctrl+F6)
+    // Checked before plain paste, whose binding also matches with modifiers held
+    let paste_and_run = matches_any(&profile.paste_and_run, code, modifiers);
+    if paste_and_run || matches_any(&profile.paste_literal, code, modifiers) {
+        if let FocusState::Window(window_id) = current_focus {
+            if let Ok(text) = clipboard_manager.paste() {
+                let kind = if paste_and_run {
+                    PasteKind::AndRun
+                } else {
+                    PasteKind::Literal
+                };
+                let (cols, rows) = backend.dimensions();
+                paste_into_window(
+                    app_state,
+                    window_manager,
+                    clipboard_manager,
+                    window_id,
+                    text,
+                    kind,
+                    cols,
+                    rows,
+                );
+                window_manager.clear_selection(window_id);
+            }
+        }
+        return true;
+    }
+
+    // Handle paste from history (CTRL+SHIFT+Y, then a digit)
+    if matches_any(&profile.paste_from_history, code, modifiers)
+        && matches!(current_focus, FocusState::Window(_))
+    {
+        let message = match clipboard_manager.history_len() {
+            0 => "Clipboard history is empty".to_string(),
+            1 => "Press 1 to paste the last copy".to_string(),
+            len => format!("Press 1-{} to paste a recent copy", len),
+        };
+        app_state.pending_history_paste = clipboard_manager.history_len() > 0;
+        app_state.active_toast = Some(crate::ui::toast::Toast::new(message));
+        return true;
+    }
+
     // Handle paste (F6)
     if matches_any(&profile.paste, code, modifiers) {
         if let FocusState::Window(window_id) = current_focus {
             if let Ok(text) = clipboard_manager.paste() {
-                let _ = window_manager.paste_to_window(window_id, &text);
+                let (cols, rows) = backend.dimensions();
+                paste_into_window(
+                    app_state,
+                    window_manager,
+                    clipboard_manager,
+                    window_id,
+                    text,
+                    PasteKind::Normal,
+                    cols,
+                    rows,
+                );
                 window_manager.clear_selection(window_id);
             }
         }
@@ -341,41 +636,40 @@ pub fn handle_desktop_keyboard(
         return true;
     }
 
-    // Platform-aware copy shortcut
-    let is_copy_shortcut = if is_macos() {
-        code == KeyCode::Char('c') && modifiers.contains(KeyModifiers::SUPER)
-    } else {
-        code == KeyCode::Char('C')
-            && modifiers.contains(KeyModifiers::CONTROL)
-            && modifiers.contains(KeyModifiers::SHIFT)
-    };
-    if is_copy_shortcut {
+    // Copy chord (Ctrl+Shift+C / Cmd+C) - without a selection the key goes to the terminal
+    if matches_any(&profile.copy_selection, code, modifiers) {
         if let FocusState::Window(window_id) = current_focus {
             if let Some(text) = window_manager.get_selected_text(window_id) {
-                if clipboard_manager.copy(text).is_ok() {
+                if copy_from_window(clipboard_manager, window_manager, window_id, text).is_ok() {
                     window_manager.clear_selection(window_id);
                 }
+                return true;
             }
         }
-        return true;
     }
 
-    // Platform-aware paste shortcut
-    let is_paste_shortcut = if is_macos() {
-        code == KeyCode::Char('v') && modifiers.contains(KeyModifiers::SUPER)
-    } else {
-        code == KeyCode::Char('V')
-            && modifiers.contains(KeyModifiers::CONTROL)
-            && modifiers.contains(KeyModifiers::SHIFT)
-    };
-    if is_paste_shortcut {
+    // Paste chords (Ctrl+Shift+V / Cmd+V, Shift+Insert) - with an empty clipboard the key
+    // goes to the terminal
+    if matches_any(&profile.paste_clipboard, code, modifiers) {
         if let FocusState::Window(window_id) = current_focus {
             if let Ok(text) = clipboard_manager.paste() {
-                let _ = window_manager.paste_to_window(window_id, &text);
-                window_manager.clear_selection(window_id);
+                if !text.is_empty() {
+                    let (cols, rows) = backend.dimensions();
+                    paste_into_window(
+                        app_state,
+                        window_manager,
+                        clipboard_manager,
+                        window_id,
+                        text,
+                        PasteKind::Normal,
+                        cols,
+                        rows,
+                    );
+                    window_manager.clear_selection(window_id);
+                    return true;
+                }
             }
         }
-        return true;
     }
 
     // Handle exit (profile-based: ESC and q for term39; ESC and F10 for hyprland)
@@ -478,6 +772,46 @@ pub fn handle_desktop_keyboard(
 
 /// Forwards keyboard input to the focused terminal window
 pub fn forward_to_terminal(key_event: KeyEvent, window_manager: &mut WindowManager) {
+    if key_event.code == KeyCode::Enter {
+        let (enter_sends, lnm) = window_manager.focused_enter_mode();
+        let _ = window_manager.send_to_focused(enter_sequence(enter_sends, lnm));
+        return;
+    }
+
+    // Cursor keys depend on application cursor keys mode (DECCKM)
+    let application_cursor_keys = matches!(
+        key_event.code,
+        KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right | KeyCode::Home | KeyCode::End
+    ) && window_manager.get_focused_application_cursor_keys();
+    if let Some(seq) = terminal_key_sequence(key_event, application_cursor_keys) {
+        let _ = window_manager.send_to_focused(&seq);
+    }
+}
+
+/// Bytes the Enter key sends: CR LF while the application has set LNM (mode 20),
+/// the configured `enter_sends` otherwise
+pub fn enter_sequence(enter_sends: EnterSends, lnm: bool) -> &'static str {
+    if lnm {
+        return "\r\n";
+    }
+    match enter_sends {
+        EnterSends::Cr => "\r",
+        EnterSends::Lf => "\n",
+        EnterSends::CrLf => "\r\n",
+    }
+}
+
+/// Bytes a key sends to the terminal, or None for keys terminals don't receive
+pub fn terminal_key_sequence(key_event: KeyEvent, application_cursor_keys: bool) -> Option<String> {
+    // Cursor keys: SS3 in application mode, CSI in normal mode
+    let cursor = |final_byte: char| {
+        let prefix = if application_cursor_keys {
+            "\x1bO"
+        } else {
+            "\x1b["
+        };
+        Some(format!("{}{}", prefix, final_byte))
+    };
     match key_event.code {
         KeyCode::Char(c) => {
             // Windows: Handle AltGr combinations (reported as CTRL+ALT)
@@ -489,8 +823,7 @@ pub fn forward_to_terminal(key_event: KeyEvent, window_manager: &mut WindowManag
                     && key_event.modifiers.contains(KeyModifiers::ALT);
                 if is_altgr {
                     // AltGr combination - send the character directly
-                    let _ = window_manager.send_char_to_focused(c);
-                    return;
+                    return Some(c.to_string());
                 }
             }
 
@@ -499,97 +832,37 @@ pub fn forward_to_terminal(key_event: KeyEvent, window_manager: &mut WindowManag
                 // Convert to control character (Ctrl+A = 0x01, Ctrl+B = 0x02, etc.)
                 if c.is_ascii_alphabetic() {
                     let control_char = (c.to_ascii_lowercase() as u8 - b'a' + 1) as char;
-                    let _ = window_manager.send_to_focused(&control_char.to_string());
+                    Some(control_char.to_string())
                 } else {
                     // For non-alphabetic characters with Ctrl, send as-is
-                    let _ = window_manager.send_char_to_focused(c);
+                    Some(c.to_string())
                 }
             } else {
                 // Normal character without Ctrl
-                let _ = window_manager.send_char_to_focused(c);
+                Some(c.to_string())
             }
         }
-        KeyCode::Enter => {
-            // Send carriage return for both Enter and Shift+Enter
-            let _ = window_manager.send_to_focused("\r");
-        }
-        KeyCode::Backspace => {
-            let _ = window_manager.send_to_focused("\x7f");
-        }
-        KeyCode::Tab => {
-            let _ = window_manager.send_to_focused("\t");
-        }
-        KeyCode::BackTab => {
-            // Shift+Tab - send ESC [ Z (reverse tab / backtab)
-            let _ = window_manager.send_to_focused("\x1b[Z");
-        }
-        KeyCode::Up => {
-            // Check application cursor keys mode (DECCKM)
-            let seq = if window_manager.get_focused_application_cursor_keys() {
-                "\x1bOA" // Application mode
-            } else {
-                "\x1b[A" // Normal mode
-            };
-            let _ = window_manager.send_to_focused(seq);
-        }
-        KeyCode::Down => {
-            let seq = if window_manager.get_focused_application_cursor_keys() {
-                "\x1bOB"
-            } else {
-                "\x1b[B"
-            };
-            let _ = window_manager.send_to_focused(seq);
-        }
-        KeyCode::Right => {
-            let seq = if window_manager.get_focused_application_cursor_keys() {
-                "\x1bOC"
-            } else {
-                "\x1b[C"
-            };
-            let _ = window_manager.send_to_focused(seq);
-        }
-        KeyCode::Left => {
-            let seq = if window_manager.get_focused_application_cursor_keys() {
-                "\x1bOD"
-            } else {
-                "\x1b[D"
-            };
-            let _ = window_manager.send_to_focused(seq);
-        }
-        KeyCode::Home => {
-            let seq = if window_manager.get_focused_application_cursor_keys() {
-                "\x1bOH"
-            } else {
-                "\x1b[H"
-            };
-            let _ = window_manager.send_to_focused(seq);
-        }
-        KeyCode::End => {
-            let seq = if window_manager.get_focused_application_cursor_keys() {
-                "\x1bOF"
-            } else {
-                "\x1b[F"
-            };
-            let _ = window_manager.send_to_focused(seq);
-        }
-        KeyCode::PageUp => {
-            let _ = window_manager.send_to_focused("\x1b[5~");
-        }
-        KeyCode::PageDown => {
-            let _ = window_manager.send_to_focused("\x1b[6~");
-        }
-        KeyCode::Delete => {
-            let _ = window_manager.send_to_focused("\x1b[3~");
-        }
-        KeyCode::Insert => {
-            let _ = window_manager.send_to_focused("\x1b[2~");
-        }
-        KeyCode::F(8) => {
-            // F8 - send as escape sequence (CSI 19~)
-            let _ = window_manager.send_to_focused("\x1b[19~");
-        }
+        // Send carriage return for both Enter and Shift+Enter (forward_to_terminal applies
+        // enter_sends and LNM)
+        KeyCode::Enter => Some("\r".to_string()),
+        KeyCode::Backspace => Some("\x7f".to_string()),
+        KeyCode::Tab => Some("\t".to_string()),
+        // Shift+Tab - send ESC [ Z (reverse tab / backtab)
+        KeyCode::BackTab => Some("\x1b[Z".to_string()),
+        KeyCode::Up => cursor('A'),
+        KeyCode::Down => cursor('B'),
+        KeyCode::Right => cursor('C'),
+        KeyCode::Left => cursor('D'),
+        KeyCode::Home => cursor('H'),
+        KeyCode::End => cursor('F'),
+        KeyCode::PageUp => Some("\x1b[5~".to_string()),
+        KeyCode::PageDown => Some("\x1b[6~".to_string()),
+        KeyCode::Delete => Some("\x1b[3~".to_string()),
+        KeyCode::Insert => Some("\x1b[2~".to_string()),
+        // F8 - send as escape sequence (CSI 19~)
+        KeyCode::F(8) => Some("\x1b[19~".to_string()),
         // Note: Backtick ('`') is handled by KeyCode::Char(c) above
-        _ => {}
+        _ => None,
     }
 }
 
@@ -638,8 +911,27 @@ pub fn show_help_window(
             {{C}}WINDOW & SESSION{{W}}\n\
             \n\
             {{Y}}Alt+TAB{{W}}/{{Y}}F2{{W}} - Switch between windows\n\
+            {{Y}}Alt+Shift+TAB{{W}}  - Switch to previous window\n\
+            {{Y}}Alt+`{{W}}          - Back to the last focused window\n\
             {{Y}}F3{{W}}             - Save session\n\
             {{Y}}F4{{W}}/{{Y}}CTRL+L{{W}}  - Clear terminal\n\
+            {{Y}}CTRL+SHIFT+K{{W}}   - Clear scrollback\n\
+            {{Y}}CTRL+SHIFT+D{{W}}   - Duplicate window (same directory)\n\
+            {{Y}}CTRL+SHIFT+B{{W}}   - Interrupt foreground job (SIGINT)\n\
+            {{Y}}CTRL+SHIFT+T{{W}}   - Terminate foreground job (SIGTERM)\n\
+            {{Y}}CTRL+SHIFT+X{{W}}   - Kill foreground job (SIGKILL)\n\
+            {{Y}}CTRL+SHIFT+W{{W}}   - Force close window (kills it)\n\
+            {{Y}}CTRL+SHIFT+L{{W}}   - Lock/unlock window input\n\
+            {{Y}}CTRL+SHIFT+R{{W}}   - Show control characters literally\n\
+            {{Y}}CTRL+SHIFT+U{{W}}   - Always show the cursor\n\
+            {{Y}}CTRL+SHIFT+G{{W}}   - Cycle window color tag\n\
+            {{Y}}CTRL+SHIFT+P{{W}}   - Pass all keys to terminal\n\
+            {{Y}}CTRL+SHIFT+O{{W}}   - Toggle mouse capture (global)\n\
+            {{Y}}CTRL+SHIFT+A{{W}}   - Cycle charset (ASCII/Unicode)\n\
+            {{Y}}CTRL+SHIFT+N{{W}}   - Cycle night dim (off/25/50/75%)\n\
+            {{Y}}CTRL+SHIFT+S{{W}}   - Show/hide scratchpad window\n\
+            {{Y}}F9{{W}}             - Window overview (expose)\n\
+            {{Y}}F11{{W}}            - Hide/show top and bottom bars\n\
             {{Y}}F8{{W}}             - Toggle Window Mode\n\
             {{Y}}Shift+F1-F12{{W}}   - Send F-key to terminal\n\
             \n\
@@ -647,6 +939,10 @@ pub fn show_help_window(
             \n\
             {{Y}}{}{{W}} or {{Y}}F5{{W}} - Copy selected text\n\
             {{Y}}{}{{W}} or {{Y}}F6{{W}} - Paste from clipboard\n\
+            {{Y}}SHIFT+INSERT{{W}}  - Paste from clipboard\n\
+            {{Y}}CTRL+F6{{W}}       - Paste without running (no trailing newline)\n\
+            {{Y}}CTRL+SHIFT+E{{W}}  - Paste and run (adds Enter)\n\
+            {{Y}}CTRL+SHIFT+Y{{W}} {{Y}}1-9{{W}} - Paste a recent copy (1 = last)\n\
             \n\
             {{C}}MOUSE CONTROLS{{W}}\n\
             \n\
@@ -675,8 +971,27 @@ pub fn show_help_window(
             {{C}}WINDOW & SESSION{{W}}\n\
             \n\
             {{Y}}F2{{W}} or {{Y}}ALT+TAB{{W}} - Switch between windows\n\
+            {{Y}}ALT+SHIFT+TAB{{W}}   - Switch to previous window\n\
+            {{Y}}ALT+`{{W}}           - Back to the last focused window\n\
             {{Y}}F3{{W}}              - Save session manually\n\
             {{Y}}F4{{W}} or {{Y}}CTRL+L{{W}}  - Clear terminal\n\
+            {{Y}}CTRL+SHIFT+K{{W}}    - Clear scrollback\n\
+            {{Y}}CTRL+SHIFT+D{{W}}    - Duplicate window (same directory)\n\
+            {{Y}}CTRL+SHIFT+B{{W}}    - Interrupt foreground job (SIGINT)\n\
+            {{Y}}CTRL+SHIFT+T{{W}}    - Terminate foreground job (SIGTERM)\n\
+            {{Y}}CTRL+SHIFT+X{{W}}    - Kill foreground job (SIGKILL)\n\
+            {{Y}}CTRL+SHIFT+W{{W}}    - Force close window (kills it)\n\
+            {{Y}}CTRL+SHIFT+L{{W}}    - Lock/unlock window input\n\
+            {{Y}}CTRL+SHIFT+R{{W}}    - Show control characters literally\n\
+            {{Y}}CTRL+SHIFT+U{{W}}    - Always show the cursor\n\
+            {{Y}}CTRL+SHIFT+G{{W}}    - Cycle window color tag\n\
+            {{Y}}CTRL+SHIFT+P{{W}}    - Pass all keys to terminal\n\
+            {{Y}}CTRL+SHIFT+O{{W}}    - Toggle mouse capture (global)\n\
+            {{Y}}CTRL+SHIFT+A{{W}}    - Cycle charset (ASCII/Unicode)\n\
+            {{Y}}CTRL+SHIFT+N{{W}}    - Cycle night dim (off/25/50/75%)\n\
+            {{Y}}CTRL+SHIFT+S{{W}}    - Show/hide scratchpad window\n\
+            {{Y}}F9{{W}}              - Window overview (expose)\n\
+            {{Y}}F11{{W}}             - Hide/show top and bottom bars\n\
             {{Y}}F7{{W}}              - Create new terminal window\n\
             {{Y}}Shift+F1-F12{{W}}    - Send F-key to terminal\n\
             \n\
@@ -684,6 +999,10 @@ pub fn show_help_window(
             \n\
             {{Y}}{}{{W}} or {{Y}}F5{{W}} - Copy selected text\n\
             {{Y}}{}{{W}} or {{Y}}F6{{W}} - Paste from clipboard\n\
+            {{Y}}SHIFT+INSERT{{W}}  - Paste from clipboard\n\
+            {{Y}}CTRL+F6{{W}}       - Paste without running (no trailing newline)\n\
+            {{Y}}CTRL+SHIFT+E{{W}}  - Paste and run (adds Enter)\n\
+            {{Y}}CTRL+SHIFT+Y{{W}} {{Y}}1-9{{W}} - Paste a recent copy (1 = last)\n\
             \n\
             {{C}}POPUP DIALOG CONTROLS{{W}}\n\
             \n\
@@ -713,14 +1032,21 @@ pub fn show_help_window(
 }
 
 /// Helper to show exit prompt
-fn show_exit_prompt(
+/// Ask for confirmation before exiting (see `confirm_exit`), or exit right away
+/// The message lists the open windows and whether the session will be saved
+pub fn show_exit_prompt(
     app_state: &mut AppState,
     window_manager: &WindowManager,
     backend: &dyn RenderBackend,
     app_config: &AppConfig,
 ) {
     let window_count = window_manager.window_count();
-    let message = if window_count > 0 {
+    if !app_config.confirm_exit.applies(window_count) {
+        app_state.should_exit = true;
+        return;
+    }
+
+    let mut message = if window_count > 0 {
         format!(
             "You have {} open terminal{}. Are you sure you want to exit?",
             window_count,
@@ -729,10 +1055,17 @@ fn show_exit_prompt(
     } else {
         "Are you sure you want to exit?".to_string()
     };
+    if window_count > 0 {
+        message.push_str(if app_config.auto_save {
+            " The session will be saved."
+        } else {
+            " The session will not be saved."
+        });
+    }
     let (cols, rows) = backend.dimensions();
     let mut buttons = vec![
         PromptButton::new("Cancel".to_string(), PromptAction::Cancel, false),
-        PromptButton::new("Exit".to_string(), PromptAction::Confirm, true),
+        PromptButton::new(app_config.exit_label.clone(), PromptAction::Confirm, true),
     ];
     // Add "Exit & Kill Daemon" option when persist mode is active and enabled
     #[cfg(unix)]
@@ -743,8 +1076,7 @@ fn show_exit_prompt(
             true,
         ));
     }
-    #[cfg(not(unix))]
-    let _ = app_config;
+    // Create prompt with "Cancel" selected by default (index 0)
     app_state.active_prompt = Some(
         Prompt::new(PromptType::Danger, message, buttons, cols, rows)
             .with_selection_indicators(true)
@@ -848,42 +1180,7 @@ fn handle_q_key(
             return;
         }
 
-        // Determine message based on window count
-        let window_count = window_manager.window_count();
-        let message = if window_count > 0 {
-            format!(
-                "You have {} open terminal{}. Are you sure you want to exit?",
-                window_count,
-                if window_count == 1 { "" } else { "s" }
-            )
-        } else {
-            "Are you sure you want to exit?".to_string()
-        };
-
-        // Get dimensions
-        let (cols, rows) = backend.dimensions();
-
-        // Create prompt with "Cancel" selected by default (index 0)
-        let mut buttons = vec![
-            PromptButton::new("Cancel".to_string(), PromptAction::Cancel, false),
-            PromptButton::new("Exit".to_string(), PromptAction::Confirm, true),
-        ];
-        // Add "Exit & Kill Daemon" option when persist mode is active and enabled
-        #[cfg(unix)]
-        if app_config.persist_enabled && window_manager.has_persist_client() {
-            buttons.push(PromptButton::new(
-                "Exit & Kill Daemon".to_string(),
-                PromptAction::Custom(1),
-                true,
-            ));
-        }
-        #[cfg(not(unix))]
-        let _ = app_config;
-        app_state.active_prompt = Some(
-            Prompt::new(PromptType::Danger, message, buttons, cols, rows)
-                .with_selection_indicators(true)
-                .with_selected_button(0),
-        ); // Select "Cancel"
+        show_exit_prompt(app_state, window_manager, backend, app_config);
     } else {
         // Send 'q' to terminal
         let _ = window_manager.send_char_to_focused('q');
@@ -898,29 +1195,75 @@ pub fn create_terminal_window(
     tiling_gaps: bool,
 ) {
     let (cols, rows) = backend.dimensions();
+    create_terminal_window_with_command(
+        app_state,
+        window_manager,
+        cols,
+        rows,
+        maximized,
+        tiling_gaps,
+        None,
+    );
+}
+
+/// Creates a new terminal window, optionally running `initial_command` instead of an idle shell
+pub fn create_terminal_window_with_command(
+    app_state: &mut AppState,
+    window_manager: &mut WindowManager,
+    cols: u16,
+    rows: u16,
+    maximized: bool,
+    tiling_gaps: bool,
+    initial_command: Option<String>,
+) {
+    create_terminal_window_in_dir(
+        app_state,
+        window_manager,
+        cols,
+        rows,
+        maximized,
+        tiling_gaps,
+        initial_command,
+        None,
+    );
+}
 
+/// Creates a new terminal window starting in `working_dir` (None uses the default start directory)
+#[allow(clippy::too_many_arguments)]
+pub fn create_terminal_window_in_dir(
+    app_state: &mut AppState,
+    window_manager: &mut WindowManager,
+    cols: u16,
+    rows: u16,
+    maximized: bool,
+    tiling_gaps: bool,
+    initial_command: Option<String>,
+    working_dir: Option<String>,
+) {
     // Calculate dynamic window size based on screen dimensions
     let (width, height) = WindowManager::calculate_window_size(cols, rows);
 
-    // Get position: cascade if auto-tiling is off, center otherwise
+    // Get position: configured placement if auto-tiling is off, center otherwise
     // Minimum y=1 to avoid overlapping with topbar at y=0
     let (x, y) = if app_state.auto_tiling_enabled {
         let x = (cols.saturating_sub(width)) / 2;
         let y = 1 + (rows.saturating_sub(2).saturating_sub(height)) / 2;
         (x, y.max(1))
     } else {
-        window_manager.get_cascade_position(width, height, cols, rows)
+        window_manager.next_window_position(width, height, cols, rows, app_state.pointer_position)
     };
 
-    match window_manager.create_window(
+    match window_manager.create_window_in_dir(
         x,
         y,
         width,
         height,
         format!("Terminal {}", window_manager.window_count() + 1),
-        None,
+        initial_command,
+        working_dir.clone(),
     ) {
         Ok(window_id) => {
+            app_state.spawn_failures = 0;
             if maximized {
                 window_manager.maximize_window(window_id, cols, rows, tiling_gaps);
             } else if app_state.auto_tiling_enabled {
@@ -928,11 +1271,335 @@ pub fn create_terminal_window(
             }
         }
         Err(error_msg) => {
-            app_state.active_error_dialog = Some(ErrorDialog::new(cols, rows, error_msg));
+            app_state.spawn_failures += 1;
+            let threshold = app_state.spawn_failure_prompt_after;
+            if threshold > 0 && app_state.spawn_failures >= threshold {
+                app_state.spawn_failures = 0;
+                show_spawn_failure_prompt(app_state, cols, rows, &error_msg, working_dir);
+            } else {
+                app_state.active_error_dialog = Some(ErrorDialog::new(cols, rows, error_msg));
+            }
+        }
+    }
+}
+
+/// After several failed spawns in a row, explain the likely cause and offer FALLBACK_SHELL
+fn show_spawn_failure_prompt(
+    app_state: &mut AppState,
+    cols: u16,
+    rows: u16,
+    error_msg: &str,
+    working_dir: Option<String>,
+) {
+    // Keep the error on one line that fits the screen
+    let max_len = cols.saturating_sub(8).max(20) as usize;
+    let error: String = if error_msg.chars().count() > max_len {
+        let mut short: String = error_msg.chars().take(max_len - 3).collect();
+        short.push_str("...");
+        short
+    } else {
+        error_msg.to_string()
+    };
+    let message = format!(
+        "New windows failed to start {} times in a row:\n{}\n\nThe shell (--shell, $SHELL), the --exec command or PATH\nis probably wrong. Open a window with {} instead?",
+        app_state.spawn_failure_prompt_after, error, FALLBACK_SHELL
+    );
+    let buttons = vec![
+        PromptButton::new("Cancel".to_string(), PromptAction::Cancel, false),
+        PromptButton::new(
+            format!("Open {}", FALLBACK_SHELL),
+            PromptAction::ConfirmPending,
+            true,
+        ),
+    ];
+    app_state.ask_confirmation(
+        Prompt::new(PromptType::Warning, message, buttons, cols, rows)
+            .with_selection_indicators(true),
+        PendingConfirmation::FallbackShell(FallbackShellRequest { working_dir }),
+    );
+}
+
+/// Duplicates the focused window: a new window in the same directory, asking first
+/// whether to rerun the command the window was launched with (if any)
+pub fn duplicate_focused_window(
+    app_state: &mut AppState,
+    window_manager: &mut WindowManager,
+    cols: u16,
+    rows: u16,
+    tiling_gaps: bool,
+) {
+    let Some(window) = window_manager.get_focused_window() else {
+        app_state.active_toast = Some(crate::ui::toast::Toast::new("No window focused"));
+        return;
+    };
+    let request = DuplicateRequest {
+        working_dir: window.working_directory(),
+        command: window.launch_command().map(str::to_string),
+    };
+
+    match request.command {
+        Some(ref command) => {
+            let buttons = vec![
+                PromptButton::new("Cancel".to_string(), PromptAction::Cancel, false),
+                PromptButton::new("Shell".to_string(), PromptAction::DuplicateShell, false),
+                PromptButton::new("Rerun".to_string(), PromptAction::ConfirmPending, true),
+            ];
+            let prompt = Prompt::new(
+                PromptType::Info,
+                format!("Rerun '{}' in the new window?", command),
+                buttons,
+                cols,
+                rows,
+            )
+            .with_selection_indicators(true)
+            .with_selected_button(2);
+            app_state.ask_confirmation(prompt, PendingConfirmation::Duplicate(request));
+        }
+        None => {
+            create_duplicate_window(app_state, window_manager, cols, rows, tiling_gaps, request)
+        }
+    }
+}
+
+/// Copy a window's selected text; text from a window at a hidden (password) prompt is
+/// kept out of the clipboard history
+pub fn copy_from_window(
+    clipboard_manager: &mut ClipboardManager,
+    window_manager: &WindowManager,
+    window_id: u32,
+    text: String,
+) -> Result<(), String> {
+    if window_manager.is_window_at_hidden_prompt(window_id) {
+        clipboard_manager.copy_unrecorded(text)
+    } else {
+        clipboard_manager.copy(text)
+    }
+}
+
+/// How long a hidden prompt paste warning lets the next paste into the window through
+/// (the warning toast's lifetime)
+const HIDDEN_PASTE_REPEAT: Duration = Duration::from_secs(5);
+
+/// What a paste into a window at a hidden (password) prompt does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HiddenPromptPaste {
+    Send,
+    Warn,
+    Ask,
+}
+
+/// Pick the `paste_guard` reaction; `warned` when this window's warning is still showing
+fn hidden_prompt_paste(guard: PasteGuard, warned: bool) -> HiddenPromptPaste {
+    match guard {
+        PasteGuard::Off => HiddenPromptPaste::Send,
+        PasteGuard::Warn if warned => HiddenPromptPaste::Send,
+        PasteGuard::Warn => HiddenPromptPaste::Warn,
+        PasteGuard::Confirm => HiddenPromptPaste::Ask,
+    }
+}
+
+/// Paste `text` into a window, guarding windows at a hidden (password) prompt
+/// per `paste_guard`: warn first (pasting again sends), or ask first
+/// Text pasted into a hidden prompt is dropped from the clipboard history
+#[allow(clippy::too_many_arguments)]
+pub fn paste_into_window(
+    app_state: &mut AppState,
+    window_manager: &mut WindowManager,
+    clipboard_manager: &mut ClipboardManager,
+    window_id: u32,
+    text: String,
+    kind: PasteKind,
+    cols: u16,
+    rows: u16,
+) {
+    let request = PasteRequest {
+        window_id,
+        text,
+        kind,
+    };
+    if !window_manager.is_window_at_hidden_prompt(window_id) {
+        send_paste_request(window_manager, &request, false);
+        return;
+    }
+    clipboard_manager.forget(&request.text);
+    let warned = app_state
+        .hidden_paste_warning
+        .take()
+        .is_some_and(|(id, at)| id == window_id && at.elapsed() < HIDDEN_PASTE_REPEAT);
+    match hidden_prompt_paste(window_manager.paste_guard(), warned) {
+        HiddenPromptPaste::Send => send_paste_request(window_manager, &request, true),
+        HiddenPromptPaste::Warn => {
+            app_state.hidden_paste_warning = Some((window_id, Instant::now()));
+            app_state.active_toast = Some(crate::ui::toast::Toast::new(
+                "The window is at a hidden prompt (password?): paste again to send",
+            ));
+        }
+        HiddenPromptPaste::Ask => {
+            let buttons = vec![
+                PromptButton::new("Cancel".to_string(), PromptAction::Cancel, false),
+                PromptButton::new("Paste".to_string(), PromptAction::ConfirmPending, true),
+            ];
+            app_state.ask_confirmation(
+                Prompt::new(
+                    PromptType::Warning,
+                    "The window is at a hidden prompt (password?). Paste anyway?".to_string(),
+                    buttons,
+                    cols,
+                    rows,
+                )
+                .with_selection_indicators(true)
+                .with_selected_button(0),
+                PendingConfirmation::Paste(request),
+            );
         }
     }
 }
 
+/// Send a paste request; at a hidden prompt paste-and-run doesn't press Enter, so a
+/// pasted password is never submitted (and nothing after it reaches the shell) unseen
+pub fn send_paste_request(
+    window_manager: &mut WindowManager,
+    request: &PasteRequest,
+    hidden_prompt: bool,
+) {
+    let (window_id, text) = (request.window_id, request.text.as_str());
+    let _ = match request.kind {
+        PasteKind::Normal => window_manager.paste_to_window(window_id, text),
+        PasteKind::AndRun if !hidden_prompt => {
+            window_manager.paste_and_run_in_window(window_id, text)
+        }
+        PasteKind::Literal | PasteKind::AndRun => {
+            window_manager.paste_literal_to_window(window_id, text)
+        }
+    };
+}
+
+/// Ask before sending SIGKILL to the focused window's foreground job
+fn confirm_kill_focused(
+    app_state: &mut AppState,
+    window_manager: &WindowManager,
+    backend: &dyn RenderBackend,
+) {
+    let Some(window) = window_manager.get_focused_window() else {
+        return;
+    };
+    let target = window
+        .get_foreground_process_name()
+        .unwrap_or_else(|| "the foreground job".to_string());
+    let (cols, rows) = backend.dimensions();
+    let buttons = vec![
+        PromptButton::new("Cancel".to_string(), PromptAction::Cancel, false),
+        PromptButton::new("Kill".to_string(), PromptAction::ConfirmPending, true),
+    ];
+    app_state.ask_confirmation(
+        Prompt::new(
+            PromptType::Danger,
+            format!("Send SIGKILL to {}? Unsaved work will be lost.", target),
+            buttons,
+            cols,
+            rows,
+        )
+        .with_selection_indicators(true)
+        .with_selected_button(0),
+        PendingConfirmation::Signal(SignalRequest {
+            window_id: window.id(),
+            signal: ForegroundSignal::Kill,
+        }),
+    );
+}
+
+/// Ask before killing the focused window's processes and closing it
+fn confirm_force_close_focused(
+    app_state: &mut AppState,
+    window_manager: &WindowManager,
+    backend: &dyn RenderBackend,
+) {
+    let Some(window) = window_manager.get_focused_window() else {
+        return;
+    };
+    let (cols, rows) = backend.dimensions();
+    let buttons = vec![
+        PromptButton::new("Cancel".to_string(), PromptAction::Cancel, false),
+        PromptButton::new(
+            "Force Close".to_string(),
+            PromptAction::ConfirmPending,
+            true,
+        ),
+    ];
+    app_state.ask_confirmation(
+        Prompt::new(
+            PromptType::Danger,
+            format!(
+                "Kill everything running in \"{}\" and close it?",
+                window.window.title
+            ),
+            buttons,
+            cols,
+            rows,
+        )
+        .with_selection_indicators(true)
+        .with_selected_button(0),
+        PendingConfirmation::ForceClose(ForceCloseRequest {
+            window_id: window.id(),
+        }),
+    );
+}
+
+/// Send a signal to the focused window's foreground job; failures are shown in a prompt
+/// (the daemon confirms signals to remote windows itself, see PersistEvent::SignalSent)
+pub fn send_signal_to_focused(
+    app_state: &mut AppState,
+    window_manager: &mut WindowManager,
+    backend: &dyn RenderBackend,
+    signal: ForegroundSignal,
+) {
+    match window_manager.signal_focused(signal) {
+        Ok(true) => {
+            app_state.active_toast = Some(crate::ui::toast::Toast::new(format!(
+                "Sent {} to foreground job",
+                signal.name()
+            )));
+        }
+        Ok(false) => {}
+        Err(error) => {
+            let (cols, rows) = backend.dimensions();
+            let buttons = vec![PromptButton::new(
+                "OK".to_string(),
+                PromptAction::Cancel,
+                true,
+            )];
+            app_state.active_prompt = Some(Prompt::new(
+                PromptType::Danger,
+                format!("Failed to send {}: {}", signal.name(), error),
+                buttons,
+                cols,
+                rows,
+            ));
+        }
+    }
+}
+
+/// Creates the window for a (confirmed) duplicate request
+pub fn create_duplicate_window(
+    app_state: &mut AppState,
+    window_manager: &mut WindowManager,
+    cols: u16,
+    rows: u16,
+    tiling_gaps: bool,
+    request: DuplicateRequest,
+) {
+    create_terminal_window_in_dir(
+        app_state,
+        window_manager,
+        cols,
+        rows,
+        false,
+        tiling_gaps,
+        request.command,
+        request.working_dir,
+    );
+}
+
 fn handle_save_session(
     app_state: &mut AppState,
     window_manager: &mut WindowManager,
@@ -992,3 +1659,126 @@ fn handle_save_session(
         ));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tab_keys_become_terminal_bytes() {
+        let tab = KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE);
+        let backtab = KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT);
+        assert_eq!(terminal_key_sequence(tab, false).as_deref(), Some("\t"));
+        assert_eq!(
+            terminal_key_sequence(backtab, false).as_deref(),
+            Some("\x1b[Z")
+        );
+        let up = KeyEvent::new(KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(terminal_key_sequence(up, true).as_deref(), Some("\x1bOA"));
+        assert_eq!(terminal_key_sequence(up, false).as_deref(), Some("\x1b[A"));
+    }
+
+    #[test]
+    fn test_alt_letter_gets_meta_prefix_only_when_forwarded() {
+        let alt_x = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::ALT);
+        // Default dispatch sends the plain character
+        assert_eq!(terminal_key_sequence(alt_x, false).as_deref(), Some("x"));
+        assert_eq!(meta_sequence(alt_x).as_deref(), Some("\x1bx"));
+
+        let ctrl_alt_x = KeyEvent::new(
+            KeyCode::Char('x'),
+            KeyModifiers::ALT | KeyModifiers::CONTROL,
+        );
+        assert_eq!(meta_sequence(ctrl_alt_x), None);
+        let plain_x = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(meta_sequence(plain_x), None);
+    }
+
+    #[test]
+    fn test_hidden_prompt_paste_follows_guard() {
+        use HiddenPromptPaste::{Ask, Send, Warn};
+        // Warn holds the first paste and sends a repeat made while the warning shows
+        assert_eq!(hidden_prompt_paste(PasteGuard::Warn, false), Warn);
+        assert_eq!(hidden_prompt_paste(PasteGuard::Warn, true), Send);
+        for warned in [false, true] {
+            assert_eq!(hidden_prompt_paste(PasteGuard::Confirm, warned), Ask);
+            assert_eq!(hidden_prompt_paste(PasteGuard::Off, warned), Send);
+        }
+    }
+
+    #[test]
+    fn test_enter_follows_config_unless_lnm() {
+        assert_eq!(enter_sequence(EnterSends::Cr, false), "\r");
+        assert_eq!(enter_sequence(EnterSends::Lf, false), "\n");
+        assert_eq!(enter_sequence(EnterSends::CrLf, false), "\r\n");
+        // An application in LNM always gets CR LF
+        for enter_sends in [EnterSends::Cr, EnterSends::Lf, EnterSends::CrLf] {
+            assert_eq!(enter_sequence(enter_sends, true), "\r\n");
+        }
+    }
+
+    /// Backend stub with a fixed 80x24 screen
+    #[cfg(unix)]
+    struct TestBackend;
+
+    #[cfg(unix)]
+    impl RenderBackend for TestBackend {
+        fn present(&mut self, _buffer: &mut crate::rendering::VideoBuffer) -> std::io::Result<()> {
+            Ok(())
+        }
+        fn dimensions(&self) -> (u16, u16) {
+            (80, 24)
+        }
+        fn check_resize(&mut self) -> std::io::Result<Option<(u16, u16)>> {
+            Ok(None)
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_plain_tab_reaches_focused_terminal() {
+        use clap::Parser;
+
+        let mut config = AppConfig::default();
+        let mut app_state = AppState::new(80, 24, &config, &crate::rendering::Charset::unicode());
+        let mut window_manager = WindowManager::new();
+        window_manager.restore_persist_windows(vec![crate::persist::protocol::WindowInfo {
+            window_id: 1,
+            title: "Terminal 1".to_string(),
+            cols: 40,
+            rows: 10,
+            x: 0,
+            y: 1,
+            width: 44,
+            height: 12,
+        }]);
+        let mut clipboard_manager = ClipboardManager::new();
+        let cli_args = Cli::parse_from(["term39"]);
+
+        for profile in [KeybindingProfile::term39(), KeybindingProfile::hyprland()] {
+            for key_event in [
+                KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE),
+                KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT),
+            ] {
+                // Not consumed as a shortcut, so the event loop forwards it to the PTY
+                let consumed = handle_desktop_keyboard(
+                    &mut app_state,
+                    key_event,
+                    FocusState::Window(1),
+                    &mut window_manager,
+                    &mut clipboard_manager,
+                    &TestBackend,
+                    &mut config,
+                    &cli_args,
+                    &profile,
+                );
+                assert!(
+                    !consumed,
+                    "{:?} taken by the {} keymap",
+                    key_event, profile.name
+                );
+                assert_eq!(window_manager.get_focus(), FocusState::Window(1));
+            }
+        }
+    }
+}
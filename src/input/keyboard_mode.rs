@@ -127,7 +127,6 @@ pub enum SnapPosition {
 
 impl SnapPosition {
     /// Create from numpad key (1-9)
-    #[allow(dead_code)]
     pub fn from_numpad(key: char) -> Option<Self> {
         match key {
             '1' => Some(SnapPosition::BottomLeft),
@@ -143,6 +142,27 @@ impl SnapPosition {
         }
     }
 
+    /// Parse a zone name from the config ("left", "top-right", "center", ...)
+    /// "left"/"right"/"top"/"bottom" are the full halves; "_" may stand in for "-"
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "top-left" => Some(SnapPosition::TopLeft),
+            "top-center" => Some(SnapPosition::TopCenter),
+            "top-right" => Some(SnapPosition::TopRight),
+            "middle-left" => Some(SnapPosition::MiddleLeft),
+            "center" => Some(SnapPosition::Center),
+            "middle-right" => Some(SnapPosition::MiddleRight),
+            "bottom-left" => Some(SnapPosition::BottomLeft),
+            "bottom-center" => Some(SnapPosition::BottomCenter),
+            "bottom-right" => Some(SnapPosition::BottomRight),
+            "left" => Some(SnapPosition::FullLeft),
+            "right" => Some(SnapPosition::FullRight),
+            "top" => Some(SnapPosition::FullTop),
+            "bottom" => Some(SnapPosition::FullBottom),
+            _ => None,
+        }
+    }
+
     /// Calculate the window rectangle for this snap position
     /// Returns (x, y, width, height)
     /// `top_bar_y` is typically 1 (row 0 is top bar)
@@ -233,6 +253,9 @@ pub enum Direction {
     Down,
 }
 
+/// Largest single keyboard move/resize step (more than any screen is wide)
+const MAX_KEYBOARD_STEP: u16 = 1000;
+
 /// Tracks movement/resize timing for adaptive step size acceleration
 #[derive(Clone, Debug, Default)]
 pub struct MovementState {
@@ -266,6 +289,16 @@ impl MovementState {
         step
     }
 
+    /// Adaptive step times the configured `base` step and `multiplier` (large steps),
+    /// capped at MAX_KEYBOARD_STEP
+    pub fn get_scaled_step(&mut self, base: u16, multiplier: u16) -> i16 {
+        let step = self
+            .get_step()
+            .saturating_mul(base.max(1))
+            .saturating_mul(multiplier.max(1));
+        step.min(MAX_KEYBOARD_STEP) as i16
+    }
+
     /// Reset the timing state
     pub fn reset(&mut self) {
         self.last_press = None;
@@ -310,4 +343,56 @@ mod tests {
         assert_eq!(SnapPosition::from_numpad('9'), Some(SnapPosition::TopRight));
         assert_eq!(SnapPosition::from_numpad('0'), None);
     }
+
+    #[test]
+    fn test_numpad_rects_stay_between_the_bars() {
+        // 80x24 with the top bar on row 0 and the bottom bar on row 23
+        for key in '1'..='9' {
+            let position = SnapPosition::from_numpad(key).unwrap();
+            let (x, y, w, h) = position.calculate_rect(80, 24, 1);
+            assert!(w > 0 && h > 0, "{:?}", position);
+            assert!(y >= 1 && y + h <= 23, "{:?}", position);
+            assert!(x + w <= 80, "{:?}", position);
+        }
+        // The bottom row reaches the bottom bar, the top row starts below the top bar
+        assert_eq!(
+            SnapPosition::BottomLeft.calculate_rect(80, 24, 1),
+            (0, 12, 40, 11)
+        );
+        assert_eq!(
+            SnapPosition::TopRight.calculate_rect(80, 24, 1),
+            (40, 1, 40, 11)
+        );
+    }
+
+    #[test]
+    fn test_snap_position_from_name() {
+        assert_eq!(
+            SnapPosition::from_name("left"),
+            Some(SnapPosition::FullLeft)
+        );
+        assert_eq!(
+            SnapPosition::from_name(" Top_Right "),
+            Some(SnapPosition::TopRight)
+        );
+        assert_eq!(
+            SnapPosition::from_name("center"),
+            Some(SnapPosition::Center)
+        );
+        assert_eq!(SnapPosition::from_name("middle"), None);
+    }
+
+    #[test]
+    fn test_scaled_step() {
+        let mut state = MovementState::new();
+        // First press is precise: exactly the base step
+        assert_eq!(state.get_scaled_step(3, 1), 3);
+        state.reset();
+        assert_eq!(state.get_scaled_step(2, 5), 10);
+        state.reset();
+        // A zero base still moves, and huge settings stay within range
+        assert_eq!(state.get_scaled_step(0, 1), 1);
+        state.reset();
+        assert_eq!(state.get_scaled_step(u16::MAX, u16::MAX), 1000);
+    }
 }
@@ -1,3 +1,4 @@
+pub mod event_queue;
 #[cfg(any(
     target_os = "linux",
     target_os = "freebsd",
@@ -72,6 +72,8 @@ pub struct MouseInputManager {
         allow(dead_code)
     )]
     swap_buttons: bool,
+    /// Mouse capture switched off by the user: raw events are read and dropped
+    paused: bool,
 }
 
 impl MouseInputManager {
@@ -126,6 +128,7 @@ impl MouseInputManager {
             prev_buttons: MouseButtons::default(),
             event_queue: VecDeque::new(),
             swap_buttons,
+            paused: false,
         })
     }
 
@@ -168,6 +171,14 @@ impl MouseInputManager {
         self.cursor.set_bounds(cols as usize, rows as usize);
     }
 
+    /// Pause or resume mouse input (the cursor stays put while paused)
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+        // Drop anything queued and forget held buttons, so resuming starts clean
+        self.event_queue.clear();
+        self.prev_buttons = MouseButtons::default();
+    }
+
     /// Poll for a mouse event
     /// Returns None if no event available or if using terminal emulator mode
     pub fn poll_event(&mut self) -> io::Result<Option<MouseEvent>> {
@@ -195,6 +206,11 @@ impl MouseInputManager {
                 None => return Ok(None),
             };
 
+            // Keep draining the device while paused so stale motion isn't replayed later
+            if self.paused {
+                return Ok(None);
+            }
+
             // Update cursor position
             self.cursor.update(raw_event.dx, raw_event.dy);
             let (col, row) = self.cursor.position_u16();
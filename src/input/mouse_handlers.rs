@@ -1,8 +1,9 @@
 //! Mouse event handlers extracted from main.rs
 //! Handles button hover states, modal dialogs, top bar buttons, menus, and text selection.
 
-use crate::app::app_state::{AppState, AutoScrollDirection};
-use crate::app::config_manager::AppConfig;
+use crate::app::app_state::{AppState, AutoScrollDirection, PasteKind};
+use crate::app::config_manager::{AppConfig, DesktopClickAction, ScrollAction, SystemMenuCommand};
+use crate::input::keyboard_handlers::{copy_from_window, paste_into_window};
 use crate::lockscreen::PinSetupState;
 use crate::rendering::{Charset, Theme};
 use crate::term_emu::SelectionType;
@@ -10,10 +11,10 @@ use crate::ui::button::ButtonState;
 use crate::ui::config_action_handler::{apply_config_result, process_config_action};
 use crate::ui::config_window::ConfigAction;
 use crate::ui::context_menu::MenuAction;
+use crate::ui::dialog_handlers::apply_prompt_action;
 use crate::ui::error_dialog::ErrorDialog;
-use crate::ui::prompt::PromptAction;
 use crate::ui::widgets::{WidgetClickResult, WidgetContext};
-use crate::utils::ClipboardManager;
+use crate::utils::{ClipboardManager, CommandHistory};
 use crate::window::manager::{FocusState, WindowManager};
 use crate::window::terminal_window::MouseContentPosition;
 #[cfg(all(target_os = "linux", feature = "framebuffer-backend"))]
@@ -204,23 +205,11 @@ pub fn handle_prompt_mouse(
         if mouse_event.kind == MouseEventKind::Down(MouseButton::Left) {
             if let Some(action) = prompt.handle_click(mouse_event.column, mouse_event.row, charset)
             {
-                match action {
-                    PromptAction::Confirm => {
-                        return ModalMouseResult::Exit;
-                    }
-                    PromptAction::Cancel => {
-                        app_state.active_prompt = None;
-                        return ModalMouseResult::Handled;
-                    }
-                    PromptAction::Custom(1) => {
-                        // Exit & Kill Daemon
-                        app_state.should_kill_daemon = true;
-                        return ModalMouseResult::Exit;
-                    }
-                    _ => {
-                        return ModalMouseResult::Handled;
-                    }
-                }
+                return if apply_prompt_action(app_state, action) {
+                    ModalMouseResult::Exit
+                } else {
+                    ModalMouseResult::Handled
+                };
             } else if prompt.contains_point(mouse_event.column, mouse_event.row) {
                 // Click inside prompt but not on a button - consume the event
                 return ModalMouseResult::Handled;
@@ -388,32 +377,68 @@ pub fn handle_calendar_mouse(
     cols: u16,
     rows: u16,
 ) -> bool {
-    use crate::ui::ui_render::is_calendar_close_button_click;
+    use crate::ui::ui_render::{CalendarClick, calendar_click_target};
 
-    if app_state.active_calendar.is_some() {
-        if mouse_event.kind == MouseEventKind::Down(MouseButton::Left)
-            && is_calendar_close_button_click(mouse_event.column, mouse_event.row, cols, rows)
-        {
-            app_state.active_calendar = None;
-            return true;
-        }
-        // Consume clicks inside the calendar area
-        if mouse_event.kind == MouseEventKind::Down(MouseButton::Left) {
-            // Calendar dimensions (must match render_calendar)
-            let width = 42u16;
-            let height = 18u16;
-            let x = (cols.saturating_sub(width)) / 2;
-            let y = (rows.saturating_sub(height)) / 2;
-            if mouse_event.column >= x
-                && mouse_event.column < x + width
-                && mouse_event.row >= y
-                && mouse_event.row < y + height
-            {
-                return true;
+    let Some(calendar) = app_state.active_calendar.as_mut() else {
+        return false;
+    };
+    let Some(target) = calendar_click_target(mouse_event.column, mouse_event.row, cols, rows)
+    else {
+        return false;
+    };
+
+    match mouse_event.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            match target {
+                CalendarClick::Close => app_state.active_calendar = None,
+                CalendarClick::PreviousMonth => calendar.previous_month(),
+                CalendarClick::NextMonth => calendar.next_month(),
+                CalendarClick::Today => calendar.reset_to_today(),
+                // Consume clicks inside the calendar area
+                CalendarClick::Inside => {}
             }
+            true
+        }
+        // The wheel flips through the months
+        MouseEventKind::ScrollUp => {
+            calendar.previous_month();
+            true
+        }
+        MouseEventKind::ScrollDown => {
+            calendar.next_month();
+            true
         }
+        _ => false,
     }
-    false
+}
+
+/// Handles mouse events on the expose overview.
+/// Clicking a tile focuses that window; clicking elsewhere returns to the desktop.
+/// Returns true if the event was handled.
+pub fn handle_expose_mouse(
+    app_state: &mut AppState,
+    window_manager: &mut WindowManager,
+    mouse_event: &MouseEvent,
+    cols: u16,
+    rows: u16,
+) -> bool {
+    if !app_state.show_expose {
+        return false;
+    }
+    if mouse_event.kind == MouseEventKind::Down(MouseButton::Left) {
+        if let Some(window_id) = crate::window::expose::window_at(
+            window_manager,
+            cols,
+            rows,
+            mouse_event.column,
+            mouse_event.row,
+        ) {
+            window_manager.restore_and_focus_window(window_id);
+        }
+        app_state.show_expose = false;
+    }
+    // Consume all mouse events while the overview is shown
+    true
 }
 
 // ============================================================================
@@ -466,7 +491,13 @@ pub fn handle_topbar_click(
                 let y = 1 + (rows.saturating_sub(2).saturating_sub(height)) / 2;
                 (x, y.max(1))
             } else {
-                window_manager.get_cascade_position(width, height, cols, rows)
+                window_manager.next_window_position(
+                    width,
+                    height,
+                    cols,
+                    rows,
+                    app_state.pointer_position,
+                )
             };
 
             match window_manager.create_window(
@@ -579,6 +610,8 @@ pub fn handle_context_menu_mouse(
     window_manager: &mut WindowManager,
     clipboard_manager: &mut ClipboardManager,
     mouse_event: &MouseEvent,
+    cols: u16,
+    rows: u16,
 ) -> bool {
     if !app_state.context_menu.visible {
         return false;
@@ -599,18 +632,37 @@ pub fn handle_context_menu_mouse(
                     match action {
                         MenuAction::Copy => {
                             if let Some(text) = window_manager.get_selected_text(window_id) {
-                                let _ = clipboard_manager.copy(text);
+                                let _ = copy_from_window(
+                                    clipboard_manager,
+                                    window_manager,
+                                    window_id,
+                                    text,
+                                );
                                 window_manager.clear_selection(window_id);
                             }
                         }
                         MenuAction::Paste => {
                             if let Ok(text) = clipboard_manager.paste() {
-                                let _ = window_manager.paste_to_window(window_id, &text);
+                                paste_into_window(
+                                    app_state,
+                                    window_manager,
+                                    clipboard_manager,
+                                    window_id,
+                                    text,
+                                    PasteKind::Normal,
+                                    cols,
+                                    rows,
+                                );
                             }
                         }
                         MenuAction::SelectAll => {
                             window_manager.select_all(window_id);
                         }
+                        MenuAction::Clear => {
+                            // Same as F4 / CTRL+L
+                            window_manager.clear_selection(window_id);
+                            let _ = window_manager.send_to_focused("\x0c");
+                        }
                         MenuAction::Close
                         | MenuAction::Restore
                         | MenuAction::Maximize
@@ -621,7 +673,10 @@ pub fn handle_context_menu_mouse(
                         | MenuAction::ClearClipboard
                         | MenuAction::Settings
                         | MenuAction::Help
-                        | MenuAction::About => {}
+                        | MenuAction::About
+                        | MenuAction::NewTerminal
+                        | MenuAction::RunCommand(_)
+                        | MenuAction::UserItem(_) => {}
                     }
                 }
             }
@@ -685,6 +740,7 @@ pub fn handle_taskbar_menu_mouse(
                         MenuAction::Copy
                         | MenuAction::Paste
                         | MenuAction::SelectAll
+                        | MenuAction::Clear
                         | MenuAction::Close
                         | MenuAction::Exit
                         | MenuAction::CopySelection
@@ -692,7 +748,10 @@ pub fn handle_taskbar_menu_mouse(
                         | MenuAction::ClearClipboard
                         | MenuAction::Settings
                         | MenuAction::Help
-                        | MenuAction::About => {}
+                        | MenuAction::About
+                        | MenuAction::NewTerminal
+                        | MenuAction::RunCommand(_)
+                        | MenuAction::UserItem(_) => {}
                     }
                 }
             }
@@ -715,20 +774,264 @@ pub fn handle_taskbar_menu_mouse(
 }
 
 /// Shows the context menu at the specified position (for right-click inside windows).
+/// Right-clicks are forwarded to applications with mouse tracking enabled unless
+/// Shift is held, so the menu is always reachable.
 /// Returns true if the menu was shown.
 pub fn show_context_menu(
     app_state: &mut AppState,
-    window_manager: &WindowManager,
+    window_manager: &mut WindowManager,
     mouse_event: &MouseEvent,
+    cols: u16,
+    rows: u16,
 ) -> bool {
-    if mouse_event.kind == MouseEventKind::Down(MouseButton::Right) {
-        if let FocusState::Window(_) = window_manager.get_focus() {
+    if mouse_event.kind != MouseEventKind::Down(MouseButton::Right) {
+        return false;
+    }
+
+    let Some(window_id) = window_manager.window_at(mouse_event.column, mouse_event.row) else {
+        return false;
+    };
+
+    // Only inside the terminal content area (not on title bar, borders or buttons)
+    if window_manager.get_mouse_content_position(window_id, mouse_event.column, mouse_event.row)
+        != Some(MouseContentPosition::Inside)
+        || window_manager.is_point_on_drag_or_resize_area(mouse_event.column, mouse_event.row)
+    {
+        return false;
+    }
+
+    if window_manager.get_focus() != FocusState::Window(window_id) {
+        window_manager.focus_window(window_id);
+    }
+
+    // Let the application handle the click when it receives the mouse (Shift overrides)
+    if window_manager.focused_receives_mouse()
+        && !mouse_event.modifiers.contains(KeyModifiers::SHIFT)
+    {
+        return false;
+    }
+
+    let has_selection = window_manager.focused_window_has_selection();
+    app_state
+        .context_menu
+        .set_item_enabled(MenuAction::Copy, has_selection);
+
+    let bar_y = rows.saturating_sub(1);
+    app_state
+        .context_menu
+        .show_clamped(mouse_event.column, mouse_event.row, cols, 1, bar_y);
+    true
+}
+
+/// Number of launcher commands listed in the desktop menu
+const DESKTOP_MENU_COMMANDS: usize = 5;
+
+/// Handles clicks on an empty area of the desktop according to the configured
+/// `desktop_click_action` (left button) and `desktop_right_click_action` (right button).
+/// Returns true if the event was handled (the default Focus action is left to the window manager).
+#[allow(clippy::too_many_arguments)]
+pub fn handle_desktop_click(
+    app_state: &mut AppState,
+    window_manager: &mut WindowManager,
+    clipboard_manager: &ClipboardManager,
+    command_history: &CommandHistory,
+    app_config: &AppConfig,
+    mouse_event: &MouseEvent,
+    cols: u16,
+    rows: u16,
+) -> bool {
+    let action = match mouse_event.kind {
+        MouseEventKind::Down(MouseButton::Left) => app_config.desktop_click_action,
+        MouseEventKind::Down(MouseButton::Right) => app_config.desktop_right_click_action,
+        _ => return false,
+    };
+
+    if action == DesktopClickAction::Focus {
+        return false;
+    }
+
+    // Only empty desktop area (between the top bar and the bottom bar)
+    let bar_y = rows.saturating_sub(1);
+    if mouse_event.row == 0
+        || mouse_event.row >= bar_y
+        || window_manager
+            .window_at(mouse_event.column, mouse_event.row)
+            .is_some()
+    {
+        return false;
+    }
+
+    // The tiling pivot sits on the desktop between windows - leave it to the window manager
+    if app_state.auto_tiling_enabled
+        && window_manager.is_point_on_pivot(
+            mouse_event.column,
+            mouse_event.row,
+            cols,
+            rows,
+            app_config.tiling_gaps,
+        )
+    {
+        return false;
+    }
+
+    window_manager.focus_desktop();
+
+    match action {
+        DesktopClickAction::Focus => {}
+        DesktopClickAction::NewTerminal => {
+            let maximized = app_state.auto_tiling_enabled && window_manager.window_count() == 0;
+            crate::input::keyboard_handlers::create_terminal_window_with_command(
+                app_state,
+                window_manager,
+                cols,
+                rows,
+                maximized,
+                app_config.tiling_gaps,
+                None,
+            );
+        }
+        DesktopClickAction::SystemMenu => {
+            let has_selection = window_manager.focused_window_has_selection();
+            let has_clipboard_content = clipboard_manager.has_content();
             app_state
-                .context_menu
-                .show(mouse_event.column, mouse_event.row);
+                .system_menu
+                .set_item_enabled(MenuAction::CopySelection, has_selection);
+            app_state
+                .system_menu
+                .set_item_enabled(MenuAction::PasteClipboard, has_clipboard_content);
+            app_state
+                .system_menu
+                .set_item_enabled(MenuAction::ClearClipboard, has_clipboard_content);
+            let menu_height = app_state.system_menu.items.len() as u16 + 3; // borders + shadow
+            let menu_y = mouse_event
+                .row
+                .min(bar_y.saturating_sub(menu_height))
+                .max(1);
+            app_state
+                .system_menu
+                .show_bounded(mouse_event.column, menu_y, cols);
+        }
+        DesktopClickAction::Menu => {
+            app_state.desktop_menu_commands = command_history
+                .get_frequent_commands()
+                .into_iter()
+                .take(DESKTOP_MENU_COMMANDS)
+                .map(|(command, _)| command)
+                .collect();
+            app_state.desktop_menu = crate::ui::context_menu::ContextMenu::new_desktop_menu(
+                0,
+                0,
+                &app_state.desktop_menu_commands,
+            );
+            let menu_height = app_state.desktop_menu.items.len() as u16 + 3; // borders + shadow
+            let menu_y = mouse_event
+                .row
+                .min(bar_y.saturating_sub(menu_height))
+                .max(1);
+            app_state
+                .desktop_menu
+                .show_bounded(mouse_event.column, menu_y, cols);
+        }
+    }
+
+    true
+}
+
+/// Handles the mouse wheel over the empty desktop (`desktop_scroll_action`) and the top bar
+/// (`top_bar_scroll_action`).
+/// Returns true if the event was handled (the default None action leaves the wheel alone).
+pub fn handle_desktop_scroll(
+    window_manager: &mut WindowManager,
+    app_config: &AppConfig,
+    mouse_event: &MouseEvent,
+    rows: u16,
+) -> bool {
+    let up = match mouse_event.kind {
+        MouseEventKind::ScrollUp => true,
+        MouseEventKind::ScrollDown => false,
+        _ => return false,
+    };
+
+    let bar_y = rows.saturating_sub(1);
+    let action = if mouse_event.row == 0 {
+        app_config.top_bar_scroll_action
+    } else if mouse_event.row < bar_y
+        && window_manager
+            .window_at(mouse_event.column, mouse_event.row)
+            .is_none()
+    {
+        app_config.desktop_scroll_action
+    } else {
+        return false;
+    };
+
+    match action {
+        ScrollAction::None => return false,
+        ScrollAction::CycleWindows if up => window_manager.cycle_to_previous_window(),
+        ScrollAction::CycleWindows => window_manager.cycle_to_next_window(),
+    }
+    true
+}
+
+/// Handles desktop menu mouse interactions (new terminal, run a launcher command).
+/// Returns true if the event was handled.
+pub fn handle_desktop_menu_mouse(
+    app_state: &mut AppState,
+    window_manager: &mut WindowManager,
+    mouse_event: &MouseEvent,
+    cols: u16,
+    rows: u16,
+    tiling_gaps: bool,
+) -> bool {
+    if !app_state.desktop_menu.visible {
+        return false;
+    }
+
+    if mouse_event.kind == MouseEventKind::Down(MouseButton::Left) {
+        if app_state
+            .desktop_menu
+            .contains_point(mouse_event.column, mouse_event.row)
+        {
+            // Update selection to clicked item before getting action
+            app_state
+                .desktop_menu
+                .update_selection_from_mouse(mouse_event.column, mouse_event.row);
+
+            let command = match app_state.desktop_menu.get_selected_action() {
+                Some(MenuAction::NewTerminal) => Some(None),
+                Some(MenuAction::RunCommand(index)) => app_state
+                    .desktop_menu_commands
+                    .get(index)
+                    .cloned()
+                    .map(Some),
+                _ => None,
+            };
+
+            if let Some(initial_command) = command {
+                let maximized = app_state.auto_tiling_enabled && window_manager.window_count() == 0;
+                crate::input::keyboard_handlers::create_terminal_window_with_command(
+                    app_state,
+                    window_manager,
+                    cols,
+                    rows,
+                    maximized,
+                    tiling_gaps,
+                    initial_command,
+                );
+            }
+            app_state.desktop_menu.hide();
             return true;
+        } else {
+            // Clicked outside menu - hide it
+            app_state.desktop_menu.hide();
         }
+    } else if mouse_event.kind == MouseEventKind::Moved {
+        // Update menu selection on hover
+        app_state
+            .desktop_menu
+            .update_selection_from_mouse(mouse_event.column, mouse_event.row);
     }
+
     false
 }
 
@@ -746,6 +1049,10 @@ pub enum SystemMenuResult {
     ShowAbout,
     /// Exit was requested - show confirmation prompt
     ShowExitPrompt,
+    /// A configured entry asked for a terminal window, running the command if any
+    OpenTerminal(Option<String>),
+    /// A configured entry asked to lock the screen
+    LockScreen,
 }
 
 /// Handles System menu mouse interactions.
@@ -755,6 +1062,8 @@ pub fn handle_system_menu_mouse(
     window_manager: &mut WindowManager,
     clipboard_manager: &mut ClipboardManager,
     mouse_event: &MouseEvent,
+    cols: u16,
+    rows: u16,
 ) -> SystemMenuResult {
     if !app_state.system_menu.visible {
         return SystemMenuResult::NotHandled;
@@ -793,7 +1102,12 @@ pub fn handle_system_menu_mouse(
                     MenuAction::CopySelection => {
                         if let FocusState::Window(window_id) = window_manager.get_focus() {
                             if let Some(text) = window_manager.get_selected_text(window_id) {
-                                let _ = clipboard_manager.copy(text);
+                                let _ = copy_from_window(
+                                    clipboard_manager,
+                                    window_manager,
+                                    window_id,
+                                    text,
+                                );
                                 window_manager.clear_selection(window_id);
                             }
                         }
@@ -801,13 +1115,39 @@ pub fn handle_system_menu_mouse(
                     MenuAction::PasteClipboard => {
                         if let FocusState::Window(window_id) = window_manager.get_focus() {
                             if let Ok(text) = clipboard_manager.paste() {
-                                let _ = window_manager.paste_to_window(window_id, &text);
+                                paste_into_window(
+                                    app_state,
+                                    window_manager,
+                                    clipboard_manager,
+                                    window_id,
+                                    text,
+                                    PasteKind::Normal,
+                                    cols,
+                                    rows,
+                                );
                             }
                         }
                     }
                     MenuAction::ClearClipboard => {
                         clipboard_manager.clear();
                     }
+                    MenuAction::UserItem(index) => {
+                        if let Some(command) = app_state.system_menu_items.get(index) {
+                            result = match command {
+                                SystemMenuCommand::Run(command) => {
+                                    SystemMenuResult::OpenTerminal(Some(command.clone()))
+                                }
+                                SystemMenuCommand::NewTerminal => {
+                                    SystemMenuResult::OpenTerminal(None)
+                                }
+                                SystemMenuCommand::Settings => SystemMenuResult::ShowSettings,
+                                SystemMenuCommand::Lock => SystemMenuResult::LockScreen,
+                                SystemMenuCommand::Help => SystemMenuResult::ShowHelp,
+                                SystemMenuCommand::About => SystemMenuResult::ShowAbout,
+                                SystemMenuCommand::Exit => SystemMenuResult::ShowExitPrompt,
+                            };
+                        }
+                    }
                     _ => {}
                 }
             }
@@ -836,14 +1176,15 @@ pub fn show_taskbar_menu(
     window_manager: &WindowManager,
     mouse_event: &MouseEvent,
     bar_y: u16,
-    window_buttons_start: u16,
+    window_buttons: (u16, u16),
 ) -> bool {
     if mouse_event.kind == MouseEventKind::Down(MouseButton::Right) && mouse_event.row == bar_y {
         if let Some(window_id) = window_manager.button_bar_get_window_at(
             mouse_event.column,
             bar_y,
             mouse_event.row,
-            window_buttons_start,
+            window_buttons.0,
+            window_buttons.1,
         ) {
             // Position menu above the click point (menu height is 5: 3 items + 2 borders)
             let menu_y = mouse_event.row.saturating_sub(5);
@@ -861,6 +1202,7 @@ pub fn show_taskbar_menu(
 
 /// Handles mouse events for text selection (single/double/triple click, drag).
 /// Returns true if the event was handled.
+#[allow(clippy::collapsible_match)]
 pub fn handle_selection_mouse(
     app_state: &mut AppState,
     window_manager: &mut WindowManager,
@@ -868,7 +1210,15 @@ pub fn handle_selection_mouse(
 ) -> bool {
     // Check if we should forward mouse to the terminal child process
     // Don't forward if a close confirmation dialog is active - dialog must capture clicks
-    let forward_to_terminal = window_manager.focused_has_mouse_tracking()
+    // Shift+wheel goes to the scrollback (handled by the window manager) instead
+    let is_wheel = matches!(
+        mouse_event.kind,
+        MouseEventKind::ScrollUp | MouseEventKind::ScrollDown
+    );
+    let wheel_to_scrollback =
+        is_wheel && window_manager.wheel_overrides_mouse_tracking(mouse_event.modifiers);
+    let forward_to_terminal = window_manager.focused_receives_mouse()
+        && !wheel_to_scrollback
         && !window_manager.is_dragging_or_resizing()
         && !window_manager.is_point_on_drag_or_resize_area(mouse_event.column, mouse_event.row)
         && !window_manager.focused_has_close_confirmation();
@@ -45,7 +45,6 @@ pub trait Authenticator: Send + Sync {
     fn get_current_username(&self) -> Option<String>;
 
     /// Get display name for the authentication system (for UI/debugging)
-    #[allow(dead_code)]
     fn system_name(&self) -> &'static str;
 }
 
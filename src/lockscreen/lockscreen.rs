@@ -2,6 +2,7 @@
 
 use crate::app::config_manager::LockscreenAuthMode;
 use crate::rendering::{Cell, Charset, Theme, VideoBuffer, render_shadow};
+use chrono::Local;
 use crossterm::style::Color;
 use std::time::{Duration, Instant};
 
@@ -55,8 +56,15 @@ pub struct LockScreen {
     // Cached PIN hash and salt for PIN mode
     pin_hash: Option<String>,
     pin_salt: Option<String>,
+
+    // Background starfield (advances once per second while locked)
+    animation: bool,
+    locked_at: Instant,
 }
 
+/// One background star per this many screen cells
+const STAR_DENSITY: u32 = 48;
+
 impl LockScreen {
     /// Create a new lockscreen instance.
     /// Auto-fills username from the system.
@@ -78,6 +86,8 @@ impl LockScreen {
             auth_mode: LockscreenAuthMode::OsAuth,
             pin_hash: None,
             pin_salt: None,
+            animation: true,
+            locked_at: Instant::now(),
         }
     }
 
@@ -109,6 +119,8 @@ impl LockScreen {
             auth_mode,
             pin_hash,
             pin_salt,
+            animation: true,
+            locked_at: Instant::now(),
         }
     }
 
@@ -153,9 +165,15 @@ impl LockScreen {
         self.auth_mode
     }
 
+    /// Enable or disable the animated starfield behind the dialog
+    pub fn set_animation(&mut self, enabled: bool) {
+        self.animation = enabled;
+    }
+
     /// Activate the lockscreen
     pub fn lock(&mut self) {
         self.state = LockScreenState::Active;
+        self.locked_at = Instant::now();
         self.password.clear();
         self.cursor_position = 0;
         self.focus = InputFocus::Password;
@@ -350,10 +368,38 @@ impl LockScreen {
             }
         }
 
+        // Slowly drifting stars so the screen doesn't look frozen
+        if self.animation {
+            let tick = self.locked_at.elapsed().as_secs();
+            let count = (cols as u32 * rows as u32) / STAR_DENSITY;
+            for i in 0..count {
+                let (x, y, layer) = star_position(i, tick, cols, rows);
+                let (ch, fg) = match layer {
+                    0 => ('.', lock_fg),
+                    1 => ('+', theme.splash_border),
+                    _ => ('*', theme.splash_fg),
+                };
+                buffer.set(x, y, Cell::new(ch, fg, lock_bg));
+            }
+        }
+
         // Calculate dialog position (centered)
         let dialog_x = (cols.saturating_sub(self.dialog_width)) / 2;
         let dialog_y = (rows.saturating_sub(self.dialog_height)) / 2;
 
+        // Clock above the dialog
+        if dialog_y >= 2 {
+            let clock = Local::now().format("%a %b %d  %H:%M:%S").to_string();
+            let clock_x = cols.saturating_sub(clock.len() as u16) / 2;
+            for (i, ch) in clock.chars().enumerate() {
+                buffer.set(
+                    clock_x + i as u16,
+                    dialog_y - 2,
+                    Cell::new(ch, theme.splash_fg, lock_bg),
+                );
+            }
+        }
+
         // Render dialog box
         self.render_dialog(buffer, charset, theme, dialog_x, dialog_y);
     }
@@ -691,6 +737,22 @@ impl Drop for LockScreen {
     }
 }
 
+/// Position and depth layer (0 = far, 2 = near) of star `index` after `tick` seconds.
+/// Stars drift left by one column per second per layer and wrap around.
+fn star_position(index: u32, tick: u64, cols: u16, rows: u16) -> (u16, u16, u8) {
+    // Scrambled so the field looks random but stays stable between frames
+    let h = crate::utils::scramble(index as u64);
+
+    let cols = cols.max(1) as u64;
+    let rows = rows.max(1) as u64;
+    let layer = (h % 3) as u8;
+    let start_x = (h >> 8) % cols;
+    let y = (h >> 32) % rows;
+    let offset = tick.wrapping_mul(layer as u64 + 1) % cols;
+    let x = (start_x + cols - offset) % cols;
+    (x as u16, y as u16, layer)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -773,4 +835,19 @@ mod tests {
         assert_eq!(ls.password, "ab");
         assert_eq!(ls.cursor_position, 2);
     }
+
+    #[test]
+    fn test_star_positions_drift_within_bounds() {
+        for i in 0..100 {
+            let (x0, y0, layer) = star_position(i, 0, 80, 24);
+            assert!(x0 < 80 && y0 < 24 && layer < 3);
+            // Stable within the same tick
+            assert_eq!(star_position(i, 0, 80, 24), (x0, y0, layer));
+
+            // Each second a star moves left by its layer speed, on the same row
+            let (x1, y1, _) = star_position(i, 1, 80, 24);
+            assert_eq!(y1, y0);
+            assert_eq!(x1, (x0 + 80 - (layer as u16 + 1)) % 80);
+        }
+    }
 }
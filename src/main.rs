@@ -31,6 +31,39 @@ fn main() -> io::Result<()> {
     // Parse command-line arguments
     let cli_args = app::cli::Cli::parse_args();
 
+    // Handle --version-info flag: print build details and exit
+    if cli_args.version_info {
+        let clipboard = ClipboardManager::with_backend(AppConfig::load().clipboard_backend);
+        println!("{}", app::cli::version_info(clipboard.backend_name()));
+        return Ok(());
+    }
+
+    // Handle --dump-keymap flag: print the resolved key bindings and exit
+    if cli_args.dump_keymap {
+        let profile =
+            app::initialization::initialize_keybinding_profile(&cli_args, &AppConfig::load());
+        print!("{}", profile.dump_keymap());
+        return Ok(());
+    }
+
+    // Handle --export-setup / --import-setup: write or apply a setup file and exit
+    if let Some(ref path) = cli_args.export_setup {
+        let app_config = AppConfig::load();
+        let theme = app::initialization::initialize_theme(&cli_args, &app_config);
+        let profile = app::initialization::initialize_keybinding_profile(&cli_args, &app_config);
+        app::setup_file::export_setup(&app_config, &theme, &profile, path.as_ref())?;
+        println!("Setup exported to {}", path);
+        return Ok(());
+    }
+    if let Some(ref path) = cli_args.import_setup {
+        let backup = app::setup_file::import_setup(path.as_ref())?;
+        println!("Setup imported from {}", path);
+        if let Some(backup) = backup {
+            println!("Previous config backed up to {}", backup.display());
+        }
+        return Ok(());
+    }
+
     // Handle --lock flag: send SIGUSR1 to running term39 instance and exit
     #[cfg(unix)]
     if cli_args.lock {
@@ -68,7 +101,12 @@ fn main() -> io::Result<()> {
         app::initialization::initialize_keybinding_profile(&cli_args, &app_config);
 
     // Validate shell configuration early (before terminal setup) so warnings are visible
-    let shell_config = app::initialization::validate_shell_config(&cli_args);
+    let mut warnings = Vec::new();
+    let shell_config =
+        app::initialization::validate_shell_config(&cli_args, &app_config, &mut warnings);
+    app::initialization::validate_startup_windows(&app_config, &mut warnings);
+    app::initialization::validate_cursor_color(&app_config, &mut warnings);
+    app::initialization::show_startup_warnings(&warnings);
 
     // ===== PERSIST MODE =====
     // Fork daemon before any thread creation (setup_terminal, mouse input, PTY readers).
@@ -187,23 +225,32 @@ fn main() -> io::Result<()> {
 
     let mut stdout = io::stdout();
 
-    // Set up terminal modes and mouse capture
-    app::initialization::setup_terminal(&mut stdout)?;
-
-    // Initialize unified mouse input manager (will try to disable GPM cursor if needed)
     #[cfg(all(target_os = "linux", feature = "framebuffer-backend"))]
     let is_framebuffer_mode = cli_args.framebuffer;
     #[cfg(not(all(target_os = "linux", feature = "framebuffer-backend")))]
     let is_framebuffer_mode = false;
 
+    // Set up terminal modes and mouse capture, initializing the unified mouse input manager
+    // (which will try to disable the GPM cursor if needed) before mouse capture is enabled
     let (cols_for_mouse, rows_for_mouse) = backend.dimensions();
     let (mut mouse_input_manager, _gpm_disable_connection) =
-        app::initialization::initialize_mouse_input(
-            &cli_args,
-            cols_for_mouse,
-            rows_for_mouse,
-            is_framebuffer_mode,
-        );
+        app::initialization::setup_terminal(&mut stdout, || {
+            app::initialization::initialize_mouse_input(
+                &cli_args,
+                cols_for_mouse,
+                rows_for_mouse,
+                is_framebuffer_mode,
+            )
+        })?;
+
+    // Restore the terminal on every exit path from here on (errors, panics, normal exit)
+    let terminal_guard = app::panic_handler::TerminalGuard::new();
+
+    // Restore hidden top/bottom bars (the buffer below then includes the hidden rows)
+    if app_config.bars_hidden {
+        backend.set_bars_hidden(true);
+        let _ = backend.check_resize();
+    }
 
     // Initialize video buffer and window manager
     let mut video_buffer = app::initialization::initialize_video_buffer(backend.as_ref());
@@ -221,6 +268,42 @@ fn main() -> io::Result<()> {
         app::initialization::initialize_window_manager(&cli_args, &mut app_config, shell_config)?
     };
 
+    window_manager.set_default_cursor_shape(app_config.default_cursor_shape);
+    window_manager.set_answerback(&app_config.answerback);
+    window_manager.set_minimum_contrast(app_config.minimum_contrast());
+    window_manager.set_cursor_color(app_config.cursor_color());
+    window_manager.set_scrollback_compression(app_config.scrollback_compression());
+    window_manager.set_ghost_unfocused(app_config.ghost_unfocused_windows);
+    window_manager.set_scroll_indicator(app_config.scroll_indicator);
+    window_manager.set_resize_grip_size(app_config.resize_grip_size);
+    window_manager.set_force_cursor_visible(app_config.force_cursor_visible);
+    window_manager.set_focus_highlight(app_config.focus_highlight, app_config.focus_dim_percent);
+    window_manager.set_tab_strip(app_config.maximized_tab_strip);
+    window_manager.set_control_notation(app_config.control_notation());
+    window_manager.set_scrollbar_mode(app_config.scrollbar_mode);
+    window_manager.set_new_window_position(app_config.new_window_position);
+    window_manager.set_output_log(
+        &app_config.output_log,
+        app_config.output_log_max_kb.saturating_mul(1024),
+    );
+    window_manager.set_session_scrollback_lines(app_config.session_scrollback_lines);
+    window_manager.set_session_trim_blanks(app_config.session_trim_trailing_blanks);
+    window_manager.set_enter_sends(app_config.enter_sends);
+    window_manager.set_mouse_forward_mode(app_config.mouse_forward_mode);
+    window_manager.set_shift_wheel_scrollback(app_config.shift_wheel_scrollback);
+    window_manager.set_paste_transform(app_config.paste_transform());
+    window_manager.set_paste_guard(app_config.paste_guard);
+    window_manager.set_close_confirm_policy(app_config.close_confirm_policy());
+    window_manager.set_protected_processes(app_config.protected_process_names.clone());
+    window_manager.set_max_bytes_per_frame(app_config.max_bytes_per_frame);
+    window_manager.set_word_chars(&app_config.word_chars);
+    window_manager.set_alt_screen_scroll(app_config.alt_screen_scroll);
+    window_manager.set_button_titles(
+        app_config.button_title_max_len,
+        app_config.button_title_truncation,
+        app_config.button_title_duplicates,
+    );
+
     // Set persist client on window manager and restore any existing windows (Unix only)
     #[cfg(unix)]
     let persist_startup_warning = {
@@ -245,7 +328,16 @@ fn main() -> io::Result<()> {
     if cli_args.tint_terminal {
         app_config.tint_terminal = true;
     }
+    if let Some(strength) = cli_args.tint_strength {
+        app_config.tint_strength = strength;
+    }
+    if let Some(path) = &cli_args.status_file {
+        app_config.status_file = path.clone();
+    }
     let mut app_state = AppState::new(cols, rows, &app_config, &charset);
+    app_state
+        .lockscreen
+        .set_animation(app_config.lockscreen_animation);
 
     // Show persist startup warning as toast (if any)
     #[cfg(unix)]
@@ -258,12 +350,18 @@ fn main() -> io::Result<()> {
         app_state.exit_button.enabled = false;
     }
 
+    // Relabel the exit button and System menu entry
+    app_state.exit_button.label = app_config.exit_label.clone();
+    app_state
+        .system_menu
+        .set_item_label(ui::context_menu::MenuAction::Exit, &app_config.exit_label);
+
     // Initialize autocomplete system (command indexer and history)
     let command_indexer = CommandIndexer::new();
     let mut command_history = CommandHistory::new();
 
     // Clipboard manager
-    let mut clipboard_manager = ClipboardManager::new();
+    let mut clipboard_manager = ClipboardManager::with_backend(app_config.clipboard_backend);
 
     // Show splash screen for 1 second (skip when reattaching to existing session)
     #[cfg(unix)]
@@ -278,8 +376,35 @@ fn main() -> io::Result<()> {
     // Set up signal handler for external lockscreen trigger (Unix only)
     lockscreen::signal_handler::setup();
 
+    // Save the session on SIGTERM (e.g. logout) like a clean exit (Unix only)
+    app::signal_handler::setup();
+
+    // Open the configured startup windows when nothing was restored
+    app::initialization::open_startup_windows(
+        &mut app_state,
+        &mut window_manager,
+        &app_config,
+        cols,
+        rows,
+    );
+
     // Start with desktop focused - no windows yet
-    // User can press 't' to create windows
+    // User can press 't' to create windows (--maximized opens one right away)
+    if cli_args.maximized {
+        if window_manager.window_count() == 0 {
+            input::keyboard_handlers::create_terminal_window_with_command(
+                &mut app_state,
+                &mut window_manager,
+                cols,
+                rows,
+                true,
+                app_config.tiling_gaps,
+                None,
+            );
+        } else if let window::FocusState::Window(id) = window_manager.get_focus() {
+            window_manager.maximize_window(id, cols, rows, app_config.tiling_gaps);
+        }
+    }
 
     // Run the main event loop
     app::event_loop::run(
@@ -310,6 +435,10 @@ fn main() -> io::Result<()> {
 
     // Save or clear session before exiting (unless --no-save flag is set)
     if !cli_args.no_save {
+        // Keep a charset mode chosen with the shortcut for the next start
+        if app_state.charset_mode_changed {
+            let _ = app_config.save();
+        }
         if app_config.auto_save {
             let _ = window_manager.save_session_to_file();
         } else {
@@ -319,7 +448,7 @@ fn main() -> io::Result<()> {
     }
 
     // Cleanup: restore terminal
-    app::initialization::cleanup(&mut stdout);
+    drop(terminal_guard);
 
     Ok(())
 }
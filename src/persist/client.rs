@@ -110,6 +110,7 @@ impl PersistClient {
     }
 
     /// Request creation of a new window
+    #[allow(clippy::too_many_arguments)]
     pub fn request_create_window(
         &mut self,
         x: u16,
@@ -118,6 +119,7 @@ impl PersistClient {
         height: u16,
         title: String,
         command: Option<String>,
+        cwd: Option<String>,
     ) -> io::Result<()> {
         self.send(&ClientMsg::CreateWindow {
             x,
@@ -126,6 +128,7 @@ impl PersistClient {
             height,
             title,
             command,
+            cwd,
         })
     }
 
@@ -161,6 +164,11 @@ impl PersistClient {
         })
     }
 
+    /// Ask the daemon to signal a window's foreground process group
+    pub fn send_signal_foreground(&mut self, window_id: u32, signal: i32) -> io::Result<()> {
+        self.send(&ClientMsg::SignalForeground { window_id, signal })
+    }
+
     /// Send detach message (graceful disconnect)
     pub fn detach(&mut self) -> io::Result<()> {
         self.send(&ClientMsg::Detach)
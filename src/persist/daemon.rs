@@ -2,7 +2,7 @@ use super::protocol::{
     ClientMsg, DaemonMsg, WindowInfo, read_message, try_read_message, write_message,
 };
 use super::socket;
-use crate::term_emu::ShellConfig;
+use crate::term_emu::{ForegroundSignal, ShellConfig, signal_foreground_group};
 use portable_pty::{Child, CommandBuilder, MasterPty, PtySize, native_pty_system};
 use std::io::{self, BufWriter, Read, Write};
 use std::os::unix::net::{UnixListener, UnixStream};
@@ -468,6 +468,7 @@ fn handle_client_msg(
             height,
             title,
             command,
+            cwd,
         } => {
             let window_id = *next_id;
             *next_id += 1;
@@ -486,7 +487,9 @@ fn handle_client_msg(
                 content_height,
                 title,
                 command,
-                shell_config,
+                &shell_config
+                    .clone()
+                    .with_working_dir(cwd.or_else(|| shell_config.working_dir.clone())),
             ) {
                 Ok(daemon_window) => {
                     daemon_log(&format!("window {} created", window_id));
@@ -527,6 +530,27 @@ fn handle_client_msg(
             }
             ClientAction::Continue
         }
+        ClientMsg::SignalForeground { window_id, signal } => {
+            let result = match (
+                windows.iter().find(|w| w.window_id == window_id),
+                ForegroundSignal::from_raw(signal),
+            ) {
+                (Some(window), Some(signal)) => match window.pty_master.as_raw_fd() {
+                    Some(fd) => signal_foreground_group(fd, signal).map_err(|e| e.to_string()),
+                    None => Err("PTY has no file descriptor".to_string()),
+                },
+                (None, _) => Err(format!("Window {} not found", window_id)),
+                (_, None) => Err(format!("Unsupported signal {}", signal)),
+            };
+            let msg = match result {
+                Ok(()) => DaemonMsg::SignalSent { window_id, signal },
+                Err(message) => DaemonMsg::Error {
+                    message: format!("Failed to send signal: {}", message),
+                },
+            };
+            let _ = write_to_client(client_stream, &msg);
+            ClientAction::Continue
+        }
         ClientMsg::UpdateWindowGeometry {
             window_id,
             x,
@@ -600,6 +624,10 @@ fn create_daemon_window(
     cmd.env("PROMPT_EOL_MARK", "");
     cmd.env("PROMPT_SP", "");
 
+    if let Some(ref dir) = shell_config.working_dir {
+        cmd.cwd(dir);
+    }
+
     let child = pty_pair
         .slave
         .spawn_command(cmd)
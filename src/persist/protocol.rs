@@ -18,6 +18,9 @@ pub enum ClientMsg {
         height: u16,
         title: String,
         command: Option<String>,
+        /// Directory to start in (None inherits the daemon's)
+        #[serde(default)]
+        cwd: Option<String>,
     },
     /// Close a window (and its PTY)
     CloseWindow { window_id: u32 },
@@ -35,6 +38,8 @@ pub enum ClientMsg {
         width: u16,
         height: u16,
     },
+    /// Send a signal to the foreground process group of a window's PTY
+    SignalForeground { window_id: u32, signal: i32 },
     /// Request full state snapshot
     GetState,
     /// Kill the daemon
@@ -60,6 +65,8 @@ pub enum DaemonMsg {
     WindowClosed { window_id: u32 },
     /// Full state snapshot
     State { windows: Vec<WindowInfo> },
+    /// A window's foreground process group was signalled
+    SignalSent { window_id: u32, signal: i32 },
     /// Error message
     Error { message: String },
     /// Heartbeat ping (client must respond with Pong)
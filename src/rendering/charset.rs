@@ -1,11 +1,50 @@
+use serde::Serialize;
+
 /// Character set configuration for rendering
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CharsetMode {
     Unicode,
     UnicodeSingleLine,
     Ascii,
 }
 
+impl CharsetMode {
+    /// Next mode in the cycle Ascii -> Unicode -> UnicodeSingleLine -> Ascii
+    pub fn next(self) -> Self {
+        match self {
+            CharsetMode::Ascii => CharsetMode::Unicode,
+            CharsetMode::Unicode => CharsetMode::UnicodeSingleLine,
+            CharsetMode::UnicodeSingleLine => CharsetMode::Ascii,
+        }
+    }
+
+    /// Name shown when the mode changes
+    pub fn name(self) -> &'static str {
+        match self {
+            CharsetMode::Unicode => "Unicode",
+            CharsetMode::UnicodeSingleLine => "Unicode (single line)",
+            CharsetMode::Ascii => "ASCII",
+        }
+    }
+}
+
+/// Window border line style a theme can ask for in place of the charset's own
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum BorderStyle {
+    Single, // ┌─┐ (QBasic, WordPerfect)
+    Double, // ╔═╗ (Turbo Pascal, Norton Commander)
+}
+
+impl BorderStyle {
+    /// Border glyphs in `Charset` field order: corners, lines, then junctions
+    fn glyphs(self) -> [char; 11] {
+        match self {
+            BorderStyle::Single => ['┌', '┐', '└', '┘', '─', '│', '├', '┤', '┬', '┴', '┼'],
+            BorderStyle::Double => ['╔', '╗', '╚', '╝', '═', '║', '╠', '╣', '╦', '╩', '╬'],
+        }
+    }
+}
+
 /// Character definitions for UI elements
 #[derive(Clone, Copy, Debug)]
 pub struct Charset {
@@ -22,6 +61,11 @@ pub struct Charset {
     pub border_horizontal: char,
     pub border_vertical: char,
     pub border_vertical_right: char, // T-junction (╠ or +)
+    // Junctions where the borders of touching windows join (join_borders)
+    pub border_vertical_left: char,
+    pub border_horizontal_down: char,
+    pub border_horizontal_up: char,
+    pub border_cross: char,
 
     // Window controls
     pub shadow: char,
@@ -41,6 +85,7 @@ pub struct Charset {
     pub icon_help: char,
     pub icon_about: char,
     pub icon_exit: char,
+    pub icon_lock: char,
 
     // Network widget icons
     pub network_signal_1: char, // Weakest signal bar
@@ -66,22 +111,35 @@ pub struct Charset {
 }
 
 impl Charset {
+    /// Create the charset for a mode
+    pub fn from_mode(mode: CharsetMode) -> Self {
+        match mode {
+            CharsetMode::Unicode => Self::unicode(),
+            CharsetMode::UnicodeSingleLine => Self::unicode_single_line(),
+            CharsetMode::Ascii => Self::ascii(),
+        }
+    }
+
     /// Create Unicode charset (default)
     pub fn unicode() -> Self {
         Self {
             mode: CharsetMode::Unicode,
-            background: '░',            // U+2591 light shade (DOS CP437 177)
-            border_top_left: '╔',       // U+2554
-            border_top_right: '╗',      // U+2557
-            border_bottom_left: '╚',    // U+255A
-            border_bottom_right: '╝',   // U+255D
-            border_horizontal: '═',     // U+2550
-            border_vertical: '║',       // U+2551
-            border_vertical_right: '╠', // U+2560 T-junction
-            shadow: '▓',                // U+2593 dark shade
-            block: '█',                 // U+2588 full block
-            shade: '░',                 // U+2591 light shade
-            pivot: '✛',                 // U+271B Heavy Greek cross
+            background: '░',             // U+2591 light shade (DOS CP437 177)
+            border_top_left: '╔',        // U+2554
+            border_top_right: '╗',       // U+2557
+            border_bottom_left: '╚',     // U+255A
+            border_bottom_right: '╝',    // U+255D
+            border_horizontal: '═',      // U+2550
+            border_vertical: '║',        // U+2551
+            border_vertical_right: '╠',  // U+2560 T-junction
+            border_vertical_left: '╣',   // U+2563
+            border_horizontal_down: '╦', // U+2566
+            border_horizontal_up: '╩',   // U+2569
+            border_cross: '╬',           // U+256C
+            shadow: '▓',                 // U+2593 dark shade
+            block: '█',                  // U+2588 full block
+            shade: '░',                  // U+2591 light shade
+            pivot: '✛',                  // U+271B Heavy Greek cross
             // Menu item icons (Unicode)
             icon_copy: '\u{29C9}',     // ⧉ Two Joined Squares
             icon_paste: '\u{29E0}',    // ⧠ Square with Contoured Outline
@@ -90,6 +148,7 @@ impl Charset {
             icon_help: '?',            // Question mark
             icon_about: '\u{24D8}',    // ⓘ Circled Latin Small Letter I
             icon_exit: '\u{23FB}',     // ⏻ Power Symbol
+            icon_lock: '\u{26BF}',     // ⚿ Squared Key
             // Network widget icons (Unicode)
             network_signal_1: '\u{2582}',  // ▂ Lower one quarter block
             network_signal_2: '\u{2584}',  // ▄ Lower half block
@@ -112,18 +171,22 @@ impl Charset {
     pub fn unicode_single_line() -> Self {
         Self {
             mode: CharsetMode::UnicodeSingleLine,
-            background: '░',            // U+2591 light shade (DOS CP437 177)
-            border_top_left: '┌',       // U+250C (single-line corner)
-            border_top_right: '┐',      // U+2510
-            border_bottom_left: '└',    // U+2514
-            border_bottom_right: '┘',   // U+2518
-            border_horizontal: '─',     // U+2500 (single-line horizontal)
-            border_vertical: '│',       // U+2502 (single-line vertical)
-            border_vertical_right: '├', // U+251C T-junction
-            shadow: '▓',                // U+2593 dark shade
-            block: '█',                 // U+2588 full block
-            shade: '░',                 // U+2591 light shade
-            pivot: '✛',                 // U+271B Heavy Greek cross
+            background: '░',             // U+2591 light shade (DOS CP437 177)
+            border_top_left: '┌',        // U+250C (single-line corner)
+            border_top_right: '┐',       // U+2510
+            border_bottom_left: '└',     // U+2514
+            border_bottom_right: '┘',    // U+2518
+            border_horizontal: '─',      // U+2500 (single-line horizontal)
+            border_vertical: '│',        // U+2502 (single-line vertical)
+            border_vertical_right: '├',  // U+251C T-junction
+            border_vertical_left: '┤',   // U+2524
+            border_horizontal_down: '┬', // U+252C
+            border_horizontal_up: '┴',   // U+2534
+            border_cross: '┼',           // U+253C
+            shadow: '▓',                 // U+2593 dark shade
+            block: '█',                  // U+2588 full block
+            shade: '░',                  // U+2591 light shade
+            pivot: '✛',                  // U+271B Heavy Greek cross
             // Menu item icons (Unicode - same as double-line)
             icon_copy: '\u{29C9}',     // ⧉ Two Joined Squares
             icon_paste: '\u{29E0}',    // ⧠ Square with Contoured Outline
@@ -132,6 +195,7 @@ impl Charset {
             icon_help: '?',            // Question mark
             icon_about: '\u{24D8}',    // ⓘ Circled Latin Small Letter I
             icon_exit: '\u{23FB}',     // ⏻ Power Symbol
+            icon_lock: '\u{26BF}',     // ⚿ Squared Key
             // Network widget icons (Unicode - same as double-line)
             network_signal_1: '\u{2582}',  // ▂ Lower one quarter block
             network_signal_2: '\u{2584}',  // ▄ Lower half block
@@ -161,10 +225,14 @@ impl Charset {
             border_horizontal: '-',     // Dash for horizontal
             border_vertical: '|',       // Pipe for vertical
             border_vertical_right: '+', // Plus for T-junction
-            shadow: '#',                // Hash for shadow
-            block: '#',                 // Hash for "on" state in ASCII mode
-            shade: ' ',                 // Space for "off" state in ASCII mode
-            pivot: '+',                 // Plus for ASCII mode
+            border_vertical_left: '+',
+            border_horizontal_down: '+',
+            border_horizontal_up: '+',
+            border_cross: '+',
+            shadow: '#', // Hash for shadow
+            block: '#',  // Hash for "on" state in ASCII mode
+            shade: ' ',  // Space for "off" state in ASCII mode
+            pivot: '+',  // Plus for ASCII mode
             // Menu item icons (ASCII)
             icon_copy: 'C',     // C for Copy
             icon_paste: 'P',    // P for Paste
@@ -173,6 +241,7 @@ impl Charset {
             icon_help: '?',     // ? for Help
             icon_about: 'i',    // i for Info/About
             icon_exit: 'Q',     // Q for Quit/Exit
+            icon_lock: '#',     // # for Locked input
             // Network widget icons (ASCII)
             network_signal_1: '_',     // _ for weakest
             network_signal_2: '.',     // . for low
@@ -224,8 +293,80 @@ impl Charset {
         self.shade
     }
 
+    /// This charset with its border glyphs replaced by a theme's `style`
+    /// Only the Unicode charset is changed: the single-line charset is for fonts without
+    /// double lines and ASCII keeps `+`, `-` and `|`
+    pub fn with_border_style(&self, style: Option<BorderStyle>) -> Self {
+        let mut charset = *self;
+        let Some(style) = style.filter(|_| self.mode == CharsetMode::Unicode) else {
+            return charset;
+        };
+        [
+            charset.border_top_left,
+            charset.border_top_right,
+            charset.border_bottom_left,
+            charset.border_bottom_right,
+            charset.border_horizontal,
+            charset.border_vertical,
+            charset.border_vertical_right,
+            charset.border_vertical_left,
+            charset.border_horizontal_down,
+            charset.border_horizontal_up,
+            charset.border_cross,
+        ] = style.glyphs();
+        charset
+    }
+
     /// Set a custom background character
     pub fn set_background(&mut self, background_char: char) {
         self.background = background_char;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_border_style_overrides_unicode_only() {
+        let single = Charset::unicode().with_border_style(Some(BorderStyle::Single));
+        assert_eq!(single.border_top_left, '┌');
+        assert_eq!(single.border_cross, '┼');
+        // Everything but the borders is the charset's
+        assert_eq!(single.background, '░');
+        assert_eq!(
+            Charset::unicode().with_border_style(None).border_top_left,
+            '╔'
+        );
+
+        // ASCII keeps +, - and |, the single-line charset keeps its lines
+        let ascii = Charset::ascii().with_border_style(Some(BorderStyle::Double));
+        assert_eq!(
+            (
+                ascii.border_top_left,
+                ascii.border_horizontal,
+                ascii.border_vertical
+            ),
+            ('+', '-', '|')
+        );
+        let single_line =
+            Charset::unicode_single_line().with_border_style(Some(BorderStyle::Double));
+        assert_eq!(single_line.border_horizontal, '─');
+    }
+
+    #[test]
+    fn test_charset_mode_cycle() {
+        assert_eq!(CharsetMode::Ascii.next(), CharsetMode::Unicode);
+        assert_eq!(CharsetMode::Unicode.next(), CharsetMode::UnicodeSingleLine);
+        assert_eq!(CharsetMode::UnicodeSingleLine.next(), CharsetMode::Ascii);
+        // Three steps visit every mode and come back to the start
+        let mut mode = CharsetMode::Unicode;
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            seen.push(mode.name());
+            mode = mode.next();
+        }
+        assert_eq!(mode, CharsetMode::Unicode);
+        assert_eq!(seen, ["Unicode", "Unicode (single line)", "ASCII"]);
+    }
+}
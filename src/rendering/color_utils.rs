@@ -219,10 +219,100 @@ pub fn ensure_contrast(fg: Color, bg: Color, min_ratio: f32) -> (Color, Color) {
     }
 }
 
+/// Lighten or darken a foreground color until it reaches `min_ratio` against `bg`.
+/// Moves toward white on dark backgrounds and black on light ones, in 10% steps;
+/// returns pure white/black when the ratio can't be reached.
+pub fn adjust_fg_for_contrast(fg: Color, bg: Color, min_ratio: f32) -> Color {
+    let toward_white = calculate_contrast_ratio(&Color::White, &bg)
+        >= calculate_contrast_ratio(&Color::Black, &bg);
+    let target = if toward_white { 255.0 } else { 0.0 };
+    let (r, g, b) = color_to_rgb(&fg);
+
+    for step in 1..=10 {
+        let t = step as f32 / 10.0;
+        let mix = |c: u8| (c as f32 + (target - c as f32) * t).round() as u8;
+        let candidate = Color::Rgb {
+            r: mix(r),
+            g: mix(g),
+            b: mix(b),
+        };
+        if calculate_contrast_ratio(&candidate, &bg) >= min_ratio {
+            return candidate;
+        }
+    }
+
+    if toward_white {
+        Color::White
+    } else {
+        Color::Black
+    }
+}
+
+/// Dim levels (percent toward black) cycled by the cycle_dim shortcut
+pub const DIM_LEVELS: [u8; 4] = [0, 25, 50, 75];
+
+/// The dim level after `current`: the next larger one in DIM_LEVELS, wrapping to off
+pub fn next_dim_level(current: u8) -> u8 {
+    DIM_LEVELS
+        .iter()
+        .copied()
+        .find(|&level| level > current)
+        .unwrap_or(0)
+}
+
+/// Scale a color `percent` toward black (the `dim_factor` night dim)
+/// Named and 256-palette colors become RGB; Reset is left to the host terminal's default
+pub fn dim_color(color: Color, percent: u8) -> Color {
+    if percent == 0 || color == Color::Reset {
+        return color;
+    }
+    let keep = 100 - percent.min(100) as u16;
+    let (r, g, b) = color_to_rgb(&color);
+    let scale = |c: u8| (c as u16 * keep / 100) as u8;
+    Color::Rgb {
+        r: scale(r),
+        g: scale(g),
+        b: scale(b),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_dim_color_and_levels() {
+        assert_eq!(dim_color(Color::White, 0), Color::White);
+        assert_eq!(
+            dim_color(Color::White, 50),
+            Color::Rgb {
+                r: 127,
+                g: 127,
+                b: 127
+            }
+        );
+        assert_eq!(
+            dim_color(
+                Color::Rgb {
+                    r: 200,
+                    g: 100,
+                    b: 0
+                },
+                25
+            ),
+            Color::Rgb {
+                r: 150,
+                g: 75,
+                b: 0
+            }
+        );
+        assert_eq!(dim_color(Color::Reset, 75), Color::Reset);
+
+        assert_eq!(next_dim_level(0), 25);
+        assert_eq!(next_dim_level(30), 50);
+        assert_eq!(next_dim_level(75), 0);
+    }
+
     #[test]
     fn test_color_to_rgb() {
         assert_eq!(color_to_rgb(&Color::Black), (0, 0, 0));
@@ -270,4 +360,23 @@ mod tests {
         assert!(matches!(fg, Color::White));
         assert!(matches!(bg, Color::Black));
     }
+
+    #[test]
+    fn test_adjust_fg_for_contrast() {
+        // Dark grey on black gets lighter until readable
+        let fg = adjust_fg_for_contrast(Color::DarkGrey, Color::Black, 4.5);
+        assert!(calculate_contrast_ratio(&fg, &Color::Black) >= 4.5);
+        assert!(calculate_luminance(&fg) > calculate_luminance(&Color::DarkGrey));
+
+        // Light grey on white gets darker
+        let fg = adjust_fg_for_contrast(Color::Grey, Color::White, 4.5);
+        assert!(calculate_contrast_ratio(&fg, &Color::White) >= 4.5);
+        assert!(calculate_luminance(&fg) < calculate_luminance(&Color::Grey));
+
+        // Unreachable ratios end at the extreme
+        assert_eq!(
+            adjust_fg_for_contrast(Color::Grey, Color::Black, 30.0),
+            Color::White
+        );
+    }
 }
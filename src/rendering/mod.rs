@@ -8,9 +8,13 @@ mod video_buffer;
 
 pub use ansi_parser::{ParsedCell, parse_ansi_to_cells};
 pub use charset::{Charset, CharsetMode};
+pub use color_utils::{
+    adjust_fg_for_contrast, calculate_contrast_ratio, calculate_luminance, dim_color,
+    next_dim_level,
+};
 #[cfg(all(target_os = "linux", feature = "framebuffer-backend"))]
 pub use render_backend::FramebufferBackend;
 pub use render_backend::{RenderBackend, TerminalBackend};
 pub use render_frame::render_frame;
 pub use theme::Theme;
-pub use video_buffer::{Cell, VideoBuffer, render_shadow};
+pub use video_buffer::{Cell, DENSE_PLACEHOLDER, DenseRegion, VideoBuffer, render_shadow};
@@ -64,6 +64,25 @@ pub trait RenderBackend {
         false // Default: no native mouse input
     }
 
+    /// Hide or show the top and bottom bar rows (fullscreen workspace)
+    /// While hidden, dimensions() includes the two hidden rows so the layout math is
+    /// unchanged; the next check_resize() reports the new size
+    fn set_bars_hidden(&mut self, _hidden: bool) {
+        // Default: no-op (the bars stay visible)
+    }
+
+    /// Rows hidden above the screen (1 while the bars are hidden, else 0)
+    /// Screen row r shows buffer row r + hidden_rows()
+    fn hidden_rows(&self) -> u16 {
+        0 // Default: nothing hidden
+    }
+
+    /// Terminal cells per screen cell (columns, rows) for windows using the dense font
+    /// Returns None when the backend can't render dense windows (terminal backend)
+    fn dense_density(&self) -> Option<(u16, u16)> {
+        None // Default: dense windows unavailable
+    }
+
     /// Set TTY cursor position (for raw mouse input mode in terminal backend)
     /// This inverts the cell colors at the cursor position
     fn set_tty_cursor(&mut self, _col: u16, _row: u16) {
@@ -74,6 +93,16 @@ pub trait RenderBackend {
     fn clear_tty_cursor(&mut self) {
         // Default: no-op
     }
+
+    /// Ring the bell of the host terminal
+    fn bell(&mut self) {
+        // Default: no-op (the framebuffer has no host terminal to ring)
+    }
+
+    /// Send an escape sequence to the host terminal (OSC 52 clipboard copies)
+    fn send_host_escape(&mut self, _escape: &str) {
+        // Default: no-op (the framebuffer has no host terminal)
+    }
 }
 
 /// Terminal-based rendering backend (using crossterm)
@@ -83,6 +112,10 @@ pub struct TerminalBackend {
     stdout: io::Stdout,
     /// TTY cursor position for raw mouse input mode
     tty_cursor: Option<(u16, u16)>,
+    /// Top and bottom bar rows hidden (fullscreen workspace)
+    bars_hidden: bool,
+    /// Dimensions last reported through check_resize()
+    reported_size: (u16, u16),
 }
 
 impl TerminalBackend {
@@ -98,21 +131,39 @@ impl TerminalBackend {
             rows,
             stdout,
             tty_cursor: None,
+            bars_hidden: false,
+            reported_size: (cols, rows),
         })
     }
 }
 
 impl RenderBackend for TerminalBackend {
     fn present(&mut self, buffer: &mut VideoBuffer) -> io::Result<()> {
-        // Apply TTY cursor to buffer before presenting
+        // Apply TTY cursor to buffer before presenting (screen rows to buffer rows)
+        let hidden_rows = self.hidden_rows();
         if let Some((col, row)) = self.tty_cursor {
-            buffer.set_tty_cursor(col, row);
+            buffer.set_tty_cursor(col, row + hidden_rows);
         } else {
             buffer.clear_tty_cursor();
         }
+        buffer.set_hidden_rows(hidden_rows);
         buffer.present(&mut self.stdout)
     }
 
+    fn bell(&mut self) {
+        use std::io::Write;
+
+        let _ = self.stdout.write_all(b"\x07");
+        let _ = self.stdout.flush();
+    }
+
+    fn send_host_escape(&mut self, escape: &str) {
+        use std::io::Write;
+
+        let _ = self.stdout.write_all(escape.as_bytes());
+        let _ = self.stdout.flush();
+    }
+
     fn set_tty_cursor(&mut self, col: u16, row: u16) {
         self.tty_cursor = Some((col, row));
     }
@@ -122,22 +173,33 @@ impl RenderBackend for TerminalBackend {
     }
 
     fn dimensions(&self) -> (u16, u16) {
-        (self.cols, self.rows)
+        (self.cols, self.rows + 2 * self.hidden_rows())
     }
 
     fn check_resize(&mut self) -> io::Result<Option<(u16, u16)>> {
         use crossterm::terminal;
 
         let (new_cols, new_rows) = terminal::size()?;
-
-        if new_cols != self.cols || new_rows != self.rows {
-            self.cols = new_cols;
-            self.rows = new_rows;
-            Ok(Some((new_cols, new_rows)))
+        self.cols = new_cols;
+        self.rows = new_rows;
+
+        // Also reports the size change from hiding or showing the bars
+        let size = self.dimensions();
+        if size != self.reported_size {
+            self.reported_size = size;
+            Ok(Some(size))
         } else {
             Ok(None)
         }
     }
+
+    fn set_bars_hidden(&mut self, hidden: bool) {
+        self.bars_hidden = hidden;
+    }
+
+    fn hidden_rows(&self) -> u16 {
+        u16::from(self.bars_hidden)
+    }
 }
 
 /// Framebuffer-based rendering backend (Linux console only)
@@ -167,6 +229,10 @@ pub struct FramebufferBackend {
     cached_char_height: usize,
     cached_cols: usize,
     cached_rows: usize,
+    // Top and bottom bar rows hidden (fullscreen workspace)
+    bars_hidden: bool,
+    // Dimensions last reported through check_resize()
+    reported_size: (u16, u16),
 }
 
 #[cfg(all(target_os = "linux", feature = "framebuffer-backend"))]
@@ -249,9 +315,26 @@ impl FramebufferBackend {
             cached_char_height,
             cached_cols: cols,
             cached_rows: rows,
+            bars_hidden: false,
+            reported_size: (cols as u16, rows as u16),
         })
     }
 
+    /// Load the font used for dense windows (see FramebufferRenderer::load_dense_font)
+    pub fn load_dense_font(&mut self, mode: crate::framebuffer::TextMode, font_name: Option<&str>) {
+        self.renderer.load_dense_font(mode, font_name);
+    }
+
+    /// Set the CRT scanline effect (see FramebufferRenderer::set_crt_effect)
+    pub fn set_crt_effect(&mut self, scanlines: u8, phosphor_glow: bool) {
+        self.renderer.set_crt_effect(scanlines, phosphor_glow);
+    }
+
+    /// Turn the mouse pointer trail on or off (see FramebufferRenderer::set_cursor_trail)
+    pub fn set_cursor_trail(&mut self, enabled: bool) {
+        self.renderer.set_cursor_trail(enabled);
+    }
+
     /// Get current cursor position (pixel coordinates)
     pub fn cursor_position(&self) -> (usize, usize) {
         (self.cursor_tracker.x, self.cursor_tracker.y)
@@ -300,17 +383,33 @@ impl RenderBackend for FramebufferBackend {
 
     fn dimensions(&self) -> (u16, u16) {
         let (cols, rows) = self.renderer.dimensions();
-        (cols as u16, rows as u16)
+        (cols as u16, rows as u16 + 2 * self.hidden_rows())
     }
 
     fn check_resize(&mut self) -> io::Result<Option<(u16, u16)>> {
-        // Framebuffer doesn't resize - mode is fixed
-        Ok(None)
+        // Framebuffer doesn't resize - mode is fixed; only hiding the bars changes the size
+        let size = self.dimensions();
+        if size != self.reported_size {
+            self.reported_size = size;
+            self.renderer.invalidate();
+            Ok(Some(size))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn set_bars_hidden(&mut self, hidden: bool) {
+        self.bars_hidden = hidden;
+    }
+
+    fn hidden_rows(&self) -> u16 {
+        u16::from(self.bars_hidden)
     }
 
     fn scale_mouse_coords(&self, col: u16, row: u16) -> (u16, u16) {
         // Scale mouse coordinates from TTY space to framebuffer space
-        let (fb_cols, fb_rows) = self.dimensions();
+        let (fb_cols, fb_rows) = self.renderer.dimensions();
+        let (fb_cols, fb_rows) = (fb_cols as u16, fb_rows as u16);
 
         // If TTY dimensions match framebuffer dimensions, no scaling needed
         if self.tty_cols == fb_cols && self.tty_rows == fb_rows {
@@ -451,6 +550,10 @@ impl RenderBackend for FramebufferBackend {
         self.mouse_input.is_some()
     }
 
+    fn dense_density(&self) -> Option<(u16, u16)> {
+        self.renderer.dense_density()
+    }
+
     fn draw_cursor(&mut self) {
         let (x, y) = self.cursor_position();
         self.renderer.draw_cursor(x, y);
@@ -8,7 +8,7 @@ use crate::input::keyboard_mode::KeyboardMode;
 use crate::lockscreen::auth::is_os_auth_available;
 use crate::ui::ui_render;
 use crate::window::manager::WindowManager;
-use crate::window::number_overlay;
+use crate::window::{border_join, expose, number_overlay};
 use std::io::{self, Write};
 
 /// Renders a complete frame to the screen
@@ -25,9 +25,18 @@ pub fn render_frame(
     has_clipboard_content: bool,
     has_selection: bool,
 ) -> io::Result<bool> {
+    let frame_start = std::time::Instant::now();
+
+    // Windows and dialogs use the theme's border lines when it has its own
+    let themed_charset = charset.with_border_style(theme.border_style);
+    let charset = &themed_charset;
+
     // Get current dimensions from backend
     let (cols, rows) = backend.dimensions();
 
+    // Dense window content is re-added by the windows rendered this frame
+    video_buffer.clear_dense_regions();
+
     // Render the background (every frame for consistency)
     ui_render::render_background(video_buffer, charset, theme);
 
@@ -51,10 +60,22 @@ pub fn render_frame(
         video_buffer,
         charset,
         theme,
-        app_state.tint_terminal,
+        app_state.effective_tint_strength(),
         keyboard_mode_active,
     );
 
+    // Join the borders of touching windows (gapless tiling)
+    if app_config.join_borders {
+        border_join::join_borders(
+            video_buffer,
+            &window_manager.visible_window_rects(),
+            charset,
+        );
+    }
+
+    // Tabs over the title of a maximized window
+    ui_render::render_tab_strip(video_buffer, window_manager, theme);
+
     // Render snap preview overlay (if dragging and snap zone is active)
     window_manager.render_snap_preview(video_buffer, charset, theme);
 
@@ -63,8 +84,33 @@ pub fn render_frame(
         number_overlay::render_window_numbers(video_buffer, window_manager, theme);
     }
 
+    // Minimize/maximize animations (window outlines moving between rectangles)
+    if let Some(duration) = app_config.animation_duration() {
+        let window_buttons = (
+            app_state.window_buttons_start(),
+            app_state.window_buttons_end(cols),
+        );
+        app_state
+            .window_animations
+            .update(window_manager, rows, window_buttons, duration);
+        app_state
+            .window_animations
+            .render(video_buffer, charset, theme);
+    }
+
+    // Render the expose overview on top of the windows (F9)
+    if app_state.show_expose {
+        expose::render_expose(
+            video_buffer,
+            window_manager,
+            charset,
+            theme,
+            app_state.effective_tint_strength(),
+        );
+    }
+
     // Render the pivot for tiled window resizing (only when auto-tiling enabled with gaps and 2-4 windows)
-    if app_state.auto_tiling_enabled {
+    if app_state.auto_tiling_enabled && !app_state.show_expose {
         window_manager.render_pivot(video_buffer, charset, theme, app_config.tiling_gaps);
     }
 
@@ -75,6 +121,13 @@ pub fn render_frame(
         &app_state.auto_tiling_button,
         app_state.auto_tiling_enabled,
         &app_state.keyboard_mode,
+        app_state.focused_mouse_tracking,
+        app_state.key_passthrough,
+        !app_state.mouse_capture_enabled,
+        (
+            app_state.window_buttons_start(),
+            app_state.window_buttons_end(cols),
+        ),
         theme,
     );
 
@@ -106,7 +159,15 @@ pub fn render_frame(
 
     // Render active calendar (if any) on top of everything
     if let Some(ref calendar) = app_state.active_calendar {
-        ui_render::render_calendar(video_buffer, calendar, charset, theme, cols, rows);
+        ui_render::render_calendar(
+            video_buffer,
+            calendar,
+            charset,
+            theme,
+            cols,
+            rows,
+            app_config.calendar_options(),
+        );
     }
 
     // Render active config window (if any) on top of everything
@@ -169,17 +230,43 @@ pub fn render_frame(
         app_state.taskbar_menu.render(video_buffer, charset, theme);
     }
 
+    // Render desktop menu (if visible)
+    if app_state.desktop_menu.visible {
+        app_state.desktop_menu.render(video_buffer, charset, theme);
+    }
+
     // Render System dropdown menu (if visible)
     if app_state.system_menu.visible {
         app_state.system_menu.render(video_buffer, charset, theme);
     }
 
+    // Debug metrics overlay (stats are only gathered while it is shown)
+    if app_state.debug_overlay.visible {
+        let stats = window_manager.window_stats();
+        app_state.debug_overlay.update(&stats);
+        app_state.debug_overlay.render(video_buffer, theme, &stats);
+    }
+
+    // Idle screensaver covers everything except the lockscreen
+    if let Some(ref screensaver) = app_state.screensaver {
+        let title = window_manager
+            .get_focused_window_id()
+            .and_then(|id| window_manager.window_display_title(id))
+            .unwrap_or_else(|| "TERM39".to_string());
+        screensaver.render(video_buffer, theme, &title);
+    }
+
     // Render lockscreen (highest priority - on top of everything)
     // This completely blocks all other UI when active
     if app_state.lockscreen.is_active() {
         app_state.lockscreen.render(video_buffer, charset, theme);
     }
 
+    // Night dim scales the finished frame toward black
+    if app_state.dim_percent > 0 {
+        video_buffer.apply_dim(app_state.dim_percent);
+    }
+
     // Restore old cursor area before presenting new frame
     backend.restore_cursor_area();
 
@@ -187,10 +274,20 @@ pub fn render_frame(
     backend.present(video_buffer)?;
 
     // Update cursor position from mouse input and draw at new position
-    backend.update_cursor();
-    backend.draw_cursor();
+    // A pointer hidden by typing (auto_hide_cursor) comes back once the mouse moves
+    if backend.update_cursor() {
+        app_state.mouse_cursor_hidden = false;
+    }
+    if !app_state.mouse_cursor_hidden {
+        backend.draw_cursor();
+    }
 
     stdout.flush()?;
 
+    // Slow backends (e.g. framebuffer) skip animations they can't render smoothly
+    let frame_time = frame_start.elapsed();
+    app_state.window_animations.record_frame_time(frame_time);
+    app_state.debug_overlay.record_frame_time(frame_time);
+
     Ok(windows_closed)
 }
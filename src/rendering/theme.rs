@@ -1,4 +1,6 @@
+use super::charset::BorderStyle;
 use crossterm::style::Color;
+use serde::Serialize;
 
 // Custom color constants for better readability and maintainability
 // Pure colors
@@ -52,6 +54,33 @@ const LIGHT_GREEN_PHOSPHOR: Color = Color::Rgb {
     b: 51,
 };
 
+// Paper theme colors - ink on an off-white page
+const PAPER_WHITE: Color = Color::Rgb {
+    r: 245,
+    g: 244,
+    b: 238,
+};
+const PAPER_SHADE: Color = Color::Rgb {
+    r: 218,
+    g: 216,
+    b: 208,
+};
+const PAPER_SHADOW: Color = Color::Rgb {
+    r: 160,
+    g: 158,
+    b: 152,
+};
+const PAPER_FADED_INK: Color = Color::Rgb {
+    r: 88,
+    g: 88,
+    b: 88,
+};
+const PAPER_INK: Color = Color::Rgb {
+    r: 24,
+    g: 24,
+    b: 24,
+};
+
 // dBase IV theme colors - authentic Borland dBASE IV 2.0 colors
 const DB_BLUE: Color = Color::Rgb { r: 0, g: 0, b: 170 }; // #0000AA - main/description background
 const DB_GREY: Color = Color::Rgb {
@@ -336,7 +365,7 @@ const DARCULA_CARET_ROW: Color = Color::Rgb {
     b: 50,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Theme {
     // Desktop
     pub desktop_bg: Color,
@@ -366,6 +395,7 @@ pub struct Theme {
     pub window_content_bg: Color,
     pub window_content_fg: Color,
     pub window_shadow_color: Color,
+    pub window_shadow_bg: Color,
 
     // Window controls
     pub button_close_color: Color,
@@ -497,6 +527,9 @@ pub struct Theme {
     // Window number overlay (Alt+1-9 selection)
     pub overlay_number_fg: Color,
     pub overlay_number_bg: Color,
+
+    // Window and dialog border lines (None = the charset's)
+    pub border_style: Option<BorderStyle>,
 }
 
 impl Theme {
@@ -529,6 +562,7 @@ impl Theme {
             window_content_bg: Color::DarkBlue,
             window_content_fg: Color::White,
             window_shadow_color: Color::DarkGrey,
+            window_shadow_bg: Color::Black,
 
             // Window controls
             button_close_color: Color::Red,
@@ -656,6 +690,9 @@ impl Theme {
             // Window number overlay
             overlay_number_fg: Color::White,
             overlay_number_bg: Color::Rgb { r: 0, g: 0, b: 128 },
+
+            // Borders
+            border_style: None,
         }
     }
 
@@ -688,6 +725,7 @@ impl Theme {
             window_content_bg: Color::Black,
             window_content_fg: Color::White,
             window_shadow_color: Color::DarkGrey,
+            window_shadow_bg: Color::Black,
 
             // Window controls
             button_close_color: Color::White,
@@ -815,6 +853,173 @@ impl Theme {
             // Window number overlay
             overlay_number_fg: Color::White,
             overlay_number_bg: Color::DarkGrey,
+
+            // Borders
+            border_style: None,
+        }
+    }
+
+    /// Paper white theme: dark ink on a light page, for bright rooms
+    /// Shadows use a mid grey background so they stay visible on the light desktop
+    pub fn paper_white() -> Self {
+        Self {
+            // Desktop
+            desktop_bg: PAPER_SHADE,
+            desktop_fg: PAPER_FADED_INK,
+
+            // Top bar
+            topbar_bg_focused: PAPER_INK,
+            topbar_bg_unfocused: PAPER_WHITE,
+            topbar_fg_focused: PAPER_WHITE,
+            topbar_fg_unfocused: PAPER_INK,
+            clock_bg: PAPER_WHITE,
+            clock_fg: PAPER_INK,
+
+            // Windows - Title bar
+            window_title_unfocused_fg: PAPER_FADED_INK,
+            window_title_unfocused_bg: PAPER_SHADE,
+            window_title_focused_fg: PAPER_WHITE,
+            window_title_focused_bg: PAPER_INK,
+            // Windows - Border
+            window_border_unfocused_fg: PAPER_FADED_INK,
+            window_border_unfocused_bg: PAPER_SHADE,
+            window_border_focused_fg: PAPER_INK,
+            window_border_focused_bg: PAPER_WHITE,
+            // Windows - Content
+            window_content_bg: PAPER_WHITE,
+            window_content_fg: PAPER_INK,
+            window_shadow_color: PAPER_FADED_INK,
+            window_shadow_bg: PAPER_SHADOW,
+
+            // Window controls
+            button_close_color: PAPER_INK,
+            button_maximize_color: PAPER_INK,
+            button_minimize_color: PAPER_INK,
+            button_bg: PAPER_WHITE,
+            resize_handle_normal_fg: PAPER_FADED_INK,
+            resize_handle_normal_bg: PAPER_WHITE,
+            resize_handle_active_fg: PAPER_WHITE,
+            resize_handle_active_bg: PAPER_INK,
+
+            // UI Buttons
+            button_normal_fg: PAPER_INK,
+            button_normal_bg: PAPER_WHITE,
+            button_hovered_fg: PAPER_WHITE,
+            button_hovered_bg: PAPER_FADED_INK,
+            button_pressed_fg: PAPER_WHITE,
+            button_pressed_bg: PAPER_INK,
+
+            // Bottom bar
+            bottombar_bg: PAPER_WHITE,
+            bottombar_fg: PAPER_INK,
+            bottombar_button_normal_fg: PAPER_INK,
+            bottombar_button_normal_bg: PAPER_SHADE,
+            bottombar_button_focused_fg: PAPER_WHITE,
+            bottombar_button_focused_bg: PAPER_INK,
+            bottombar_button_minimized_fg: PAPER_FADED_INK,
+            bottombar_button_minimized_bg: PAPER_WHITE,
+
+            // Toggle button
+            toggle_enabled_fg: PAPER_WHITE,
+            toggle_enabled_bg_normal: PAPER_INK,
+            toggle_enabled_bg_hovered: PAPER_FADED_INK,
+            toggle_enabled_bg_pressed: PAPER_INK,
+            toggle_disabled_fg: PAPER_FADED_INK,
+            toggle_disabled_bg_normal: PAPER_WHITE,
+            toggle_disabled_bg_hovered: PAPER_SHADE,
+            toggle_disabled_bg_pressed: PAPER_WHITE,
+
+            // Prompts/Dialogs
+            prompt_info_bg: PAPER_WHITE,
+            prompt_info_fg: PAPER_INK,
+            prompt_success_bg: PAPER_SHADE,
+            prompt_success_fg: PAPER_INK,
+            prompt_warning_bg: PAPER_FADED_INK,
+            prompt_warning_fg: PAPER_WHITE,
+            prompt_danger_bg: PAPER_INK,
+            prompt_danger_fg: PAPER_WHITE,
+
+            // Dialog buttons
+            dialog_button_primary_info_fg: PAPER_WHITE,
+            dialog_button_primary_info_bg: PAPER_INK,
+            dialog_button_primary_success_fg: PAPER_WHITE,
+            dialog_button_primary_success_bg: PAPER_INK,
+            dialog_button_primary_warning_fg: PAPER_INK,
+            dialog_button_primary_warning_bg: PAPER_WHITE,
+            dialog_button_primary_danger_fg: PAPER_INK,
+            dialog_button_primary_danger_bg: PAPER_WHITE,
+            dialog_button_secondary_fg: PAPER_INK,
+            dialog_button_secondary_bg: PAPER_SHADE,
+
+            // Config window
+            config_title_bg: PAPER_INK,
+            config_title_fg: PAPER_WHITE,
+            config_border: PAPER_INK,
+            config_content_bg: PAPER_WHITE,
+            config_content_fg: PAPER_INK,
+            config_instructions_fg: PAPER_FADED_INK,
+            config_toggle_on_color: PAPER_INK,
+            config_toggle_off_color: PAPER_FADED_INK,
+
+            // Calendar
+            calendar_bg: PAPER_WHITE,
+            calendar_fg: PAPER_INK,
+            calendar_title_color: PAPER_INK,
+            calendar_today_bg: PAPER_INK,
+            calendar_today_fg: PAPER_WHITE,
+
+            // Scrollbar
+            scrollbar_track_fg: PAPER_SHADOW,
+            scrollbar_thumb_fg: PAPER_INK,
+
+            // Context menu
+            menu_bg: PAPER_WHITE,
+            menu_fg: PAPER_INK,
+            menu_border: PAPER_INK,
+            menu_selected_bg: PAPER_INK,
+            menu_selected_fg: PAPER_WHITE,
+            menu_shadow_fg: PAPER_FADED_INK,
+            menu_disabled_fg: PAPER_FADED_INK,
+
+            // Snap preview
+            snap_preview_border: PAPER_INK,
+            snap_preview_bg: PAPER_SHADE,
+
+            // Splash screen
+            splash_border: PAPER_INK,
+            splash_bg: PAPER_WHITE,
+            splash_fg: PAPER_INK,
+
+            // Slight input popup - light variant
+            slight_bg: PAPER_WHITE,
+            slight_fg: PAPER_INK,
+            slight_border: PAPER_INK,
+            slight_input_bg: PAPER_SHADE,
+            slight_input_fg: PAPER_INK,
+            slight_suggestion_fg: PAPER_FADED_INK,
+            slight_dropdown_bg: PAPER_WHITE,
+            slight_dropdown_fg: PAPER_INK,
+            slight_dropdown_selected_bg: PAPER_INK, // Inverted
+            slight_dropdown_selected_fg: PAPER_WHITE,
+
+            // Keyboard mode (vim-like window control) - inverted ink
+            keyboard_mode_border_fg: PAPER_WHITE,
+            keyboard_mode_border_bg: PAPER_INK,
+            keyboard_mode_title_fg: PAPER_WHITE,
+            keyboard_mode_title_bg: PAPER_INK,
+            mode_indicator_window_fg: PAPER_WHITE,
+            mode_indicator_window_bg: PAPER_INK,
+            mode_indicator_move_fg: PAPER_WHITE,
+            mode_indicator_move_bg: PAPER_FADED_INK,
+            mode_indicator_resize_fg: PAPER_INK,
+            mode_indicator_resize_bg: PAPER_SHADE,
+
+            // Window number overlay
+            overlay_number_fg: PAPER_WHITE,
+            overlay_number_bg: PAPER_INK,
+
+            // Borders
+            border_style: None,
         }
     }
 
@@ -849,6 +1054,7 @@ impl Theme {
             window_content_bg: DRACULA_BACKGROUND,
             window_content_fg: DRACULA_FOREGROUND,
             window_shadow_color: PURE_BLACK,
+            window_shadow_bg: Color::Black,
 
             // Window controls - Dracula semantic colors
             button_close_color: DRACULA_RED,
@@ -976,6 +1182,9 @@ impl Theme {
             // Window number overlay
             overlay_number_fg: DRACULA_FOREGROUND,
             overlay_number_bg: DRACULA_SELECTION,
+
+            // Borders
+            border_style: None,
         }
     }
 
@@ -1010,6 +1219,7 @@ impl Theme {
             window_content_bg: DARCULA_BACKGROUND,
             window_content_fg: DARCULA_FOREGROUND,
             window_shadow_color: PURE_BLACK,
+            window_shadow_bg: Color::Black,
 
             // Window controls - Darcula semantic colors
             button_close_color: Color::Rgb {
@@ -1149,6 +1359,9 @@ impl Theme {
             // Window number overlay
             overlay_number_fg: DARCULA_FOREGROUND,
             overlay_number_bg: DARCULA_UI_BACKGROUND,
+
+            // Borders
+            border_style: None,
         }
     }
 
@@ -1182,6 +1395,7 @@ impl Theme {
             window_content_bg: Color::Black,
             window_content_fg: Color::Green,
             window_shadow_color: Color::DarkGreen,
+            window_shadow_bg: Color::Black,
 
             // Window controls - vary brightness for semantic distinction
             button_close_color: Color::Green, // Bright green for close (primary action)
@@ -1309,6 +1523,9 @@ impl Theme {
             // Window number overlay
             overlay_number_fg: Color::Green,
             overlay_number_bg: Color::Black,
+
+            // Borders
+            border_style: None,
         }
     }
 
@@ -1342,6 +1559,7 @@ impl Theme {
             window_content_bg: Color::Black,
             window_content_fg: Color::Yellow,
             window_shadow_color: Color::DarkYellow,
+            window_shadow_bg: Color::Black,
 
             // Window controls - vary brightness for semantic distinction
             button_close_color: Color::Yellow, // Bright amber for close (primary action)
@@ -1469,6 +1687,9 @@ impl Theme {
             // Window number overlay
             overlay_number_fg: Color::Yellow,
             overlay_number_bg: Color::Black,
+
+            // Borders
+            border_style: None,
         }
     }
 
@@ -1502,6 +1723,7 @@ impl Theme {
             window_content_bg: NDD_LIGHT_PURPLE,
             window_content_fg: Color::White,
             window_shadow_color: Color::Black,
+            window_shadow_bg: Color::Black,
 
             // Window controls
             button_close_color: Color::White,
@@ -1629,6 +1851,9 @@ impl Theme {
             // Window number overlay
             overlay_number_fg: Color::White,
             overlay_number_bg: NDD_DARK_GRAY,
+
+            // Borders
+            border_style: None,
         }
     }
 
@@ -1662,6 +1887,7 @@ impl Theme {
             window_content_bg: QBASIC_ROYAL_BLUE,
             window_content_fg: Color::White,
             window_shadow_color: PURE_BLACK,
+            window_shadow_bg: Color::Black,
 
             // Window controls
             button_close_color: Color::Red,
@@ -1789,6 +2015,9 @@ impl Theme {
             // Window number overlay
             overlay_number_fg: Color::White,
             overlay_number_bg: QBASIC_ROYAL_BLUE,
+
+            // Borders
+            border_style: Some(BorderStyle::Single),
         }
     }
 
@@ -1822,6 +2051,7 @@ impl Theme {
             window_content_bg: TURBO_DARK_BLUE,
             window_content_fg: Color::White,
             window_shadow_color: PURE_BLACK,
+            window_shadow_bg: Color::Black,
 
             // Window controls
             button_close_color: Color::Red,
@@ -1949,6 +2179,9 @@ impl Theme {
             // Window number overlay
             overlay_number_fg: Color::White,
             overlay_number_bg: TURBO_DARK_BLUE,
+
+            // Borders
+            border_style: Some(BorderStyle::Double),
         }
     }
 
@@ -1982,6 +2215,7 @@ impl Theme {
             window_content_bg: NC_BLUE,
             window_content_fg: NC_CYAN,
             window_shadow_color: PURE_BLACK,
+            window_shadow_bg: Color::Black,
 
             // Window controls
             button_close_color: NC_ORANGE_RED,
@@ -2109,6 +2343,9 @@ impl Theme {
             // Window number overlay
             overlay_number_fg: NC_CYAN,
             overlay_number_bg: NC_BLUE,
+
+            // Borders
+            border_style: Some(BorderStyle::Double),
         }
     }
 
@@ -2142,6 +2379,7 @@ impl Theme {
             window_content_bg: XT_DARK_BLUE,
             window_content_fg: XT_CYAN,
             window_shadow_color: PURE_BLACK,
+            window_shadow_bg: Color::Black,
 
             // Window controls
             button_close_color: XT_ORANGE,
@@ -2269,6 +2507,9 @@ impl Theme {
             // Window number overlay
             overlay_number_fg: XT_CYAN,
             overlay_number_bg: XT_DARK_BLUE,
+
+            // Borders
+            border_style: None,
         }
     }
 
@@ -2302,6 +2543,7 @@ impl Theme {
             window_content_bg: WP_BLUE,
             window_content_fg: Color::White,
             window_shadow_color: PURE_BLACK,
+            window_shadow_bg: Color::Black,
 
             // Window controls
             button_close_color: WP_RED,
@@ -2429,6 +2671,9 @@ impl Theme {
             // Window number overlay
             overlay_number_fg: Color::White,
             overlay_number_bg: WP_BLUE,
+
+            // Borders
+            border_style: Some(BorderStyle::Single),
         }
     }
 
@@ -2462,6 +2707,7 @@ impl Theme {
             window_content_bg: DB_LIGHT_GREY,
             window_content_fg: Color::White,
             window_shadow_color: DB_BLUE,
+            window_shadow_bg: Color::Black,
 
             // Window controls
             button_close_color: DB_BRIGHT_RED,
@@ -2589,6 +2835,9 @@ impl Theme {
             // Window number overlay
             overlay_number_fg: Color::White,
             overlay_number_bg: DB_BLUE,
+
+            // Borders
+            border_style: None,
         }
     }
 
@@ -2621,6 +2870,7 @@ impl Theme {
             window_content_bg: Color::Black,
             window_content_fg: Color::White,
             window_shadow_color: Color::DarkGrey,
+            window_shadow_bg: Color::Black,
 
             // Window controls
             button_close_color: Color::Red,
@@ -2748,14 +2998,26 @@ impl Theme {
             // Window number overlay
             overlay_number_fg: Color::White,
             overlay_number_bg: Color::Blue,
+
+            // Borders
+            border_style: None,
         }
     }
 
     /// Create a theme from a name string, falling back to Classic if invalid
     pub fn from_name(name: &str) -> Self {
-        match name.to_lowercase().as_str() {
+        Self::try_from_name(name).unwrap_or_else(|| {
+            eprintln!("Unknown theme '{}', falling back to 'classic'", name);
+            Self::classic()
+        })
+    }
+
+    /// Create a theme from a name string, None if no theme has that name
+    pub fn try_from_name(name: &str) -> Option<Self> {
+        let theme = match name.to_lowercase().as_str() {
             "classic" => Self::classic(),
-            "monochrome" => Self::monochrome(),
+            "monochrome" | "paper_black" | "paperblack" => Self::monochrome(),
+            "paper" | "paper_white" | "paperwhite" => Self::paper_white(),
             "dark" => Self::dark(),
             "dracu" | "darcula" | "intellij" => Self::dracu(),
             "green" | "green_phosphor" | "greenphosphor" => Self::green_phosphor(),
@@ -2768,10 +3030,102 @@ impl Theme {
             "wordperfect" | "wp" | "wp51" => Self::wordperfect(),
             "dbase" | "dbase4" | "dbaseiv" => Self::dbase(),
             "system" => Self::system(),
-            _ => {
-                eprintln!("Unknown theme '{}', falling back to 'classic'", name);
-                Self::classic()
-            }
+            _ => return None,
+        };
+        Some(theme)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rendering::calculate_contrast_ratio;
+
+    #[test]
+    fn test_retro_themes_pick_their_border_style() {
+        assert_eq!(
+            Theme::from_name("turbo").border_style,
+            Some(BorderStyle::Double)
+        );
+        assert_eq!(
+            Theme::from_name("qbasic").border_style,
+            Some(BorderStyle::Single)
+        );
+        assert_eq!(Theme::from_name("classic").border_style, None);
+    }
+
+    #[test]
+    fn test_paper_white_text_pairs_are_legible() {
+        let theme = Theme::paper_white();
+        let pairs = [
+            (theme.window_content_fg, theme.window_content_bg),
+            (theme.window_title_focused_fg, theme.window_title_focused_bg),
+            (
+                theme.window_title_unfocused_fg,
+                theme.window_title_unfocused_bg,
+            ),
+            (
+                theme.window_border_focused_fg,
+                theme.window_border_focused_bg,
+            ),
+            (
+                theme.window_border_unfocused_fg,
+                theme.window_border_unfocused_bg,
+            ),
+            (theme.topbar_fg_focused, theme.topbar_bg_focused),
+            (
+                theme.bottombar_button_normal_fg,
+                theme.bottombar_button_normal_bg,
+            ),
+            (
+                theme.bottombar_button_focused_fg,
+                theme.bottombar_button_focused_bg,
+            ),
+            (
+                theme.bottombar_button_minimized_fg,
+                theme.bottombar_button_minimized_bg,
+            ),
+            (theme.menu_fg, theme.menu_bg),
+            (theme.menu_selected_fg, theme.menu_selected_bg),
+            (theme.menu_disabled_fg, theme.menu_bg),
+            (theme.prompt_info_fg, theme.prompt_info_bg),
+            (theme.prompt_warning_fg, theme.prompt_warning_bg),
+            (theme.config_instructions_fg, theme.config_content_bg),
+            (theme.slight_suggestion_fg, theme.slight_bg),
+            (theme.slight_input_fg, theme.slight_input_bg),
+            (theme.desktop_fg, theme.desktop_bg),
+        ];
+        for (fg, bg) in pairs {
+            let ratio = calculate_contrast_ratio(&fg, &bg);
+            assert!(
+                ratio >= 4.5,
+                "{:?} on {:?} has contrast {:.2}",
+                fg,
+                bg,
+                ratio
+            );
         }
+
+        // Shadowed text stays readable and the shadow stands out from the desktop
+        assert!(
+            calculate_contrast_ratio(&theme.window_shadow_color, &theme.window_shadow_bg) >= 2.0
+        );
+        assert!(calculate_contrast_ratio(&theme.window_shadow_bg, &theme.desktop_bg) >= 1.5);
+    }
+
+    #[test]
+    fn test_paper_theme_names() {
+        assert_eq!(
+            Theme::from_name("paper").window_content_bg,
+            Theme::paper_white().window_content_bg
+        );
+        assert_eq!(
+            Theme::from_name("PaperWhite").window_content_bg,
+            Theme::paper_white().window_content_bg
+        );
+        assert_eq!(
+            Theme::from_name("paper_black").window_content_bg,
+            Theme::monochrome().window_content_bg
+        );
     }
 }
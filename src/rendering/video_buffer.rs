@@ -61,6 +61,39 @@ impl Default for Cell {
     }
 }
 
+/// Character marking cells whose pixels come from a dense region (private use area)
+/// Backends without dense rendering draw it as a blank cell
+pub const DENSE_PLACEHOLDER: char = '\u{F8F0}';
+
+/// Window content rendered at a higher density than the global cell grid
+/// Used by the framebuffer backend for per-window font overrides: each screen cell
+/// of the region holds `density.0` x `density.1` cells of `cells`
+#[derive(Clone, Debug, PartialEq)]
+pub struct DenseRegion {
+    /// Screen cell rectangle (x, y, width, height) covered by the region
+    pub rect: (u16, u16, u16, u16),
+    /// Dense cells per screen cell (columns, rows)
+    pub density: (u16, u16),
+    /// Row-major cells, `rect.2 * density.0` wide and `rect.3 * density.1` tall
+    pub cells: Vec<Cell>,
+}
+
+#[cfg_attr(
+    not(all(target_os = "linux", feature = "framebuffer-backend")),
+    allow(dead_code)
+)]
+impl DenseRegion {
+    /// Width of the dense grid in cells
+    pub fn grid_width(&self) -> usize {
+        self.rect.2 as usize * self.density.0 as usize
+    }
+
+    /// Get the dense cell at (col, row)
+    pub fn get(&self, col: usize, row: usize) -> Option<&Cell> {
+        self.cells.get(row * self.grid_width() + col)
+    }
+}
+
 /// Double-buffered video memory for efficient rendering
 pub struct VideoBuffer {
     width: u16,
@@ -73,6 +106,11 @@ pub struct VideoBuffer {
     /// Track dirty rows for optimized rendering
     /// Only rows marked dirty need to be processed during present()
     dirty_rows: Vec<bool>,
+    /// Dense window content for this frame (drawn over DENSE_PLACEHOLDER cells)
+    dense_regions: Vec<DenseRegion>,
+    /// Rows hidden at the top and at the bottom when presenting (bars hidden)
+    /// Screen row r shows buffer row r + hidden_rows
+    hidden_rows: u16,
 }
 
 impl VideoBuffer {
@@ -89,6 +127,8 @@ impl VideoBuffer {
             tty_cursor: None,
             // All rows dirty initially to ensure first frame renders completely
             dirty_rows: vec![true; height as usize],
+            dense_regions: Vec::new(),
+            hidden_rows: 0,
         }
     }
 
@@ -150,6 +190,40 @@ impl VideoBuffer {
         (self.width, self.height)
     }
 
+    /// Add dense window content for this frame
+    /// The covered cells should be filled with DENSE_PLACEHOLDER cells
+    pub fn add_dense_region(&mut self, region: DenseRegion) {
+        self.dense_regions.push(region);
+    }
+
+    /// Remove all dense regions (called at the start of every frame)
+    pub fn clear_dense_regions(&mut self) {
+        self.dense_regions.clear();
+    }
+
+    /// Dense regions added this frame, in render order
+    #[cfg_attr(
+        not(all(target_os = "linux", feature = "framebuffer-backend")),
+        allow(dead_code)
+    )]
+    pub fn dense_regions(&self) -> &[DenseRegion] {
+        &self.dense_regions
+    }
+
+    /// Set how many rows at the top and at the bottom are hidden when presenting
+    pub fn set_hidden_rows(&mut self, rows: u16) {
+        self.hidden_rows = rows;
+    }
+
+    /// Rows hidden at the top and at the bottom when presenting
+    #[cfg_attr(
+        not(all(target_os = "linux", feature = "framebuffer-backend")),
+        allow(dead_code)
+    )]
+    pub fn hidden_rows(&self) -> u16 {
+        self.hidden_rows
+    }
+
     /// Set TTY cursor position for raw mouse input mode
     /// The cell at this position will be rendered with inverted colors
     pub fn set_tty_cursor(&mut self, col: u16, row: u16) {
@@ -179,11 +253,25 @@ impl VideoBuffer {
         self.mark_all_dirty();
     }
 
+    /// Scale every color of the frame `percent` toward black (night dim), dense window
+    /// content included
+    pub fn apply_dim(&mut self, percent: u8) {
+        let dense_cells = self
+            .dense_regions
+            .iter_mut()
+            .flat_map(|r| r.cells.iter_mut());
+        for cell in self.back_buffer.iter_mut().chain(dense_cells) {
+            cell.fg_color = color_utils::dim_color(cell.fg_color, percent);
+            cell.bg_color = color_utils::dim_color(cell.bg_color, percent);
+        }
+        self.mark_all_dirty();
+    }
+
     /// Present back buffer to screen, only updating changed cells
     /// Uses queued commands for batched I/O - significantly reduces syscalls
     /// Optimized with run-length encoding for consecutive cells
     /// Skips rows that haven't been marked dirty for additional performance
-    pub fn present(&mut self, stdout: &mut io::Stdout) -> io::Result<()> {
+    pub fn present(&mut self, stdout: &mut impl Write) -> io::Result<()> {
         // Hide cursor at the START of rendering to prevent any cursor flicker
         // This ensures the cursor stays hidden even if PTY output or other
         // operations between frames affected cursor state
@@ -203,7 +291,9 @@ impl VideoBuffer {
         // Extract cursor position once to avoid is_some_and() call per cell
         let cursor_pos = self.tty_cursor;
 
-        for y in 0..self.height {
+        // Rows hidden above and below the screen (bars hidden) are never drawn
+        let hidden_rows = self.hidden_rows;
+        for y in hidden_rows..self.height.saturating_sub(hidden_rows) {
             // Skip rows that haven't changed (dirty row optimization)
             if (y as usize) < self.dirty_rows.len() && !self.dirty_rows[y as usize] {
                 continue;
@@ -233,6 +323,12 @@ impl VideoBuffer {
 
                 // Only update if cell changed (compare with inverted if cursor)
                 if front_cell != &display_cell {
+                    let character = if display_cell.character == DENSE_PLACEHOLDER {
+                        ' '
+                    } else {
+                        display_cell.character
+                    };
+
                     // Check if we can extend the current run
                     // Cell must be immediately adjacent (same row, next column) with same colors
                     let can_extend = in_run
@@ -243,12 +339,12 @@ impl VideoBuffer {
 
                     if can_extend {
                         // Extend the current run
-                        run_buffer.push(display_cell.character);
+                        run_buffer.push(character);
                         run_char_count += 1;
                     } else {
                         // Flush previous run if any
                         if in_run && !run_buffer.is_empty() {
-                            stdout.queue(cursor::MoveTo(run_start_x, run_y))?;
+                            stdout.queue(cursor::MoveTo(run_start_x, run_y - hidden_rows))?;
                             stdout.write_all(run_buffer.as_bytes())?;
                             run_buffer.clear();
                         }
@@ -266,7 +362,7 @@ impl VideoBuffer {
                         // Start new run
                         run_start_x = x;
                         run_y = y;
-                        run_buffer.push(display_cell.character);
+                        run_buffer.push(character);
                         run_char_count = 1;
                         in_run = true;
                     }
@@ -275,7 +371,7 @@ impl VideoBuffer {
 
             // Flush run at end of each row (can't span rows)
             if in_run && !run_buffer.is_empty() {
-                stdout.queue(cursor::MoveTo(run_start_x, run_y))?;
+                stdout.queue(cursor::MoveTo(run_start_x, run_y - hidden_rows))?;
                 stdout.write_all(run_buffer.as_bytes())?;
                 run_buffer.clear();
                 run_char_count = 0;
@@ -353,7 +449,7 @@ impl VideoBuffer {
 /// Render a shadow for a rectangular region
 /// Draws a 2-cell shadow on the right side and 1-cell shadow on the bottom of the given region
 /// Instead of drawing with a shadow character, this preserves the existing character
-/// and modifies its colors to create a "shadowed" effect (black bg, dark grey fg)
+/// and modifies its colors to create a "shadowed" effect (the theme's shadow colors)
 pub fn render_shadow(
     buffer: &mut VideoBuffer,
     x: u16,
@@ -364,7 +460,7 @@ pub fn render_shadow(
     theme: &Theme,
 ) {
     let shadow_fg = theme.window_shadow_color;
-    let shadow_bg = Color::Black;
+    let shadow_bg = theme.window_shadow_bg;
     let (buffer_width, buffer_height) = buffer.dimensions();
 
     // Pre-compute shadow boundaries
@@ -417,11 +513,35 @@ pub fn render_shadow(
 
 /// Render a full-screen shadow overlay (for modal dialogs)
 /// This shadows the entire screen to indicate that only the modal dialog is interactive
-/// Preserves existing characters and only modifies colors (black bg, dark grey fg)
+/// Preserves existing characters and only modifies colors (the theme's shadow colors)
 pub fn render_fullscreen_shadow(buffer: &mut VideoBuffer, theme: &Theme) {
     let shadow_fg = theme.window_shadow_color;
-    let shadow_bg = Color::Black;
+    let shadow_bg = theme.window_shadow_bg;
 
     // Use the optimized method that directly modifies the buffer
     buffer.apply_fullscreen_shadow(shadow_fg, shadow_bg);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_present_skips_hidden_bar_rows() {
+        let mut buffer = VideoBuffer::new(4, 4);
+        for (y, ch) in ['T', 'A', 'B', 'Z'].into_iter().enumerate() {
+            for x in 0..4 {
+                buffer.set(x, y as u16, Cell::new(ch, Color::White, Color::Black));
+            }
+        }
+        buffer.set_hidden_rows(1);
+        let mut out = Vec::new();
+        buffer.present(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        // Buffer rows 1-2 are drawn on screen rows 0-1; the bar rows never are
+        assert!(out.contains("\x1b[1;1HAAAA"));
+        assert!(out.contains("\x1b[2;1HBBBB"));
+        assert!(!out.contains('T') && !out.contains('Z'));
+    }
+}
@@ -1,6 +1,46 @@
-use super::term_grid::{CharacterSet, Color, CursorShape, NamedColor, TerminalGrid};
+use super::term_grid::{CharacterSet, Color, NamedColor, TerminalGrid};
 use vte::{Params, Perform};
 
+/// Most parameters a CSI sequence carries (vte stops collecting at 32)
+const MAX_CSI_PARAMS: usize = 32;
+
+/// First value of each CSI parameter, collected on the stack once per sequence
+struct CsiParams {
+    values: [u16; MAX_CSI_PARAMS],
+    len: usize,
+}
+
+impl CsiParams {
+    fn new(params: &Params) -> Self {
+        let mut values = [0; MAX_CSI_PARAMS];
+        for (slot, param) in values.iter_mut().zip(params.iter()) {
+            *slot = param.first().copied().unwrap_or(0);
+        }
+        Self {
+            values,
+            len: params.len().min(MAX_CSI_PARAMS),
+        }
+    }
+
+    fn as_slice(&self) -> &[u16] {
+        &self.values[..self.len]
+    }
+
+    /// Parameter `index`, 0 when omitted
+    fn get(&self, index: usize) -> u16 {
+        self.as_slice().get(index).copied().unwrap_or(0)
+    }
+
+    /// Parameter `index` with a default
+    /// According to ECMA-48, if a parameter is 0 or omitted, use the default
+    fn or(&self, index: usize, default: u16) -> u16 {
+        match self.get(index) {
+            0 => default,
+            value => value,
+        }
+    }
+}
+
 /// ANSI escape sequence handler that implements the VTE Perform trait
 pub struct AnsiHandler<'a> {
     pub grid: &'a mut TerminalGrid,
@@ -11,18 +51,8 @@ impl<'a> AnsiHandler<'a> {
         Self { grid }
     }
 
-    /// Parse a CSI parameter with default value
-    /// According to ECMA-48, if a parameter is 0 or omitted, use the default
-    fn parse_param_with_default(param: Option<&[u16]>, default: u16) -> u16 {
-        param
-            .and_then(|p| p.first())
-            .copied()
-            .map(|v| if v == 0 { default } else { v })
-            .unwrap_or(default)
-    }
-
     /// Parse SGR (Select Graphic Rendition) parameters
-    fn handle_sgr(&mut self, params: &Params) {
+    fn handle_sgr(&mut self, params: &[u16]) {
         if params.is_empty() {
             // Reset all attributes (same as SGR 0)
             self.grid.current_attrs = Default::default();
@@ -32,8 +62,8 @@ impl<'a> AnsiHandler<'a> {
         }
 
         let mut iter = params.iter();
-        while let Some(param) = iter.next() {
-            match param[0] {
+        while let Some(&param) = iter.next() {
+            match param {
                 0 => {
                     // Reset
                     self.grid.current_attrs = Default::default();
@@ -71,20 +101,19 @@ impl<'a> AnsiHandler<'a> {
                 38 => {
                     // Extended foreground color
                     if let Some(next_param) = iter.next() {
-                        match next_param[0] {
+                        match next_param {
                             2 => {
                                 // RGB color
                                 if let (Some(r), Some(g), Some(b)) =
                                     (iter.next(), iter.next(), iter.next())
                                 {
-                                    self.grid.current_fg =
-                                        Color::Rgb(r[0] as u8, g[0] as u8, b[0] as u8);
+                                    self.grid.current_fg = Color::Rgb(*r as u8, *g as u8, *b as u8);
                                 }
                             }
                             5 => {
                                 // 256-color palette
                                 if let Some(idx) = iter.next() {
-                                    self.grid.current_fg = Color::Indexed(idx[0] as u8);
+                                    self.grid.current_fg = Color::Indexed(*idx as u8);
                                 }
                             }
                             _ => {}
@@ -104,20 +133,19 @@ impl<'a> AnsiHandler<'a> {
                 48 => {
                     // Extended background color
                     if let Some(next_param) = iter.next() {
-                        match next_param[0] {
+                        match next_param {
                             2 => {
                                 // RGB color
                                 if let (Some(r), Some(g), Some(b)) =
                                     (iter.next(), iter.next(), iter.next())
                                 {
-                                    self.grid.current_bg =
-                                        Color::Rgb(r[0] as u8, g[0] as u8, b[0] as u8);
+                                    self.grid.current_bg = Color::Rgb(*r as u8, *g as u8, *b as u8);
                                 }
                             }
                             5 => {
                                 // 256-color palette
                                 if let Some(idx) = iter.next() {
-                                    self.grid.current_bg = Color::Indexed(idx[0] as u8);
+                                    self.grid.current_bg = Color::Indexed(*idx as u8);
                                 }
                             }
                             _ => {}
@@ -149,6 +177,64 @@ impl<'a> AnsiHandler<'a> {
     }
 }
 
+/// Parse an X11 color spec as used by OSC 10-12: "#rgb", "#rrggbb" or "rgb:r/g/b" with 1-4
+/// hex digits per channel (only the high byte of each channel is kept)
+pub fn parse_color_spec(spec: &[u8]) -> Option<(u8, u8, u8)> {
+    let spec = std::str::from_utf8(spec).ok()?.trim();
+    // Scale a channel of `digits` hex digits to 8 bits
+    let channel = |hex: &str| -> Option<u8> {
+        if hex.is_empty() || hex.len() > 4 {
+            return None;
+        }
+        let value = u32::from_str_radix(hex, 16).ok()?;
+        let max = (1u32 << (4 * hex.len())) - 1;
+        Some((value * 255 / max) as u8)
+    };
+    if let Some(rest) = spec.strip_prefix("rgb:") {
+        let mut parts = rest.split('/');
+        let rgb = (
+            channel(parts.next()?)?,
+            channel(parts.next()?)?,
+            channel(parts.next()?)?,
+        );
+        return parts.next().is_none().then_some(rgb);
+    }
+    let hex = spec.strip_prefix('#')?;
+    if !hex.is_ascii() || (hex.len() != 3 && hex.len() != 6) {
+        return None;
+    }
+    let n = hex.len() / 3;
+    Some((
+        channel(&hex[..n])?,
+        channel(&hex[n..2 * n])?,
+        channel(&hex[2 * n..])?,
+    ))
+}
+
+/// Extract the path from an OSC 7 "file://host/path" URI (percent-encoded bytes decoded)
+fn parse_osc7_path(uri: &[u8]) -> Option<String> {
+    let rest = uri.strip_prefix(b"file://")?;
+    // Skip the host part; the path starts at the first '/'
+    let path = &rest[rest.iter().position(|&b| b == b'/')?..];
+
+    let mut decoded = Vec::with_capacity(path.len());
+    let mut i = 0;
+    while i < path.len() {
+        let hex = |b: u8| (b as char).to_digit(16);
+        match (path[i], path.get(i + 1), path.get(i + 2)) {
+            (b'%', Some(&hi), Some(&lo)) if hex(hi).is_some() && hex(lo).is_some() => {
+                decoded.push((hex(hi)? * 16 + hex(lo)?) as u8);
+                i += 3;
+            }
+            (b, ..) => {
+                decoded.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
 impl Perform for AnsiHandler<'_> {
     fn print(&mut self, c: char) {
         self.grid.put_char(c);
@@ -160,7 +246,8 @@ impl Perform for AnsiHandler<'_> {
             b'\r' => self.grid.put_char('\r'),
             b'\t' => self.grid.put_char('\t'),
             b'\x08' => self.grid.put_char('\x08'), // Backspace
-            b'\x07' => {}                          // Bell (ignore for now)
+            b'\x05' => self.grid.queue_answerback(), // ENQ - send answerback string
+            b'\x07' => self.grid.ring_bell(),      // Bell
             b'\x0b' => self.grid.put_char('\n'),   // Vertical Tab - treat as linefeed
             b'\x0c' => {
                 // Form feed (Ctrl+L) - clear screen and move cursor to home
@@ -185,9 +272,26 @@ impl Perform for AnsiHandler<'_> {
         // End of DCS sequence
     }
 
-    fn osc_dispatch(&mut self, _params: &[&[u8]], _bell_terminated: bool) {
+    fn osc_dispatch(&mut self, params: &[&[u8]], _bell_terminated: bool) {
         // OSC (Operating System Command) sequences
         // Could be used for window title, clipboard, etc.
+        match params {
+            // OSC 7 ; file://host/path - the shell reports its current directory
+            [b"7", uri, ..] => {
+                if let Some(path) = parse_osc7_path(uri) {
+                    self.grid.set_working_directory(path);
+                }
+            }
+            // OSC 12 ; color - set the cursor color (queries with "?" are not answered)
+            [b"12", spec, ..] => {
+                if let Some(color) = parse_color_spec(spec) {
+                    self.grid.set_cursor_color(Some(color));
+                }
+            }
+            // OSC 112 - reset the cursor color
+            [b"112", ..] => self.grid.set_cursor_color(None),
+            _ => {}
+        }
     }
 
     fn csi_dispatch(&mut self, params: &Params, intermediates: &[u8], ignore: bool, c: char) {
@@ -195,30 +299,36 @@ impl Perform for AnsiHandler<'_> {
             return;
         }
 
+        let params = CsiParams::new(params);
+
         // CSI (Control Sequence Introducer) sequences
         match (c, intermediates) {
             // Cursor movement
             // Per ECMA-48, parameter 0 is treated as 1 for movement commands
             ('A', []) => {
                 // Cursor Up
-                let n = Self::parse_param_with_default(params.iter().next(), 1) as usize;
+                let n = params.or(0, 1) as usize;
                 self.grid.move_cursor(0, -(n as isize));
             }
             ('B', []) => {
                 // Cursor Down
-                let n = Self::parse_param_with_default(params.iter().next(), 1) as usize;
+                let n = params.or(0, 1) as usize;
                 self.grid.move_cursor(0, n as isize);
             }
             ('C', []) => {
                 // Cursor Forward
-                let n = Self::parse_param_with_default(params.iter().next(), 1) as usize;
+                let n = params.or(0, 1) as usize;
                 self.grid.move_cursor(n as isize, 0);
             }
             ('D', []) => {
                 // Cursor Back
-                let n = Self::parse_param_with_default(params.iter().next(), 1) as usize;
+                let n = params.or(0, 1) as usize;
                 self.grid.move_cursor(-(n as isize), 0);
             }
+            ('p', [b'!']) => {
+                // Soft Terminal Reset (DECSTR)
+                self.grid.soft_reset();
+            }
             ('c', []) => {
                 // Primary Device Attributes (DA1)
                 // Respond as VT220 with no options
@@ -233,19 +343,19 @@ impl Perform for AnsiHandler<'_> {
             }
             ('E', []) => {
                 // Cursor Next Line
-                let n = Self::parse_param_with_default(params.iter().next(), 1) as usize;
+                let n = params.or(0, 1) as usize;
                 self.grid.move_cursor(0, n as isize);
                 self.grid.cursor.x = 0;
             }
             ('F', []) => {
                 // Cursor Previous Line
-                let n = Self::parse_param_with_default(params.iter().next(), 1) as usize;
+                let n = params.or(0, 1) as usize;
                 self.grid.move_cursor(0, -(n as isize));
                 self.grid.cursor.x = 0;
             }
             ('G', []) => {
                 // Cursor Horizontal Absolute
-                let col = Self::parse_param_with_default(params.iter().next(), 1) as usize;
+                let col = params.or(0, 1) as usize;
                 self.grid.cursor.x = col
                     .saturating_sub(1)
                     .min(self.grid.cols().saturating_sub(1));
@@ -254,35 +364,25 @@ impl Perform for AnsiHandler<'_> {
                 // Cursor Position
                 // When origin mode (DECOM) is set, position is relative to scroll region
                 // Per ECMA-48, parameter 0 is treated as 1
-                let mut iter = params.iter();
-                let row = Self::parse_param_with_default(iter.next(), 1) as usize;
-                let col = Self::parse_param_with_default(iter.next(), 1) as usize;
+                let row = params.or(0, 1) as usize;
+                let col = params.or(1, 1) as usize;
                 self.grid
                     .goto_origin_aware(col.saturating_sub(1), row.saturating_sub(1));
             }
             ('J', []) => {
                 // Erase in Display
-                let mode = params
-                    .iter()
-                    .next()
-                    .and_then(|p| p.first())
-                    .copied()
-                    .unwrap_or(0);
+                let mode = params.get(0);
                 match mode {
                     0 => self.grid.erase_to_eos(),     // Erase below
                     1 => self.grid.erase_from_bos(),   // Erase above
-                    2 | 3 => self.grid.clear_screen(), // Erase all
+                    2 => self.grid.clear_screen(),     // Erase all
+                    3 => self.grid.clear_scrollback(), // Erase saved lines (scrollback)
                     _ => {}
                 }
             }
             ('K', []) => {
                 // Erase in Line
-                let mode = params
-                    .iter()
-                    .next()
-                    .and_then(|p| p.first())
-                    .copied()
-                    .unwrap_or(0);
+                let mode = params.get(0);
                 match mode {
                     0 => self.grid.erase_to_eol(), // Erase to right
                     1 => self.grid.erase_to_bol(), // Erase to left
@@ -292,43 +392,43 @@ impl Perform for AnsiHandler<'_> {
             }
             ('P', []) => {
                 // Delete Characters (DCH)
-                let n = Self::parse_param_with_default(params.iter().next(), 1) as usize;
+                let n = params.or(0, 1) as usize;
                 self.grid.delete_chars(n);
             }
             ('@', []) => {
                 // Insert Characters (ICH)
-                let n = Self::parse_param_with_default(params.iter().next(), 1) as usize;
+                let n = params.or(0, 1) as usize;
                 self.grid.insert_chars(n);
             }
             ('X', []) => {
                 // Erase Characters (ECH)
-                let n = Self::parse_param_with_default(params.iter().next(), 1) as usize;
+                let n = params.or(0, 1) as usize;
                 self.grid.erase_chars(n);
             }
             ('L', []) => {
                 // Insert Lines
-                let n = Self::parse_param_with_default(params.iter().next(), 1) as usize;
+                let n = params.or(0, 1) as usize;
                 self.grid.scroll_down(n);
             }
             ('M', []) => {
                 // Delete Lines
-                let n = Self::parse_param_with_default(params.iter().next(), 1) as usize;
+                let n = params.or(0, 1) as usize;
                 self.grid.scroll_up(n);
             }
             ('S', []) => {
                 // Scroll Up
-                let n = Self::parse_param_with_default(params.iter().next(), 1) as usize;
+                let n = params.or(0, 1) as usize;
                 self.grid.scroll_up(n);
             }
             ('T', []) => {
                 // Scroll Down
-                let n = Self::parse_param_with_default(params.iter().next(), 1) as usize;
+                let n = params.or(0, 1) as usize;
                 self.grid.scroll_down(n);
             }
             ('d', []) => {
                 // Vertical Position Absolute (VPA)
                 // When origin mode (DECOM) is set, position is relative to scroll region
-                let row = Self::parse_param_with_default(params.iter().next(), 1) as usize;
+                let row = params.or(0, 1) as usize;
                 let y = row.saturating_sub(1);
                 if self.grid.origin_mode {
                     // In origin mode, position is relative to scroll region
@@ -341,8 +441,8 @@ impl Perform for AnsiHandler<'_> {
             }
             ('h', []) => {
                 // Standard Mode Set (SM)
-                for param in params.iter() {
-                    match param[0] {
+                for &param in params.as_slice() {
+                    match param {
                         4 => self.grid.insert_mode = true, // IRM - Insert/Replace Mode
                         20 => self.grid.lnm_mode = true,   // LNM - Line Feed/New Line Mode
                         _ => {}
@@ -351,8 +451,8 @@ impl Perform for AnsiHandler<'_> {
             }
             ('h', [b'?']) => {
                 // DEC Private Mode Set
-                for param in params.iter() {
-                    match param[0] {
+                for &param in params.as_slice() {
+                    match param {
                         1 => self.grid.application_cursor_keys = true, // DECCKM
                         6 => self.grid.set_origin_mode(true),          // DECOM
                         7 => self.grid.auto_wrap_mode = true,          // DECAWM
@@ -376,8 +476,8 @@ impl Perform for AnsiHandler<'_> {
             }
             ('l', []) => {
                 // Standard Mode Reset (RM)
-                for param in params.iter() {
-                    match param[0] {
+                for &param in params.as_slice() {
+                    match param {
                         4 => self.grid.insert_mode = false, // IRM - Insert/Replace Mode
                         20 => self.grid.lnm_mode = false,   // LNM - Line Feed/New Line Mode
                         _ => {}
@@ -386,8 +486,8 @@ impl Perform for AnsiHandler<'_> {
             }
             ('l', [b'?']) => {
                 // DEC Private Mode Reset
-                for param in params.iter() {
-                    match param[0] {
+                for &param in params.as_slice() {
+                    match param {
                         1 => self.grid.application_cursor_keys = false, // DECCKM
                         6 => self.grid.set_origin_mode(false),          // DECOM
                         7 => self.grid.auto_wrap_mode = false,          // DECAWM
@@ -411,12 +511,7 @@ impl Perform for AnsiHandler<'_> {
             }
             ('n', []) => {
                 // Device Status Report (DSR)
-                let mode = params
-                    .iter()
-                    .next()
-                    .and_then(|p| p.first())
-                    .copied()
-                    .unwrap_or(0);
+                let mode = params.get(0);
                 match mode {
                     5 => {
                         // Status report - respond with "OK"
@@ -431,12 +526,7 @@ impl Perform for AnsiHandler<'_> {
             }
             ('n', [b'?']) => {
                 // DEC Private Device Status Report
-                let mode = params
-                    .iter()
-                    .next()
-                    .and_then(|p| p.first())
-                    .copied()
-                    .unwrap_or(0);
+                let mode = params.get(0);
                 match mode {
                     6 => {
                         // DECXCPR - Extended Cursor Position Report
@@ -475,15 +565,13 @@ impl Perform for AnsiHandler<'_> {
             }
             ('m', []) => {
                 // SGR (Select Graphic Rendition)
-                self.handle_sgr(params);
+                self.handle_sgr(params.as_slice());
             }
             ('r', []) => {
                 // Set Scroll Region (DECSTBM)
                 // Parameters: top ; bottom (1-based, 0 treated as default)
-                let mut iter = params.iter();
-                let top = Self::parse_param_with_default(iter.next(), 1) as usize;
-                let bottom_default = self.grid.rows() as u16;
-                let bottom = Self::parse_param_with_default(iter.next(), bottom_default) as usize;
+                let top = params.or(0, 1) as usize;
+                let bottom = params.or(1, self.grid.rows() as u16) as usize;
                 self.grid
                     .set_scroll_region(top.saturating_sub(1), bottom.saturating_sub(1));
             }
@@ -497,27 +585,12 @@ impl Perform for AnsiHandler<'_> {
             }
             ('q', [b' ']) => {
                 // Set Cursor Shape (DECSCUSR)
-                let shape = params
-                    .iter()
-                    .next()
-                    .and_then(|p| p.first())
-                    .copied()
-                    .unwrap_or(0);
-                self.grid.cursor.shape = match shape {
-                    1 | 2 => CursorShape::Block,
-                    3 | 4 => CursorShape::Underline,
-                    5 | 6 => CursorShape::Bar,
-                    _ => CursorShape::Block,
-                };
+                let shape = params.get(0);
+                self.grid.set_cursor_style(shape);
             }
             ('t', []) => {
                 // Window manipulation (XTWINOPS)
-                let mode = params
-                    .iter()
-                    .next()
-                    .and_then(|p| p.first())
-                    .copied()
-                    .unwrap_or(0);
+                let mode = params.get(0);
                 match mode {
                     18 => {
                         // Report text area size in characters
@@ -630,3 +703,189 @@ impl Perform for AnsiHandler<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::term_grid::CursorShape;
+    use super::*;
+
+    fn feed(grid: &mut TerminalGrid, data: &[u8]) {
+        let mut parser = vte::Parser::new();
+        let mut handler = AnsiHandler::new(grid);
+        parser.advance(&mut handler, data);
+    }
+
+    /// Every mode flag that RIS must return to its default
+    fn mode_flags(grid: &TerminalGrid) -> Vec<(&'static str, bool)> {
+        vec![
+            ("application_cursor_keys", grid.application_cursor_keys),
+            ("bracketed_paste_mode", grid.bracketed_paste_mode),
+            ("focus_event_mode", grid.focus_event_mode),
+            ("synchronized_output", grid.synchronized_output),
+            ("mouse_normal_tracking", grid.mouse_normal_tracking),
+            ("mouse_button_tracking", grid.mouse_button_tracking),
+            ("mouse_any_event_tracking", grid.mouse_any_event_tracking),
+            ("mouse_utf8_mode", grid.mouse_utf8_mode),
+            ("mouse_sgr_mode", grid.mouse_sgr_mode),
+            ("mouse_urxvt_mode", grid.mouse_urxvt_mode),
+            ("lnm_mode", grid.lnm_mode),
+            ("auto_wrap_mode", grid.auto_wrap_mode),
+            ("insert_mode", grid.insert_mode),
+            ("origin_mode", grid.origin_mode),
+            ("charset_use_g0", grid.charset_use_g0),
+            ("cursor_visible", grid.cursor.visible),
+        ]
+    }
+
+    #[test]
+    fn test_ris_resets_every_mode() {
+        let fresh = TerminalGrid::new(20, 5, 100);
+        let mut grid = TerminalGrid::new(20, 5, 100);
+        feed(
+            &mut grid,
+            b"\x1b[?1h\x1b[?2004h\x1b[?1004h\x1b[?1000h\x1b[?1002h\x1b[?1003h\
+              \x1b[?1005h\x1b[?1006h\x1b[?1015h\x1b[20h\x1b[?7l\x1b[4h\x1b[?6h\x1b[?25l\
+              \x1b)0\x0e\x1b[2;4r\x1b[1;41mtext",
+        );
+        assert_ne!(mode_flags(&grid), mode_flags(&fresh));
+
+        feed(&mut grid, b"\x1bc");
+
+        assert_eq!(mode_flags(&grid), mode_flags(&fresh));
+        assert_eq!(grid.scroll_region_top(), 0);
+        assert_eq!(grid.scroll_region_bottom(), 4);
+        assert_eq!(grid.charset_g1, CharacterSet::Ascii);
+        assert_eq!((grid.cursor.x, grid.cursor.y), (0, 0));
+        assert_eq!(grid.current_bg, Color::Default);
+        assert_eq!(grid.scrollback_len(), 0);
+        // Cleared with the default background, not the one set before the reset
+        let cell = grid.get_cell(0, 0).unwrap();
+        assert_eq!((cell.c, cell.bg), (' ', Color::Default));
+    }
+
+    #[test]
+    fn test_lnm_set_and_reset() {
+        let mut grid = TerminalGrid::new(20, 5, 100);
+        feed(&mut grid, b"\x1b[20h");
+        assert!(grid.lnm_mode);
+        // A line feed also returns the carriage in LNM
+        feed(&mut grid, b"ab\ncd");
+        assert_eq!((grid.cursor.x, grid.cursor.y), (2, 1));
+
+        feed(&mut grid, b"\x1b[20l\nef");
+        assert!(!grid.lnm_mode);
+        assert_eq!((grid.cursor.x, grid.cursor.y), (4, 2));
+    }
+
+    #[test]
+    fn test_bel_rings_but_osc_terminator_does_not() {
+        let mut grid = TerminalGrid::new(20, 5, 100);
+        feed(&mut grid, b"\x1b]0;title\x07");
+        assert!(!grid.take_bell());
+        feed(&mut grid, b"done\x07");
+        assert!(grid.take_bell());
+        assert!(!grid.take_bell());
+    }
+
+    #[test]
+    fn test_decstr_keeps_screen() {
+        let mut grid = TerminalGrid::new(20, 5, 100);
+        feed(&mut grid, b"\x1b[?1h\x1b[2;4r\x1b[Hkeep\x1b[!p");
+
+        assert!(!grid.application_cursor_keys);
+        assert_eq!(grid.scroll_region_top(), 0);
+        assert_eq!(grid.scroll_region_bottom(), 4);
+        assert_eq!(grid.get_cell(0, 0).unwrap().c, 'k');
+    }
+
+    #[test]
+    fn test_decscusr_sets_cursor_shape() {
+        let cases = [
+            (1, CursorShape::Block),
+            (2, CursorShape::Block),
+            (3, CursorShape::Underline),
+            (4, CursorShape::Underline),
+            (5, CursorShape::Bar),
+            (6, CursorShape::Bar),
+        ];
+        for (ps, expected) in cases {
+            let mut grid = TerminalGrid::new(10, 3, 100);
+            feed(&mut grid, format!("\x1b[{} q", ps).as_bytes());
+            assert_eq!(grid.cursor.shape, expected, "DECSCUSR {}", ps);
+        }
+    }
+
+    #[test]
+    fn test_decscusr_zero_restores_default_shape() {
+        let mut grid = TerminalGrid::new(10, 3, 100);
+        grid.set_default_cursor_shape(CursorShape::Underline);
+        assert_eq!(grid.cursor.shape, CursorShape::Underline);
+
+        feed(&mut grid, b"\x1b[6 q");
+        assert_eq!(grid.cursor.shape, CursorShape::Bar);
+
+        feed(&mut grid, b"\x1b[0 q");
+        assert_eq!(grid.cursor.shape, CursorShape::Underline);
+
+        feed(&mut grid, b"\x1b[2 q");
+        feed(&mut grid, b"\x1b[ q");
+        assert_eq!(grid.cursor.shape, CursorShape::Underline);
+    }
+
+    #[test]
+    fn test_enq_sends_configured_answerback() {
+        let mut grid = TerminalGrid::new(10, 3, 100);
+        feed(&mut grid, b"\x05");
+        assert!(grid.take_responses().is_empty());
+
+        grid.set_answerback("VT100");
+        feed(&mut grid, b"ab\x05cd");
+        assert_eq!(grid.take_responses(), vec!["VT100".to_string()]);
+        // ENQ itself doesn't print anything
+        assert_eq!(grid.get_cell(2, 0).map(|c| c.c), Some('c'));
+    }
+
+    #[test]
+    fn test_osc7_records_working_directory() {
+        let mut grid = TerminalGrid::new(10, 3, 100);
+        assert_eq!(grid.working_directory(), None);
+
+        feed(&mut grid, b"\x1b]7;file://host/home/user/my%20dir\x07");
+        assert_eq!(grid.working_directory(), Some("/home/user/my dir"));
+
+        // ST-terminated, empty host
+        feed(&mut grid, b"\x1b]7;file:///tmp\x1b\\");
+        assert_eq!(grid.working_directory(), Some("/tmp"));
+
+        // Not a file URI: keep the previous directory
+        feed(&mut grid, b"\x1b]7;http://host/x\x07");
+        assert_eq!(grid.working_directory(), Some("/tmp"));
+    }
+
+    #[test]
+    fn test_osc12_sets_and_resets_cursor_color() {
+        let mut grid = TerminalGrid::new(10, 3, 100);
+        feed(&mut grid, b"\x1b]12;#ff8000\x07");
+        assert_eq!(grid.cursor_color(), Some((255, 128, 0)));
+        feed(&mut grid, b"\x1b]12;rgb:ffff/0/8080\x1b\\");
+        assert_eq!(grid.cursor_color(), Some((255, 0, 128)));
+
+        // Queries and unknown color names leave the color alone
+        feed(&mut grid, b"\x1b]12;?\x07");
+        feed(&mut grid, b"\x1b]12;orchid\x07");
+        assert_eq!(grid.cursor_color(), Some((255, 0, 128)));
+
+        feed(&mut grid, b"\x1b]112\x07");
+        assert_eq!(grid.cursor_color(), None);
+        assert_eq!(parse_color_spec(b"#f0a"), Some((255, 0, 170)));
+        assert_eq!(parse_color_spec(b"#12345"), None);
+    }
+
+    #[test]
+    fn test_decscusr_unknown_parameter_is_ignored() {
+        let mut grid = TerminalGrid::new(10, 3, 100);
+        feed(&mut grid, b"\x1b[5 q");
+        feed(&mut grid, b"\x1b[9 q");
+        assert_eq!(grid.cursor.shape, CursorShape::Bar);
+    }
+}
@@ -0,0 +1,128 @@
+//! 8-bit C1 control codes (0x80-0x9F, e.g. 0x9B for CSI)
+//! The VTE parser only recognizes the 7-bit `ESC`-prefixed forms, so output is rewritten
+//! (0x9B -> `ESC [`, 0x9D -> `ESC ]`, 0x90 -> `ESC P`, ...) before it is parsed.
+//! Bytes in the same range that continue a UTF-8 sequence are left alone.
+
+const ESC: u8 = 0x1b;
+
+/// Rewrites C1 controls to their 7-bit equivalents, tracking UTF-8 sequences across chunks
+#[derive(Debug, Default, Clone)]
+pub struct C1Translator {
+    /// Continuation bytes still expected by the current UTF-8 sequence
+    utf8_remaining: u8,
+    /// Rewritten output, reused across chunks
+    scratch: Vec<u8>,
+}
+
+impl C1Translator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Translate one chunk of output (`data` itself when it holds no C1 controls)
+    pub fn translate<'a>(&'a mut self, data: &'a [u8]) -> &'a [u8] {
+        // Fast path: plain ASCII can't hold C1 controls or start a UTF-8 sequence
+        if data.is_ascii() {
+            self.utf8_remaining = 0;
+            return data;
+        }
+
+        let mut rewriting = false;
+        for (i, &byte) in data.iter().enumerate() {
+            if self.is_c1(byte) {
+                if !rewriting {
+                    rewriting = true;
+                    self.scratch.clear();
+                    self.scratch.extend_from_slice(&data[..i]);
+                }
+                self.scratch.extend_from_slice(&[ESC, byte - 0x40]);
+            } else if rewriting {
+                self.scratch.push(byte);
+            }
+        }
+        if rewriting { &self.scratch } else { data }
+    }
+
+    /// Advance the UTF-8 state by one byte; true if the byte is a C1 control
+    fn is_c1(&mut self, byte: u8) -> bool {
+        match byte {
+            0x80..=0xBF if self.utf8_remaining > 0 => {
+                self.utf8_remaining -= 1;
+                false
+            }
+            0x80..=0x9F => true,
+            0xC2..=0xDF => {
+                self.utf8_remaining = 1;
+                false
+            }
+            0xE0..=0xEF => {
+                self.utf8_remaining = 2;
+                false
+            }
+            0xF0..=0xF4 => {
+                self.utf8_remaining = 3;
+                false
+            }
+            _ => {
+                // ASCII or a byte that can't be part of UTF-8 ends any pending sequence
+                self.utf8_remaining = 0;
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translate(data: &[u8]) -> Vec<u8> {
+        C1Translator::new().translate(data).to_vec()
+    }
+
+    #[test]
+    fn test_c1_controls_become_escape_sequences() {
+        assert_eq!(translate(b"\x9b31mred"), b"\x1b[31mred");
+        assert_eq!(translate(b"\x9d0;title\x9c"), b"\x1b]0;title\x1b\\");
+        assert_eq!(translate(b"\x90q\x9c"), b"\x1bPq\x1b\\");
+        assert_eq!(translate(b"a\x84b\x85c\x8d"), b"a\x1bDb\x1bEc\x1bM");
+    }
+
+    #[test]
+    fn test_utf8_continuation_bytes_are_kept() {
+        // U+00DB (0xC3 0x9B), U+2014 em dash (0xE2 0x80 0x94), U+1F600 (0xF0 0x9F 0x98 0x80)
+        for text in ["Û", "—", "😀", "x—y Û 😀"] {
+            let mut translator = C1Translator::new();
+            let data = text.as_bytes();
+            assert!(std::ptr::eq(translator.translate(data), data));
+        }
+        // C1 right after a complete character is still a control
+        assert_eq!(translate(b"\xc3\x9b\x9b1m"), b"\xc3\x9b\x1b[1m");
+    }
+
+    #[test]
+    fn test_utf8_sequence_split_across_chunks() {
+        let mut translator = C1Translator::new();
+        assert_eq!(translator.translate(b"ab\xe2"), b"ab\xe2");
+        // 0x80 and 0x94 finish the em dash; the following 0x9B is a CSI
+        assert_eq!(translator.translate(b"\x80\x94\x9b2J"), b"\x80\x94\x1b[2J");
+        assert_eq!(translator.translate(b"plain"), b"plain");
+    }
+
+    #[test]
+    fn test_translated_csi_reaches_the_grid() {
+        use super::super::ansi_handler::AnsiHandler;
+        use super::super::term_grid::{Color, NamedColor, TerminalGrid};
+
+        let mut grid = TerminalGrid::new(20, 5, 100);
+        let mut parser = vte::Parser::new();
+        let mut translator = C1Translator::new();
+        let data = translator.translate(b"\x9b2;5Hx\x9b31my");
+        parser.advance(&mut AnsiHandler::new(&mut grid), data);
+
+        assert_eq!(grid.get_cell(4, 1).unwrap().c, 'x');
+        let cell = grid.get_cell(5, 1).unwrap();
+        assert_eq!(cell.c, 'y');
+        assert_eq!(cell.fg, Color::Named(NamedColor::Red));
+    }
+}
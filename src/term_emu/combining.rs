@@ -0,0 +1,124 @@
+//! Combining marks written after a base character (e.g. `e` + U+0301)
+//! Marks take no column of their own: they are attached to the cell before the cursor.
+//! Common Latin accents are folded into the precomposed character, so decomposed and
+//! precomposed text end up in identical cells; other marks are kept on the cell and only
+//! show up in copied text, since every backend draws a single glyph per cell.
+
+/// Marks kept on one cell at most; more (e.g. "zalgo" text) are dropped
+pub const MAX_COMBINING: usize = 4;
+
+/// Precomposed Latin letters: (base, combining mark, composed)
+const COMPOSITIONS: &[(char, char, char)] = &[
+    // U+0300 grave
+    ('A', '\u{300}', 'À'),
+    ('E', '\u{300}', 'È'),
+    ('I', '\u{300}', 'Ì'),
+    ('O', '\u{300}', 'Ò'),
+    ('U', '\u{300}', 'Ù'),
+    ('a', '\u{300}', 'à'),
+    ('e', '\u{300}', 'è'),
+    ('i', '\u{300}', 'ì'),
+    ('o', '\u{300}', 'ò'),
+    ('u', '\u{300}', 'ù'),
+    // U+0301 acute
+    ('A', '\u{301}', 'Á'),
+    ('E', '\u{301}', 'É'),
+    ('I', '\u{301}', 'Í'),
+    ('O', '\u{301}', 'Ó'),
+    ('U', '\u{301}', 'Ú'),
+    ('Y', '\u{301}', 'Ý'),
+    ('C', '\u{301}', 'Ć'),
+    ('N', '\u{301}', 'Ń'),
+    ('S', '\u{301}', 'Ś'),
+    ('Z', '\u{301}', 'Ź'),
+    ('a', '\u{301}', 'á'),
+    ('e', '\u{301}', 'é'),
+    ('i', '\u{301}', 'í'),
+    ('o', '\u{301}', 'ó'),
+    ('u', '\u{301}', 'ú'),
+    ('y', '\u{301}', 'ý'),
+    ('c', '\u{301}', 'ć'),
+    ('n', '\u{301}', 'ń'),
+    ('s', '\u{301}', 'ś'),
+    ('z', '\u{301}', 'ź'),
+    // U+0302 circumflex
+    ('A', '\u{302}', 'Â'),
+    ('E', '\u{302}', 'Ê'),
+    ('I', '\u{302}', 'Î'),
+    ('O', '\u{302}', 'Ô'),
+    ('U', '\u{302}', 'Û'),
+    ('a', '\u{302}', 'â'),
+    ('e', '\u{302}', 'ê'),
+    ('i', '\u{302}', 'î'),
+    ('o', '\u{302}', 'ô'),
+    ('u', '\u{302}', 'û'),
+    // U+0303 tilde
+    ('A', '\u{303}', 'Ã'),
+    ('N', '\u{303}', 'Ñ'),
+    ('O', '\u{303}', 'Õ'),
+    ('a', '\u{303}', 'ã'),
+    ('n', '\u{303}', 'ñ'),
+    ('o', '\u{303}', 'õ'),
+    // U+0308 diaeresis
+    ('A', '\u{308}', 'Ä'),
+    ('E', '\u{308}', 'Ë'),
+    ('I', '\u{308}', 'Ï'),
+    ('O', '\u{308}', 'Ö'),
+    ('U', '\u{308}', 'Ü'),
+    ('a', '\u{308}', 'ä'),
+    ('e', '\u{308}', 'ë'),
+    ('i', '\u{308}', 'ï'),
+    ('o', '\u{308}', 'ö'),
+    ('u', '\u{308}', 'ü'),
+    ('y', '\u{308}', 'ÿ'),
+    // U+030A ring above
+    ('A', '\u{30A}', 'Å'),
+    ('a', '\u{30A}', 'å'),
+    ('U', '\u{30A}', 'Ů'),
+    ('u', '\u{30A}', 'ů'),
+    // U+030C caron
+    ('C', '\u{30C}', 'Č'),
+    ('D', '\u{30C}', 'Ď'),
+    ('E', '\u{30C}', 'Ě'),
+    ('N', '\u{30C}', 'Ň'),
+    ('R', '\u{30C}', 'Ř'),
+    ('S', '\u{30C}', 'Š'),
+    ('T', '\u{30C}', 'Ť'),
+    ('Z', '\u{30C}', 'Ž'),
+    ('c', '\u{30C}', 'č'),
+    ('d', '\u{30C}', 'ď'),
+    ('e', '\u{30C}', 'ě'),
+    ('n', '\u{30C}', 'ň'),
+    ('r', '\u{30C}', 'ř'),
+    ('s', '\u{30C}', 'š'),
+    ('t', '\u{30C}', 'ť'),
+    ('z', '\u{30C}', 'ž'),
+    // U+0327 cedilla
+    ('C', '\u{327}', 'Ç'),
+    ('c', '\u{327}', 'ç'),
+    ('S', '\u{327}', 'Ş'),
+    ('s', '\u{327}', 'ş'),
+];
+
+/// The precomposed form of `base` followed by the combining `mark`, if there is one
+pub fn compose(base: char, mark: char) -> Option<char> {
+    COMPOSITIONS
+        .iter()
+        .find(|&&(b, m, _)| b == base && m == mark)
+        .map(|&(_, _, composed)| composed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compose_latin_accents() {
+        assert_eq!(compose('e', '\u{301}'), Some('é'));
+        assert_eq!(compose('N', '\u{303}'), Some('Ñ'));
+        assert_eq!(compose('s', '\u{30C}'), Some('š'));
+        // No precomposed form
+        assert_eq!(compose('q', '\u{301}'), None);
+        assert_eq!(compose('é', '\u{301}'), None);
+    }
+}
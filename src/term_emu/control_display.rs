@@ -0,0 +1,136 @@
+//! Literal display of control characters (the `show_control_chars` option, CTRL+SHIFT+R)
+//! While on, output bypasses the escape sequence parser: C0 controls and DEL are drawn as
+//! Unicode control pictures (␀ ␁ … ␛ ␡) or in caret notation (^@ ^A … ^[ ^?), and escape
+//! sequences show up as plain text, so a window shows exactly what a program sends.
+//! A line feed still starts a new line after its glyph to keep the output readable.
+
+use super::term_grid::TerminalGrid;
+
+/// How control characters are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlNotation {
+    /// One Unicode control picture per control (U+2400 block)
+    Pictures,
+    /// `^` followed by the character 0x40 away (two cells)
+    Caret,
+}
+
+/// Literal display state, kept between output chunks
+#[derive(Debug)]
+pub struct ControlDisplay {
+    notation: ControlNotation,
+    /// Start of a UTF-8 sequence cut off at the end of the previous chunk
+    pending: Vec<u8>,
+}
+
+impl ControlDisplay {
+    pub fn new(notation: ControlNotation) -> Self {
+        Self {
+            notation,
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn notation(&self) -> ControlNotation {
+        self.notation
+    }
+
+    /// Draw `data` into the grid without interpreting it
+    pub fn feed(&mut self, grid: &mut TerminalGrid, data: &[u8]) {
+        let mut bytes = std::mem::take(&mut self.pending);
+        bytes.extend_from_slice(data);
+
+        let mut rest = bytes.as_slice();
+        while !rest.is_empty() {
+            let (valid, skip) = match std::str::from_utf8(rest) {
+                Ok(text) => (text, rest.len()),
+                Err(e) => {
+                    let valid = std::str::from_utf8(&rest[..e.valid_up_to()]).unwrap_or_default();
+                    match e.error_len() {
+                        Some(len) => (valid, e.valid_up_to() + len),
+                        None => {
+                            // Incomplete sequence at the end: wait for the next chunk
+                            self.pending = rest[e.valid_up_to()..].to_vec();
+                            (valid, rest.len())
+                        }
+                    }
+                }
+            };
+            for c in valid.chars() {
+                self.put(grid, c);
+            }
+            if skip > valid.len() && self.pending.is_empty() {
+                grid.put_char('\u{FFFD}');
+            }
+            rest = &rest[skip..];
+        }
+    }
+
+    fn put(&self, grid: &mut TerminalGrid, c: char) {
+        let code = c as u32;
+        match code {
+            0x00..=0x1f | 0x7f => {
+                match self.notation {
+                    ControlNotation::Pictures => {
+                        let picture = if code == 0x7f { 0x2421 } else { 0x2400 + code };
+                        grid.put_char(char::from_u32(picture).unwrap_or('?'));
+                    }
+                    ControlNotation::Caret => {
+                        grid.put_char('^');
+                        grid.put_char(char::from(code as u8 ^ 0x40));
+                    }
+                }
+                if c == '\n' {
+                    grid.put_char('\r');
+                    grid.put_char('\n');
+                }
+            }
+            // C1 controls stand for ESC followed by the character 0x40 below
+            0x80..=0x9f => {
+                self.put(grid, '\x1b');
+                grid.put_char(char::from((code - 0x40) as u8));
+            }
+            _ => grid.put_char(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row_text(grid: &TerminalGrid, y: usize) -> String {
+        (0..grid.cols())
+            .map(|x| grid.get_cell(x, y).map_or(' ', |cell| cell.c))
+            .collect::<String>()
+            .trim_end()
+            .to_string()
+    }
+
+    #[test]
+    fn test_controls_and_escapes_are_shown_literally() {
+        let mut grid = TerminalGrid::new(20, 4, 100);
+        let mut display = ControlDisplay::new(ControlNotation::Pictures);
+        display.feed(&mut grid, b"a\x01\x1b[31mb\r\nc\x7f");
+        assert_eq!(row_text(&grid, 0), "a␁␛[31mb␍␊");
+        assert_eq!(row_text(&grid, 1), "c␡");
+
+        let mut grid = TerminalGrid::new(20, 4, 100);
+        let mut display = ControlDisplay::new(ControlNotation::Caret);
+        display.feed(&mut grid, "\x00\t\u{9b}1m".as_bytes());
+        assert_eq!(row_text(&grid, 0), "^@^I^[[1m");
+    }
+
+    #[test]
+    fn test_utf8_split_across_chunks() {
+        let mut grid = TerminalGrid::new(20, 4, 100);
+        let mut display = ControlDisplay::new(ControlNotation::Pictures);
+        let text = "é✓".as_bytes();
+        display.feed(&mut grid, &text[..1]);
+        display.feed(&mut grid, &text[1..3]);
+        display.feed(&mut grid, &text[3..]);
+        // Invalid bytes show up as the replacement character
+        display.feed(&mut grid, b"\xffz");
+        assert_eq!(row_text(&grid, 0), "é✓\u{FFFD}z");
+    }
+}
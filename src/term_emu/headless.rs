@@ -0,0 +1,13 @@
+//! Headless parser core (VTE parser, ANSI handler and grid, no PTY), built as the
+//! `term39` library so the benches can feed it; the application compiles these modules
+//! through `term_emu/mod.rs` instead
+#![allow(clippy::collapsible_if)]
+
+mod ansi_handler;
+mod c1;
+mod combining;
+mod control_display;
+pub mod sample_output;
+mod scrollback;
+mod term_grid;
+pub mod terminal_renderer;
@@ -1,10 +1,26 @@
 mod ansi_handler;
+mod c1;
+mod combining;
+mod control_display;
+mod output_log;
+mod paste;
+#[cfg(test)]
+mod sample_output;
+mod scrollback;
 mod selection;
+mod signal;
 mod term_grid;
 mod terminal_emulator;
 pub mod terminal_renderer;
 
+pub use ansi_handler::parse_color_spec;
+pub use control_display::ControlNotation;
+pub use output_log::{OutputLog, SharedOutputLog};
+pub use paste::{PasteControls, PasteTransform, trim_trailing_newline};
 pub use selection::{Position, Selection, SelectionType};
+pub use signal::ForegroundSignal;
+#[cfg(unix)]
+pub use signal::signal_foreground_group;
 pub use term_grid::{
     CellAttributes, Color, Cursor, CursorShape, NamedColor, TerminalCell, TerminalGrid,
 };
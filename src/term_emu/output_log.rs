@@ -0,0 +1,168 @@
+//! Live plain-text tee of terminal output (the `output_log` config option)
+//! Lines are appended as they scroll off the top of the screen into the scrollback, without
+//! colors or attributes, so screen readers and loggers can follow along with `tail -f`.
+//! When the file grows past its size limit it is renamed to `<path>.1` and started again.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// An output log shared by every window writing to the same file
+pub type SharedOutputLog = Arc<Mutex<OutputLog>>;
+
+/// Append-only text log with size-based rotation
+#[derive(Debug)]
+pub struct OutputLog {
+    path: PathBuf,
+    /// Rotate once the file reaches this many bytes (0 = never)
+    max_bytes: u64,
+    /// Opened on the first write, so configuring a log creates no file until output arrives
+    file: Option<File>,
+    /// Current size of the file (including what other processes appended before opening)
+    size: u64,
+}
+
+impl OutputLog {
+    pub fn new(path: impl Into<PathBuf>, max_bytes: u64) -> Self {
+        Self {
+            path: path.into(),
+            max_bytes,
+            file: None,
+            size: 0,
+        }
+    }
+
+    pub fn shared(path: impl Into<PathBuf>, max_bytes: u64) -> SharedOutputLog {
+        Arc::new(Mutex::new(Self::new(path, max_bytes)))
+    }
+
+    /// Append lines (a newline is added after each one)
+    pub fn append(&mut self, lines: &[String]) -> io::Result<()> {
+        if lines.is_empty() {
+            return Ok(());
+        }
+        let mut text = String::new();
+        for line in lines {
+            text.push_str(line);
+            text.push('\n');
+        }
+
+        // A file already over the limit when opened is rotated before the first write too
+        let mut file = match self.file.take() {
+            Some(file) => file,
+            None => self.open()?,
+        };
+        if self.max_bytes > 0 && self.size > 0 && self.size + text.len() as u64 > self.max_bytes {
+            drop(file);
+            self.rotate()?;
+            file = self.open()?;
+        }
+        file.write_all(text.as_bytes())?;
+        self.size += text.len() as u64;
+        self.file = Some(file);
+        Ok(())
+    }
+
+    /// Open the log for appending and pick up its current size
+    fn open(&mut self) -> io::Result<File> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = file.metadata().map(|m| m.len()).unwrap_or(0);
+        Ok(file)
+    }
+
+    /// Move the full log to `<path>.1` (replacing an older one); the next open starts an
+    /// empty file
+    fn rotate(&mut self) -> io::Result<()> {
+        let mut rotated = self.path.clone().into_os_string();
+        rotated.push(".1");
+        match fs::rename(&self.path, rotated) {
+            // Deleted from outside since it was opened: nothing to move
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => {
+                self.size = 0;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("term39-{}-{}", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_lines_are_appended() {
+        let path = temp_path("output-log");
+        let mut log = OutputLog::new(&path, 0);
+        log.append(&["$ ls".to_string(), "a  b".to_string()])
+            .unwrap();
+        log.append(&[]).unwrap();
+        log.append(&["$ ".to_string()]).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "$ ls\na  b\n$ \n");
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_log_rotates_by_size() {
+        let path = temp_path("output-log-rotate");
+        let rotated = PathBuf::from(format!("{}.1", path.display()));
+        let _ = fs::remove_file(&rotated);
+
+        let mut log = OutputLog::new(&path, 10);
+        log.append(&["12345678".to_string()]).unwrap();
+        // Would pass 10 bytes: the first line moves to .1
+        log.append(&["abc".to_string()]).unwrap();
+        assert_eq!(fs::read_to_string(&rotated).unwrap(), "12345678\n");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "abc\n");
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(&rotated);
+    }
+
+    #[test]
+    fn test_oversized_log_rotates_on_open() {
+        let path = temp_path("output-log-oversized");
+        let rotated = PathBuf::from(format!("{}.1", path.display()));
+        let _ = fs::remove_file(&rotated);
+        fs::write(&path, "left over from a previous run\n").unwrap();
+
+        let mut log = OutputLog::new(&path, 10);
+        log.append(&["new".to_string()]).unwrap();
+        assert_eq!(
+            fs::read_to_string(&rotated).unwrap(),
+            "left over from a previous run\n"
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new\n");
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(&rotated);
+    }
+
+    #[test]
+    fn test_log_deleted_from_outside_keeps_logging() {
+        let path = temp_path("output-log-deleted");
+        let rotated = PathBuf::from(format!("{}.1", path.display()));
+        let _ = fs::remove_file(&rotated);
+
+        let mut log = OutputLog::new(&path, 10);
+        log.append(&["12345678".to_string()]).unwrap();
+        fs::remove_file(&path).unwrap();
+        // Rotation finds nothing to move and starts a new file
+        log.append(&["abc".to_string()]).unwrap();
+        log.append(&["de".to_string()]).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "abc\nde\n");
+        assert!(!rotated.exists());
+
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,232 @@
+//! Clean-up of pasted text before it is written to the PTY
+//! Applied to plain pastes and pastes into password prompts; bracketed pastes reach the
+//! application verbatim
+//! Control characters other than tab and line breaks are dropped by default, together with
+//! the escape sequences they start, so a line copied from a colored log pastes as its text
+//! and cannot change terminal state or the shell's input on its way in.
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// Tab stop interval used when expanding tabs
+const TAB_STOP: usize = 8;
+
+/// What happens to control characters (C0 except tab/CR/LF, DEL, C1) in pasted text
+/// (the `paste_control_chars` config option)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum PasteControls {
+    /// Send them as they are
+    Keep,
+    /// Drop them, and the rest of any escape sequence they start (default)
+    #[default]
+    Strip,
+    /// Send them in caret notation (ESC becomes `^[`)
+    Escape,
+}
+
+/// Which transformations are applied to pasted text (see the `paste_*` config options)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PasteTransform {
+    /// Convert CRLF line endings to LF
+    pub crlf_to_lf: bool,
+    /// Remove carriage returns that are not part of a CRLF pair
+    pub strip_cr: bool,
+    /// Replace tabs with spaces up to the next tab stop
+    pub expand_tabs: bool,
+    pub controls: PasteControls,
+}
+
+impl Default for PasteTransform {
+    fn default() -> Self {
+        Self {
+            crlf_to_lf: true,
+            strip_cr: false,
+            expand_tabs: false,
+            controls: PasteControls::Strip,
+        }
+    }
+}
+
+impl PasteTransform {
+    /// Apply the enabled transformations, borrowing the text when nothing changes
+    pub fn apply<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let text = self.apply_whitespace(text);
+        match clean_controls(&text, self.controls) {
+            Some(cleaned) => Cow::Owned(cleaned),
+            None => text,
+        }
+    }
+
+    /// Line ending and tab transformations
+    fn apply_whitespace<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let touches_cr = (self.crlf_to_lf || self.strip_cr) && text.contains('\r');
+        let touches_tab = self.expand_tabs && text.contains('\t');
+        if !touches_cr && !touches_tab {
+            return Cow::Borrowed(text);
+        }
+
+        let mut out = String::with_capacity(text.len());
+        let mut column = 0;
+        let mut chars = text.chars().peekable();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\r' if chars.peek() == Some(&'\n') && self.crlf_to_lf => {}
+                '\r' if chars.peek() != Some(&'\n') && self.strip_cr => {}
+                '\t' if self.expand_tabs => {
+                    let spaces = TAB_STOP - column % TAB_STOP;
+                    out.extend(std::iter::repeat_n(' ', spaces));
+                    column += spaces;
+                }
+                '\n' | '\r' => {
+                    out.push(ch);
+                    column = 0;
+                }
+                _ => {
+                    out.push(ch);
+                    column += 1;
+                }
+            }
+        }
+        Cow::Owned(out)
+    }
+}
+
+fn is_paste_control(c: char) -> bool {
+    (c < ' ' && !matches!(c, '\t' | '\n' | '\r')) || ('\x7f'..='\u{9f}').contains(&c)
+}
+
+/// `text` with its control characters handled as `controls` says, None when unchanged
+fn clean_controls(text: &str, controls: PasteControls) -> Option<String> {
+    if controls == PasteControls::Keep || !text.chars().any(is_paste_control) {
+        return None;
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if !is_paste_control(c) {
+            out.push(c);
+            continue;
+        }
+        if controls == PasteControls::Escape {
+            // ^@ .. ^_ and ^? for C0 and DEL, ESC plus the 7-bit form for C1
+            match c as u32 {
+                0x80..=0x9f => {
+                    out.push_str("^[");
+                    out.push(char::from(c as u8 - 0x40));
+                }
+                code => {
+                    out.push('^');
+                    out.push(char::from(code as u8 ^ 0x40));
+                }
+            }
+            continue;
+        }
+        // Strip: also skip the rest of the sequence it starts. ESC followed by one of
+        // [ ] P X ^ _ is the 7-bit form of the C1 control 0x40 above it
+        let c = if c == '\x1b' {
+            match chars.next_if(|next| matches!(next, '[' | ']' | 'P' | 'X' | '^' | '_')) {
+                Some(next) => char::from_u32(next as u32 + 0x40).unwrap_or(c),
+                None => {
+                    // Other escape sequences: intermediates, then the final character
+                    while chars.next_if(|next| (' '..='/').contains(next)).is_some() {}
+                    chars.next();
+                    continue;
+                }
+            }
+        } else {
+            c
+        };
+        match c {
+            // CSI: parameters and intermediates up to the final character
+            '\u{9b}' => while chars.next().is_some_and(|c| !('@'..='~').contains(&c)) {},
+            // DCS, SOS, OSC, PM and APC strings: up to BEL or ST (ESC \ or U+009C)
+            '\u{90}' | '\u{98}' | '\u{9d}' | '\u{9e}' | '\u{9f}' => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07'
+                        || c == '\u{9c}'
+                        || (c == '\x1b' && chars.next_if_eq(&'\\').is_some())
+                    {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Some(out)
+}
+
+/// Text without its trailing line breaks (so pasting it doesn't run it)
+pub fn trim_trailing_newline(text: &str) -> &str {
+    text.trim_end_matches(['\r', '\n'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NONE: PasteTransform = PasteTransform {
+        crlf_to_lf: false,
+        strip_cr: false,
+        expand_tabs: false,
+        controls: PasteControls::Keep,
+    };
+
+    #[test]
+    fn test_default_converts_crlf_only() {
+        let transform = PasteTransform::default();
+        assert_eq!(transform.apply("a\r\nb\r\n"), "a\nb\n");
+        assert_eq!(transform.apply("a\rb\tc"), "a\rb\tc");
+        assert!(matches!(transform.apply("plain"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_strip_lone_cr() {
+        let transform = PasteTransform {
+            strip_cr: true,
+            ..NONE
+        };
+        assert_eq!(transform.apply("a\rb\r\nc"), "ab\r\nc");
+    }
+
+    #[test]
+    fn test_expand_tabs_to_tab_stops() {
+        let transform = PasteTransform {
+            expand_tabs: true,
+            ..NONE
+        };
+        assert_eq!(transform.apply("\tx"), "        x");
+        assert_eq!(transform.apply("abc\td\n\te"), "abc     d\n        e");
+    }
+
+    #[test]
+    fn test_control_characters_stripped_or_escaped() {
+        let transform = PasteTransform::default();
+        // Whole escape sequences go, tabs and line breaks stay
+        assert_eq!(
+            transform.apply("\x1b[1;31mERROR\x1b[0m\tdisk\r\n\x1b]0;pwned\x07ok\x1b(B\x08\n"),
+            "ERROR\tdisk\nok\n"
+        );
+        assert_eq!(transform.apply("a\u{9b}2Jb\x1b]8;;x\x1b\\c\x7f"), "abc");
+        assert!(matches!(transform.apply("plain\ttext"), Cow::Borrowed(_)));
+
+        let escape = PasteTransform {
+            controls: PasteControls::Escape,
+            ..NONE
+        };
+        assert_eq!(escape.apply("\x1b[31mx\x03\u{9b}"), "^[[31mx^C^[[");
+        assert_eq!(NONE.apply("\x1b[31m"), "\x1b[31m");
+    }
+
+    #[test]
+    fn test_trim_trailing_newline() {
+        assert_eq!(trim_trailing_newline("ls -la\n"), "ls -la");
+        assert_eq!(trim_trailing_newline("ls -la\r\n\n"), "ls -la");
+        assert_eq!(trim_trailing_newline("a\nb"), "a\nb");
+    }
+
+    #[test]
+    fn test_disabled_transform_keeps_text() {
+        assert_eq!(NONE.apply("a\r\nb\r\tc"), "a\r\nb\r\tc");
+    }
+}
@@ -0,0 +1,34 @@
+//! Representative terminal output for the parser tests and the `ansi_throughput` benchmark
+
+use std::fmt::Write;
+
+/// Typical output of about `target_len` bytes: colored listings, truecolor text, a progress
+/// line redrawn with CR, cursor movement and erases, like a build log or a TUI refresh
+pub fn sample_output(target_len: usize) -> Vec<u8> {
+    let mut out = String::with_capacity(target_len + 256);
+    let mut n = 0u32;
+    while out.len() < target_len {
+        n += 1;
+        let _ = write!(
+            out,
+            "\x1b[01;34mdir{n}\x1b[0m  \x1b[01;32mscript{n}.sh\x1b[0m  file{n}.txt  \
+             \x1b[38;2;{};{};200mtruecolor\x1b[0m  naïve café ✓\r\n",
+            n % 256,
+            (n * 7) % 256
+        );
+        let _ = write!(
+            out,
+            "\x1b[33m[{:>3}%]\x1b[0m Compiling crate{n} v0.1.{n}\r",
+            n % 100
+        );
+        let _ = write!(out, "\x1b[K   Finished \x1b[1mdev\x1b[22m profile\r\n");
+        if n.is_multiple_of(20) {
+            // A status line repainted inside a synchronized update
+            let _ = write!(
+                out,
+                "\x1b[?2026h\x1b[s\x1b[1;1H\x1b[7m status {n} \x1b[27m\x1b[u\x1b[2A\x1b[2B\x1b[?2026l"
+            );
+        }
+    }
+    out.into_bytes()
+}
@@ -0,0 +1,264 @@
+//! Scrollback line store with optional compression (the `scrollback_compression` option)
+//! Lines older than the newest `compress_after` are packed: the characters as a string up
+//! to the last non-blank cell, plus one run per stretch of cells sharing colors and
+//! attributes. A packed 80-column line of plain text takes around a hundred bytes instead
+//! of a few kilobytes, and is unpacked again only when it is read (scrolled into view,
+//! selected or saved). Lines holding combining marks are rare and stay unpacked.
+
+use super::term_grid::{CellAttributes, Color, TerminalCell};
+use std::borrow::Cow;
+use std::collections::VecDeque;
+
+/// Cells sharing one style in a packed line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Run {
+    len: u16,
+    fg: Color,
+    bg: Color,
+    attrs: CellAttributes,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PackedLine {
+    /// Cell count of the original line, restored with blank cells
+    width: usize,
+    /// One char per cell up to the last non-blank cell
+    text: Box<str>,
+    runs: Box<[Run]>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Line {
+    Plain(Vec<TerminalCell>),
+    Packed(PackedLine),
+}
+
+/// Scrollback lines, oldest first
+#[derive(Debug, Default)]
+pub struct Scrollback {
+    lines: VecDeque<Line>,
+    /// Keep this many of the newest lines unpacked (None = compression off)
+    compress_after: Option<usize>,
+}
+
+impl Scrollback {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+
+    /// Turn compression on (packing every line but the newest `compress_after`) or off
+    /// (unpacking everything)
+    pub fn set_compression(&mut self, compress_after: Option<usize>) {
+        self.compress_after = compress_after;
+        let packed_count = compress_after.map_or(0, |n| self.lines.len().saturating_sub(n));
+        for (i, line) in self.lines.iter_mut().enumerate() {
+            let pack = i < packed_count;
+            *line = match std::mem::replace(line, Line::Plain(Vec::new())) {
+                Line::Plain(cells) if pack => pack_line(cells),
+                Line::Packed(packed) if !pack => Line::Plain(unpack_line(&packed)),
+                other => other,
+            };
+        }
+    }
+
+    /// Append the newest line, packing the one that just fell past the threshold
+    pub fn push_back(&mut self, cells: Vec<TerminalCell>) {
+        self.lines.push_back(Line::Plain(cells));
+        if let Some(n) = self.compress_after
+            && let Some(i) = self.lines.len().checked_sub(n + 1)
+            && let Line::Plain(cells) = &mut self.lines[i]
+        {
+            self.lines[i] = pack_line(std::mem::take(cells));
+        }
+    }
+
+    /// Drop the oldest line, returning its cells when it was unpacked (for reuse)
+    pub fn pop_front(&mut self) -> Option<Vec<TerminalCell>> {
+        match self.lines.pop_front()? {
+            Line::Plain(cells) => Some(cells),
+            Line::Packed(_) => None,
+        }
+    }
+
+    /// Cells of a line (0 = oldest), unpacked when needed
+    pub fn get(&self, idx: usize) -> Option<Cow<'_, [TerminalCell]>> {
+        match self.lines.get(idx)? {
+            Line::Plain(cells) => Some(Cow::Borrowed(cells)),
+            Line::Packed(packed) => Some(Cow::Owned(unpack_line(packed))),
+        }
+    }
+
+    /// Character of one cell, without unpacking the whole line
+    pub fn char_at(&self, idx: usize, col: usize) -> Option<char> {
+        match self.lines.get(idx)? {
+            Line::Plain(cells) => cells.get(col).map(|cell| cell.c),
+            Line::Packed(packed) if col < packed.width => {
+                Some(packed.text.chars().nth(col).unwrap_or(' '))
+            }
+            Line::Packed(_) => None,
+        }
+    }
+
+    /// Cell count of a line, which keeps the width the grid had when it scrolled off
+    pub fn width(&self, idx: usize) -> Option<usize> {
+        match self.lines.get(idx)? {
+            Line::Plain(cells) => Some(cells.len()),
+            Line::Packed(packed) => Some(packed.width),
+        }
+    }
+
+    /// Approximate heap memory held by the lines, for the memory benchmark
+    #[cfg(test)]
+    fn heap_bytes(&self) -> usize {
+        let lines = self.lines.capacity() * std::mem::size_of::<Line>();
+        let contents: usize = self
+            .lines
+            .iter()
+            .map(|line| match line {
+                Line::Plain(cells) => cells.capacity() * std::mem::size_of::<TerminalCell>(),
+                Line::Packed(packed) => {
+                    packed.text.len() + packed.runs.len() * std::mem::size_of::<Run>()
+                }
+            })
+            .sum();
+        lines + contents
+    }
+}
+
+/// Pack a line, keeping it as is when it has combining marks or is too wide for a run
+fn pack_line(cells: Vec<TerminalCell>) -> Line {
+    if cells.len() > u16::MAX as usize || cells.iter().any(|cell| cell.combining.is_some()) {
+        return Line::Plain(cells);
+    }
+    let blank = TerminalCell::default();
+    let used = cells
+        .iter()
+        .rposition(|cell| *cell != blank)
+        .map_or(0, |p| p + 1);
+
+    let mut runs: Vec<Run> = Vec::new();
+    for cell in &cells[..used] {
+        match runs.last_mut() {
+            Some(run) if run.fg == cell.fg && run.bg == cell.bg && run.attrs == cell.attrs => {
+                run.len += 1;
+            }
+            _ => runs.push(Run {
+                len: 1,
+                fg: cell.fg,
+                bg: cell.bg,
+                attrs: cell.attrs,
+            }),
+        }
+    }
+    Line::Packed(PackedLine {
+        width: cells.len(),
+        text: cells[..used].iter().map(|cell| cell.c).collect(),
+        runs: runs.into_boxed_slice(),
+    })
+}
+
+fn unpack_line(packed: &PackedLine) -> Vec<TerminalCell> {
+    let mut cells = Vec::with_capacity(packed.width);
+    let mut chars = packed.text.chars();
+    for run in packed.runs.iter() {
+        for _ in 0..run.len {
+            cells.push(TerminalCell {
+                c: chars.next().unwrap_or(' '),
+                fg: run.fg,
+                bg: run.bg,
+                attrs: run.attrs,
+                combining: None,
+            });
+        }
+    }
+    cells.resize(packed.width, TerminalCell::default());
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::term_grid::NamedColor;
+    use super::*;
+
+    /// A shell-looking line: a colored prompt, then plain output padded to `cols`
+    fn sample_line(n: usize, cols: usize) -> Vec<TerminalCell> {
+        let mut cells = vec![TerminalCell::default(); cols];
+        let text = format!("user@host:~$ echo line {}", n);
+        for (i, c) in text.chars().enumerate() {
+            cells[i].c = c;
+            if i < 12 {
+                cells[i].fg = Color::Named(NamedColor::Green);
+                cells[i].attrs.bold = true;
+            }
+        }
+        cells
+    }
+
+    #[test]
+    fn test_packed_lines_read_back_unchanged() {
+        let mut scrollback = Scrollback::new();
+        scrollback.set_compression(Some(2));
+        let mut marked = sample_line(2, 30);
+        marked[0].combining = Some("\u{20DD}".into());
+        let lines = [
+            sample_line(1, 30),
+            marked,
+            sample_line(3, 30),
+            sample_line(4, 30),
+        ];
+        for line in &lines {
+            scrollback.push_back(line.clone());
+        }
+
+        assert!(matches!(scrollback.lines[0], Line::Packed(_)));
+        assert!(matches!(scrollback.lines[1], Line::Plain(_)));
+        for (i, line) in lines.iter().enumerate() {
+            assert_eq!(scrollback.get(i).unwrap().as_ref(), line.as_slice());
+        }
+        assert_eq!(scrollback.char_at(0, 0), Some('u'));
+        assert_eq!(scrollback.char_at(0, 29), Some(' '));
+        assert_eq!(scrollback.char_at(0, 30), None);
+        assert_eq!(scrollback.width(0), Some(30));
+
+        // Turning compression off unpacks everything
+        scrollback.set_compression(None);
+        assert!(matches!(scrollback.lines[0], Line::Plain(_)));
+        assert_eq!(scrollback.get(0).unwrap().as_ref(), lines[0].as_slice());
+    }
+
+    /// Memory of a 50k-line, 120-column scrollback with and without compression
+    /// Run with `cargo test --release bench_scrollback_memory -- --ignored --nocapture`
+    #[test]
+    #[ignore = "benchmark"]
+    fn bench_scrollback_memory() {
+        const LINES: usize = 50_000;
+        let fill = |compress_after| {
+            let mut scrollback = Scrollback::new();
+            scrollback.set_compression(compress_after);
+            let start = std::time::Instant::now();
+            for n in 0..LINES {
+                scrollback.push_back(sample_line(n, 120));
+            }
+            (scrollback.heap_bytes(), start.elapsed())
+        };
+        let (plain, plain_time) = fill(None);
+        let (packed, packed_time) = fill(Some(200));
+        println!(
+            "{} lines: {:.1} MiB uncompressed ({:?}), {:.1} MiB compressed ({:?})",
+            LINES,
+            plain as f64 / (1 << 20) as f64,
+            plain_time,
+            packed as f64 / (1 << 20) as f64,
+            packed_time,
+        );
+        assert!(packed * 10 < plain);
+    }
+}
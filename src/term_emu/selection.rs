@@ -117,7 +117,12 @@ impl Selection {
     }
 
     /// Expand selection to word boundaries
-    pub fn expand_to_word(&mut self, get_char: impl Fn(Position) -> Option<char>) {
+    /// Words are letters, digits and the characters in `word_chars`
+    pub fn expand_to_word(
+        &mut self,
+        word_chars: &str,
+        get_char: impl Fn(Position) -> Option<char>,
+    ) {
         self.selection_type = SelectionType::Word;
 
         // Expand start backward to word boundary
@@ -125,7 +130,7 @@ impl Selection {
         while start.col > 0 {
             let prev_pos = Position::new(start.col - 1, start.row);
             if let Some(ch) = get_char(prev_pos) {
-                if is_word_char(ch) {
+                if is_word_char(ch, word_chars) {
                     start = prev_pos;
                 } else {
                     break;
@@ -138,7 +143,7 @@ impl Selection {
         // Expand end forward to word boundary
         let mut end = self.end;
         while let Some(ch) = get_char(end) {
-            if is_word_char(ch) {
+            if is_word_char(ch, word_chars) {
                 end.col += 1;
             } else {
                 break;
@@ -160,6 +165,26 @@ impl Selection {
         self.end = Position::new(width.saturating_sub(1), end.row);
     }
 
+    /// Move the selection up by `lines` rows (absolute rows shift when old scrollback is dropped)
+    /// Endpoints that scroll off the top are clamped to the first cell
+    /// Returns false if the whole selection is gone
+    pub fn shift_up(&mut self, lines: u16) -> bool {
+        let (start, end) = self.normalized_bounds();
+        if start.row.max(end.row) < lines {
+            return false;
+        }
+        let is_block = self.selection_type == SelectionType::Block;
+        for pos in [&mut self.start, &mut self.end] {
+            if pos.row < lines {
+                // Block selections keep their columns; linear ones start at the first cell
+                *pos = Position::new(if is_block { pos.col } else { 0 }, 0);
+            } else {
+                pos.row -= lines;
+            }
+        }
+        true
+    }
+
     /// Check if selection is empty
     pub fn is_empty(&self) -> bool {
         self.start == self.end
@@ -191,8 +216,8 @@ impl Selection {
 }
 
 /// Check if a character is part of a word (for word selection)
-fn is_word_char(ch: char) -> bool {
-    ch.is_alphanumeric() || ch == '_' || ch == '-'
+fn is_word_char(ch: char, word_chars: &str) -> bool {
+    ch.is_alphanumeric() || word_chars.contains(ch)
 }
 
 #[cfg(test)]
@@ -223,6 +248,40 @@ mod tests {
         assert!(!sel.contains(Position::new(11, 7)));
     }
 
+    #[test]
+    fn test_expand_to_word_uses_word_chars() {
+        let line: Vec<char> = "cat ~/src/main.rs now".chars().collect();
+        let get_char = |pos: Position| line.get(pos.col as usize).copied();
+
+        // Default: the path splits at '/' and '.'
+        let mut sel = Selection::new(Position::new(7, 0), SelectionType::Character);
+        sel.expand_to_word("_-", get_char);
+        assert_eq!((sel.start.col, sel.end.col), (6, 9));
+
+        // With path characters the whole path is one word
+        let mut sel = Selection::new(Position::new(7, 0), SelectionType::Character);
+        sel.expand_to_word("_-./~", get_char);
+        assert_eq!((sel.start.col, sel.end.col), (4, 17));
+    }
+
+    #[test]
+    fn test_shift_up() {
+        let mut sel = Selection::new(Position::new(3, 10), SelectionType::Character);
+        sel.update_end(Position::new(7, 12));
+
+        assert!(sel.shift_up(4));
+        assert_eq!(sel.start, Position::new(3, 6));
+        assert_eq!(sel.end, Position::new(7, 8));
+
+        // Start scrolls off the top: clamped to the first cell
+        assert!(sel.shift_up(7));
+        assert_eq!(sel.start, Position::new(0, 0));
+        assert_eq!(sel.end, Position::new(7, 1));
+
+        // Whole selection scrolls off
+        assert!(!sel.shift_up(2));
+    }
+
     #[test]
     fn test_normalized_bounds() {
         let mut sel = Selection::new(Position::new(10, 0), SelectionType::Character);
@@ -0,0 +1,120 @@
+//! Signals sent to the foreground process group of a terminal
+//! The group is read from the PTY (`tcgetpgrp`), so a job started from the shell
+//! (vim, a stuck build) is signalled rather than the shell itself
+
+/// Signal that can be sent to a window's foreground job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForegroundSignal {
+    Interrupt, // SIGINT
+    Terminate, // SIGTERM
+    Kill,      // SIGKILL (cannot be caught; asks for confirmation first)
+}
+
+impl ForegroundSignal {
+    /// Conventional signal name (e.g. "SIGTERM")
+    pub fn name(self) -> &'static str {
+        match self {
+            ForegroundSignal::Interrupt => "SIGINT",
+            ForegroundSignal::Terminate => "SIGTERM",
+            ForegroundSignal::Kill => "SIGKILL",
+        }
+    }
+
+    /// The platform signal number
+    #[cfg(unix)]
+    pub fn as_raw(self) -> libc::c_int {
+        match self {
+            ForegroundSignal::Interrupt => libc::SIGINT,
+            ForegroundSignal::Terminate => libc::SIGTERM,
+            ForegroundSignal::Kill => libc::SIGKILL,
+        }
+    }
+
+    /// Map a signal number back (None for signals that can't be requested)
+    #[cfg(unix)]
+    pub fn from_raw(signal: libc::c_int) -> Option<Self> {
+        [
+            ForegroundSignal::Interrupt,
+            ForegroundSignal::Terminate,
+            ForegroundSignal::Kill,
+        ]
+        .into_iter()
+        .find(|s| s.as_raw() == signal)
+    }
+}
+
+/// Send `signal` to the foreground process group of the terminal whose PTY master is `fd`
+#[cfg(unix)]
+pub fn signal_foreground_group(
+    fd: std::os::unix::io::RawFd,
+    signal: ForegroundSignal,
+) -> std::io::Result<()> {
+    unsafe {
+        let pgid = libc::tcgetpgrp(fd);
+        if pgid <= 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        if libc::killpg(pgid, signal.as_raw()) != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// Send SIGKILL to every process in the group `pgid` (a PTY child leads its own group)
+#[cfg(unix)]
+pub fn kill_process_group(pgid: u32) -> std::io::Result<()> {
+    let pgid = libc::pid_t::try_from(pgid)
+        .ok()
+        .filter(|&pgid| pgid > 0)
+        .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::InvalidInput))?;
+    if unsafe { libc::killpg(pgid, libc::SIGKILL) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signal_numbers_round_trip() {
+        for signal in [
+            ForegroundSignal::Interrupt,
+            ForegroundSignal::Terminate,
+            ForegroundSignal::Kill,
+        ] {
+            assert_eq!(ForegroundSignal::from_raw(signal.as_raw()), Some(signal));
+        }
+        assert_eq!(ForegroundSignal::from_raw(libc::SIGHUP), None);
+        assert_eq!(ForegroundSignal::Kill.name(), "SIGKILL");
+    }
+
+    #[test]
+    fn test_signal_without_terminal_fails() {
+        // A pipe is not a terminal, so there is no foreground group to signal
+        let mut fds = [0; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        assert!(signal_foreground_group(fds[0], ForegroundSignal::Terminate).is_err());
+        unsafe {
+            libc::close(fds[0]);
+            libc::close(fds[1]);
+        }
+    }
+
+    #[test]
+    fn test_kill_process_group() {
+        use std::os::unix::process::{CommandExt, ExitStatusExt};
+
+        // A child leading its own group, like a shell on a PTY
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .process_group(0)
+            .spawn()
+            .unwrap();
+        kill_process_group(child.id()).unwrap();
+        assert_eq!(child.wait().unwrap().signal(), Some(libc::SIGKILL));
+        assert!(kill_process_group(0).is_err());
+    }
+}
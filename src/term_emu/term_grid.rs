@@ -1,4 +1,8 @@
-use std::collections::VecDeque;
+use super::combining::{MAX_COMBINING, compose};
+use super::control_display::{ControlDisplay, ControlNotation};
+use super::scrollback::Scrollback;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fmt;
 use unicode_width::UnicodeWidthChar;
 
@@ -56,6 +60,8 @@ pub struct TerminalCell {
     pub fg: Color,
     pub bg: Color,
     pub attrs: CellAttributes,
+    /// Combining marks after `c` that have no precomposed form (None for almost every cell)
+    pub combining: Option<Box<str>>,
 }
 
 impl Default for TerminalCell {
@@ -65,6 +71,7 @@ impl Default for TerminalCell {
             fg: Color::Default,
             bg: Color::Default,
             attrs: CellAttributes::default(),
+            combining: None,
         }
     }
 }
@@ -76,6 +83,7 @@ impl TerminalCell {
         self.fg = Color::Default;
         self.bg = Color::Default;
         self.attrs = CellAttributes::default();
+        self.combining = None;
     }
 }
 
@@ -97,11 +105,13 @@ pub struct SavedCursorState {
     pub bg: Color,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Cursor shape, also the `default_cursor_shape` config option (DECSCUSR changes it)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum CursorShape {
-    Block,
-    Underline,
-    Bar,
+    #[default]
+    Block, // Full cell block (default)
+    Underline, // Bottom line of the cell
+    Bar,       // Vertical bar at the left of the cell
 }
 
 /// VT100 Character Set designation
@@ -131,10 +141,13 @@ pub struct TerminalGrid {
     /// Screen rows (visible portion)
     rows: Vec<Vec<TerminalCell>>,
     /// Scrollback buffer (lines that have scrolled off the top)
-    /// Uses VecDeque for O(1) removal from front when exceeding max_scrollback
-    scrollback: VecDeque<Vec<TerminalCell>>,
+    /// O(1) removal from front when exceeding max_scrollback; older lines may be packed
+    scrollback: Scrollback,
     /// Maximum scrollback lines
     max_scrollback: usize,
+    /// Total lines discarded from the front of the scrollback (trimmed or cleared)
+    /// Lets holders of absolute row indices (e.g. selections) re-anchor to their content
+    scrollback_discarded: u64,
     /// Terminal dimensions
     cols: usize,
     rows_count: usize,
@@ -160,10 +173,14 @@ pub struct TerminalGrid {
     pub bracketed_paste_mode: bool,
     /// Focus event reporting (?1004)
     pub focus_event_mode: bool,
+    /// Output is drawn literally instead of being interpreted (None = normal)
+    control_display: Option<ControlDisplay>,
     /// Synchronized output mode (?2026)
     pub synchronized_output: bool,
     /// Snapshot of rows when synchronized output began (for rendering during sync mode)
     sync_snapshot: Option<Vec<Vec<TerminalCell>>>,
+    /// The previous snapshot, kept so the next one reuses its rows instead of allocating
+    spare_sync_snapshot: Vec<Vec<TerminalCell>>,
     /// Snapshot of cursor when synchronized output began
     sync_cursor_snapshot: Option<Cursor>,
     /// Mouse tracking modes
@@ -196,6 +213,19 @@ pub struct TerminalGrid {
     pub charset_g1: CharacterSet,
     /// Active character set: true = G0, false = G1 (toggled by SI/SO)
     pub charset_use_g0: bool,
+    /// Cursor shape used when no application has requested one (DECSCUSR 0, RIS)
+    default_cursor_shape: CursorShape,
+    /// Sent back to the PTY when ENQ (0x05) is received (empty = no response)
+    answerback: String,
+    /// Set by BEL (0x07), cleared when read with take_bell
+    bell: bool,
+    /// Current directory last reported by the shell (OSC 7)
+    working_directory: Option<String>,
+    /// Cursor color set by the application (OSC 12); None uses the configured color
+    cursor_color: Option<(u8, u8, u8)>,
+    /// Plain text of lines pushed into the scrollback, kept for the output log
+    /// (None while no log is attached)
+    committed_lines: Option<Vec<String>>,
     /// Generation counter - incremented when grid content changes
     /// Used for render cache invalidation
     generation: u64,
@@ -210,8 +240,9 @@ impl TerminalGrid {
 
         Self {
             rows: vec![vec![TerminalCell::default(); cols]; rows],
-            scrollback: VecDeque::new(),
+            scrollback: Scrollback::new(),
             max_scrollback,
+            scrollback_discarded: 0,
             cols,
             rows_count: rows,
             cursor: Cursor::default(),
@@ -226,8 +257,10 @@ impl TerminalGrid {
             application_cursor_keys: false,
             bracketed_paste_mode: false,
             focus_event_mode: false,
+            control_display: None,
             synchronized_output: false,
             sync_snapshot: None,
+            spare_sync_snapshot: Vec::new(),
             sync_cursor_snapshot: None,
             mouse_normal_tracking: false,
             mouse_button_tracking: false,
@@ -244,6 +277,12 @@ impl TerminalGrid {
             charset_g0: CharacterSet::Ascii,
             charset_g1: CharacterSet::Ascii,
             charset_use_g0: true, // Default: use G0
+            default_cursor_shape: CursorShape::Block,
+            answerback: String::new(),
+            bell: false,
+            working_directory: None,
+            cursor_color: None,
+            committed_lines: None,
             generation: 0,
         }
     }
@@ -269,9 +308,19 @@ impl TerminalGrid {
         self.scrollback.len()
     }
 
+    /// Total number of lines discarded from the front of the scrollback so far
+    pub fn scrollback_discarded(&self) -> u64 {
+        self.scrollback_discarded
+    }
+
+    /// Drop all scrollback lines, counting them as discarded
+    fn discard_scrollback(&mut self) {
+        self.scrollback_discarded += self.scrollback.len() as u64;
+        self.scrollback.clear();
+    }
+
     /// Get the current generation counter
     /// This is incremented whenever grid content changes
-    #[allow(dead_code)]
     pub fn generation(&self) -> u64 {
         self.generation
     }
@@ -461,18 +510,34 @@ impl TerminalGrid {
         self.rows.get_mut(y)?.get_mut(x)
     }
 
-    /// Get a line from scrollback (0 = oldest)
-    #[allow(dead_code)]
-    pub fn get_scrollback_line(&self, idx: usize) -> Option<&Vec<TerminalCell>> {
+    /// Get a line from scrollback (0 = oldest), unpacked if it was compressed
+    pub fn get_scrollback_line(&self, idx: usize) -> Option<Cow<'_, [TerminalCell]>> {
         self.scrollback.get(idx)
     }
 
+    /// Character of one scrollback cell (cheaper than a whole compressed line)
+    pub fn get_scrollback_char(&self, idx: usize, col: usize) -> Option<char> {
+        self.scrollback.char_at(idx, col)
+    }
+
+    /// Cell count of a scrollback line, wider than the grid when the window has shrunk since
+    pub fn get_scrollback_width(&self, idx: usize) -> Option<usize> {
+        self.scrollback.width(idx)
+    }
+
+    /// Compress scrollback lines older than the newest `compress_after` (None = off)
+    pub fn set_scrollback_compression(&mut self, compress_after: Option<usize>) {
+        self.scrollback.set_compression(compress_after);
+    }
+
     /// Begin synchronized output mode - takes a snapshot of current state for rendering
     pub fn begin_synchronized_output(&mut self) {
         if !self.synchronized_output {
             self.synchronized_output = true;
             // Take snapshot of current state for rendering during sync mode
-            self.sync_snapshot = Some(self.rows.clone());
+            let mut snapshot = std::mem::take(&mut self.spare_sync_snapshot);
+            snapshot.clone_from(&self.rows);
+            self.sync_snapshot = Some(snapshot);
             self.sync_cursor_snapshot = Some(self.cursor);
         }
     }
@@ -480,7 +545,9 @@ impl TerminalGrid {
     /// End synchronized output mode - clears snapshot to allow live rendering
     pub fn end_synchronized_output(&mut self) {
         self.synchronized_output = false;
-        self.sync_snapshot = None;
+        if let Some(snapshot) = self.sync_snapshot.take() {
+            self.spare_sync_snapshot = snapshot;
+        }
         self.sync_cursor_snapshot = None;
         // Increment generation when sync mode ends to trigger re-render with live data
         self.generation = self.generation.wrapping_add(1);
@@ -512,9 +579,10 @@ impl TerminalGrid {
                     c.width().unwrap_or(0)
                 };
 
-                // Skip zero-width characters (combining marks, etc.) - they don't advance cursor
-                // but we should still render them (TODO: proper combining char support)
+                // Zero-width characters (combining marks, etc.) don't advance the cursor:
+                // they belong to the character written before them
                 if char_width == 0 {
+                    self.attach_combining(c);
                     return;
                 }
 
@@ -563,6 +631,7 @@ impl TerminalGrid {
                     // Write the wide character to first cell
                     if let Some(cell) = self.get_cell_mut(self.cursor.x, self.cursor.y) {
                         cell.c = c;
+                        cell.combining = None;
                         cell.fg = fg;
                         cell.bg = bg;
                         cell.attrs = attrs;
@@ -571,6 +640,7 @@ impl TerminalGrid {
                     // Write a placeholder space to second cell (for wide char continuation)
                     if let Some(cell) = self.get_cell_mut(self.cursor.x + 1, self.cursor.y) {
                         cell.c = ' ';
+                        cell.combining = None;
                         cell.fg = fg;
                         cell.bg = bg;
                         cell.attrs = attrs;
@@ -593,6 +663,7 @@ impl TerminalGrid {
                     if self.cursor.x < self.cols {
                         if let Some(cell) = self.get_cell_mut(self.cursor.x, self.cursor.y) {
                             cell.c = c;
+                            cell.combining = None;
                             cell.fg = fg;
                             cell.bg = bg;
                             cell.attrs = attrs;
@@ -619,6 +690,42 @@ impl TerminalGrid {
         }
     }
 
+    /// Attach a zero-width character to the last written cell, composing it into the
+    /// character when a precomposed form exists
+    fn attach_combining(&mut self, mark: char) {
+        // The last written cell is under the cursor while a wrap is pending, else left of it
+        let x = if self.wrap_pending {
+            self.cursor.x
+        } else {
+            match self.cursor.x.checked_sub(1) {
+                Some(x) => x,
+                None => return,
+            }
+        };
+        let y = self.cursor.y;
+        // A wide character's placeholder: the mark belongs to the character itself
+        let is_wide_placeholder = x > 0
+            && self.get_cell(x, y).is_some_and(|cell| cell.c == ' ')
+            && self
+                .get_cell(x - 1, y)
+                .is_some_and(|cell| !cell.c.is_ascii() && cell.c.width() == Some(2));
+        let x = if is_wide_placeholder { x - 1 } else { x };
+
+        if let Some(cell) = self.get_cell_mut(x, y) {
+            match compose(cell.c, mark) {
+                Some(composed) if cell.combining.is_none() => cell.c = composed,
+                _ => {
+                    let mut marks = cell.combining.take().map(String::from).unwrap_or_default();
+                    if marks.chars().count() < MAX_COMBINING {
+                        marks.push(mark);
+                    }
+                    cell.combining = Some(marks.into_boxed_str());
+                }
+            }
+            self.generation = self.generation.wrapping_add(1);
+        }
+    }
+
     /// Move cursor to the next line, scrolling if necessary
     /// If LNM (Line Feed/New Line Mode) is set, also performs carriage return
     fn linefeed(&mut self) {
@@ -663,13 +770,107 @@ impl TerminalGrid {
         self.linefeed();
     }
 
-    /// Reset terminal to initial state
-    pub fn reset(&mut self) {
-        // Clear screen
-        self.clear_screen();
+    /// Set the default cursor shape
+    /// The current cursor follows along unless an application has changed it
+    pub fn set_default_cursor_shape(&mut self, shape: CursorShape) {
+        if self.cursor.shape == self.default_cursor_shape {
+            self.cursor.shape = shape;
+        }
+        self.default_cursor_shape = shape;
+    }
 
-        // Reset cursor
-        self.cursor = Cursor::default();
+    /// Start or stop keeping the text of lines as they move into the scrollback
+    pub fn set_capture_committed_lines(&mut self, capture: bool) {
+        self.committed_lines = capture.then(Vec::new);
+    }
+
+    /// Take the lines committed to the scrollback since the last call (oldest first)
+    pub fn take_committed_lines(&mut self) -> Vec<String> {
+        self.committed_lines
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Set the answerback string sent in response to ENQ
+    pub fn set_answerback(&mut self, answerback: &str) {
+        self.answerback = answerback.to_string();
+    }
+
+    /// Queue the answerback string in response to ENQ (nothing when unset)
+    pub fn queue_answerback(&mut self) {
+        if !self.answerback.is_empty() {
+            self.queue_response(self.answerback.clone());
+        }
+    }
+
+    /// Record a bell (BEL) from the application
+    pub fn ring_bell(&mut self) {
+        self.bell = true;
+    }
+
+    /// Whether the application rang the bell since the last call
+    pub fn take_bell(&mut self) -> bool {
+        std::mem::take(&mut self.bell)
+    }
+
+    /// Current directory last reported by the shell via OSC 7
+    pub fn working_directory(&self) -> Option<&str> {
+        self.working_directory.as_deref()
+    }
+
+    /// Record the current directory reported by the shell
+    pub fn set_working_directory(&mut self, path: String) {
+        self.working_directory = Some(path);
+    }
+
+    /// Cursor color requested by the application via OSC 12 (None = not set)
+    pub fn cursor_color(&self) -> Option<(u8, u8, u8)> {
+        self.cursor_color
+    }
+
+    /// Show control characters and escape sequences literally (None = interpret them)
+    pub fn set_control_display(&mut self, notation: Option<ControlNotation>) {
+        self.control_display = notation.map(ControlDisplay::new);
+    }
+
+    /// The notation control characters are shown in, None when they are interpreted
+    pub fn control_notation(&self) -> Option<ControlNotation> {
+        self.control_display.as_ref().map(ControlDisplay::notation)
+    }
+
+    /// Draw output literally when control characters are shown
+    /// Returns false (doing nothing) when the output should go through the parser
+    pub fn display_controls(&mut self, data: &[u8]) -> bool {
+        let Some(mut display) = self.control_display.take() else {
+            return false;
+        };
+        display.feed(self, data);
+        self.control_display = Some(display);
+        true
+    }
+
+    /// Set or clear (OSC 112) the application cursor color
+    pub fn set_cursor_color(&mut self, color: Option<(u8, u8, u8)>) {
+        self.cursor_color = color;
+    }
+
+    /// Set the cursor shape from a DECSCUSR parameter (CSI Ps SP q)
+    /// 0 restores the default, 1/2 block, 3/4 underline, 5/6 bar (blinking is not rendered)
+    pub fn set_cursor_style(&mut self, ps: u16) {
+        self.cursor.shape = match ps {
+            0 => self.default_cursor_shape,
+            1 | 2 => CursorShape::Block,
+            3 | 4 => CursorShape::Underline,
+            5 | 6 => CursorShape::Bar,
+            _ => return,
+        };
+    }
+
+    /// Soft terminal reset (DECSTR): modes, margins, attributes and character sets
+    /// The screen, scrollback and cursor position are kept
+    pub fn soft_reset(&mut self) {
+        self.cursor.visible = true;
 
         // Reset attributes
         self.current_attrs = CellAttributes::default();
@@ -683,14 +884,38 @@ impl TerminalGrid {
         // Clear saved cursor
         self.saved_cursor = None;
 
+        // Reset input and positioning modes
+        self.application_cursor_keys = false;
+        self.auto_wrap_mode = true;
+        self.wrap_pending = false;
+        self.insert_mode = false;
+        self.origin_mode = false;
+
+        // Reset character sets
+        self.charset_g0 = CharacterSet::Ascii;
+        self.charset_g1 = CharacterSet::Ascii;
+        self.charset_use_g0 = true;
+    }
+
+    /// Full terminal reset (RIS): back to the state of a newly created terminal
+    pub fn reset(&mut self) {
+        self.soft_reset();
+
+        // Reset cursor (back to the configured default shape)
+        self.cursor = Cursor {
+            shape: self.default_cursor_shape,
+            ..Cursor::default()
+        };
+
+        self.cursor_color = None;
+
         // Clear alt screen
         self.alt_screen = None;
 
         // Reset scrollback
-        self.scrollback.clear();
+        self.discard_scrollback();
 
-        // Reset DEC private modes
-        self.application_cursor_keys = false;
+        // Reset the remaining DEC private modes
         self.bracketed_paste_mode = false;
         self.focus_event_mode = false;
         self.synchronized_output = false;
@@ -703,18 +928,17 @@ impl TerminalGrid {
         self.mouse_sgr_mode = false;
         self.mouse_urxvt_mode = false;
         self.lnm_mode = false;
-        self.auto_wrap_mode = true;
-        self.wrap_pending = false;
-        self.insert_mode = false;
-        self.origin_mode = false;
 
-        // Reset character sets
-        self.charset_g0 = CharacterSet::Ascii;
-        self.charset_g1 = CharacterSet::Ascii;
-        self.charset_use_g0 = true;
+        // Reset tab stops (every 8 columns)
+        for (i, stop) in self.tab_stops.iter_mut().enumerate() {
+            *stop = i % 8 == 0;
+        }
 
         // Clear response queue
         self.response_queue.clear();
+
+        // Clear screen last so it gets the default background
+        self.clear_screen();
     }
 
     /// Move cursor to next tab stop
@@ -739,6 +963,19 @@ impl TerminalGrid {
         }
     }
 
+    /// A blank row, reusing the allocation of a line that is no longer needed
+    /// Scrolling happens for every line of output, so this saves an alloc/free pair each time
+    fn blank_line(&self, spare: Option<Vec<TerminalCell>>) -> Vec<TerminalCell> {
+        match spare {
+            Some(mut line) => {
+                line.clear();
+                line.resize(self.cols, TerminalCell::default());
+                line
+            }
+            None => vec![TerminalCell::default(); self.cols],
+        }
+    }
+
     /// Scroll the scroll region up by n lines
     pub fn scroll_up(&mut self, n: usize) {
         for _ in 0..n {
@@ -747,19 +984,27 @@ impl TerminalGrid {
                 let line = self.rows.remove(self.scroll_region_top);
 
                 // Only add to scrollback if NOT in alternate screen
-                if self.alt_screen.is_none() {
+                let spare = if self.alt_screen.is_none() {
+                    if let Some(committed) = &mut self.committed_lines {
+                        committed.push(line_text(&line));
+                    }
                     self.scrollback.push_back(line);
 
-                    // Limit scrollback size (O(1) with VecDeque)
+                    // Limit scrollback size (O(1))
                     if self.scrollback.len() > self.max_scrollback {
-                        self.scrollback.pop_front();
+                        self.scrollback_discarded += 1;
+                        self.scrollback.pop_front()
+                    } else {
+                        None
                     }
-                }
+                } else {
+                    Some(line)
+                };
 
                 // Insert blank line at bottom of scroll region
                 let insert_pos = self.scroll_region_bottom.min(self.rows_count - 1);
-                self.rows
-                    .insert(insert_pos, vec![TerminalCell::default(); self.cols]);
+                let blank = self.blank_line(spare);
+                self.rows.insert(insert_pos, blank);
             }
         }
         self.generation = self.generation.wrapping_add(1);
@@ -770,13 +1015,11 @@ impl TerminalGrid {
         for _ in 0..n {
             // Remove line at bottom of scroll region
             if self.scroll_region_bottom < self.rows_count {
-                self.rows.remove(self.scroll_region_bottom);
+                let line = self.rows.remove(self.scroll_region_bottom);
 
                 // Insert blank line at top of scroll region
-                self.rows.insert(
-                    self.scroll_region_top,
-                    vec![TerminalCell::default(); self.cols],
-                );
+                let blank = self.blank_line(Some(line));
+                self.rows.insert(self.scroll_region_top, blank);
             }
         }
         self.generation = self.generation.wrapping_add(1);
@@ -788,6 +1031,7 @@ impl TerminalGrid {
         for row in &mut self.rows {
             for cell in row {
                 cell.c = ' ';
+                cell.combining = None;
                 cell.fg = Color::Named(NamedColor::White);
                 cell.bg = bg;
                 cell.attrs = CellAttributes::default();
@@ -796,12 +1040,19 @@ impl TerminalGrid {
         self.generation = self.generation.wrapping_add(1);
     }
 
+    /// Clear the scrollback buffer (ESC[3J), leaving the visible screen intact
+    pub fn clear_scrollback(&mut self) {
+        self.discard_scrollback();
+        self.generation = self.generation.wrapping_add(1);
+    }
+
     /// Clear the current line
     pub fn clear_line(&mut self) {
         let bg = self.current_bg;
         if let Some(row) = self.rows.get_mut(self.cursor.y) {
             for cell in row {
                 cell.c = ' ';
+                cell.combining = None;
                 cell.fg = Color::Named(NamedColor::White);
                 cell.bg = bg;
                 cell.attrs = CellAttributes::default();
@@ -817,6 +1068,7 @@ impl TerminalGrid {
             for x in self.cursor.x..self.cols {
                 if let Some(cell) = row.get_mut(x) {
                     cell.c = ' ';
+                    cell.combining = None;
                     cell.fg = Color::Named(NamedColor::White);
                     cell.bg = bg;
                     cell.attrs = CellAttributes::default();
@@ -833,6 +1085,7 @@ impl TerminalGrid {
             for x in 0..=self.cursor.x {
                 if let Some(cell) = row.get_mut(x) {
                     cell.c = ' ';
+                    cell.combining = None;
                     cell.fg = Color::Named(NamedColor::White);
                     cell.bg = bg;
                     cell.attrs = CellAttributes::default();
@@ -853,6 +1106,7 @@ impl TerminalGrid {
             if let Some(row) = self.rows.get_mut(y) {
                 for cell in row {
                     cell.c = ' ';
+                    cell.combining = None;
                     cell.fg = Color::Named(NamedColor::White);
                     cell.bg = bg;
                     cell.attrs = CellAttributes::default();
@@ -871,6 +1125,7 @@ impl TerminalGrid {
             if let Some(row) = self.rows.get_mut(y) {
                 for cell in row {
                     cell.c = ' ';
+                    cell.combining = None;
                     cell.fg = Color::Named(NamedColor::White);
                     cell.bg = bg;
                     cell.attrs = CellAttributes::default();
@@ -904,6 +1159,7 @@ impl TerminalGrid {
             for x in (end - n)..end {
                 if let Some(cell) = row.get_mut(x) {
                     cell.c = ' ';
+                    cell.combining = None;
                     cell.fg = Color::Named(NamedColor::White);
                     cell.bg = bg;
                     cell.attrs = CellAttributes::default();
@@ -934,6 +1190,7 @@ impl TerminalGrid {
             for x in start..(start + n).min(end) {
                 if let Some(cell) = row.get_mut(x) {
                     cell.c = ' ';
+                    cell.combining = None;
                     cell.fg = Color::Named(NamedColor::White);
                     cell.bg = bg;
                     cell.attrs = CellAttributes::default();
@@ -950,6 +1207,7 @@ impl TerminalGrid {
             for x in self.cursor.x..(self.cursor.x + n).min(self.cols) {
                 if let Some(cell) = row.get_mut(x) {
                     cell.c = ' ';
+                    cell.combining = None;
                     cell.fg = Color::Named(NamedColor::White);
                     cell.bg = bg;
                     cell.attrs = CellAttributes::default();
@@ -1048,6 +1306,11 @@ impl TerminalGrid {
         }
     }
 
+    /// Whether the alternate screen buffer is shown
+    pub fn is_alt_screen(&self) -> bool {
+        self.alt_screen.is_some()
+    }
+
     /// Switch to alternate screen buffer
     pub fn use_alt_screen(&mut self) {
         if self.alt_screen.is_none() {
@@ -1057,8 +1320,11 @@ impl TerminalGrid {
             self.alt_screen = Some(self.rows.clone());
             // Clear current screen
             self.clear_screen();
-            // Reset cursor to home position
-            self.cursor = Cursor::default();
+            // Reset cursor to home position (the cursor style is not part of the screen)
+            self.cursor = Cursor {
+                shape: self.cursor.shape,
+                ..Cursor::default()
+            };
             self.generation = self.generation.wrapping_add(1);
         }
     }
@@ -1147,7 +1413,7 @@ impl TerminalGrid {
         }
 
         // Clear current content
-        self.scrollback.clear();
+        self.discard_scrollback();
         self.rows.clear();
 
         // Split lines into scrollback and visible screen
@@ -1162,17 +1428,18 @@ impl TerminalGrid {
         } else {
             // Some lines go into scrollback
             let scrollback_count = total_lines - self.rows_count;
-            self.scrollback = lines[..scrollback_count].to_vec().into();
-            self.rows = lines[scrollback_count..].to_vec();
+            let mut lines = lines;
+            self.rows = lines.split_off(scrollback_count);
+            for mut line in lines {
+                line.resize(self.cols, TerminalCell::default());
+                self.scrollback.push_back(line);
+            }
         }
 
         // Ensure rows match the current column count
         for row in &mut self.rows {
             row.resize(self.cols, TerminalCell::default());
         }
-        for row in &mut self.scrollback {
-            row.resize(self.cols, TerminalCell::default());
-        }
 
         self.generation = self.generation.wrapping_add(1);
     }
@@ -1195,3 +1462,115 @@ impl fmt::Debug for TerminalGrid {
             .finish()
     }
 }
+
+/// Plain text of a grid line: no attributes, wide-character placeholders skipped,
+/// trailing blanks trimmed
+fn line_text(line: &[TerminalCell]) -> String {
+    let mut text = String::with_capacity(line.len());
+    let mut skip_placeholder = false;
+    for cell in line {
+        if std::mem::take(&mut skip_placeholder) && cell.c == ' ' {
+            continue;
+        }
+        text.push(cell.c);
+        if let Some(marks) = &cell.combining {
+            text.push_str(marks);
+        }
+        skip_placeholder = !cell.c.is_ascii() && cell.c.width() == Some(2);
+    }
+    text.truncate(text.trim_end().len());
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_line(grid: &mut TerminalGrid, text: &str) {
+        for c in text.chars() {
+            grid.put_char(c);
+        }
+        grid.next_line();
+    }
+
+    #[test]
+    fn test_decomposed_text_uses_the_same_columns_as_precomposed() {
+        let mut precomposed = TerminalGrid::new(20, 3, 10);
+        let mut decomposed = TerminalGrid::new(20, 3, 10);
+        for c in "café|".chars() {
+            precomposed.put_char(c);
+        }
+        for c in "cafe\u{301}|".chars() {
+            decomposed.put_char(c);
+        }
+        assert_eq!(precomposed.cursor.x, 5);
+        assert_eq!(decomposed.cursor.x, 5);
+        assert_eq!(decomposed.rows[0], precomposed.rows[0]);
+
+        // Without a precomposed form the mark stays on its cell, also after a wide character
+        let mut grid = TerminalGrid::new(20, 3, 10);
+        for c in "q\u{301}中\u{308}x".chars() {
+            grid.put_char(c);
+        }
+        assert_eq!(grid.cursor.x, 4);
+        assert_eq!(grid.rows[0][0].combining.as_deref(), Some("\u{301}"));
+        assert_eq!(grid.rows[0][1].combining.as_deref(), Some("\u{308}"));
+        assert_eq!(line_text(&grid.rows[0]), "q\u{301}中\u{308}x");
+
+        // Overwriting the cell drops its marks
+        grid.cursor.x = 0;
+        grid.put_char('a');
+        assert_eq!(grid.rows[0][0].combining, None);
+    }
+
+    #[test]
+    fn test_clear_scrollback_keeps_visible_screen() {
+        let mut grid = TerminalGrid::new(10, 3, 100);
+        for i in 0..6 {
+            write_line(&mut grid, &format!("line {}", i));
+        }
+        assert!(grid.scrollback_len() > 0);
+
+        let visible_before: Vec<char> = (0..grid.rows())
+            .flat_map(|y| (0..grid.cols()).map(move |x| (x, y)))
+            .map(|(x, y)| grid.get_cell(x, y).unwrap().c)
+            .collect();
+
+        grid.clear_scrollback();
+
+        assert_eq!(grid.scrollback_len(), 0);
+        let visible_after: Vec<char> = (0..grid.rows())
+            .flat_map(|y| (0..grid.cols()).map(move |x| (x, y)))
+            .map(|(x, y)| grid.get_cell(x, y).unwrap().c)
+            .collect();
+        assert_eq!(visible_before, visible_after);
+    }
+
+    #[test]
+    fn test_committed_lines_follow_scrollback() {
+        let mut grid = TerminalGrid::new(10, 3, 100);
+        grid.set_capture_committed_lines(true);
+        for text in ["one", "two 漢字", "three", "four"] {
+            write_line(&mut grid, text);
+        }
+        // Rows still on screen aren't committed yet
+        assert_eq!(
+            grid.take_committed_lines(),
+            vec!["one".to_string(), "two 漢字".to_string()]
+        );
+        assert!(grid.take_committed_lines().is_empty());
+
+        write_line(&mut grid, "five");
+        assert_eq!(grid.take_committed_lines(), vec!["three".to_string()]);
+
+        // Full-screen applications on the alternate screen don't reach the log
+        grid.use_alt_screen();
+        for _ in 0..4 {
+            write_line(&mut grid, "six");
+        }
+        assert!(grid.take_committed_lines().is_empty());
+
+        grid.set_capture_committed_lines(false);
+        assert!(grid.take_committed_lines().is_empty());
+    }
+}
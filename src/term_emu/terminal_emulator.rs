@@ -1,5 +1,8 @@
 use super::ansi_handler::AnsiHandler;
-use super::term_grid::TerminalGrid;
+use super::c1::C1Translator;
+use super::paste::PasteTransform;
+use super::signal::ForegroundSignal;
+use super::term_grid::{Color, TerminalCell, TerminalGrid};
 use crate::app::session::{
     MAX_LINES_PER_TERMINAL, SerializableCell, SerializableCursor, SerializableTerminalLine,
 };
@@ -18,6 +21,10 @@ use std::process::Command;
 pub struct ShellConfig {
     /// Path to shell executable, None means use OS default
     pub shell_path: Option<String>,
+    /// Command to run instead of the shell in windows without their own command (--exec)
+    pub exec_command: Option<String>,
+    /// Directory to start in (--cwd or default_cwd), None means inherit term39's current directory
+    pub working_dir: Option<String>,
 }
 
 /// Check if a shell can be found, either as a direct path or via PATH lookup
@@ -56,9 +63,23 @@ impl ShellConfig {
     pub fn custom_shell(path: String) -> Self {
         Self {
             shell_path: Some(path),
+            exec_command: None,
+            working_dir: None,
         }
     }
 
+    /// Run the given command instead of the shell in every window (--exec)
+    pub fn with_exec_command(mut self, command: Option<String>) -> Self {
+        self.exec_command = command.filter(|c| !c.trim().is_empty());
+        self
+    }
+
+    /// Start new processes in the given directory
+    pub fn with_working_dir(mut self, dir: Option<String>) -> Self {
+        self.working_dir = dir;
+        self
+    }
+
     /// Validate the shell configuration
     /// Returns Ok(()) if valid, Err with message if invalid
     pub fn validate(&self) -> Result<(), String> {
@@ -87,6 +108,8 @@ pub struct TerminalEmulator {
     grid: Arc<Mutex<TerminalGrid>>,
     /// VTE parser
     parser: Parser,
+    /// Rewrites 8-bit C1 controls before parsing
+    c1: C1Translator,
     /// PTY master (for reading/writing)
     pty_master: Box<dyn MasterPty + Send>,
     /// PTY writer (buffered for efficiency)
@@ -95,6 +118,52 @@ pub struct TerminalEmulator {
     child: Box<dyn Child + Send>,
     /// Channel to receive data from PTY reader thread
     rx: Receiver<Vec<u8>>,
+    /// Total PTY output bytes parsed (for the debug overlay)
+    bytes_processed: u64,
+}
+
+/// Output taken from the reader thread in one `process_output` call
+struct DrainedOutput {
+    chunks: Vec<Vec<u8>>,
+    /// The reader thread is gone (the child closed the PTY)
+    disconnected: bool,
+    /// Chunks were left queued because the byte budget ran out
+    backlog: bool,
+}
+
+/// Take queued chunks until `max_bytes` have been collected (0 = no limit)
+/// Whatever is left stays queued; once the bounded channel fills up the reader thread
+/// blocks, the kernel PTY buffer fills behind it and the flooding program is paused by
+/// ordinary PTY flow control until the UI catches up.
+fn drain_output(rx: &Receiver<Vec<u8>>, max_bytes: usize) -> DrainedOutput {
+    let mut drained = DrainedOutput {
+        chunks: Vec::new(),
+        disconnected: false,
+        backlog: false,
+    };
+    let mut bytes = 0;
+    loop {
+        if max_bytes > 0 && bytes >= max_bytes {
+            drained.backlog = true;
+            break;
+        }
+        match rx.try_recv() {
+            Ok(data) => {
+                bytes += data.len();
+                drained.chunks.push(data);
+            }
+            Err(std::sync::mpsc::TryRecvError::Empty) => {
+                // No more data available right now
+                break;
+            }
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                // Reader thread died - child process exited
+                drained.disconnected = true;
+                break;
+            }
+        }
+    }
+    drained
 }
 
 impl Drop for TerminalEmulator {
@@ -172,6 +241,10 @@ impl TerminalEmulator {
         // Disable PROMPT_SP entirely to prevent any cursor positioning at startup
         cmd.env("PROMPT_SP", "");
 
+        if let Some(ref dir) = shell_config.working_dir {
+            cmd.cwd(dir);
+        }
+
         let child = pty_pair
             .slave
             .spawn_command(cmd)
@@ -221,10 +294,12 @@ impl TerminalEmulator {
         Ok(Self {
             grid,
             parser,
+            c1: C1Translator::new(),
             pty_master,
             writer,
             child,
             rx,
+            bytes_processed: 0,
         })
     }
 
@@ -233,31 +308,25 @@ impl TerminalEmulator {
         self.grid.clone()
     }
 
+    /// Total PTY output bytes parsed so far
+    pub fn bytes_processed(&self) -> u64 {
+        self.bytes_processed
+    }
+
     /// Read output from PTY and process it through the parser
-    pub fn process_output(&mut self) -> std::io::Result<bool> {
-        // Collect ALL available data from PTY reader thread (non-blocking)
+    /// At most about `max_bytes` are parsed per call (0 = everything available), so a
+    /// flooding program can't stall the frame; the rest is picked up on the next call
+    pub fn process_output(&mut self, max_bytes: usize) -> std::io::Result<bool> {
+        // Collect the available data from PTY reader thread (non-blocking)
         // This ensures complete escape sequences are processed before rendering,
         // which is important for TUI applications that use cursor movement for redraws
-        let mut chunks = Vec::new();
-        let mut process_result = Ok(true);
-
-        // First, drain all available chunks without holding the grid lock
-        loop {
-            match self.rx.try_recv() {
-                Ok(data) => {
-                    chunks.push(data);
-                }
-                Err(std::sync::mpsc::TryRecvError::Empty) => {
-                    // No more data available right now
-                    break;
-                }
-                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
-                    // Reader thread died - child process exited
-                    process_result = Ok(false);
-                    break;
-                }
-            }
-        }
+        // First, drain the chunks without holding the grid lock
+        let DrainedOutput {
+            chunks,
+            disconnected,
+            backlog,
+        } = drain_output(&self.rx, max_bytes);
+        let mut process_result = Ok(!disconnected);
 
         // Now process all chunks with a single grid lock acquisition
         if !chunks.is_empty() {
@@ -265,7 +334,12 @@ impl TerminalEmulator {
             let mut handler = AnsiHandler::new(&mut grid);
 
             for data in chunks {
-                self.parser.advance(&mut handler, &data);
+                self.bytes_processed += data.len() as u64;
+                if handler.grid.display_controls(&data) {
+                    continue;
+                }
+                let data = self.c1.translate(&data);
+                self.parser.advance(&mut handler, data);
             }
         }
 
@@ -288,7 +362,8 @@ impl TerminalEmulator {
         //   auto-closed properly.
         // - On Unix, try_wait() reaps the child as soon as it exits, preventing
         //   it from lingering as a zombie until the emulator is dropped.
-        if let Ok(Some(_exit_status)) = self.child.try_wait() {
+        // With output still queued, the exit is reported once the backlog is parsed.
+        if !backlog && let Ok(Some(_exit_status)) = self.child.try_wait() {
             // Child process has exited
             process_result = Ok(false);
         }
@@ -349,90 +424,120 @@ impl TerminalEmulator {
     /// Send pasted text to the terminal, respecting bracketed paste mode
     /// When bracketed paste mode is enabled (?2004), wraps the text with
     /// ESC[200~ (start) and ESC[201~ (end) sequences
-    pub fn send_paste(&mut self, text: &str) -> std::io::Result<()> {
+    /// At a password prompt the text is cleaned up like a plain paste and sent without markers
+    pub fn send_paste(&mut self, text: &str, transform: PasteTransform) -> std::io::Result<()> {
         let bracketed_paste_mode = {
             let grid = self.grid.lock().expect("terminal grid mutex poisoned");
             grid.bracketed_paste_mode
         };
 
-        if bracketed_paste_mode {
+        if self.is_password_input() {
+            // Password prompt: markers would become part of the password
+            self.write_input(transform.apply(text).as_bytes())?;
+        } else if bracketed_paste_mode {
             // Bracketed paste: wrap with ESC[200~ and ESC[201~
             self.write_input(b"\x1b[200~")?;
             self.write_input(text.as_bytes())?;
             self.write_input(b"\x1b[201~")?;
         } else {
-            // Normal paste: send text directly
-            self.write_input(text.as_bytes())?;
+            // Normal paste: clean up line endings and tabs, then send directly
+            self.write_input(transform.apply(text).as_bytes())?;
         }
 
         // Flush to ensure paste is sent immediately
         self.writer.flush()
     }
 
-    /// Extract terminal content (scrollback + visible lines) for session persistence
-    /// Returns at most MAX_LINES_PER_TERMINAL lines (most recent lines are kept)
-    pub fn get_terminal_content(&self) -> (Vec<SerializableTerminalLine>, SerializableCursor) {
+    /// Extract terminal content for session persistence
+    /// Returns (scrollback, visible lines, cursor). Scrollback is limited to the most
+    /// recent `max_scrollback` lines (and MAX_LINES_PER_TERMINAL); with `trim_blanks`
+    /// trailing blank cells are left out to keep the session file small
+    pub fn get_terminal_content(
+        &self,
+        max_scrollback: usize,
+        trim_blanks: bool,
+    ) -> (
+        Vec<SerializableTerminalLine>,
+        Vec<SerializableTerminalLine>,
+        SerializableCursor,
+    ) {
         let grid = self.grid.lock().expect("terminal grid mutex poisoned");
-
-        let mut all_lines = Vec::new();
-
-        // Get scrollback lines (oldest first)
-        let scrollback_len = grid.scrollback_len();
-        for i in 0..scrollback_len {
-            if let Some(line) = grid.get_scrollback_line(i) {
-                let cells: Vec<SerializableCell> =
-                    line.iter().map(SerializableCell::from).collect();
-                all_lines.push(SerializableTerminalLine { cells });
-            }
-        }
-
-        // Get visible screen lines
-        let rows = grid.rows();
-        for y in 0..rows {
-            let mut cells = Vec::new();
-            let cols = grid.cols();
-            for x in 0..cols {
-                if let Some(cell) = grid.get_cell(x, y) {
-                    cells.push(SerializableCell::from(cell));
-                }
-            }
-            all_lines.push(SerializableTerminalLine { cells });
-        }
-
-        // Limit to MAX_LINES_PER_TERMINAL (keep most recent lines)
-        if all_lines.len() > MAX_LINES_PER_TERMINAL {
-            let skip = all_lines.len() - MAX_LINES_PER_TERMINAL;
-            all_lines = all_lines.into_iter().skip(skip).collect();
-        }
-
-        // Get cursor position
-        let cursor = SerializableCursor::from(&grid.cursor);
-
-        (all_lines, cursor)
+        grid_content(&grid, max_scrollback, trim_blanks)
     }
 
     /// Restore terminal content from saved session data
     /// This is called after creating a new terminal to restore previous session content
+    /// Scrollback lines come first (oldest first), followed by the visible screen
     pub fn restore_terminal_content(
         &mut self,
+        scrollback: Vec<SerializableTerminalLine>,
         lines: Vec<SerializableTerminalLine>,
         cursor: &SerializableCursor,
+        trim_blanks: bool,
     ) {
-        // Convert SerializableTerminalLine back to Vec<TerminalCell>
-        let terminal_lines: Vec<Vec<super::term_grid::TerminalCell>> = lines
-            .iter()
-            .map(|line| {
-                line.cells
-                    .iter()
-                    .map(super::term_grid::TerminalCell::from)
-                    .collect()
-            })
-            .collect();
-
-        // Restore content to grid
         let mut grid = self.grid.lock().expect("terminal grid mutex poisoned");
-        grid.restore_content(terminal_lines);
-        grid.set_cursor(cursor.x, cursor.y, cursor.visible);
+        restore_grid_content(&mut grid, &scrollback, &lines, cursor, trim_blanks);
+    }
+
+    /// Whether the terminal is waiting for hidden input such as a password
+    /// Read from the PTY line discipline: echo off in canonical (line) mode is how
+    /// getpass-style prompts (sudo, ssh, passwd) read input; raw-mode apps are not matched
+    #[cfg(unix)]
+    pub fn is_password_input(&self) -> bool {
+        self.pty_master.as_raw_fd().is_some_and(pty_hides_input)
+    }
+
+    /// Whether the terminal is waiting for hidden input (not detectable on Windows)
+    #[cfg(not(unix))]
+    pub fn is_password_input(&self) -> bool {
+        false
+    }
+
+    /// Send a signal to the terminal's foreground process group
+    #[cfg(unix)]
+    pub fn signal_foreground(&self, signal: ForegroundSignal) -> std::io::Result<()> {
+        let fd = self
+            .pty_master
+            .as_raw_fd()
+            .ok_or_else(|| std::io::Error::other("PTY has no file descriptor"))?;
+        super::signal::signal_foreground_group(fd, signal)
+    }
+
+    /// Signals are not available on Windows
+    #[cfg(not(unix))]
+    pub fn signal_foreground(&self, _signal: ForegroundSignal) -> std::io::Result<()> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "signals are not supported on this platform",
+        ))
+    }
+
+    /// Kill the child and everything started from it without waiting for it to exit:
+    /// SIGKILL to the foreground job and the shell's process group, then the child itself
+    /// (only the child on Windows)
+    pub fn force_kill(&mut self) {
+        #[cfg(unix)]
+        {
+            let _ = self.signal_foreground(ForegroundSignal::Kill);
+            if let Some(pid) = self.child.process_id() {
+                let _ = super::signal::kill_process_group(pid);
+            }
+        }
+        let _ = self.child.kill();
+    }
+
+    /// Get the current directory of the shell process (Linux)
+    #[cfg(target_os = "linux")]
+    pub fn get_working_directory(&self) -> Option<String> {
+        let child_pid = self.child.process_id()?;
+        let path = std::fs::read_link(format!("/proc/{}/cwd", child_pid)).ok()?;
+        Some(path.to_string_lossy().into_owned())
+    }
+
+    /// Get the current directory of the shell process (only OSC 7 reports elsewhere)
+    #[cfg(not(target_os = "linux"))]
+    pub fn get_working_directory(&self) -> Option<String> {
+        None
     }
 
     /// Get the name of the foreground process running in the terminal (macOS)
@@ -758,3 +863,236 @@ impl TerminalEmulator {
         None
     }
 }
+
+/// Session lines of a grid: the newest `max_scrollback` scrollback lines, the screen and
+/// the cursor, with trailing blank cells trimmed when `trim_blanks` is set
+fn grid_content(
+    grid: &TerminalGrid,
+    max_scrollback: usize,
+    trim_blanks: bool,
+) -> (
+    Vec<SerializableTerminalLine>,
+    Vec<SerializableTerminalLine>,
+    SerializableCursor,
+) {
+    let to_line = |cells: &[TerminalCell]| {
+        let used = if trim_blanks {
+            trimmed_len(cells)
+        } else {
+            cells.len()
+        };
+        SerializableTerminalLine {
+            cells: cells[..used].iter().map(SerializableCell::from).collect(),
+        }
+    };
+
+    // Get the most recent scrollback lines (oldest first)
+    let scrollback_len = grid.scrollback_len();
+    let keep = scrollback_len.min(max_scrollback.min(MAX_LINES_PER_TERMINAL));
+    let scrollback = ((scrollback_len - keep)..scrollback_len)
+        .filter_map(|i| grid.get_scrollback_line(i))
+        .map(|line| to_line(&line))
+        .collect();
+
+    // Get visible screen lines
+    let lines = (0..grid.rows())
+        .map(|y| {
+            let cells: Vec<TerminalCell> = (0..grid.cols())
+                .filter_map(|x| grid.get_cell(x, y).cloned())
+                .collect();
+            to_line(&cells)
+        })
+        .collect();
+
+    (scrollback, lines, SerializableCursor::from(&grid.cursor))
+}
+
+/// Put saved session lines back into a grid (scrollback first, then the screen)
+/// With `trim_blanks`, trailing blank cells of older session files are dropped too; the
+/// grid pads every row back to its width with default cells
+fn restore_grid_content(
+    grid: &mut TerminalGrid,
+    scrollback: &[SerializableTerminalLine],
+    lines: &[SerializableTerminalLine],
+    cursor: &SerializableCursor,
+    trim_blanks: bool,
+) {
+    let terminal_lines: Vec<Vec<TerminalCell>> = scrollback
+        .iter()
+        .chain(lines)
+        .map(|line| {
+            let mut cells: Vec<TerminalCell> = line.cells.iter().map(TerminalCell::from).collect();
+            if trim_blanks {
+                cells.truncate(trimmed_len(&cells));
+            }
+            cells
+        })
+        .collect();
+
+    grid.restore_content(terminal_lines);
+    grid.set_cursor(cursor.x, cursor.y, cursor.visible);
+}
+
+/// Length of a line without its trailing blank cells: spaces on the default background
+/// with nothing drawn on them (an underline or reverse video makes a space visible)
+fn trimmed_len(cells: &[TerminalCell]) -> usize {
+    let is_blank = |cell: &TerminalCell| {
+        cell.c == ' '
+            && cell.bg == Color::Default
+            && cell.combining.is_none()
+            && !cell.attrs.reverse
+            && !cell.attrs.underline
+            && !cell.attrs.strikethrough
+    };
+    cells
+        .iter()
+        .rposition(|cell| !is_blank(cell))
+        .map_or(0, |p| p + 1)
+}
+
+/// Whether the PTY's line discipline reads hidden input: canonical mode with echo off
+#[cfg(unix)]
+fn pty_hides_input(fd: std::os::unix::io::RawFd) -> bool {
+    unsafe {
+        let mut termios: libc::termios = std::mem::zeroed();
+        if libc::tcgetattr(fd, &mut termios) != 0 {
+            return false;
+        }
+        termios.c_lflag & libc::ICANON != 0 && termios.c_lflag & libc::ECHO == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::term_emu::NamedColor;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_password_input_follows_pty_echo() {
+        let (mut master, mut slave) = (0, 0);
+        let opened = unsafe {
+            libc::openpty(
+                &mut master,
+                &mut slave,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            )
+        };
+        assert_eq!(opened, 0);
+        let set_lflag = |update: &dyn Fn(&mut libc::tcflag_t)| unsafe {
+            let mut termios: libc::termios = std::mem::zeroed();
+            libc::tcgetattr(slave, &mut termios);
+            update(&mut termios.c_lflag);
+            libc::tcsetattr(slave, libc::TCSANOW, &termios);
+        };
+
+        // A fresh PTY echoes, like a shell prompt
+        set_lflag(&|lflag| *lflag |= libc::ICANON | libc::ECHO);
+        assert!(!pty_hides_input(master));
+        // getpass(): line mode without echo
+        set_lflag(&|lflag| *lflag &= !libc::ECHO);
+        assert!(pty_hides_input(master));
+        // Raw mode (full-screen apps) is not a password prompt
+        set_lflag(&|lflag| *lflag &= !libc::ICANON);
+        assert!(!pty_hides_input(master));
+        // Closed descriptor
+        unsafe {
+            libc::close(slave);
+            libc::close(master);
+        }
+        assert!(!pty_hides_input(master));
+    }
+
+    #[test]
+    fn test_drain_output_respects_byte_budget() {
+        let (tx, rx) = sync_channel(8);
+        for _ in 0..5 {
+            tx.send(vec![b'y'; 100]).unwrap();
+        }
+
+        // Stops once the budget is reached and leaves the rest queued
+        let first = drain_output(&rx, 250);
+        assert_eq!(first.chunks.len(), 3);
+        assert!(first.backlog && !first.disconnected);
+
+        drop(tx);
+        // No limit: the rest, then the closed channel
+        let rest = drain_output(&rx, 0);
+        assert_eq!(rest.chunks.len(), 2);
+        assert!(!rest.backlog && rest.disconnected);
+    }
+
+    #[test]
+    fn test_session_scrollback_keeps_most_recent_lines() {
+        let mut grid = TerminalGrid::new(12, 3, 100);
+        let mut handler = AnsiHandler::new(&mut grid);
+        for i in 0..10 {
+            Parser::new().advance(&mut handler, format!("line {}\r\n", i).as_bytes());
+        }
+        let first_char = |line: &[TerminalCell]| line[5].c;
+        assert_eq!(grid.scrollback_len(), 8);
+
+        let (scrollback, lines, cursor) = grid_content(&grid, 4, true);
+        assert_eq!(scrollback.len(), 4);
+        assert_eq!(lines.len(), 3);
+
+        // Scrollback comes back as scrollback, the screen as the screen
+        let mut restored = TerminalGrid::new(12, 3, 100);
+        restore_grid_content(&mut restored, &scrollback, &lines, &cursor, true);
+        assert_eq!(restored.scrollback_len(), 4);
+        let oldest = restored.get_scrollback_line(0).unwrap();
+        assert_eq!(first_char(&oldest), '4');
+        let newest = restored.get_scrollback_line(3).unwrap();
+        assert_eq!(first_char(&newest), '7');
+        assert_eq!(restored.get_cell(5, 0).unwrap().c, '8');
+
+        // A limit of zero saves the screen only
+        let (scrollback, lines, _) = grid_content(&grid, 0, true);
+        assert!(scrollback.is_empty());
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn test_session_content_round_trip_trims_blanks() {
+        let mut grid = TerminalGrid::new(12, 3, 100);
+        let mut handler = AnsiHandler::new(&mut grid);
+        // Red spaces after "ls" look blank; the underlined spaces after "a  b" do not
+        Parser::new().advance(
+            &mut handler,
+            b"ls\x1b[31m   \x1b[0m\r\na  b\x1b[4m  \x1b[0m\r\n$ ",
+        );
+        let text = |grid: &TerminalGrid, y| -> String {
+            (0..grid.cols())
+                .map(|x| grid.get_cell(x, y).unwrap().c)
+                .collect()
+        };
+
+        let (scrollback, lines, cursor) = grid_content(&grid, 100, true);
+        assert!(scrollback.is_empty());
+        assert_eq!(lines[0].cells.len(), 2);
+        assert_eq!(lines[1].cells.len(), 6);
+        assert_eq!(lines[2].cells.len(), 1);
+
+        let mut restored = TerminalGrid::new(12, 3, 100);
+        restore_grid_content(&mut restored, &scrollback, &lines, &cursor, true);
+        for y in 0..3 {
+            assert_eq!(text(&restored, y), text(&grid, y));
+        }
+        assert!(restored.get_cell(1, 1).unwrap().c == ' ');
+        assert!(restored.get_cell(4, 1).unwrap().attrs.underline);
+        assert_eq!(restored.get_cell(2, 0).unwrap().fg, Color::Default);
+        assert_eq!((restored.cursor.x, restored.cursor.y), (2, 2));
+
+        // Without trimming the grid comes back cell for cell
+        let (_, lines, cursor) = grid_content(&grid, 100, false);
+        assert_eq!(lines[0].cells.len(), 12);
+        let mut exact = TerminalGrid::new(12, 3, 100);
+        restore_grid_content(&mut exact, &[], &lines, &cursor, false);
+        assert_eq!(
+            exact.get_cell(2, 0).unwrap().fg,
+            Color::Named(NamedColor::Red)
+        );
+    }
+}
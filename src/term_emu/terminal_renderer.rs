@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 use super::ansi_handler::AnsiHandler;
+use super::c1::C1Translator;
 use super::term_grid::TerminalGrid;
 use std::sync::{Arc, Mutex};
 use vte::Parser;
@@ -15,6 +16,10 @@ pub struct TerminalRenderer {
     grid: Arc<Mutex<TerminalGrid>>,
     /// VTE parser
     parser: Parser,
+    /// Rewrites 8-bit C1 controls before parsing
+    c1: C1Translator,
+    /// Total output bytes parsed (for the debug overlay)
+    bytes_processed: u64,
 }
 
 impl TerminalRenderer {
@@ -23,6 +28,8 @@ impl TerminalRenderer {
         Self {
             grid: Arc::new(Mutex::new(TerminalGrid::new(cols, rows, max_scrollback))),
             parser: Parser::new(),
+            c1: C1Translator::new(),
+            bytes_processed: 0,
         }
     }
 
@@ -31,6 +38,8 @@ impl TerminalRenderer {
         Self {
             grid,
             parser: Parser::new(),
+            c1: C1Translator::new(),
+            bytes_processed: 0,
         }
     }
 
@@ -39,10 +48,20 @@ impl TerminalRenderer {
         self.grid.clone()
     }
 
+    /// Total output bytes parsed so far
+    pub fn bytes_processed(&self) -> u64 {
+        self.bytes_processed
+    }
+
     /// Feed raw PTY output bytes through the VTE parser into the grid
     pub fn feed_output(&mut self, data: &[u8]) {
         let mut grid = self.grid.lock().expect("terminal grid mutex poisoned");
         let mut handler = AnsiHandler::new(&mut grid);
+        self.bytes_processed += data.len() as u64;
+        if handler.grid.display_controls(data) {
+            return;
+        }
+        let data = self.c1.translate(data);
         self.parser.advance(&mut handler, data);
     }
 
@@ -54,6 +73,11 @@ impl TerminalRenderer {
         let mut grid = self.grid.lock().expect("terminal grid mutex poisoned");
         let mut handler = AnsiHandler::new(&mut grid);
         for chunk in chunks {
+            self.bytes_processed += chunk.len() as u64;
+            if handler.grid.display_controls(chunk) {
+                continue;
+            }
+            let chunk = self.c1.translate(chunk);
             self.parser.advance(&mut handler, chunk);
         }
     }
@@ -70,3 +94,28 @@ impl TerminalRenderer {
         grid.resize(cols, rows);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::sample_output::sample_output;
+    use super::*;
+
+    #[test]
+    fn test_feeding_in_chunks_matches_one_feed() {
+        let data = sample_output(64 * 1024);
+        let mut whole = TerminalRenderer::new(80, 24, 1000);
+        whole.feed_output(&data);
+        let mut chunked = TerminalRenderer::new(80, 24, 1000);
+        let chunks: Vec<Vec<u8>> = data.chunks(4093).map(<[u8]>::to_vec).collect();
+        chunked.feed_output_chunks(&chunks);
+
+        let (a, b) = (whole.grid(), chunked.grid());
+        let (a, b) = (a.lock().unwrap(), b.lock().unwrap());
+        assert_eq!(a.scrollback_len(), b.scrollback_len());
+        for y in 0..a.rows() {
+            for x in 0..a.cols() {
+                assert_eq!(a.get_cell(x, y), b.get_cell(x, y));
+            }
+        }
+    }
+}
@@ -72,7 +72,8 @@ pub fn process_config_action(
         ConfigAction::CycleTheme => {
             let next_theme = match app_config.theme.as_str() {
                 "classic" => "monochrome",
-                "monochrome" => "dark",
+                "monochrome" => "paper_white",
+                "paper_white" => "dark",
                 "dark" => "dracu",
                 "dracu" => "green_phosphor",
                 "green_phosphor" => "amber",
@@ -96,7 +97,8 @@ pub fn process_config_action(
                 "classic" => "system",
                 "system" => "dbase",
                 "monochrome" => "classic",
-                "dark" => "monochrome",
+                "paper_white" => "monochrome",
+                "dark" => "paper_white",
                 "dracu" => "dark",
                 "green_phosphor" => "dracu",
                 "amber" => "green_phosphor",
@@ -115,12 +117,14 @@ pub fn process_config_action(
         }
         ConfigAction::CycleKeybindingProfile => {
             app_config.cycle_keybinding_profile();
-            let new_profile = KeybindingProfile::from_name(&app_config.keybinding_profile);
+            let new_profile = KeybindingProfile::from_name(&app_config.keybinding_profile)
+                .with_clipboard_shortcuts(app_config.clipboard_shortcuts);
             result.new_keybinding_profile = Some(new_profile);
         }
         ConfigAction::CycleKeybindingProfileBackward => {
             app_config.cycle_keybinding_profile_backward();
-            let new_profile = KeybindingProfile::from_name(&app_config.keybinding_profile);
+            let new_profile = KeybindingProfile::from_name(&app_config.keybinding_profile)
+                .with_clipboard_shortcuts(app_config.clipboard_shortcuts);
             result.new_keybinding_profile = Some(new_profile);
         }
         ConfigAction::CycleBackgroundChar => {
@@ -672,6 +672,7 @@ impl ConfigWindow {
         let theme_display = match current_theme {
             "classic" => "Classic",
             "monochrome" => "Monochrome",
+            "paper_white" => "Paper White",
             "dark" => "Dark",
             "dracu" => "Dracu",
             "green_phosphor" => "Green Phosphor",
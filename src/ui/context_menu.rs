@@ -1,5 +1,4 @@
 use crate::rendering::{Cell, Charset, Theme, VideoBuffer};
-use crossterm::style::Color;
 
 /// Menu item action
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -7,6 +6,7 @@ pub enum MenuAction {
     Copy,
     Paste,
     SelectAll,
+    Clear,
     #[allow(dead_code)]
     Close,
     // Taskbar menu actions
@@ -21,6 +21,12 @@ pub enum MenuAction {
     Help,
     About,
     Exit,
+    // Desktop menu actions
+    NewTerminal,
+    /// Run the command at this index of the menu's command list
+    RunCommand(usize),
+    /// The configured System menu entry at this index (`system_menu_items`)
+    UserItem(usize),
 }
 
 /// Menu item definition
@@ -73,6 +79,8 @@ impl ContextMenu {
             MenuItem::new("Copy", None, MenuAction::Copy),
             MenuItem::new("Paste", None, MenuAction::Paste),
             MenuItem::new("Select All", None, MenuAction::SelectAll),
+            MenuItem::separator(),
+            MenuItem::new("Clear", None, MenuAction::Clear),
         ];
 
         Self {
@@ -103,12 +111,49 @@ impl ContextMenu {
         }
     }
 
-    /// Create a System dropdown menu
-    pub fn new_system_menu(x: u16, y: u16, menu_width: u16, charset: &Charset) -> Self {
+    /// Create a desktop menu (click on empty desktop)
+    /// Lists a "New Terminal" entry followed by the given launcher commands
+    pub fn new_desktop_menu(x: u16, y: u16, commands: &[String]) -> Self {
+        let mut items = vec![MenuItem::new("New Terminal", None, MenuAction::NewTerminal)];
+
+        if !commands.is_empty() {
+            items.push(MenuItem::separator());
+            for (i, command) in commands.iter().enumerate() {
+                items.push(MenuItem::new(command, None, MenuAction::RunCommand(i)));
+            }
+        }
+
+        Self {
+            x,
+            y,
+            items,
+            selected_index: 0,
+            visible: false,
+            min_width: None,
+        }
+    }
+
+    /// Create a System dropdown menu, listing the configured `user_items` labels first
+    pub fn new_system_menu(
+        x: u16,
+        y: u16,
+        menu_width: u16,
+        charset: &Charset,
+        user_items: &[String],
+    ) -> Self {
+        let mut items: Vec<MenuItem> = user_items
+            .iter()
+            .enumerate()
+            .map(|(i, label)| MenuItem::new(label, None, MenuAction::UserItem(i)))
+            .collect();
+        if !items.is_empty() {
+            items.push(MenuItem::separator());
+        }
+
         // Clipboard operations + Settings + About + Exit
         // Copy, Paste, Clear Clipboard are always visible but enabled/disabled based on context
         // Icons use charset for Unicode/ASCII compatibility
-        let items = vec![
+        items.extend([
             MenuItem::new("Copy", Some(charset.icon_copy), MenuAction::CopySelection),
             MenuItem::new(
                 "Paste",
@@ -130,7 +175,7 @@ impl ContextMenu {
             MenuItem::new("About...", Some(charset.icon_about), MenuAction::About),
             MenuItem::separator(),
             MenuItem::new("Exit", Some(charset.icon_exit), MenuAction::Exit),
-        ];
+        ]);
 
         Self {
             x,
@@ -151,6 +196,15 @@ impl ContextMenu {
         }
     }
 
+    /// Change the label of the item with the given action
+    pub fn set_item_label(&mut self, action: MenuAction, label: &str) {
+        for item in &mut self.items {
+            if item.action == Some(action) {
+                item.label = label.to_string();
+            }
+        }
+    }
+
     /// Show the menu at a new position
     pub fn show(&mut self, x: u16, y: u16) {
         self.x = x;
@@ -174,6 +228,18 @@ impl ContextMenu {
         self.selected_index = 0;
     }
 
+    /// Show the menu with bounds checking on both axes
+    /// Adjusts x and y so the menu and its shadow stay within `min_y..max_y` and the screen width
+    pub fn show_clamped(&mut self, x: u16, y: u16, screen_width: u16, min_y: u16, max_y: u16) {
+        let total_height = self.items.len() as u16 + 3; // +2 for borders, +1 for shadow row
+        let y = if y + total_height > max_y {
+            max_y.saturating_sub(total_height)
+        } else {
+            y
+        };
+        self.show_bounded(x, y.max(min_y), screen_width);
+    }
+
     /// Get total width including shadow (for bounds calculations)
     pub fn total_width_with_shadow(&self) -> u16 {
         self.calculate_width() + 1 // +1 for shadow column
@@ -422,7 +488,7 @@ impl ContextMenu {
         theme: &Theme,
     ) {
         let shadow_fg = theme.menu_shadow_fg;
-        let shadow_bg = Color::Black;
+        let shadow_bg = theme.window_shadow_bg;
 
         // Right shadow
         // Use new_unchecked for performance - shadow colors are intentionally low contrast
@@ -0,0 +1,132 @@
+//! Debug overlay with per-window emulator metrics (enabled with --debug-overlay)
+//! Nothing is sampled while the overlay is hidden
+
+use crate::rendering::{Cell, Theme, VideoBuffer};
+use crate::window::terminal_window::WindowStats;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How often byte rates are recalculated
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Metrics overlay drawn in the top-right corner
+pub struct DebugOverlay {
+    pub visible: bool,
+    frame_time: Duration,
+    sampled_at: Instant,
+    /// Per window: (bytes at the last sample, bytes per second over the last interval)
+    samples: HashMap<u32, (u64, u64)>,
+}
+
+impl DebugOverlay {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            frame_time: Duration::ZERO,
+            sampled_at: Instant::now(),
+            samples: HashMap::new(),
+        }
+    }
+
+    /// Show or hide the overlay (rates restart from zero when shown)
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+        self.samples.clear();
+        self.sampled_at = Instant::now();
+    }
+
+    /// Record how long the last frame took to render
+    pub fn record_frame_time(&mut self, elapsed: Duration) {
+        self.frame_time = elapsed;
+    }
+
+    /// Refresh byte rates once per sample interval
+    pub fn update(&mut self, stats: &[WindowStats]) {
+        let elapsed = self.sampled_at.elapsed();
+        if elapsed < SAMPLE_INTERVAL && !self.samples.is_empty() {
+            return;
+        }
+        self.sampled_at = Instant::now();
+
+        let mut samples = HashMap::with_capacity(stats.len());
+        for stat in stats {
+            let rate = self.samples.get(&stat.id).map_or(0, |&(prev, _)| {
+                bytes_per_second(prev, stat.bytes_processed, elapsed)
+            });
+            samples.insert(stat.id, (stat.bytes_processed, rate));
+        }
+        self.samples = samples;
+    }
+
+    /// Draw the overlay below the top bar using the menu colors
+    pub fn render(&self, buffer: &mut VideoBuffer, theme: &Theme, stats: &[WindowStats]) {
+        let mut lines = vec![format!(
+            "frame {:.1} ms  windows {}",
+            self.frame_time.as_secs_f32() * 1000.0,
+            stats.len()
+        )];
+        for stat in stats {
+            let rate = self.samples.get(&stat.id).map_or(0, |&(_, rate)| rate);
+            lines.push(format!(
+                "#{} {}x{}  sb {}  {}",
+                stat.id,
+                stat.cols,
+                stat.rows,
+                stat.scrollback_len,
+                format_rate(rate)
+            ));
+        }
+
+        let (cols, rows) = buffer.dimensions();
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) as u16 + 2;
+        let x = cols.saturating_sub(width + 1);
+        let fg = theme.menu_fg;
+        let bg = theme.menu_bg;
+        for (i, line) in lines.iter().enumerate() {
+            let y = 1 + i as u16;
+            if y >= rows.saturating_sub(1) {
+                break;
+            }
+            for dx in 0..width {
+                buffer.set(x + dx, y, Cell::new_unchecked(' ', fg, bg));
+            }
+            for (j, ch) in line.chars().enumerate() {
+                buffer.set(x + 1 + j as u16, y, Cell::new_unchecked(ch, fg, bg));
+            }
+        }
+    }
+}
+
+/// Average throughput between two byte counts taken `elapsed` apart
+fn bytes_per_second(previous: u64, current: u64, elapsed: Duration) -> u64 {
+    let secs = elapsed.as_secs_f64();
+    if secs <= 0.0 {
+        return 0;
+    }
+    (current.saturating_sub(previous) as f64 / secs) as u64
+}
+
+/// Human-readable byte rate ("512 B/s", "12.3 KB/s", "4.0 MB/s")
+fn format_rate(bytes_per_sec: u64) -> String {
+    match bytes_per_sec {
+        0..1024 => format!("{} B/s", bytes_per_sec),
+        1024..1_048_576 => format!("{:.1} KB/s", bytes_per_sec as f64 / 1024.0),
+        _ => format!("{:.1} MB/s", bytes_per_sec as f64 / 1_048_576.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_calculation_and_format() {
+        assert_eq!(bytes_per_second(1000, 3000, Duration::from_secs(2)), 1000);
+        assert_eq!(bytes_per_second(3000, 1000, Duration::from_secs(1)), 0);
+        assert_eq!(bytes_per_second(0, 100, Duration::ZERO), 0);
+
+        assert_eq!(format_rate(512), "512 B/s");
+        assert_eq!(format_rate(12_595), "12.3 KB/s");
+        assert_eq!(format_rate(4 * 1_048_576), "4.0 MB/s");
+    }
+}
@@ -1,8 +1,8 @@
 use super::config_window::ConfigAction;
 use super::error_dialog::ErrorDialog;
-use super::prompt::PromptAction;
-use crate::app::app_state::AppState;
-use crate::app::config_manager::AppConfig;
+use super::prompt::{Prompt, PromptAction, PromptButton, PromptType};
+use crate::app::app_state::{AppState, LaunchRequest, PendingConfirmation};
+use crate::app::config_manager::{AppConfig, LauncherEmptyAction};
 use crate::rendering::RenderBackend;
 use crate::utils::{CommandHistory, CommandIndexer};
 use crate::window::manager::WindowManager;
@@ -35,22 +35,7 @@ pub fn handle_prompt_keyboard(app_state: &mut AppState, key_event: KeyEvent) ->
             KeyCode::Enter => {
                 // Enter - activate selected button
                 if let Some(action) = prompt.get_selected_action() {
-                    match action {
-                        PromptAction::Confirm => {
-                            // Exit confirmed
-                            return Some(true);
-                        }
-                        PromptAction::Cancel => {
-                            // Dismiss prompt
-                            app_state.active_prompt = None;
-                        }
-                        PromptAction::Custom(1) => {
-                            // Exit & Kill Daemon
-                            app_state.should_kill_daemon = true;
-                            return Some(true);
-                        }
-                        _ => {}
-                    }
+                    return Some(apply_prompt_action(app_state, action));
                 }
                 return Some(false);
             }
@@ -68,6 +53,33 @@ pub fn handle_prompt_keyboard(app_state: &mut AppState, key_event: KeyEvent) ->
     None
 }
 
+/// Carry out a prompt button's action (Enter on the selected button, or a click)
+/// Returns true if the action exits the app
+pub fn apply_prompt_action(app_state: &mut AppState, action: PromptAction) -> bool {
+    match action {
+        // Exit confirmed
+        PromptAction::Confirm => true,
+        PromptAction::Cancel => {
+            app_state.active_prompt = None;
+            false
+        }
+        PromptAction::Custom(1) => {
+            // Exit & Kill Daemon
+            app_state.should_kill_daemon = true;
+            true
+        }
+        PromptAction::ConfirmPending => {
+            app_state.confirm_pending();
+            false
+        }
+        PromptAction::DuplicateShell => {
+            app_state.confirm_duplicate_shell();
+            false
+        }
+        PromptAction::Custom(_) => false,
+    }
+}
+
 /// Handles keyboard events when an error dialog is active
 /// Returns true if event was handled
 pub fn handle_error_dialog_keyboard(app_state: &mut AppState, key_event: KeyEvent) -> bool {
@@ -87,16 +99,37 @@ pub fn handle_error_dialog_keyboard(app_state: &mut AppState, key_event: KeyEven
     false
 }
 
+/// Handles keyboard events when a popup menu (context, taskbar, system, desktop) is visible
+/// Escape dismisses the menu; other keys pass through
+/// Returns true if event was handled
+pub fn handle_menu_keyboard(app_state: &mut AppState, key_event: KeyEvent) -> bool {
+    let any_menu_visible = app_state.context_menu.visible
+        || app_state.taskbar_menu.visible
+        || app_state.system_menu.visible
+        || app_state.desktop_menu.visible;
+
+    if any_menu_visible && key_event.code == KeyCode::Esc {
+        app_state.context_menu.hide();
+        app_state.taskbar_menu.hide();
+        app_state.taskbar_menu_window_id = None;
+        app_state.system_menu.hide();
+        app_state.top_bar.close_system_menu();
+        app_state.desktop_menu.hide();
+        return true;
+    }
+    false
+}
+
 /// Handles keyboard events when Slight input is active
 /// Returns true if event was handled
 pub fn handle_slight_input_keyboard(
     app_state: &mut AppState,
     key_event: KeyEvent,
-    _command_indexer: &CommandIndexer,
+    command_indexer: &CommandIndexer,
     command_history: &mut CommandHistory,
     window_manager: &mut WindowManager,
     backend: &dyn RenderBackend,
-    tiling_gaps: bool,
+    app_config: &AppConfig,
 ) -> bool {
     if let Some(ref mut slight_input) = app_state.active_slight_input {
         match key_event.code {
@@ -146,51 +179,71 @@ pub fn handle_slight_input_keyboard(
                 // Get the command and create a new terminal window with it
                 let command = slight_input.get_input();
 
-                // Record command in history before closing
-                if !command.is_empty() {
-                    command_history.record_command(&command);
+                // Built-in launcher commands (":name") act on the focused window
+                if let Some(builtin) = command.strip_prefix(':') {
+                    app_state.active_slight_input = None;
+                    run_launcher_builtin(
+                        app_state,
+                        builtin.trim(),
+                        window_manager,
+                        backend,
+                        app_config.tiling_gaps,
+                    );
+                    return true;
                 }
 
-                app_state.active_slight_input = None;
-
-                if !command.is_empty() {
-                    // Create a new terminal window and run the command
-                    let (cols, rows) = backend.dimensions();
-
-                    // Calculate dynamic window size based on screen dimensions
-                    let (width, height) = WindowManager::calculate_window_size(cols, rows);
-
-                    // Get position: cascade if auto-tiling is off, center otherwise
-                    // Minimum y=1 to avoid overlapping with topbar at y=0
-                    let (x, y) = if app_state.auto_tiling_enabled {
-                        let x = (cols.saturating_sub(width)) / 2;
-                        let y = 1 + (rows.saturating_sub(2).saturating_sub(height)) / 2;
-                        (x, y.max(1))
-                    } else {
-                        window_manager.get_cascade_position(width, height, cols, rows)
-                    };
-
-                    match window_manager.create_window(
-                        x,
-                        y,
-                        width,
-                        height,
-                        format!("Terminal {}", window_manager.window_count() + 1),
-                        Some(command),
-                    ) {
-                        Ok(_terminal_id) => {
-                            // Auto-position all windows based on the snap pattern
-                            if app_state.auto_tiling_enabled {
-                                window_manager.auto_position_windows(cols, rows, tiling_gaps);
-                            }
+                // An empty query follows the configured action
+                if command.trim().is_empty() {
+                    let top = slight_input.selected_command().map(str::to_string);
+                    app_state.active_slight_input = None;
+                    match app_config.launcher_empty_action {
+                        LauncherEmptyAction::Nothing => {}
+                        LauncherEmptyAction::NewTerminal => {
+                            crate::input::keyboard_handlers::create_terminal_window(
+                                app_state,
+                                window_manager,
+                                backend,
+                                false,
+                                app_config.tiling_gaps,
+                            );
                         }
-                        Err(error_msg) => {
-                            // Show error dialog
-                            app_state.active_error_dialog =
-                                Some(ErrorDialog::new(cols, rows, error_msg));
+                        LauncherEmptyAction::TopSuggestion => {
+                            if let Some(top) = top {
+                                command_history.record_command(&top);
+                                launch_command(
+                                    app_state,
+                                    window_manager,
+                                    backend,
+                                    app_config.tiling_gaps,
+                                    top,
+                                );
+                            }
                         }
                     }
+                    return true;
                 }
+
+                // A query with no matches is run verbatim only after confirmation
+                let unknown = app_config.launcher_confirm_unknown
+                    && !slight_input.has_suggestions()
+                    && !is_known_command(&command, command_indexer, command_history);
+
+                app_state.active_slight_input = None;
+
+                if unknown {
+                    confirm_unknown_launch(app_state, backend, command);
+                    return true;
+                }
+
+                // Record command in history before running it
+                command_history.record_command(&command);
+                launch_command(
+                    app_state,
+                    window_manager,
+                    backend,
+                    app_config.tiling_gaps,
+                    command,
+                );
                 return true;
             }
             KeyCode::Esc => {
@@ -207,6 +260,151 @@ pub fn handle_slight_input_keyboard(
     false
 }
 
+/// Whether the launcher knows `command`: its program is on PATH or a path, or it was run before
+fn is_known_command(
+    command: &str,
+    command_indexer: &CommandIndexer,
+    command_history: &CommandHistory,
+) -> bool {
+    let program = command.split_whitespace().next().unwrap_or("");
+    program.contains('/')
+        || command_indexer.get_commands().iter().any(|c| c == program)
+        // The history counts program names, not whole command lines
+        || command_history.get_frequency(program) > 0
+}
+
+/// Ask before running a launcher query that matched nothing
+fn confirm_unknown_launch(app_state: &mut AppState, backend: &dyn RenderBackend, command: String) {
+    let (cols, rows) = backend.dimensions();
+    let buttons = vec![
+        PromptButton::new("Cancel".to_string(), PromptAction::Cancel, false),
+        PromptButton::new("Run".to_string(), PromptAction::ConfirmPending, true),
+    ];
+    app_state.ask_confirmation(
+        Prompt::new(
+            PromptType::Info,
+            format!("No command matches \"{}\". Run it anyway?", command),
+            buttons,
+            cols,
+            rows,
+        )
+        .with_selection_indicators(true)
+        .with_selected_button(1),
+        PendingConfirmation::Launch(LaunchRequest { command }),
+    );
+}
+
+/// Open a new terminal window running `command` (centered when auto-tiling, cascaded otherwise)
+pub fn launch_command(
+    app_state: &mut AppState,
+    window_manager: &mut WindowManager,
+    backend: &dyn RenderBackend,
+    tiling_gaps: bool,
+    command: String,
+) {
+    let (cols, rows) = backend.dimensions();
+
+    // Calculate dynamic window size based on screen dimensions
+    let (width, height) = WindowManager::calculate_window_size(cols, rows);
+
+    // Get position: configured placement if auto-tiling is off, center otherwise
+    // Minimum y=1 to avoid overlapping with topbar at y=0
+    let (x, y) = if app_state.auto_tiling_enabled {
+        let x = (cols.saturating_sub(width)) / 2;
+        let y = 1 + (rows.saturating_sub(2).saturating_sub(height)) / 2;
+        (x, y.max(1))
+    } else {
+        window_manager.next_window_position(width, height, cols, rows, app_state.pointer_position)
+    };
+
+    match window_manager.create_window(
+        x,
+        y,
+        width,
+        height,
+        format!("Terminal {}", window_manager.window_count() + 1),
+        Some(command),
+    ) {
+        Ok(_terminal_id) => {
+            // Auto-position all windows based on the snap pattern
+            if app_state.auto_tiling_enabled {
+                window_manager.auto_position_windows(cols, rows, tiling_gaps);
+            }
+        }
+        Err(error_msg) => {
+            // Show error dialog
+            app_state.active_error_dialog = Some(ErrorDialog::new(cols, rows, error_msg));
+        }
+    }
+}
+
+/// Runs a built-in launcher command (entered as ":name [arg]" in the Slight input)
+fn run_launcher_builtin(
+    app_state: &mut AppState,
+    command: &str,
+    window_manager: &mut WindowManager,
+    backend: &dyn RenderBackend,
+    tiling_gaps: bool,
+) {
+    let (name, arg) = command
+        .split_once(char::is_whitespace)
+        .map_or((command, ""), |(name, arg)| (name, arg.trim()));
+    match name {
+        "clear-scrollback" => {
+            if !window_manager.clear_focused_scrollback() {
+                app_state.active_toast = Some(crate::ui::toast::Toast::new("No window focused"));
+            }
+        }
+        "duplicate" => {
+            let (cols, rows) = backend.dimensions();
+            crate::input::keyboard_handlers::duplicate_focused_window(
+                app_state,
+                window_manager,
+                cols,
+                rows,
+                tiling_gaps,
+            );
+        }
+        "tag" => {
+            // ":tag <color>" sets, ":tag none" clears, bare ":tag" cycles
+            let tag = match arg {
+                "" => window_manager.cycle_focused_tag(),
+                "none" | "clear" => window_manager.set_focused_tag(None).then_some(None),
+                color => match crate::window::base::tag_color_by_name(color) {
+                    Some(tag) => window_manager
+                        .set_focused_tag(Some(tag))
+                        .then_some(Some(tag)),
+                    None => {
+                        let names: Vec<&str> = crate::window::base::WINDOW_TAGS
+                            .iter()
+                            .map(|(name, _)| *name)
+                            .collect();
+                        app_state.active_toast = Some(crate::ui::toast::Toast::new(format!(
+                            "Tag colors: {}, none",
+                            names.join(", ")
+                        )));
+                        return;
+                    }
+                },
+            };
+            let message = match tag {
+                None => "No window focused".to_string(),
+                Some(tag) => match tag.and_then(crate::window::base::tag_name) {
+                    Some(name) => format!("Window tag: {}", name),
+                    None => "Window tag cleared".to_string(),
+                },
+            };
+            app_state.active_toast = Some(crate::ui::toast::Toast::new(message));
+        }
+        _ => {
+            app_state.active_toast = Some(crate::ui::toast::Toast::new(format!(
+                "Unknown command: :{}",
+                name
+            )));
+        }
+    }
+}
+
 /// Handles keyboard events when calendar is active
 /// Returns true if event was handled
 pub fn handle_calendar_keyboard(app_state: &mut AppState, key_event: KeyEvent) -> bool {
@@ -308,6 +506,37 @@ pub fn handle_winmode_help_window_keyboard(app_state: &mut AppState, key_event:
     false
 }
 
+/// Handles keyboard events when the expose overview is active
+/// Returns true if event was handled
+pub fn handle_expose_keyboard(
+    app_state: &mut AppState,
+    key_event: KeyEvent,
+    window_manager: &mut WindowManager,
+) -> bool {
+    if !app_state.show_expose {
+        return false;
+    }
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Enter | KeyCode::F(9) => {
+            // Return to the desktop without changing focus
+            app_state.show_expose = false;
+        }
+        KeyCode::Char(c @ '1'..='9') => {
+            // Select the window with this number
+            if let Some(num) = c.to_digit(10) {
+                if let Some(window_id) = window_manager.find_window_by_title_number(num) {
+                    window_manager.restore_and_focus_window(window_id);
+                    app_state.show_expose = false;
+                }
+            }
+        }
+        _ => {
+            // Ignore other keys while the overview is shown
+        }
+    }
+    true
+}
+
 /// Handles keyboard events when config window is active
 /// Returns Some(ConfigAction) if event was handled, None otherwise
 pub fn handle_config_window_keyboard(
@@ -488,3 +717,32 @@ pub fn handle_lockscreen_keyboard(app_state: &mut AppState, key_event: KeyEvent)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_known_command() {
+        let indexer = CommandIndexer::with_commands(vec!["htop".to_string(), "vim".to_string()]);
+        // Deserialized history has no file, so nothing is written to disk
+        let history: CommandHistory =
+            serde_json::from_str(r#"{"frequency":{"mytool":3}}"#).unwrap();
+
+        assert!(is_known_command("htop", &indexer, &history));
+        assert!(is_known_command("vim notes.txt", &indexer, &history));
+        // Run before, though no longer on PATH
+        assert!(is_known_command("mytool --flag", &indexer, &history));
+        // Paths are run as given
+        assert!(is_known_command("./build.sh", &indexer, &history));
+        assert!(is_known_command(
+            "/usr/local/bin/tool -v",
+            &indexer,
+            &history
+        ));
+
+        assert!(!is_known_command("htpo", &indexer, &history));
+        assert!(!is_known_command("not-a-command vim", &indexer, &history));
+        assert!(!is_known_command("", &indexer, &history));
+    }
+}
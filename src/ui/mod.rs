@@ -2,10 +2,12 @@ pub mod button;
 pub mod config_action_handler;
 pub mod config_window;
 pub mod context_menu;
+pub mod debug_overlay;
 pub mod dialog_handlers;
 pub mod error_dialog;
 pub mod info_window;
 pub mod prompt;
+pub mod screensaver;
 pub mod simple_input;
 pub mod slight_input;
 pub mod splash_screen;
@@ -1,5 +1,6 @@
 use crate::rendering::{Cell, Charset, CharsetMode, Theme, VideoBuffer, render_shadow};
 use crossterm::style::Color;
+use std::time::{Duration, Instant};
 
 /// Prompt types with different visual styles (similar to Bootstrap alerts)
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -48,6 +49,10 @@ pub struct PromptButton {
 pub enum PromptAction {
     Confirm,
     Cancel,
+    /// Carry out the request waiting on the prompt (`AppState::pending_confirmation`)
+    ConfirmPending,
+    /// Duplicate a window with a plain shell instead of rerunning its command
+    DuplicateShell,
     #[allow(dead_code)]
     Custom(u32),
 }
@@ -136,6 +141,7 @@ pub struct Prompt {
     pub text_align: TextAlign,        // Text alignment for the message
     pub show_selection_indicators: bool, // Whether to show >< around selected button
     pub position_mode: PositionMode,  // How to position the dialog
+    pub created_at: Instant,          // When the prompt was shown (for auto-dismiss)
 }
 
 impl Prompt {
@@ -206,6 +212,23 @@ impl Prompt {
             text_align,
             show_selection_indicators: false, // Default: no indicators (backward compatible)
             position_mode: PositionMode::CenteredOnScreen,
+            created_at: Instant::now(),
+        }
+    }
+
+    /// Check if this is a notification (only dismiss buttons, nothing to confirm)
+    pub fn is_notification(&self) -> bool {
+        self.buttons
+            .iter()
+            .all(|b| b.action == PromptAction::Cancel)
+    }
+
+    /// Check if a notification prompt has been shown longer than the timeout
+    /// Prompts that ask for a decision never expire
+    pub fn is_expired(&self, timeout: Option<Duration>) -> bool {
+        match timeout {
+            Some(timeout) => self.is_notification() && self.created_at.elapsed() >= timeout,
+            None => false,
         }
     }
 
@@ -639,6 +662,35 @@ mod tests {
         assert_eq!(prompt.buttons.len(), 2);
     }
 
+    #[test]
+    fn test_notification_prompt_expires() {
+        let buttons = vec![PromptButton::new(
+            "OK".to_string(),
+            PromptAction::Cancel,
+            true,
+        )];
+        let mut prompt = Prompt::new(PromptType::Info, "Saved".to_string(), buttons, 80, 24);
+        prompt.created_at = Instant::now() - Duration::from_secs(10);
+
+        assert!(prompt.is_notification());
+        assert!(prompt.is_expired(Some(Duration::from_secs(5))));
+        assert!(!prompt.is_expired(Some(Duration::from_secs(30))));
+        assert!(!prompt.is_expired(None));
+    }
+
+    #[test]
+    fn test_confirmation_prompt_never_expires() {
+        let buttons = vec![
+            PromptButton::new("Yes".to_string(), PromptAction::Confirm, true),
+            PromptButton::new("No".to_string(), PromptAction::Cancel, false),
+        ];
+        let mut prompt = Prompt::new(PromptType::Info, "Proceed?".to_string(), buttons, 80, 24);
+        prompt.created_at = Instant::now() - Duration::from_secs(10);
+
+        assert!(!prompt.is_notification());
+        assert!(!prompt.is_expired(Some(Duration::from_secs(5))));
+    }
+
     #[test]
     fn test_button_width() {
         let button = PromptButton::new("OK".to_string(), PromptAction::Confirm, true);
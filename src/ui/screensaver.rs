@@ -0,0 +1,202 @@
+//! Desktop screensaver shown after a configurable idle time (distinct from the lockscreen)
+//! Effects are pure functions of the elapsed time, so a frame costs one pass over the
+//! screen and nothing is kept between frames. Any key or mouse input dismisses it and is
+//! still delivered as usual.
+
+use crate::app::config_manager::ScreensaverEffect;
+use crate::rendering::{Cell, Theme, VideoBuffer};
+use crate::utils::scramble;
+use crossterm::style::Color;
+use std::time::Instant;
+
+/// Warp stars per this many screen cells
+const STAR_DENSITY: u32 = 24;
+
+/// Glyphs falling in the matrix rain (ASCII so every charset can show them)
+const RAIN_GLYPHS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ$+-*/=%#&<>";
+
+/// Milliseconds per cell moved by the bouncing title (vertical steps take twice as long)
+const BOUNCE_STEP_MS: u64 = 120;
+
+/// An active screensaver
+pub struct Screensaver {
+    effect: ScreensaverEffect,
+    started_at: Instant,
+}
+
+impl Screensaver {
+    pub fn new(effect: ScreensaverEffect) -> Self {
+        Self {
+            effect,
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Draw the effect over the whole screen; `title` is what the bouncer shows
+    pub fn render(&self, buffer: &mut VideoBuffer, theme: &Theme, title: &str) {
+        let (cols, rows) = buffer.dimensions();
+        if cols == 0 || rows == 0 {
+            return;
+        }
+        let bg = Color::Black;
+        for y in 0..rows {
+            for x in 0..cols {
+                buffer.set(x, y, Cell::new(' ', theme.splash_border, bg));
+            }
+        }
+
+        let elapsed_ms = self.started_at.elapsed().as_millis() as u64;
+        match self.effect {
+            ScreensaverEffect::Starfield => {
+                let count = (cols as u32 * rows as u32) / STAR_DENSITY;
+                for i in 0..count {
+                    if let Some((x, y, depth)) = warp_star(i, elapsed_ms, cols, rows) {
+                        let (ch, fg) = match depth {
+                            0 => ('.', theme.splash_border),
+                            1 => ('+', theme.splash_border),
+                            _ => ('*', theme.splash_fg),
+                        };
+                        buffer.set(x, y, Cell::new(ch, fg, bg));
+                    }
+                }
+            }
+            ScreensaverEffect::Matrix => {
+                for x in 0..cols {
+                    for (y, ch, is_head) in rain_column(x, elapsed_ms, rows) {
+                        let fg = if is_head {
+                            theme.splash_fg
+                        } else {
+                            theme.splash_border
+                        };
+                        buffer.set(x, y, Cell::new(ch, fg, bg));
+                    }
+                }
+            }
+            ScreensaverEffect::Bouncer => {
+                let label = format!("[ {} ]", title);
+                let width = (label.chars().count() as u16).min(cols);
+                let (x, y, bounces) = bounce_position(elapsed_ms, width, cols, rows);
+                // Change color on every bounce, like the DVD logo
+                let palette = [
+                    theme.splash_fg,
+                    theme.button_close_color,
+                    theme.button_maximize_color,
+                    theme.button_minimize_color,
+                ];
+                let fg = palette[(bounces % palette.len() as u64) as usize];
+                for (i, ch) in label.chars().take(width as usize).enumerate() {
+                    buffer.set(x + i as u16, y, Cell::new(ch, fg, bg));
+                }
+            }
+        }
+    }
+}
+
+/// Position and depth (0 = far, 2 = near) of warp star `index`, flying out of the centre
+/// Returns None while the star is outside the screen
+fn warp_star(index: u32, elapsed_ms: u64, cols: u16, rows: u16) -> Option<(u16, u16, u8)> {
+    let h = scramble(index as u64);
+    let period = 1500 + h % 2500;
+    let progress = ((elapsed_ms + (h >> 16)) % period) as f32 / period as f32;
+    let angle = ((h >> 32) % 360) as f32 * std::f32::consts::PI / 180.0;
+
+    // Accelerate outwards; cells are about twice as tall as wide
+    let radius = progress * progress * cols.max(rows * 2) as f32;
+    let x = cols as f32 / 2.0 + angle.cos() * radius;
+    let y = rows as f32 / 2.0 + angle.sin() * radius / 2.0;
+    if x < 0.0 || y < 0.0 || x >= cols as f32 || y >= rows as f32 {
+        return None;
+    }
+    let depth = (progress * 3.0).min(2.0) as u8;
+    Some((x as u16, y as u16, depth))
+}
+
+/// Visible cells (row, glyph, is_head) of the matrix rain column `x`
+fn rain_column(x: u16, elapsed_ms: u64, rows: u16) -> Vec<(u16, char, bool)> {
+    let h = scramble(x as u64);
+    let ms_per_row = 60 + h % 120;
+    let trail = 4 + (h >> 8) % (rows as u64 / 2).max(1);
+    let cycle = rows as u64 + trail;
+    let head = ((elapsed_ms / ms_per_row) + (h >> 16)) % cycle;
+
+    (0..trail)
+        .filter_map(|offset| {
+            let y = head.checked_sub(offset)?;
+            if y >= rows as u64 {
+                return None;
+            }
+            // Glyphs change as the drop passes, but stay put between frames
+            let g = scramble((x as u64) << 32 | (y + elapsed_ms / 400));
+            let ch = RAIN_GLYPHS[(g % RAIN_GLYPHS.len() as u64) as usize] as char;
+            Some((y as u16, ch, offset == 0))
+        })
+        .collect()
+}
+
+/// Top-left corner of a `width`-wide label bouncing off the screen edges, and the number
+/// of bounces so far
+fn bounce_position(elapsed_ms: u64, width: u16, cols: u16, rows: u16) -> (u16, u16, u64) {
+    // Triangle wave over [0, span]: position and completed edge hits
+    let wave = |steps: u64, span: u16| -> (u16, u64) {
+        if span == 0 {
+            return (0, 0);
+        }
+        let span = span as u64;
+        let phase = steps % (2 * span);
+        let pos = if phase <= span {
+            phase
+        } else {
+            2 * span - phase
+        };
+        (pos as u16, steps / span)
+    };
+    let (x, x_bounces) = wave(elapsed_ms / BOUNCE_STEP_MS, cols.saturating_sub(width));
+    let (y, y_bounces) = wave(elapsed_ms / (BOUNCE_STEP_MS * 2), rows.saturating_sub(1));
+    (x, y, x_bounces + y_bounces)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effects_stay_on_screen() {
+        for t in (0..20_000).step_by(37) {
+            for i in 0..50 {
+                if let Some((x, y, depth)) = warp_star(i, t, 80, 24) {
+                    assert!(x < 80 && y < 24 && depth <= 2);
+                }
+            }
+            for x in 0..80 {
+                assert!(rain_column(x, t, 24).iter().all(|&(y, ..)| y < 24));
+            }
+            let (x, y, _) = bounce_position(t, 12, 80, 24);
+            assert!(x + 12 <= 80 && y < 24);
+        }
+    }
+
+    #[test]
+    fn test_bouncer_turns_around_at_the_edges() {
+        // 10 free columns: right edge after 10 steps, back at the left after 20
+        let step = BOUNCE_STEP_MS;
+        assert_eq!(bounce_position(0, 70, 80, 24).0, 0);
+        assert_eq!(bounce_position(10 * step, 70, 80, 24).0, 10);
+        assert_eq!(bounce_position(15 * step, 70, 80, 24).0, 5);
+        assert_eq!(bounce_position(20 * step, 70, 80, 24).0, 0);
+        assert!(bounce_position(20 * step, 70, 80, 24).2 >= 2);
+        // A label wider than the screen stays pinned to the left
+        assert_eq!(bounce_position(5 * step, 100, 80, 24).0, 0);
+    }
+
+    #[test]
+    fn test_rain_head_leads_its_trail() {
+        for x in 0..40 {
+            let cells = rain_column(x, 5_000, 24);
+            let heads: Vec<u16> = cells.iter().filter(|c| c.2).map(|c| c.0).collect();
+            assert!(heads.len() <= 1);
+            if let Some(&head_y) = heads.first() {
+                assert!(cells.iter().all(|&(y, _, head)| head || y < head_y));
+            }
+        }
+    }
+}
@@ -451,6 +451,18 @@ impl SlightInput {
         }
     }
 
+    /// Command of the selected dropdown suggestion (the top one unless the user moved)
+    pub fn selected_command(&self) -> Option<&str> {
+        self.suggestions
+            .get(self.selected_suggestion)
+            .map(|s| s.command.as_str())
+    }
+
+    /// Whether the current input matched any suggestion
+    pub fn has_suggestions(&self) -> bool {
+        !self.suggestions.is_empty()
+    }
+
     /// Accepts the current inline suggestion (Right Arrow key)
     pub fn accept_inline_suggestion(&mut self) {
         if let Some(suggestion) = self.get_inline_suggestion() {
@@ -1,7 +1,9 @@
 use super::button::{Button, ButtonState};
 use super::widgets::{TopBar, WidgetContext};
+use crate::app::config_manager::CalendarOptions;
 use crate::input::keyboard_mode::{KeyboardMode, WindowSubMode};
 use crate::rendering::{Cell, Charset, Theme, VideoBuffer};
+use crate::window::button_bar::ButtonBarLayout;
 use crate::window::manager::{FocusState, WindowManager};
 use chrono::{Datelike, Local, NaiveDate};
 use crossterm::style::Color;
@@ -130,6 +132,26 @@ impl CalendarState {
         self.month = self.today.month();
     }
 
+    /// First date of the week holding the 1st of the shown month
+    fn grid_start(&self, monday_first: bool) -> Option<NaiveDate> {
+        let first = NaiveDate::from_ymd_opt(self.year, self.month, 1)?;
+        let offset = if monday_first {
+            first.weekday().num_days_from_monday()
+        } else {
+            first.weekday().num_days_from_sunday()
+        };
+        first.checked_sub_days(chrono::Days::new(offset as u64))
+    }
+
+    /// ISO week number of the calendar row starting at `week_start` (the week of its Monday)
+    fn iso_week(week_start: NaiveDate) -> u32 {
+        let monday = match week_start.weekday() {
+            chrono::Weekday::Sun => week_start + chrono::Days::new(1),
+            _ => week_start,
+        };
+        monday.iso_week().week()
+    }
+
     pub fn month_name(&self) -> &'static str {
         match self.month {
             1 => "January",
@@ -205,6 +227,60 @@ pub fn render_top_bar_widgets(
     top_bar.render(buffer, theme, &ctx);
 }
 
+/// Help text shown on the right side of the bottom bar
+const BUTTON_BAR_HELP_TEXT: &str = " > '?' Help | 's' Settings | ` Window mode < ";
+
+/// Bottom bar indicator shown while the focused application has mouse tracking enabled
+const MOUSE_TRACKING_INDICATOR: &str = "[MOUSE:APP]";
+
+/// Bottom bar indicator shown while all keys go to the focused application
+const KEY_PASSTHROUGH_INDICATOR: &str = "[KEYS:APP]";
+
+/// Bottom bar indicator shown while mouse capture is switched off
+const MOUSE_OFF_INDICATOR: &str = "[MOUSE:OFF]";
+
+/// Text of the keyboard mode indicator (None in normal mode)
+fn mode_indicator_text(keyboard_mode: &KeyboardMode) -> Option<&'static str> {
+    match keyboard_mode {
+        KeyboardMode::Normal => None,
+        KeyboardMode::WindowMode(WindowSubMode::Navigation) => Some("[WIN]"),
+        KeyboardMode::WindowMode(WindowSubMode::Move) => Some("[WIN:MOVE]"),
+        KeyboardMode::WindowMode(WindowSubMode::Resize(_)) => Some("[WIN:SIZE]"),
+    }
+}
+
+/// Width of the keyboard mode indicator in the bottom bar (0 if not shown)
+pub fn mode_indicator_width(keyboard_mode: &KeyboardMode) -> u16 {
+    mode_indicator_text(keyboard_mode).map_or(0, |text| text.len() as u16)
+}
+
+/// X position where the bottom bar window buttons end: before the status indicators and
+/// the help text, keeping a 2 char gap (mirrors the layout drawn by render_button_bar)
+pub fn window_buttons_end(
+    cols: u16,
+    mouse_tracking: bool,
+    key_passthrough: bool,
+    mouse_off: bool,
+) -> u16 {
+    let help_text_len = BUTTON_BAR_HELP_TEXT.len() as u16;
+    let mut end_x = if cols > help_text_len {
+        cols - help_text_len - 1
+    } else {
+        cols
+    };
+    for (shown, text) in [
+        (mouse_tracking, MOUSE_TRACKING_INDICATOR),
+        (key_passthrough, KEY_PASSTHROUGH_INDICATOR),
+        (mouse_off, MOUSE_OFF_INDICATOR),
+    ] {
+        let width = text.len() as u16;
+        if shown && end_x > width {
+            end_x -= width + 1;
+        }
+    }
+    end_x.saturating_sub(2)
+}
+
 /// Render the keyboard mode indicator in the bottom bar
 /// Returns the width of the indicator (0 if not shown)
 pub fn render_mode_indicator(
@@ -214,24 +290,23 @@ pub fn render_mode_indicator(
     x: u16,
     y: u16,
 ) -> u16 {
-    // Build text and get colors based on mode
-    let (text, fg, bg): (String, _, _) = match keyboard_mode {
-        KeyboardMode::Normal => return 0, // No indicator in normal mode
-        KeyboardMode::WindowMode(WindowSubMode::Navigation) => (
-            "[WIN]".to_string(),
-            theme.mode_indicator_window_fg,
-            theme.mode_indicator_window_bg,
-        ),
-        KeyboardMode::WindowMode(WindowSubMode::Move) => (
-            "[WIN:MOVE]".to_string(),
-            theme.mode_indicator_move_fg,
-            theme.mode_indicator_move_bg,
-        ),
+    let Some(text) = mode_indicator_text(keyboard_mode) else {
+        return 0; // No indicator in normal mode
+    };
+
+    // Colors based on mode
+    let (fg, bg) = match keyboard_mode {
+        KeyboardMode::WindowMode(WindowSubMode::Move) => {
+            (theme.mode_indicator_move_fg, theme.mode_indicator_move_bg)
+        }
         KeyboardMode::WindowMode(WindowSubMode::Resize(_)) => (
-            "[WIN:SIZE]".to_string(),
             theme.mode_indicator_resize_fg,
             theme.mode_indicator_resize_bg,
         ),
+        _ => (
+            theme.mode_indicator_window_fg,
+            theme.mode_indicator_window_bg,
+        ),
     };
 
     for (i, ch) in text.chars().enumerate() {
@@ -241,12 +316,46 @@ pub fn render_mode_indicator(
     text.len() as u16
 }
 
+/// Render a status indicator (e.g. "[MOUSE:APP]") in the bottom bar, ending just before `end_x`
+/// Returns the x position where the indicator starts (`end_x` if it doesn't fit)
+fn render_bar_indicator(
+    buffer: &mut VideoBuffer,
+    text: &str,
+    theme: &Theme,
+    end_x: u16,
+    y: u16,
+) -> u16 {
+    let width = text.len() as u16;
+    if end_x < width + 1 {
+        return end_x;
+    }
+
+    let x = end_x - width - 1;
+    for (i, ch) in text.chars().enumerate() {
+        buffer.set(
+            x + i as u16,
+            y,
+            Cell::new_unchecked(
+                ch,
+                theme.mode_indicator_window_fg,
+                theme.mode_indicator_window_bg,
+            ),
+        );
+    }
+    x
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn render_button_bar(
     buffer: &mut VideoBuffer,
     window_manager: &WindowManager,
     auto_tiling_button: &Button,
     auto_tiling_enabled: bool,
     keyboard_mode: &KeyboardMode,
+    mouse_tracking: bool,
+    key_passthrough: bool,
+    mouse_off: bool,
+    window_buttons: (u16, u16),
     theme: &Theme,
 ) {
     let (cols, rows) = buffer.dimensions();
@@ -325,13 +434,9 @@ pub fn render_button_bar(
         bar_y,
         Cell::new_unchecked(' ', toggle_color, toggle_bg),
     );
-    current_x += 1;
-
-    // Add spacing after toggle
-    current_x += 2;
 
     // Render help text on the right side
-    let help_text = " > '?' Help | 's' Settings | ` Window mode < ";
+    let help_text = BUTTON_BAR_HELP_TEXT;
     let help_text_len = help_text.len() as u16;
     let help_x = if cols > help_text_len {
         let hx = cols - help_text_len - 1;
@@ -347,27 +452,64 @@ pub fn render_button_bar(
         cols // No room for help text
     };
 
-    // Get list of windows
-    let windows = window_manager.get_window_list();
-    if windows.is_empty() {
-        return;
+    // Render mouse capture indicator to the left of the help text
+    // (shown while the focused application has mouse tracking enabled)
+    let mut help_x = help_x;
+    if mouse_tracking {
+        help_x = render_bar_indicator(buffer, MOUSE_TRACKING_INDICATOR, theme, help_x, bar_y);
     }
 
-    // Calculate max position for window buttons (don't overlap help text)
-    let max_button_x = help_x.saturating_sub(2);
+    // Render key pass-through indicator (all keys go to the focused application)
+    if key_passthrough {
+        help_x = render_bar_indicator(buffer, KEY_PASSTHROUGH_INDICATOR, theme, help_x, bar_y);
+    }
 
-    for (_id, title, is_focused, is_minimized) in windows {
-        // Max button width is 18 chars total
-        let max_title_len = 14;
-        let button_title = if title.len() > max_title_len {
-            &title[..max_title_len]
-        } else {
-            title
-        };
+    // Render mouse capture off indicator (the host terminal gets the mouse)
+    if mouse_off {
+        render_bar_indicator(buffer, MOUSE_OFF_INDICATOR, theme, help_x, bar_y);
+    }
+
+    // Window buttons (2 spaces after the toggle), laid out the same way as for hit-testing
+    let (buttons_start, buttons_end) = window_buttons;
+    let layout = window_manager.button_bar_layout(buttons_start, buttons_end);
+    render_window_buttons(
+        buffer,
+        window_manager,
+        &layout,
+        bar_y,
+        (theme.bottombar_fg, theme.bottombar_bg),
+        theme,
+    );
+}
+
+/// Tabs for every window in the title bar of a maximized focused window
+/// (the `maximized_tab_strip` option), drawn like the bottom bar buttons
+pub fn render_tab_strip(buffer: &mut VideoBuffer, window_manager: &WindowManager, theme: &Theme) {
+    let Some((y, start_x, end_x)) = window_manager.tab_strip_area() else {
+        return;
+    };
+    let title_colors = (theme.window_title_focused_fg, theme.window_title_focused_bg);
+    let title_cell = Cell::new_unchecked(' ', title_colors.0, title_colors.1);
+    for x in start_x..end_x {
+        buffer.set(x, y, title_cell);
+    }
+    let layout = window_manager.button_bar_layout(start_x, end_x);
+    render_window_buttons(buffer, window_manager, &layout, y, title_colors, theme);
+}
 
+/// Draw laid out window buttons on row `y`; `marker_colors` (fg, bg) are for the scroll markers
+fn render_window_buttons(
+    buffer: &mut VideoBuffer,
+    window_manager: &WindowManager,
+    layout: &ButtonBarLayout,
+    y: u16,
+    marker_colors: (Color, Color),
+    theme: &Theme,
+) {
+    for button in &layout.buttons {
         // Button format: [ Title ] for normal, ( Title ) for minimized
         // Use different brackets and colors for minimized windows
-        let (open_bracket, close_bracket, button_bg, button_fg) = if is_minimized {
+        let (open_bracket, close_bracket, button_bg, button_fg) = if button.is_minimized {
             // Minimized windows: use parentheses and grey color
             (
                 '(',
@@ -375,7 +517,7 @@ pub fn render_button_bar(
                 theme.bottombar_button_minimized_bg,
                 theme.bottombar_button_minimized_fg,
             )
-        } else if is_focused {
+        } else if button.is_focused {
             // Focused window: cyan background
             (
                 '[',
@@ -393,65 +535,86 @@ pub fn render_button_bar(
             )
         };
 
-        // Check if there's room for at least the brackets and minimal content
-        if current_x + 4 >= max_button_x {
-            break; // Not enough room for this button
-        }
-
-        // Render opening bracket and space
         // Use new_unchecked for performance - theme colors are pre-validated
-        buffer.set(
-            current_x,
-            bar_y,
-            Cell::new_unchecked(open_bracket, button_fg, button_bg),
-        );
-        current_x += 1;
-        buffer.set(
-            current_x,
-            bar_y,
-            Cell::new_unchecked(' ', button_fg, button_bg),
-        );
-        current_x += 1;
-
-        // Render title
-        for ch in button_title.chars() {
-            if current_x >= max_button_x {
-                break;
-            }
+        let text = format!("{} {} {}", open_bracket, button.label, close_bracket);
+        for (i, ch) in text.chars().enumerate() {
             buffer.set(
-                current_x,
-                bar_y,
+                button.x + i as u16,
+                y,
                 Cell::new_unchecked(ch, button_fg, button_bg),
             );
-            current_x += 1;
         }
 
-        // Render space and closing bracket
-        if current_x < max_button_x {
+        // A bell rang in the background: "[!Title ]", in place of the space
+        if window_manager.window_has_bell(button.id) {
             buffer.set(
-                current_x,
-                bar_y,
-                Cell::new_unchecked(' ', button_fg, button_bg),
+                button.x + 1,
+                y,
+                Cell::new_unchecked('!', theme.prompt_warning_fg, theme.prompt_warning_bg),
             );
-            current_x += 1;
         }
-        if current_x < max_button_x {
+    }
+
+    // Scroll markers when not all buttons fit
+    for (marker, ch) in [(layout.scroll_left, '<'), (layout.scroll_right, '>')] {
+        if let Some((x, _)) = marker {
             buffer.set(
-                current_x,
-                bar_y,
-                Cell::new_unchecked(close_bracket, button_fg, button_bg),
+                x,
+                y,
+                Cell::new_unchecked(ch, marker_colors.0, marker_colors.1),
             );
-            current_x += 1;
         }
+    }
+}
 
-        // Add space between buttons
-        current_x += 1;
+// Calendar dimensions (shared by rendering and click handling)
+const CALENDAR_WIDTH: u16 = 42;
+const CALENDAR_HEIGHT: u16 = 18;
 
-        // Stop if we've run out of space (before help text)
-        if current_x >= max_button_x {
-            break;
-        }
+/// Top-left corner of the centered calendar
+fn calendar_origin(cols: u16, rows: u16) -> (u16, u16) {
+    (
+        cols.saturating_sub(CALENDAR_WIDTH) / 2,
+        rows.saturating_sub(CALENDAR_HEIGHT) / 2,
+    )
+}
+
+/// Active part of the calendar under a click
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarClick {
+    Close,
+    PreviousMonth,
+    NextMonth,
+    Today,
+    /// Anywhere else inside the calendar
+    Inside,
+}
+
+/// What a click at (click_x, click_y) hits, or None when it is outside the calendar
+pub fn calendar_click_target(
+    click_x: u16,
+    click_y: u16,
+    cols: u16,
+    rows: u16,
+) -> Option<CalendarClick> {
+    let (x, y) = calendar_origin(cols, rows);
+    if click_x < x || click_x >= x + CALENDAR_WIDTH || click_y < y || click_y >= y + CALENDAR_HEIGHT
+    {
+        return None;
     }
+    let col = click_x - x;
+    let target = match (click_y - y, col) {
+        // [Today] ... [X]
+        (0, 1..=7) => CalendarClick::Today,
+        (0, c) if (CALENDAR_WIDTH - 4..=CALENDAR_WIDTH - 2).contains(&c) => CalendarClick::Close,
+        // [<] Month Year [>]
+        (1, 2..=4) => CalendarClick::PreviousMonth,
+        (1, c) if (CALENDAR_WIDTH - 5..=CALENDAR_WIDTH - 3).contains(&c) => {
+            CalendarClick::NextMonth
+        }
+        _ => CalendarClick::Inside,
+    };
+    Some(target)
 }
 
 pub fn render_calendar(
@@ -461,35 +624,18 @@ pub fn render_calendar(
     theme: &Theme,
     cols: u16,
     rows: u16,
+    options: CalendarOptions,
 ) {
-    // Calendar dimensions
-    let width = 42u16;
-    let height = 18u16;
-    let x = (cols.saturating_sub(width)) / 2;
-    let y = (rows.saturating_sub(height)) / 2;
-
-    // Get the first day of the month
-    let first_day = match NaiveDate::from_ymd_opt(calendar.year, calendar.month, 1) {
+    let width = CALENDAR_WIDTH;
+    let height = CALENDAR_HEIGHT;
+    let (x, y) = calendar_origin(cols, rows);
+
+    // First date shown in the grid (the start of the week holding the 1st)
+    let grid_start = match calendar.grid_start(options.monday_first) {
         Some(date) => date,
         None => return, // Invalid date, don't render
     };
 
-    // Get the number of days in the month
-    let days_in_month = if calendar.month == 12 {
-        match NaiveDate::from_ymd_opt(calendar.year + 1, 1, 1) {
-            Some(next_month) => (next_month - chrono::Duration::days(1)).day(),
-            None => 31,
-        }
-    } else {
-        match NaiveDate::from_ymd_opt(calendar.year, calendar.month + 1, 1) {
-            Some(next_month) => (next_month - chrono::Duration::days(1)).day(),
-            None => 31,
-        }
-    };
-
-    // Get the weekday of the first day (0 = Sunday, 6 = Saturday)
-    let first_weekday = first_day.weekday().num_days_from_sunday() as u16;
-
     // Colors
     let bg_color = theme.calendar_bg;
     let fg_color = theme.calendar_fg;
@@ -506,113 +652,102 @@ pub fn render_calendar(
         }
     }
 
-    // Render title (Month Year)
-    let title = format!("{} {}", calendar.month_name(), calendar.year);
-    let title_len = title.len() as u16;
-    let title_x = if title_len < width {
-        x + (width - title_len) / 2
-    } else {
-        x + 1
+    let mut put_text = |text: &str, tx: u16, ty: u16, fg: Color, bg: Color| {
+        for (i, ch) in text.chars().enumerate() {
+            let char_x = tx + i as u16;
+            if char_x < x + width {
+                buffer.set(char_x, ty, Cell::new_unchecked(ch, fg, bg));
+            }
+        }
     };
-    for (i, ch) in title.chars().enumerate() {
-        let char_x = title_x + i as u16;
-        if char_x < x + width {
-            buffer.set(
-                char_x,
-                y + 1,
-                Cell::new_unchecked(ch, title_color, bg_color),
-            );
+    let centered = |len: u16| {
+        if len < width {
+            x + (width - len) / 2
+        } else {
+            x + 1
         }
-    }
+    };
 
-    // Render [X] close button in top-right corner
-    buffer.set(
-        x + width - 4,
-        y,
-        Cell::new_unchecked('[', fg_color, bg_color),
-    );
-    buffer.set(
-        x + width - 3,
-        y,
-        Cell::new_unchecked('X', Color::Red, bg_color),
-    );
-    buffer.set(
-        x + width - 2,
-        y,
-        Cell::new_unchecked(']', fg_color, bg_color),
+    // Render title (Month Year) between the [<] and [>] buttons
+    let title = format!("{} {}", calendar.month_name(), calendar.year);
+    put_text(
+        &title,
+        centered(title.len() as u16),
+        y + 1,
+        title_color,
+        bg_color,
     );
+    for (label, bx) in [("<", x + 2), (">", x + width - 5)] {
+        put_text("[", bx, y + 1, fg_color, bg_color);
+        put_text(label, bx + 1, y + 1, title_color, bg_color);
+        put_text("]", bx + 2, y + 1, fg_color, bg_color);
+    }
 
-    // Render day headers (Su  Mo  Tu  We  Th  Fr  Sa)
-    let day_headers = "Su   Mo   Tu   We   Th   Fr   Sa";
-    let header_len = day_headers.len() as u16;
-    let header_x = if header_len < width {
-        x + (width - header_len) / 2
+    // Render [Today] in the top-left and [X] close button in the top-right corner
+    put_text("[", x + 1, y, fg_color, bg_color);
+    put_text("Today", x + 2, y, title_color, bg_color);
+    put_text("]", x + 7, y, fg_color, bg_color);
+    put_text("[", x + width - 4, y, fg_color, bg_color);
+    put_text("X", x + width - 3, y, Color::Red, bg_color);
+    put_text("]", x + width - 2, y, fg_color, bg_color);
+
+    // Render day headers (Su   Mo   Tu ...), after the week number column when shown
+    let mut day_names = ["Su", "Mo", "Tu", "We", "Th", "Fr", "Sa"];
+    if options.monday_first {
+        day_names.rotate_left(1);
+    }
+    let mut day_headers = day_names.join("   ");
+    if options.week_numbers {
+        day_headers.insert_str(0, "Wk   ");
+    }
+    let header_x = centered(day_headers.len() as u16);
+    put_text(&day_headers, header_x, y + 3, fg_color, bg_color);
+    let days_x = if options.week_numbers {
+        header_x + 5
     } else {
-        x + 1
+        header_x
     };
-    for (i, ch) in day_headers.chars().enumerate() {
-        let char_x = header_x + i as u16;
-        if char_x < x + width {
-            buffer.set(char_x, y + 3, Cell::new_unchecked(ch, fg_color, bg_color));
-        }
-    }
 
     // Render calendar days
-    let mut day = 1u32;
     let calendar_start_y = y + 5;
-
-    for week in 0..6 {
-        for weekday in 0..7 {
-            let day_x = header_x + (weekday * 5);
-            let day_y = calendar_start_y + (week * 2);
-
-            if (week == 0 && weekday < first_weekday) || day > days_in_month {
+    for week in 0..6u16 {
+        let day_y = calendar_start_y + (week * 2);
+        let week_start = grid_start + chrono::Days::new(week as u64 * 7);
+        let mut week_in_month = false;
+
+        for weekday in 0..7u16 {
+            let date = week_start + chrono::Days::new(weekday as u64);
+            if date.month() != calendar.month {
                 continue;
             }
+            week_in_month = true;
 
-            // Check if this is today
-            let is_today = calendar.today.year() == calendar.year
-                && calendar.today.month() == calendar.month
-                && calendar.today.day() == day;
-
-            let (day_fg, day_bg) = if is_today {
+            let (day_fg, day_bg) = if date == calendar.today {
                 (today_fg, today_bg)
             } else {
                 (fg_color, bg_color)
             };
 
             // Render day number (right-aligned in 2-char space)
-            let day_str = format!("{:>2}", day);
-            for (i, ch) in day_str.chars().enumerate() {
-                buffer.set(
-                    day_x + i as u16,
-                    day_y,
-                    Cell::new_unchecked(ch, day_fg, day_bg),
-                );
-            }
+            let day_str = format!("{:>2}", date.day());
+            put_text(&day_str, days_x + (weekday * 5), day_y, day_fg, day_bg);
+        }
 
-            day += 1;
+        if options.week_numbers && week_in_month {
+            let week_str = format!("{:>2}", CalendarState::iso_week(week_start));
+            put_text(&week_str, header_x, day_y, title_color, bg_color);
         }
     }
 
     // Render navigation hints at bottom
     let hint = "\u{2190}\u{2192} Month | \u{2191}\u{2193} Year | T Today | ESC/[X]";
-    let hint_len = hint.chars().count() as u16;
-    let hint_x = if hint_len < width {
-        x + (width - hint_len) / 2
-    } else {
-        x + 1
-    };
-    for (i, ch) in hint.chars().enumerate() {
-        let char_x = hint_x + i as u16;
-        if char_x < x + width {
-            buffer.set(
-                char_x,
-                y + height - 1,
-                Cell::new_unchecked(ch, theme.config_instructions_fg, bg_color),
-            );
-        }
-    }
+    put_text(
+        hint,
+        centered(hint.chars().count() as u16),
+        y + height - 1,
+        theme.config_instructions_fg,
+        bg_color,
+    );
 
     // Add shadow effect
     // Use new_unchecked for performance - shadow colors are intentionally low contrast
@@ -621,26 +756,83 @@ pub fn render_calendar(
         buffer.set(
             x + width,
             y + sy,
-            Cell::new_unchecked(shadow_char, theme.window_shadow_color, Color::Black),
+            Cell::new_unchecked(
+                shadow_char,
+                theme.window_shadow_color,
+                theme.window_shadow_bg,
+            ),
         );
     }
     for sx in 1..=width {
         buffer.set(
             x + sx,
             y + height,
-            Cell::new_unchecked(shadow_char, theme.window_shadow_color, Color::Black),
+            Cell::new_unchecked(
+                shadow_char,
+                theme.window_shadow_color,
+                theme.window_shadow_bg,
+            ),
         );
     }
 }
 
-/// Check if a click is on the calendar's [X] close button
-pub fn is_calendar_close_button_click(click_x: u16, click_y: u16, cols: u16, rows: u16) -> bool {
-    // Calendar dimensions (must match render_calendar)
-    let width = 42u16;
-    let height = 18u16;
-    let x = (cols.saturating_sub(width)) / 2;
-    let y = (rows.saturating_sub(height)) / 2;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // [X] is at positions (x + width - 4) to (x + width - 2), row y
-    click_y == y && click_x >= x + width - 4 && click_x <= x + width - 2
+    fn calendar(year: i32, month: u32) -> CalendarState {
+        CalendarState {
+            year,
+            month,
+            today: NaiveDate::from_ymd_opt(2026, 10, 14).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_grid_starts_on_configured_weekday() {
+        // October 2026 starts on a Thursday
+        let october = calendar(2026, 10);
+        assert_eq!(
+            october.grid_start(false),
+            NaiveDate::from_ymd_opt(2026, 9, 27)
+        );
+        assert_eq!(
+            october.grid_start(true),
+            NaiveDate::from_ymd_opt(2026, 9, 28)
+        );
+        // Week 40 holds October 1st, whichever day starts the row
+        for monday_first in [false, true] {
+            let start = october.grid_start(monday_first).unwrap();
+            assert_eq!(CalendarState::iso_week(start), 40);
+        }
+        // A month starting on the first weekday has no leading days
+        let march = calendar(2026, 3);
+        assert_eq!(march.grid_start(false), NaiveDate::from_ymd_opt(2026, 3, 1));
+    }
+
+    #[test]
+    fn test_calendar_click_targets() {
+        let (x, y) = calendar_origin(80, 24);
+        assert_eq!(
+            calendar_click_target(x + 3, y + 1, 80, 24),
+            Some(CalendarClick::PreviousMonth)
+        );
+        assert_eq!(
+            calendar_click_target(x + CALENDAR_WIDTH - 4, y + 1, 80, 24),
+            Some(CalendarClick::NextMonth)
+        );
+        assert_eq!(
+            calendar_click_target(x + 4, y, 80, 24),
+            Some(CalendarClick::Today)
+        );
+        assert_eq!(
+            calendar_click_target(x + CALENDAR_WIDTH - 3, y, 80, 24),
+            Some(CalendarClick::Close)
+        );
+        assert_eq!(
+            calendar_click_target(x + 20, y + 8, 80, 24),
+            Some(CalendarClick::Inside)
+        );
+        assert_eq!(calendar_click_target(x + CALENDAR_WIDTH, y, 80, 24), None);
+    }
 }
@@ -1,23 +1,84 @@
+use crate::app::config_manager::ClipboardBackend;
 #[cfg(feature = "clipboard")]
 use arboard::Clipboard;
+use std::collections::VecDeque;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Copies kept for pasting by number (`paste_from_history`)
+pub const HISTORY_LEN: usize = 9;
+
+/// Clipboard mechanism in use, resolved from the configured `ClipboardBackend`
+enum Backend {
+    /// Platform clipboard through arboard (X11 on Linux and the BSDs)
+    #[cfg(feature = "clipboard")]
+    System(Clipboard),
+    /// `wl-copy` / `wl-paste` from wl-clipboard
+    Wayland,
+    /// Copies go to the host terminal as OSC 52 escapes; pastes come from the internal buffer
+    Osc52,
+    /// Process-local buffer only
+    Internal,
+}
 
 /// Manages clipboard operations with system clipboard integration
 pub struct ClipboardManager {
-    #[cfg(feature = "clipboard")]
-    clipboard: Option<Clipboard>,
+    backend: Backend,
     last_copied: Option<String>,
+    /// Recent copies, most recent first, without duplicates
+    history: VecDeque<String>,
+    /// OSC 52 copy waiting to be sent to the host terminal after the next frame
+    pending_osc52: Option<String>,
 }
 
 impl ClipboardManager {
-    /// Create a new clipboard manager
+    /// Create a new clipboard manager, picking the system clipboard when there is one
     pub fn new() -> Self {
-        #[cfg(feature = "clipboard")]
-        let clipboard_instance = Clipboard::new().ok();
+        Self::with_backend(ClipboardBackend::Auto)
+    }
+
+    /// Create a clipboard manager using the configured backend
+    /// A system backend that can't be opened falls back to the internal buffer
+    pub fn with_backend(backend: ClipboardBackend) -> Self {
+        let backend = match backend {
+            ClipboardBackend::Auto | ClipboardBackend::X11 => Self::system_backend(),
+            ClipboardBackend::Wayland if wl_clipboard_available() => Backend::Wayland,
+            ClipboardBackend::Wayland => Backend::Internal,
+            ClipboardBackend::Osc52 => Backend::Osc52,
+            ClipboardBackend::Internal => Backend::Internal,
+        };
 
         Self {
-            #[cfg(feature = "clipboard")]
-            clipboard: clipboard_instance,
+            backend,
             last_copied: None,
+            history: VecDeque::new(),
+            pending_osc52: None,
+        }
+    }
+
+    #[cfg(feature = "clipboard")]
+    fn system_backend() -> Backend {
+        match Clipboard::new() {
+            Ok(clipboard) => Backend::System(clipboard),
+            Err(_) => Backend::Internal,
+        }
+    }
+
+    #[cfg(not(feature = "clipboard"))]
+    fn system_backend() -> Backend {
+        Backend::Internal
+    }
+
+    /// Name of the backend in use (shown by --version-info)
+    pub fn backend_name(&self) -> &'static str {
+        match self.backend {
+            #[cfg(all(feature = "clipboard", unix, not(target_os = "macos")))]
+            Backend::System(_) => "x11",
+            #[cfg(all(feature = "clipboard", any(not(unix), target_os = "macos")))]
+            Backend::System(_) => "system",
+            Backend::Wayland => "wayland (wl-clipboard)",
+            Backend::Osc52 => "osc52",
+            Backend::Internal => "internal",
         }
     }
 
@@ -27,28 +88,69 @@ impl ClipboardManager {
             return Err("Cannot copy empty text".to_string());
         }
 
-        // Store in internal buffer
-        self.last_copied = Some(text.clone());
+        // Store in history
+        self.history.retain(|entry| *entry != text);
+        self.history.push_front(text.clone());
+        self.history.truncate(HISTORY_LEN);
+        self.set_clipboard(text)
+    }
+
+    /// Copy text to clipboard without adding it to the history (text taken from a
+    /// window at a hidden password prompt)
+    pub fn copy_unrecorded(&mut self, text: String) -> Result<(), String> {
+        if text.is_empty() {
+            return Err("Cannot copy empty text".to_string());
+        }
+        self.set_clipboard(text)
+    }
+
+    /// Drop text from the history, e.g. once it was pasted into a hidden password prompt
+    pub fn forget(&mut self, text: &str) {
+        self.history.retain(|entry| entry != text);
+    }
 
-        // Try to copy to system clipboard if available
-        #[cfg(feature = "clipboard")]
-        if let Some(clipboard) = &mut self.clipboard {
-            clipboard
+    /// Store text in the internal buffer and the backend's clipboard
+    fn set_clipboard(&mut self, text: String) -> Result<(), String> {
+        self.last_copied = Some(text.clone());
+        match &mut self.backend {
+            #[cfg(feature = "clipboard")]
+            Backend::System(clipboard) => clipboard
                 .set_text(text)
-                .map_err(|e| format!("Failed to copy to system clipboard: {}", e))?;
+                .map_err(|e| format!("Failed to copy to system clipboard: {}", e)),
+            Backend::Wayland => run_with_input("wl-copy", &[], &text),
+            Backend::Osc52 => {
+                self.pending_osc52 =
+                    Some(format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes())));
+                Ok(())
+            }
+            Backend::Internal => Ok(()),
         }
+    }
 
-        Ok(())
+    /// Take the OSC 52 copy queued for the host terminal, if any
+    /// The event loop sends it through the render backend once the frame is flushed,
+    /// so the escape never lands in the middle of a frame
+    pub fn take_osc52(&mut self) -> Option<String> {
+        self.pending_osc52.take()
     }
 
     /// Get text from clipboard (system or internal)
     pub fn paste(&mut self) -> Result<String, String> {
         // Try system clipboard first if available
-        #[cfg(feature = "clipboard")]
-        if let Some(clipboard) = &mut self.clipboard {
-            if let Ok(text) = clipboard.get_text() {
-                return Ok(text);
+        match &mut self.backend {
+            #[cfg(feature = "clipboard")]
+            Backend::System(clipboard) => {
+                if let Ok(text) = clipboard.get_text() {
+                    return Ok(text);
+                }
+            }
+            Backend::Wayland => {
+                if let Some(text) = run_for_output("wl-paste", &["--no-newline"]) {
+                    return Ok(text);
+                }
             }
+            // Reading the host clipboard through OSC 52 is rarely allowed
+            Backend::Osc52 | Backend::Internal => {}
         }
 
         // Fall back to internal buffer
@@ -62,12 +164,29 @@ impl ClipboardManager {
         self.last_copied.is_some()
     }
 
-    /// Clear clipboard
+    /// The `n`th most recent copy (1 = the last one)
+    pub fn history_entry(&self, n: usize) -> Option<&str> {
+        self.history.get(n.checked_sub(1)?).map(String::as_str)
+    }
+
+    /// Number of copies in the history
+    pub fn history_len(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Clear clipboard and history
     pub fn clear(&mut self) {
         self.last_copied = None;
-        #[cfg(feature = "clipboard")]
-        if let Some(clipboard) = &mut self.clipboard {
-            let _ = clipboard.clear();
+        self.history.clear();
+        match &mut self.backend {
+            #[cfg(feature = "clipboard")]
+            Backend::System(clipboard) => {
+                let _ = clipboard.clear();
+            }
+            Backend::Wayland => {
+                let _ = run_for_output("wl-copy", &["--clear"]);
+            }
+            Backend::Osc52 | Backend::Internal => {}
         }
     }
 
@@ -83,3 +202,147 @@ impl Default for ClipboardManager {
         Self::new()
     }
 }
+
+/// True when wl-clipboard is installed and a Wayland compositor is running
+fn wl_clipboard_available() -> bool {
+    std::env::var_os("WAYLAND_DISPLAY").is_some()
+        && Command::new("wl-paste")
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+}
+
+/// Run `program` with `text` on its stdin
+fn run_with_input(program: &str, args: &[&str], text: &str) -> Result<(), String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(|e| format!("Failed to write to {}: {}", program, e))?;
+    }
+    match child.wait() {
+        Ok(status) if status.success() => Ok(()),
+        _ => Err(format!("{} failed", program)),
+    }
+}
+
+/// Run `program` and return its stdout when it succeeds
+fn run_for_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Standard base64 with padding, as OSC 52 expects
+fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode("ls -la ✓".as_bytes()), "bHMgLWxhIOKckw==");
+    }
+
+    #[test]
+    fn test_internal_backend_keeps_copies_in_process() {
+        let mut clipboard = ClipboardManager::with_backend(ClipboardBackend::Internal);
+        assert_eq!(clipboard.backend_name(), "internal");
+        assert!(clipboard.paste().is_err());
+        clipboard.copy("echo hi".to_string()).unwrap();
+        assert_eq!(clipboard.paste().unwrap(), "echo hi");
+        clipboard.clear();
+        assert!(!clipboard.has_content());
+    }
+
+    #[test]
+    fn test_osc52_copy_is_queued_for_the_backend() {
+        let mut clipboard = ClipboardManager::with_backend(ClipboardBackend::Osc52);
+        clipboard.copy("foo".to_string()).unwrap();
+        assert_eq!(
+            clipboard.take_osc52().as_deref(),
+            Some("\x1b]52;c;Zm9v\x07")
+        );
+        assert_eq!(clipboard.take_osc52(), None);
+        assert_eq!(clipboard.paste().unwrap(), "foo");
+    }
+
+    #[test]
+    fn test_history_keeps_recent_distinct_copies() {
+        let mut clipboard = ClipboardManager::with_backend(ClipboardBackend::Internal);
+        for text in ["a", "b", "a", "c"] {
+            clipboard.copy(text.to_string()).unwrap();
+        }
+        // Copying "a" again moved it to the front instead of keeping two entries
+        assert_eq!(clipboard.history_len(), 3);
+        assert_eq!(clipboard.history_entry(1), Some("c"));
+        assert_eq!(clipboard.history_entry(2), Some("a"));
+        assert_eq!(clipboard.history_entry(3), Some("b"));
+        assert_eq!(clipboard.history_entry(0), None);
+        assert_eq!(clipboard.history_entry(4), None);
+
+        for n in 0..20 {
+            clipboard.copy(n.to_string()).unwrap();
+        }
+        assert_eq!(clipboard.history_len(), HISTORY_LEN);
+        assert_eq!(clipboard.history_entry(1), Some("19"));
+        clipboard.clear();
+        assert_eq!(clipboard.history_len(), 0);
+    }
+
+    #[test]
+    fn test_hidden_prompt_text_stays_out_of_history() {
+        let mut clipboard = ClipboardManager::with_backend(ClipboardBackend::Internal);
+        clipboard.copy("ls".to_string()).unwrap();
+        // Copied from a window at a password prompt: pasteable, but not kept
+        clipboard.copy_unrecorded("hunter2".to_string()).unwrap();
+        assert_eq!(clipboard.paste().unwrap(), "hunter2");
+        assert_eq!(clipboard.history_len(), 1);
+        assert_eq!(clipboard.history_entry(1), Some("ls"));
+
+        // Pasted into a password prompt: dropped from the history
+        clipboard.copy("secret".to_string()).unwrap();
+        clipboard.forget("secret");
+        assert_eq!(clipboard.history_len(), 1);
+        assert_eq!(clipboard.history_entry(1), Some("ls"));
+    }
+}
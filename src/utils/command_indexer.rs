@@ -70,6 +70,13 @@ impl CommandIndexer {
         }
     }
 
+    /// An indexer over a fixed command list instead of PATH
+    #[cfg(test)]
+    pub fn with_commands(mut commands: Vec<String>) -> Self {
+        commands.sort();
+        CommandIndexer { commands }
+    }
+
     /// Returns all indexed commands
     pub fn get_commands(&self) -> &[String] {
         &self.commands
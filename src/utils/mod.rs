@@ -2,8 +2,10 @@ mod clipboard_manager;
 mod command_history;
 mod command_indexer;
 mod fuzzy_matcher;
+mod splitmix;
 
 pub use clipboard_manager::ClipboardManager;
 pub use command_history::CommandHistory;
 pub use command_indexer::CommandIndexer;
 pub use fuzzy_matcher::{FuzzyMatch, FuzzyMatcher};
+pub use splitmix::scramble;
@@ -0,0 +1,21 @@
+/// SplitMix64 scramble: stable pseudo-random bits per index
+/// Used by the screensaver and lock screen animations so their star fields look random
+/// but stay the same from one frame to the next
+pub fn scramble(index: u64) -> u64 {
+    let mut h = index.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    h = (h ^ (h >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    h = (h ^ (h >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    h ^ (h >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scramble_matches_splitmix64() {
+        // First outputs of SplitMix64 seeded with 0
+        assert_eq!(scramble(0), 0xE220_A839_7B1D_CDAF);
+        assert_eq!(scramble(1), 0x6E78_9E6A_A1B9_65F4);
+    }
+}
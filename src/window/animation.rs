@@ -0,0 +1,214 @@
+//! Minimize/maximize animations: a window outline interpolated between two rectangles
+//! Purely cosmetic and disabled by default (see `animations` in the config)
+
+use super::manager::WindowManager;
+use crate::rendering::{Cell, Charset, Theme, VideoBuffer};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Upper bound for the animation duration so it never feels sluggish
+pub const MAX_ANIMATION_DURATION_MS: u64 = 400;
+
+/// Minimum number of frames an animation must get; slower backends skip animations
+const MIN_ANIMATION_FRAMES: u32 = 4;
+
+/// Rectangle as (x, y, width, height)
+type Rect = (u16, u16, u16, u16);
+
+/// A single outline animation from one rectangle to another
+#[derive(Debug, Clone)]
+pub struct WindowAnimation {
+    from: Rect,
+    to: Rect,
+    started: Instant,
+    duration: Duration,
+}
+
+impl WindowAnimation {
+    pub fn new(from: Rect, to: Rect, duration: Duration) -> Self {
+        Self {
+            from,
+            to,
+            started: Instant::now(),
+            duration,
+        }
+    }
+
+    /// Animation progress from 0.0 (start) to 1.0 (finished)
+    fn progress(&self) -> f32 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+        (self.started.elapsed().as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.progress() >= 1.0
+    }
+
+    /// The outline rectangle for the current frame
+    pub fn current_rect(&self) -> Rect {
+        lerp_rect(self.from, self.to, self.progress())
+    }
+}
+
+/// Interpolate between two rectangles (t = 0.0 gives `from`, t = 1.0 gives `to`)
+fn lerp_rect(from: Rect, to: Rect, t: f32) -> Rect {
+    let lerp = |a: u16, b: u16| (a as f32 + (b as f32 - a as f32) * t).round() as u16;
+    (
+        lerp(from.0, to.0),
+        lerp(from.1, to.1),
+        lerp(from.2, to.2),
+        lerp(from.3, to.3),
+    )
+}
+
+/// Per-window state seen on the previous frame
+#[derive(Debug, Clone, Copy)]
+struct WindowState {
+    is_minimized: bool,
+    is_maximized: bool,
+    rect: Rect,
+}
+
+/// Detects minimize/maximize transitions between frames and plays their animations
+#[derive(Debug, Default)]
+pub struct AnimationTracker {
+    previous: HashMap<u32, WindowState>,
+    active: Vec<WindowAnimation>,
+    last_frame_time: Duration,
+}
+
+impl AnimationTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record how long the last frame took (used to skip animations on slow backends)
+    pub fn record_frame_time(&mut self, elapsed: Duration) {
+        self.last_frame_time = elapsed;
+    }
+
+    /// Compare window states with the previous frame and start animations for changes
+    /// `window_buttons` is the x range of the bottom bar window buttons
+    pub fn update(
+        &mut self,
+        window_manager: &WindowManager,
+        rows: u16,
+        window_buttons: (u16, u16),
+        duration: Duration,
+    ) {
+        let budget_ok = self.last_frame_time * MIN_ANIMATION_FRAMES <= duration;
+        let bar_y = rows.saturating_sub(1);
+        let mut current = HashMap::new();
+
+        for (id, x, y, width, height, is_minimized, _) in window_manager.get_window_positions() {
+            let is_maximized = window_manager.is_window_maximized(id);
+            let state = WindowState {
+                is_minimized,
+                is_maximized,
+                rect: (x, y, width, height),
+            };
+
+            if let Some(prev) = self.previous.get(&id).filter(|_| budget_ok) {
+                if is_minimized && !prev.is_minimized {
+                    // Shrink toward the window's bottom bar button
+                    let target = window_manager
+                        .button_bar_button_span(id, window_buttons.0, window_buttons.1)
+                        .map_or((x, bar_y, 1, 1), |(bx, bw)| (bx, bar_y, bw, 1));
+                    self.active
+                        .push(WindowAnimation::new(prev.rect, target, duration));
+                } else if is_maximized && !prev.is_maximized && !is_minimized {
+                    // Expand from the previous rectangle
+                    self.active
+                        .push(WindowAnimation::new(prev.rect, state.rect, duration));
+                }
+            }
+
+            current.insert(id, state);
+        }
+
+        self.previous = current;
+        self.active.retain(|a| !a.is_finished());
+    }
+
+    /// Draw the outlines of all running animations
+    pub fn render(&self, buffer: &mut VideoBuffer, charset: &Charset, theme: &Theme) {
+        for animation in &self.active {
+            render_outline(buffer, animation.current_rect(), charset, theme);
+        }
+    }
+}
+
+/// Draw a window-sized outline using the focused border colors
+fn render_outline(buffer: &mut VideoBuffer, rect: Rect, charset: &Charset, theme: &Theme) {
+    let (x, y, width, height) = rect;
+    if width == 0 || height == 0 {
+        return;
+    }
+
+    let fg = theme.window_border_focused_fg;
+    let bg = theme.window_border_focused_bg;
+    let right = x + width - 1;
+    let bottom = y + height - 1;
+
+    for dx in x..=right {
+        buffer.set(
+            dx,
+            y,
+            Cell::new_unchecked(charset.border_horizontal, fg, bg),
+        );
+        buffer.set(
+            dx,
+            bottom,
+            Cell::new_unchecked(charset.border_horizontal, fg, bg),
+        );
+    }
+    for dy in y..=bottom {
+        buffer.set(x, dy, Cell::new_unchecked(charset.border_vertical, fg, bg));
+        buffer.set(
+            right,
+            dy,
+            Cell::new_unchecked(charset.border_vertical, fg, bg),
+        );
+    }
+    if width > 1 && height > 1 {
+        buffer.set(x, y, Cell::new_unchecked(charset.border_top_left, fg, bg));
+        buffer.set(
+            right,
+            y,
+            Cell::new_unchecked(charset.border_top_right, fg, bg),
+        );
+        buffer.set(
+            x,
+            bottom,
+            Cell::new_unchecked(charset.border_bottom_left, fg, bg),
+        );
+        buffer.set(
+            right,
+            bottom,
+            Cell::new_unchecked(charset.border_bottom_right, fg, bg),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lerp_rect_endpoints_and_midpoint() {
+        let from = (10, 10, 40, 20);
+        let to = (0, 24, 10, 1);
+        assert_eq!(lerp_rect(from, to, 0.0), from);
+        assert_eq!(lerp_rect(from, to, 1.0), to);
+        assert_eq!(lerp_rect(from, to, 0.5), (5, 17, 25, 11));
+    }
+
+    #[test]
+    fn test_zero_duration_finishes_immediately() {
+        let animation = WindowAnimation::new((0, 0, 10, 5), (0, 0, 20, 10), Duration::ZERO);
+        assert!(animation.is_finished());
+        assert_eq!(animation.current_rect(), (0, 0, 20, 10));
+    }
+}
@@ -1,4 +1,41 @@
-use crate::rendering::{Cell, Charset, Theme, VideoBuffer, render_shadow};
+use crate::app::config_manager::{FocusHighlight, ScrollbarMode};
+use crate::rendering::{Cell, Charset, Theme, VideoBuffer, dim_color, render_shadow};
+use crossterm::style::Color;
+
+/// Title bar color tags, in cycling order (dark shades so white title text stays readable)
+pub const WINDOW_TAGS: &[(&str, Color)] = &[
+    ("red", Color::DarkRed),
+    ("green", Color::DarkGreen),
+    ("yellow", Color::DarkYellow),
+    ("blue", Color::DarkBlue),
+    ("magenta", Color::DarkMagenta),
+    ("cyan", Color::DarkCyan),
+];
+
+/// Title text color on a tagged title bar
+const TAG_TITLE_FG: Color = Color::White;
+
+/// Title bar button columns relative to the window's left edge ("[X] [+] [_] ")
+const CLOSE_BUTTON_X: u16 = 2;
+const MAXIMIZE_BUTTON_X: u16 = 6;
+const MINIMIZE_BUTTON_X: u16 = 10;
+const TITLE_BUTTON_WIDTH: u16 = 3;
+
+/// Tag color for a palette name (case-insensitive)
+pub fn tag_color_by_name(name: &str) -> Option<Color> {
+    WINDOW_TAGS
+        .iter()
+        .find(|(tag, _)| tag.eq_ignore_ascii_case(name))
+        .map(|(_, color)| *color)
+}
+
+/// Palette name of a tag color
+pub fn tag_name(color: Color) -> Option<&'static str> {
+    WINDOW_TAGS
+        .iter()
+        .find(|(_, tag_color)| *tag_color == color)
+        .map(|(name, _)| *name)
+}
 
 /// Which edge is being resized
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -26,12 +63,24 @@ pub struct Window {
     pub is_focused: bool,
     pub is_minimized: bool,
     pub is_maximized: bool,
+    // Drop-down scratchpad: hidden instead of closed, outside the button bar and tiling
+    pub is_scratchpad: bool,
+
+    // Color tag overriding the theme's title bar background (None = untagged)
+    pub tag_color: Option<Color>,
+
+    // When the scrollbar is shown (Never gives its column to the content area)
+    pub scrollbar_mode: ScrollbarMode,
 
     // Pre-maximize state (for restore)
     pre_maximize_x: u16,
     pre_maximize_y: u16,
     pre_maximize_width: u16,
     pre_maximize_height: u16,
+
+    // Pre-maximize state per axis: (y, height) for vertical, (x, width) for horizontal
+    pre_maximize_vertical: Option<(u16, u16)>,
+    pre_maximize_horizontal: Option<(u16, u16)>,
 }
 
 impl Window {
@@ -51,10 +100,50 @@ impl Window {
             is_focused: false,
             is_minimized: false,
             is_maximized: false,
+            is_scratchpad: false,
+            tag_color: None,
+            scrollbar_mode: ScrollbarMode::default(),
             pre_maximize_x: x,
             pre_maximize_y: y,
             pre_maximize_width: width,
             pre_maximize_height: height,
+            pre_maximize_vertical: None,
+            pre_maximize_horizontal: None,
+        }
+    }
+
+    /// Width of the content area in screen cells: inside the 2-char borders, plus the
+    /// inner right border column when the scrollbar is never shown
+    pub fn content_width(&self) -> u16 {
+        let borders = match self.scrollbar_mode {
+            ScrollbarMode::Never => 3, // -2 left, -1 right
+            _ => 4,                    // -2 left, -2 right
+        };
+        self.width.saturating_sub(borders)
+    }
+
+    /// Step the color tag to the next palette entry, then back to untagged
+    pub fn cycle_tag(&mut self) {
+        let next = match self.tag_color {
+            None => 0,
+            Some(color) => match WINDOW_TAGS.iter().position(|(_, tag)| *tag == color) {
+                Some(i) => i + 1,
+                None => 0,
+            },
+        };
+        self.tag_color = WINDOW_TAGS.get(next).map(|(_, color)| *color);
+    }
+
+    /// Title bar background: keyboard mode color, else the tag, else the focus color
+    fn title_bg(&self, theme: &Theme, use_keyboard_colors: bool) -> Color {
+        if use_keyboard_colors {
+            theme.keyboard_mode_title_bg
+        } else if let Some(tag) = self.tag_color {
+            tag
+        } else if self.is_focused {
+            theme.window_title_focused_bg
+        } else {
+            theme.window_title_unfocused_bg
         }
     }
 
@@ -67,79 +156,106 @@ impl Window {
     pub fn is_in_close_button(&self, x: u16, y: u16) -> bool {
         // [X] is at position x+2 (after 2-char left border)
         // Button layout: "[X] [+] [_] " - Close is at positions 2-4
-        y == self.y && x >= self.x + 2 && x <= self.x + 4
+        self.is_in_title_button(CLOSE_BUTTON_X, x, y)
     }
 
     /// Check if point is in maximize button [+]
     pub fn is_in_maximize_button(&self, x: u16, y: u16) -> bool {
         // [+] is at position x+6 (after "[X] ")
         // Button layout: "[X] [+] [_] " - Maximize is chars 4-6 (positions 6-8)
-        y == self.y && x >= self.x + 6 && x <= self.x + 8
+        self.is_in_title_button(MAXIMIZE_BUTTON_X, x, y)
     }
 
     /// Check if point is in minimize button [_]
     pub fn is_in_minimize_button(&self, x: u16, y: u16) -> bool {
         // [_] is at position x+10 (after "[X] [+] ")
         // Button layout: "[X] [+] [_] " - Minimize is chars 8-10 (positions 10-12)
-        y == self.y && x >= self.x + 10 && x <= self.x + 12
+        self.is_in_title_button(MINIMIZE_BUTTON_X, x, y)
+    }
+
+    /// Check if point is in the 3-char title bar button starting `offset` columns in
+    fn is_in_title_button(&self, offset: u16, x: u16, y: u16) -> bool {
+        y == self.y && x >= self.x + offset && x < self.x + offset + TITLE_BUTTON_WIDTH
+    }
+
+    /// First title bar column after the "[X] [+] [_] " buttons and their trailing space
+    pub fn title_buttons_end(&self) -> u16 {
+        self.x + MINIMIZE_BUTTON_X + TITLE_BUTTON_WIDTH + 1
     }
 
     /// Check if point is on left border (excluding corners)
-    /// Both characters of the 2-char left border are resizable
-    pub fn is_on_left_border(&self, x: u16, y: u16) -> bool {
-        (x == self.x || x == self.x + 1) && y > self.y && y < self.y + self.height - 1
+    /// Both characters of the 2-char left border are resizable, plus `extra` cells inside
+    fn is_on_left_border(&self, x: u16, y: u16, extra: u16) -> bool {
+        x <= self.x + 1 + extra && y > self.y && y < self.bottom()
     }
 
-    /// Check if point is on bottom border (excluding 2-char corners)
-    pub fn is_on_bottom_border(&self, x: u16, y: u16) -> bool {
-        y == self.y + self.height - 1 && x > self.x + 1 && x < self.x + self.width - 2
+    /// Check if point is on bottom border (excluding 2-char corners), or `extra` rows above it
+    fn is_on_bottom_border(&self, x: u16, y: u16, extra: u16) -> bool {
+        y + extra >= self.bottom() && x > self.x + 1 && x < self.x + self.width - 2
     }
 
     /// Check if point is on right border outer edge (resizable, excluding scrollbar)
-    /// Only the outer character (width-1) is resizable, inner char (width-2) has scrollbar
-    pub fn is_on_right_border(&self, x: u16, y: u16) -> bool {
-        x == self.x + self.width - 1 && y > self.y && y < self.y + self.height - 1
+    /// Only the outer character (width-1) is resizable, inner char (width-2) has scrollbar;
+    /// `extra` widens the edge inward (the scrollbar still takes its clicks)
+    fn is_on_right_border(&self, x: u16, y: u16, extra: u16) -> bool {
+        x + extra >= self.right() && y > self.y && y < self.bottom()
+    }
+
+    /// Check if point is in bottom-left corner (2-char wide corner area, `extra` larger)
+    fn is_in_bottom_left_corner(&self, x: u16, y: u16, extra: u16) -> bool {
+        y + extra >= self.bottom() && x <= self.x + 1 + extra
     }
 
-    /// Check if point is in bottom-left corner (2-char wide corner area)
-    pub fn is_in_bottom_left_corner(&self, x: u16, y: u16) -> bool {
-        y == self.y + self.height - 1 && (x == self.x || x == self.x + 1)
+    /// Check if point is in bottom-right corner (2-char wide corner area, `extra` larger)
+    fn is_in_bottom_right_corner(&self, x: u16, y: u16, extra: u16) -> bool {
+        y + extra >= self.bottom() && x + 1 + extra >= self.right()
     }
 
-    /// Check if point is in bottom-right corner (2-char wide corner area)
-    pub fn is_in_bottom_right_corner(&self, x: u16, y: u16) -> bool {
-        y == self.y + self.height - 1
-            && (x == self.x + self.width - 2 || x == self.x + self.width - 1)
+    /// Check if point is in top-left corner (2-char wide corner area, `extra` larger)
+    fn is_in_top_left_corner(&self, x: u16, y: u16, extra: u16) -> bool {
+        y <= self.y + extra && x <= self.x + 1 + extra
     }
 
-    /// Check if point is in top-left corner (2-char wide corner area)
-    pub fn is_in_top_left_corner(&self, x: u16, y: u16) -> bool {
-        y == self.y && (x == self.x || x == self.x + 1)
+    /// Check if point is in top-right corner (2-char wide corner area, `extra` larger)
+    fn is_in_top_right_corner(&self, x: u16, y: u16, extra: u16) -> bool {
+        y <= self.y + extra && x + 1 + extra >= self.right()
     }
 
-    /// Check if point is in top-right corner (2-char wide corner area)
-    pub fn is_in_top_right_corner(&self, x: u16, y: u16) -> bool {
-        y == self.y && (x == self.x + self.width - 2 || x == self.x + self.width - 1)
+    /// Last column of the window (right border)
+    fn right(&self) -> u16 {
+        self.x + self.width.saturating_sub(1)
+    }
+
+    /// Last row of the window (bottom border)
+    fn bottom(&self) -> u16 {
+        self.y + self.height.saturating_sub(1)
     }
 
     /// Determine which resize edge (if any) is at the given point
     /// Returns Some(edge) if on a resizable border, None otherwise
     /// Checks corners first, then edges
-    pub fn get_resize_edge(&self, x: u16, y: u16) -> Option<ResizeEdge> {
+    /// `grip` is the hit area in cells (1 = the border itself, the `resize_grip_size`
+    /// option): wider grips reach into the window, and corners one cell further than edges
+    pub fn get_resize_edge(&self, x: u16, y: u16, grip: u16) -> Option<ResizeEdge> {
+        if !self.contains_point(x, y) {
+            return None;
+        }
+        let extra = grip.saturating_sub(1);
+        let corner_extra = if extra > 0 { extra + 1 } else { 0 };
         // Check corners first (they take priority over edges)
-        if self.is_in_bottom_left_corner(x, y) {
+        if self.is_in_bottom_left_corner(x, y, corner_extra) {
             Some(ResizeEdge::BottomLeft)
-        } else if self.is_in_bottom_right_corner(x, y) {
+        } else if self.is_in_bottom_right_corner(x, y, corner_extra) {
             Some(ResizeEdge::BottomRight)
-        } else if self.is_in_top_left_corner(x, y) {
+        } else if self.is_in_top_left_corner(x, y, corner_extra) {
             Some(ResizeEdge::TopLeft)
-        } else if self.is_in_top_right_corner(x, y) {
+        } else if self.is_in_top_right_corner(x, y, corner_extra) {
             Some(ResizeEdge::TopRight)
-        } else if self.is_on_left_border(x, y) {
+        } else if self.is_on_left_border(x, y, extra) {
             Some(ResizeEdge::Left)
-        } else if self.is_on_right_border(x, y) {
+        } else if self.is_on_right_border(x, y, extra) {
             Some(ResizeEdge::Right)
-        } else if self.is_on_bottom_border(x, y) {
+        } else if self.is_on_bottom_border(x, y, extra) {
             Some(ResizeEdge::Bottom)
         } else {
             None
@@ -184,6 +300,27 @@ impl Window {
         }
     }
 
+    /// Re-apply the maximized geometry for a new screen size
+    /// The saved pre-maximize geometry is kept so restoring still works
+    pub fn refit_maximize(&mut self, buffer_width: u16, buffer_height: u16, gaps: bool) {
+        if self.is_maximized {
+            let saved = (
+                self.pre_maximize_x,
+                self.pre_maximize_y,
+                self.pre_maximize_width,
+                self.pre_maximize_height,
+            );
+            self.is_maximized = false;
+            self.maximize(buffer_width, buffer_height, gaps);
+            (
+                self.pre_maximize_x,
+                self.pre_maximize_y,
+                self.pre_maximize_width,
+                self.pre_maximize_height,
+            ) = saved;
+        }
+    }
+
     /// Restore the window to its pre-maximize state
     pub fn restore_from_maximize(&mut self) {
         if self.is_maximized {
@@ -206,6 +343,60 @@ impl Window {
         }
     }
 
+    /// Maximize the window's height only (between the top bar and the bottom bar)
+    /// Repeating restores the previous y/height. No-op while fully maximized
+    pub fn toggle_maximize_vertical(&mut self, buffer_height: u16, gaps: bool) {
+        if self.is_maximized {
+            return;
+        }
+
+        // With gaps: 1 char edge gap + 2 char shadow, as in maximize()
+        let (top, height) = if gaps {
+            (2, buffer_height.saturating_sub(5))
+        } else {
+            (1, buffer_height.saturating_sub(2))
+        };
+
+        // Only a window still at the maximized geometry toggles back
+        if let Some((y, h)) = self.pre_maximize_vertical.take() {
+            if self.y == top && self.height == height {
+                self.y = y;
+                self.height = h;
+                return;
+            }
+        }
+
+        self.pre_maximize_vertical = Some((self.y, self.height));
+        self.y = top;
+        self.height = height;
+    }
+
+    /// Maximize the window's width only (screen edge to screen edge)
+    /// Repeating restores the previous x/width. No-op while fully maximized
+    pub fn toggle_maximize_horizontal(&mut self, buffer_width: u16, gaps: bool) {
+        if self.is_maximized {
+            return;
+        }
+
+        let (left, width) = if gaps {
+            (1, buffer_width.saturating_sub(4))
+        } else {
+            (0, buffer_width)
+        };
+
+        if let Some((x, w)) = self.pre_maximize_horizontal.take() {
+            if self.x == left && self.width == width {
+                self.x = x;
+                self.width = w;
+                return;
+            }
+        }
+
+        self.pre_maximize_horizontal = Some((self.x, self.width));
+        self.x = left;
+        self.width = width;
+    }
+
     /// Minimize the window (hide it from view)
     pub fn minimize(&mut self) {
         self.is_minimized = true;
@@ -216,6 +407,11 @@ impl Window {
         self.is_minimized = false;
     }
 
+    /// Whether the window is placed by auto-tiling and the tiled split
+    pub fn in_layout(&self) -> bool {
+        !self.is_minimized && !self.is_scratchpad
+    }
+
     /// Toggle minimize state
     #[allow(dead_code)]
     pub fn toggle_minimize(&mut self) {
@@ -267,6 +463,57 @@ impl Window {
         );
     }
 
+    /// Make the focused window stand out (the `focus_highlight` option): Border paints its
+    /// side and bottom borders in the title bar colors, DimOthers mutes the whole frame of
+    /// an unfocused window (title bar and scrollbar included) by `dim_percent`
+    /// Drawn over the finished frame, so it also covers the scrollbar and title indicators
+    pub fn render_focus_highlight(
+        &self,
+        buffer: &mut VideoBuffer,
+        theme: &Theme,
+        highlight: FocusHighlight,
+        dim_percent: u8,
+        keyboard_mode_active: bool,
+    ) {
+        if self.is_minimized || self.width < 4 || self.height < 2 {
+            return;
+        }
+        let (left, right) = (self.x, self.x + self.width - 1);
+        let (top, bottom) = (self.y, self.y + self.height - 1);
+
+        if self.is_focused && highlight.border() {
+            let bg = self.title_bg(theme, keyboard_mode_active);
+            let fg = if keyboard_mode_active {
+                theme.keyboard_mode_title_fg
+            } else if self.tag_color.is_some() {
+                TAG_TITLE_FG
+            } else {
+                theme.window_title_focused_fg
+            };
+            // The inner right column is left to the scrollbar
+            let sides = (top + 1..bottom).flat_map(|y| [(left, y), (left + 1, y), (right, y)]);
+            let bottom_row = (left..=right).map(|x| (x, bottom));
+            for (x, y) in sides.chain(bottom_row) {
+                if let Some(&cell) = buffer.get(x, y) {
+                    buffer.set(x, y, Cell::new_unchecked(cell.character, fg, bg));
+                }
+            }
+        } else if !self.is_focused && highlight.dim_others() && dim_percent > 0 {
+            let rows = [top, bottom]
+                .into_iter()
+                .flat_map(|y| (left..=right).map(move |x| (x, y)));
+            let sides = (top + 1..bottom)
+                .flat_map(|y| [(left, y), (left + 1, y), (right - 1, y), (right, y)]);
+            for (x, y) in rows.chain(sides) {
+                if let Some(&cell) = buffer.get(x, y) {
+                    let fg = dim_color(cell.fg_color, dim_percent);
+                    let bg = dim_color(cell.bg_color, dim_percent);
+                    buffer.set(x, y, Cell::new_unchecked(cell.character, fg, bg));
+                }
+            }
+        }
+    }
+
     fn render_frame(
         &self,
         buffer: &mut VideoBuffer,
@@ -277,14 +524,8 @@ impl Window {
         // Use keyboard mode colors when active and focused, otherwise normal focus state colors
         let use_keyboard_colors = keyboard_mode_active && self.is_focused;
 
-        // Use different backgrounds based on focus state
-        let title_bg = if use_keyboard_colors {
-            theme.keyboard_mode_title_bg
-        } else if self.is_focused {
-            theme.window_title_focused_bg
-        } else {
-            theme.window_title_unfocused_bg
-        };
+        // Use different backgrounds based on focus state (or the window's color tag)
+        let title_bg = self.title_bg(theme, use_keyboard_colors);
 
         // Border colors based on focus state
         let border_bg = if use_keyboard_colors {
@@ -424,14 +665,8 @@ impl Window {
         // Use keyboard mode colors when active and focused
         let use_keyboard_colors = keyboard_mode_active && self.is_focused;
 
-        // Use different colors based on focus state
-        let title_bg = if use_keyboard_colors {
-            theme.keyboard_mode_title_bg
-        } else if self.is_focused {
-            theme.window_title_focused_bg
-        } else {
-            theme.window_title_unfocused_bg
-        };
+        // Use different colors based on focus state (or the window's color tag)
+        let title_bg = self.title_bg(theme, use_keyboard_colors);
 
         // Border foreground color based on focus state
         let border_fg = if use_keyboard_colors {
@@ -445,6 +680,8 @@ impl Window {
         // Title text color based on focus state
         let title_fg = if use_keyboard_colors {
             theme.keyboard_mode_title_fg
+        } else if self.tag_color.is_some() {
+            TAG_TITLE_FG
         } else if self.is_focused {
             theme.window_title_focused_fg
         } else {
@@ -509,7 +746,7 @@ impl Window {
         // Pre-compute base positions to avoid repeated additions
         let base_x = self.x + 2;
         let base_y = self.y + 1;
-        let content_width = self.width.saturating_sub(4); // -2 left, -2 right
+        let content_width = self.content_width();
         let content_height = self.height.saturating_sub(2); // -1 top, -1 bottom
 
         for dy in 0..content_height {
@@ -538,3 +775,212 @@ impl Window {
         self.pre_maximize_height = height;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_cycles_through_palette() {
+        let mut window = Window::new(1, 0, 1, 40, 10, "prod".to_string());
+        assert_eq!(window.tag_color, None);
+        for (_, color) in WINDOW_TAGS {
+            window.cycle_tag();
+            assert_eq!(window.tag_color, Some(*color));
+        }
+        window.cycle_tag();
+        assert_eq!(window.tag_color, None);
+    }
+
+    #[test]
+    fn test_refit_maximize_keeps_restore_geometry() {
+        let mut window = Window::new(1, 10, 5, 40, 10, "refit".to_string());
+        window.maximize(80, 24, false);
+        // Bars hidden: two more rows
+        window.refit_maximize(80, 26, false);
+        assert_eq!(
+            (window.x, window.y, window.width, window.height),
+            (0, 1, 80, 25)
+        );
+        assert!(window.is_maximized);
+
+        window.restore_from_maximize();
+        assert_eq!(
+            (window.x, window.y, window.width, window.height),
+            (10, 5, 40, 10)
+        );
+
+        // Windows that aren't maximized are left alone
+        window.refit_maximize(80, 24, false);
+        assert_eq!(
+            (window.x, window.y, window.width, window.height),
+            (10, 5, 40, 10)
+        );
+    }
+
+    #[test]
+    fn test_axis_maximize_toggles_one_axis() {
+        let mut window = Window::new(1, 10, 5, 40, 10, "axis".to_string());
+        window.toggle_maximize_vertical(30, false);
+        assert_eq!(
+            (window.x, window.y, window.width, window.height),
+            (10, 1, 40, 28)
+        );
+        window.toggle_maximize_horizontal(100, true);
+        assert_eq!(
+            (window.x, window.y, window.width, window.height),
+            (1, 1, 96, 28)
+        );
+
+        // Repeating restores each axis on its own
+        window.toggle_maximize_vertical(30, false);
+        assert_eq!(
+            (window.x, window.y, window.width, window.height),
+            (1, 5, 96, 10)
+        );
+        window.toggle_maximize_horizontal(100, true);
+        assert_eq!(
+            (window.x, window.y, window.width, window.height),
+            (10, 5, 40, 10)
+        );
+    }
+
+    #[test]
+    fn test_axis_maximize_forgets_geometry_after_a_move() {
+        let mut window = Window::new(1, 10, 5, 40, 10, "axis".to_string());
+        window.toggle_maximize_vertical(30, false);
+        // Moved away from the maximized geometry: the next toggle maximizes again
+        window.y = 3;
+        window.toggle_maximize_vertical(30, false);
+        assert_eq!((window.y, window.height), (1, 28));
+        window.toggle_maximize_vertical(30, false);
+        assert_eq!((window.y, window.height), (3, 28));
+    }
+
+    #[test]
+    fn test_axis_maximize_ignored_while_maximized() {
+        let mut window = Window::new(1, 10, 5, 40, 10, "axis".to_string());
+        window.is_maximized = true;
+        window.toggle_maximize_vertical(30, false);
+        window.toggle_maximize_horizontal(100, false);
+        assert_eq!(
+            (window.x, window.y, window.width, window.height),
+            (10, 5, 40, 10)
+        );
+    }
+
+    #[test]
+    fn test_resize_grip_widens_hit_area() {
+        // Columns 10..=49, rows 5..=24
+        let window = Window::new(1, 10, 5, 40, 20, "grip".to_string());
+        // Default grip: only the borders themselves
+        assert_eq!(window.get_resize_edge(11, 10, 1), Some(ResizeEdge::Left));
+        assert_eq!(window.get_resize_edge(12, 10, 1), None);
+        assert_eq!(window.get_resize_edge(49, 10, 1), Some(ResizeEdge::Right));
+        assert_eq!(window.get_resize_edge(48, 10, 1), None);
+        assert_eq!(window.get_resize_edge(30, 24, 1), Some(ResizeEdge::Bottom));
+        assert_eq!(window.get_resize_edge(30, 23, 1), None);
+        assert_eq!(
+            window.get_resize_edge(48, 24, 1),
+            Some(ResizeEdge::BottomRight)
+        );
+        assert_eq!(window.get_resize_edge(47, 24, 1), Some(ResizeEdge::Bottom));
+        assert_eq!(window.get_resize_edge(10, 5, 1), Some(ResizeEdge::TopLeft));
+        assert_eq!(window.get_resize_edge(10, 6, 1), Some(ResizeEdge::Left));
+
+        // A grip of 3 reaches two more cells in, corners one cell more
+        assert_eq!(window.get_resize_edge(13, 12, 3), Some(ResizeEdge::Left));
+        assert_eq!(window.get_resize_edge(14, 12, 3), None);
+        assert_eq!(window.get_resize_edge(47, 12, 3), Some(ResizeEdge::Right));
+        assert_eq!(window.get_resize_edge(30, 22, 3), Some(ResizeEdge::Bottom));
+        assert_eq!(window.get_resize_edge(30, 21, 3), None);
+        assert_eq!(
+            window.get_resize_edge(46, 21, 3),
+            Some(ResizeEdge::BottomRight)
+        );
+        assert_eq!(window.get_resize_edge(14, 8, 3), Some(ResizeEdge::TopLeft));
+        assert_eq!(window.get_resize_edge(50, 12, 3), None);
+    }
+
+    #[test]
+    fn test_focus_highlight() {
+        let theme = Theme::from_name("classic");
+        let charset = Charset::unicode();
+        let render = |focused: bool, highlight: FocusHighlight| {
+            let mut window = Window::new(1, 0, 1, 30, 8, "focus".to_string());
+            window.is_focused = focused;
+            let mut buffer = VideoBuffer::new(40, 12);
+            window.render_with_title(&mut buffer, &charset, &theme, None, false);
+            window.render_focus_highlight(&mut buffer, &theme, highlight, 50, false);
+            buffer
+        };
+
+        // Off: the frame keeps the theme's colors
+        let plain = render(true, FocusHighlight::None);
+        let left = *plain.get(0, 4).unwrap();
+        assert_eq!(left.bg_color, theme.window_border_focused_bg);
+
+        // Border: sides and bottom take the title bar colors, characters unchanged
+        let ring = render(true, FocusHighlight::Both);
+        for (x, y) in [(0, 4), (1, 4), (29, 4), (10, 8)] {
+            let cell = ring.get(x, y).unwrap();
+            assert_eq!(
+                cell.bg_color, theme.window_title_focused_bg,
+                "({}, {})",
+                x, y
+            );
+            assert_eq!(cell.character, plain.get(x, y).unwrap().character);
+        }
+        assert_eq!(ring.get(5, 4), plain.get(5, 4));
+
+        // DimOthers: only unfocused frames are muted, the content is not
+        let dimmed = render(false, FocusHighlight::DimOthers);
+        let unfocused = render(false, FocusHighlight::Border);
+        for (x, y) in [(0, 1), (0, 4), (28, 4), (10, 8)] {
+            assert_eq!(
+                dimmed.get(x, y).unwrap().bg_color,
+                dim_color(unfocused.get(x, y).unwrap().bg_color, 50)
+            );
+        }
+        assert_eq!(dimmed.get(5, 4), unfocused.get(5, 4));
+        assert_eq!(
+            render(true, FocusHighlight::DimOthers).get(0, 4),
+            plain.get(0, 4)
+        );
+    }
+
+    #[test]
+    fn test_tag_overrides_title_background() {
+        let theme = Theme::from_name("classic");
+        let mut window = Window::new(1, 0, 1, 40, 10, "prod".to_string());
+        window.is_focused = true;
+        assert_eq!(
+            window.title_bg(&theme, false),
+            theme.window_title_focused_bg
+        );
+
+        window.tag_color = tag_color_by_name("Red");
+        assert_eq!(window.title_bg(&theme, false), Color::DarkRed);
+        // Window Mode still marks the focused window
+        assert_eq!(window.title_bg(&theme, true), theme.keyboard_mode_title_bg);
+        assert_eq!(tag_name(Color::DarkRed), Some("red"));
+    }
+
+    #[test]
+    fn test_content_fill_follows_scrollbar_mode() {
+        let theme = Theme::from_name("classic");
+        let mut buffer = VideoBuffer::new(40, 12);
+        let mut window = Window::new(1, 0, 1, 30, 8, "w".to_string());
+        assert_eq!(window.content_width(), 26);
+
+        // Never: the scrollbar column (x = 28) is filled as content
+        window.scrollbar_mode = ScrollbarMode::Never;
+        assert_eq!(window.content_width(), 27);
+        window.render(&mut buffer, &Charset::unicode(), &theme);
+        let cell = buffer.get(28, 3).unwrap();
+        assert_eq!(
+            (cell.character, cell.bg_color),
+            (' ', theme.window_content_bg)
+        );
+    }
+}
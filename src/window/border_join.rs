@@ -0,0 +1,186 @@
+//! Joined borders for touching windows (the `join_borders` config option)
+//! Runs after all windows are drawn: a window's right edge next to another window's left
+//! edge collapses into one shared line, and border ends meeting another window's border
+//! become T-junctions or crosses, so gapless tiled layouts look like one multi-pane TUI.
+//! Only cells still showing the frame glyph of the topmost window are touched, so window
+//! content that happens to use box drawing characters is left alone.
+
+use crate::rendering::{Charset, VideoBuffer};
+
+const UP: u8 = 1;
+const DOWN: u8 = 2;
+const LEFT: u8 = 4;
+const RIGHT: u8 = 8;
+const VERTICAL: u8 = UP | DOWN;
+
+/// Join the borders of `windows` (x, y, width, height), ordered bottom to top
+pub fn join_borders(buffer: &mut VideoBuffer, windows: &[(u16, u16, u16, u16)], charset: &Charset) {
+    let (cols, rows) = buffer.dimensions();
+    let mut arms = frame_arms(windows, cols, rows);
+
+    // Drop cells something else was drawn over (dialogs inside a window, etc.)
+    for y in 0..rows {
+        for x in 0..cols {
+            let i = y as usize * cols as usize + x as usize;
+            if arms[i] != 0
+                && buffer.get(x, y).map(|c| c.character) != Some(glyph(arms[i], charset))
+            {
+                arms[i] = 0;
+            }
+        }
+    }
+    let before = arms.clone();
+    join(&mut arms, cols as usize, rows as usize);
+
+    for y in 0..rows {
+        for x in 0..cols {
+            let i = y as usize * cols as usize + x as usize;
+            if arms[i] == before[i] {
+                continue;
+            }
+            if let Some(mut cell) = buffer.get(x, y).copied() {
+                cell.character = match arms[i] {
+                    0 => ' ',
+                    a => glyph(a, charset),
+                };
+                buffer.set(x, y, cell);
+            }
+        }
+    }
+}
+
+/// Line arms of every screen cell on the frame of the topmost window covering it
+/// Title bar cells other than the corners are not lines
+fn frame_arms(windows: &[(u16, u16, u16, u16)], cols: u16, rows: u16) -> Vec<u8> {
+    let mut arms = vec![0u8; cols as usize * rows as usize];
+    for &(wx, wy, width, height) in windows {
+        if width < 4 || height < 2 {
+            continue;
+        }
+        let right = wx + width - 1;
+        let bottom = wy + height - 1;
+        for y in wy..=bottom.min(rows.saturating_sub(1)) {
+            for x in wx..=right.min(cols.saturating_sub(1)) {
+                let a = match (x == wx, x == right, y == wy, y == bottom) {
+                    (true, _, true, _) => RIGHT | DOWN,
+                    (_, true, true, _) => LEFT | DOWN,
+                    (true, _, _, true) => UP | RIGHT,
+                    (_, true, _, true) => UP | LEFT,
+                    (true, ..) | (_, true, ..) => VERTICAL,
+                    (_, _, false, true) => LEFT | RIGHT,
+                    _ => 0,
+                };
+                arms[y as usize * cols as usize + x as usize] = a;
+            }
+        }
+    }
+    arms
+}
+
+/// Merge side-by-side vertical borders into the right-hand one, then connect border ends
+/// to the border directly above or below them
+fn join(arms: &mut [u8], cols: usize, rows: usize) {
+    for y in 0..rows {
+        for x in 0..cols.saturating_sub(1) {
+            let (a, b) = (arms[y * cols + x], arms[y * cols + x + 1]);
+            if a & VERTICAL != 0 && b & VERTICAL != 0 {
+                arms[y * cols + x + 1] = b | (a & (VERTICAL | LEFT));
+                arms[y * cols + x] = if a & LEFT != 0 { LEFT | RIGHT } else { 0 };
+            }
+        }
+    }
+    for y in 0..rows.saturating_sub(1) {
+        for x in 0..cols {
+            let (a, b) = (arms[y * cols + x], arms[(y + 1) * cols + x]);
+            if a != 0 && b != 0 && a & DOWN == 0 && b & UP == 0 && (a & UP != 0 || b & DOWN != 0) {
+                arms[y * cols + x] = a | DOWN;
+                arms[(y + 1) * cols + x] = b | UP;
+            }
+        }
+    }
+}
+
+/// Box drawing glyph for a set of arms
+fn glyph(arms: u8, charset: &Charset) -> char {
+    match arms {
+        a if a == RIGHT | DOWN => charset.border_top_left,
+        a if a == LEFT | DOWN => charset.border_top_right,
+        a if a == UP | RIGHT => charset.border_bottom_left,
+        a if a == UP | LEFT => charset.border_bottom_right,
+        a if a == VERTICAL => charset.border_vertical,
+        a if a == VERTICAL | RIGHT => charset.border_vertical_right,
+        a if a == VERTICAL | LEFT => charset.border_vertical_left,
+        a if a == LEFT | RIGHT | DOWN => charset.border_horizontal_down,
+        a if a == LEFT | RIGHT | UP => charset.border_horizontal_up,
+        a if a == VERTICAL | LEFT | RIGHT => charset.border_cross,
+        _ => charset.border_horizontal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rendering::Cell;
+    use crossterm::style::Color;
+
+    /// Draw bare window frames in order, like the windows would (the title bar between
+    /// the corners is left blank)
+    fn draw(windows: &[(u16, u16, u16, u16)], cols: u16, rows: u16) -> VideoBuffer {
+        let charset = Charset::unicode();
+        let mut buffer = VideoBuffer::new(cols, rows);
+        for &window in windows {
+            let arms = frame_arms(&[window], cols, rows);
+            let (wx, wy, width, height) = window;
+            for y in wy..wy + height {
+                for x in wx..wx + width {
+                    let a = arms[y as usize * cols as usize + x as usize];
+                    let ch = if a == 0 { ' ' } else { glyph(a, &charset) };
+                    buffer.set(x, y, Cell::new(ch, Color::White, Color::Blue));
+                }
+            }
+        }
+        buffer
+    }
+
+    fn row(buffer: &VideoBuffer, y: u16) -> String {
+        let (cols, _) = buffer.dimensions();
+        (0..cols)
+            .map(|x| buffer.get(x, y).unwrap().character)
+            .collect()
+    }
+
+    #[test]
+    fn test_side_by_side_windows_share_one_border() {
+        let windows = [(0, 0, 5, 3), (5, 0, 5, 3)];
+        let mut buffer = draw(&windows, 10, 3);
+        assert_eq!(row(&buffer, 1), "║   ║║   ║");
+
+        join_borders(&mut buffer, &windows, &Charset::unicode());
+        assert_eq!(row(&buffer, 0), "╔   ═╦   ╗");
+        assert_eq!(row(&buffer, 1), "║    ║   ║");
+        assert_eq!(row(&buffer, 2), "╚════╩═══╝");
+    }
+
+    #[test]
+    fn test_stacked_windows_connect_corners() {
+        // A left window spanning the height of two stacked windows on the right
+        let windows = [(0, 0, 5, 6), (5, 0, 5, 3), (5, 3, 5, 3)];
+        let mut buffer = draw(&windows, 10, 6);
+        join_borders(&mut buffer, &windows, &Charset::unicode());
+        assert_eq!(row(&buffer, 0), "╔   ═╦   ╗");
+        assert_eq!(row(&buffer, 2), "║    ╠═══╣");
+        assert_eq!(row(&buffer, 3), "║    ╠   ╣");
+        assert_eq!(row(&buffer, 5), "╚════╩═══╝");
+    }
+
+    #[test]
+    fn test_content_box_drawing_is_untouched() {
+        let windows = [(0, 0, 6, 3)];
+        let mut buffer = draw(&windows, 8, 3);
+        // An application drew its own vertical lines inside the window
+        buffer.set(2, 1, Cell::new('║', Color::White, Color::Black));
+        buffer.set(3, 1, Cell::new('║', Color::White, Color::Black));
+        join_borders(&mut buffer, &windows, &Charset::unicode());
+        assert_eq!(row(&buffer, 1), "║ ║║ ║  ");
+    }
+}
@@ -0,0 +1,281 @@
+//! Layout of the window buttons in the bottom bar, shared by rendering and hit-testing
+//! Long titles are condensed and the list scrolls to the focused window when it overflows
+
+use crate::app::config_manager::{ButtonTitleDuplicates, ButtonTitleTruncation};
+
+/// Shortest title a button is condensed to before the list starts scrolling
+const MIN_TITLE_LEN: usize = 3;
+
+/// Width of a scroll marker ("<" or ">") including its gap
+const SCROLL_MARKER_WIDTH: u16 = 2;
+
+/// A window button: "[ label ]" (or "( label )" when minimized)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ButtonBarEntry {
+    pub id: u32,
+    pub x: u16,
+    pub width: u16,
+    pub label: String,
+    pub is_focused: bool,
+    pub is_minimized: bool,
+}
+
+/// Visible window buttons plus the scroll markers at either end
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ButtonBarLayout {
+    pub buttons: Vec<ButtonBarEntry>,
+    /// Marker position and the nearest window hidden to the left
+    pub scroll_left: Option<(u16, u32)>,
+    /// Marker position and the nearest window hidden to the right
+    pub scroll_right: Option<(u16, u32)>,
+}
+
+impl ButtonBarLayout {
+    /// Window under the given x position (a scroll marker selects the next hidden window)
+    pub fn window_at(&self, x: u16) -> Option<u32> {
+        let marker = |m: Option<(u16, u32)>| m.filter(|&(mx, _)| mx == x).map(|(_, id)| id);
+        self.buttons
+            .iter()
+            .find(|b| x >= b.x && x < b.x + b.width)
+            .map(|b| b.id)
+            .or_else(|| marker(self.scroll_left))
+            .or_else(|| marker(self.scroll_right))
+    }
+}
+
+/// Shorten a title to at most `max_len` characters
+pub fn truncate_title(title: &str, max_len: usize, truncation: ButtonTitleTruncation) -> String {
+    if title.chars().count() <= max_len {
+        return title.to_string();
+    }
+    match truncation {
+        ButtonTitleTruncation::Ellipsis if max_len > 3 => {
+            let mut label: String = title.chars().take(max_len - 3).collect();
+            label.push_str("...");
+            label
+        }
+        _ => title.chars().take(max_len).collect(),
+    }
+}
+
+/// Suffixes telling apart `windows` (id, title, ..) that share a title, one per window
+/// (empty for unique titles); `process` gives a window's running program for `Process`
+pub fn duplicate_suffixes(
+    windows: &[(u32, &str, bool, bool)],
+    mode: ButtonTitleDuplicates,
+    process: impl Fn(u32) -> Option<String>,
+) -> Vec<String> {
+    let mut suffixes = vec![String::new(); windows.len()];
+    if mode == ButtonTitleDuplicates::Off {
+        return suffixes;
+    }
+    let shared =
+        |labels: &[String], i: usize| labels.iter().filter(|l| **l == labels[i]).count() > 1;
+
+    let titles: Vec<String> = windows.iter().map(|w| w.1.to_string()).collect();
+    if mode == ButtonTitleDuplicates::Process {
+        for (i, &(id, ..)) in windows.iter().enumerate() {
+            if shared(&titles, i)
+                && let Some(name) = process(id)
+            {
+                suffixes[i] = format!(" ({})", name);
+            }
+        }
+    }
+
+    // Number whatever still collides, in window order
+    let labels: Vec<String> = titles
+        .iter()
+        .zip(&suffixes)
+        .map(|(t, s)| format!("{}{}", t, s))
+        .collect();
+    for i in 0..windows.len() {
+        if shared(&labels, i) {
+            let n = labels[..=i].iter().filter(|l| **l == labels[i]).count();
+            suffixes[i].push_str(&format!(" #{}", n));
+        }
+    }
+    suffixes
+}
+
+/// Lay out buttons for `windows` (id, title, is_focused, is_minimized) between
+/// `start_x` and `end_x` (exclusive), one space apart; `suffixes` (one per window, or
+/// none) are kept when titles are condensed
+pub fn layout(
+    windows: &[(u32, &str, bool, bool)],
+    suffixes: &[String],
+    start_x: u16,
+    end_x: u16,
+    max_title_len: usize,
+    truncation: ButtonTitleTruncation,
+) -> ButtonBarLayout {
+    let available = end_x.saturating_sub(start_x);
+    let total_width = |widths: &[u16]| widths.iter().map(|w| w + 1).sum::<u16>().saturating_sub(1);
+
+    // Condense titles until everything fits (or the minimum length is reached), starting
+    // from the longest label since lengths above it change nothing
+    let longest = windows
+        .iter()
+        .enumerate()
+        .map(|(i, (_, title, ..))| {
+            title.chars().count() + suffixes.get(i).map_or(0, |s| s.chars().count())
+        })
+        .max()
+        .unwrap_or(0);
+    let mut title_len = max_title_len.min(longest).max(1);
+    let (labels, widths) = loop {
+        let labels: Vec<String> = windows
+            .iter()
+            .enumerate()
+            .map(|(i, (_, title, ..))| {
+                let suffix = suffixes.get(i).map_or("", String::as_str);
+                let len = title_len.saturating_sub(suffix.chars().count()).max(1);
+                truncate_title(title, len, truncation) + suffix
+            })
+            .collect();
+        let widths: Vec<u16> = labels
+            .iter()
+            .map(|l| l.chars().count() as u16 + 4)
+            .collect();
+        if total_width(&widths) <= available || title_len <= MIN_TITLE_LEN {
+            break (labels, widths);
+        }
+        title_len -= 1;
+    };
+
+    // Still overflowing: show a run of buttons around the focused window
+    let (first, last) = if total_width(&widths) <= available {
+        (0, windows.len())
+    } else {
+        let room = available.saturating_sub(2 * SCROLL_MARKER_WIDTH);
+        let focused = windows.iter().position(|w| w.2).unwrap_or(0);
+        let mut first = focused;
+        while first > 0 && total_width(&widths[first - 1..=focused]) <= room {
+            first -= 1;
+        }
+        let mut last = focused + 1;
+        while last < windows.len() && total_width(&widths[first..=last]) <= room {
+            last += 1;
+        }
+        if total_width(&widths[first..last]) > room {
+            // Not even the focused button fits
+            return ButtonBarLayout::default();
+        }
+        (first, last)
+    };
+
+    let mut result = ButtonBarLayout::default();
+    let mut x = start_x;
+    if first > 0 {
+        result.scroll_left = Some((x, windows[first - 1].0));
+        x += SCROLL_MARKER_WIDTH;
+    }
+    for i in first..last {
+        let (id, _, is_focused, is_minimized) = windows[i];
+        result.buttons.push(ButtonBarEntry {
+            id,
+            x,
+            width: widths[i],
+            label: labels[i].clone(),
+            is_focused,
+            is_minimized,
+        });
+        x += widths[i] + 1;
+    }
+    if last < windows.len() {
+        result.scroll_right = Some((end_x.saturating_sub(1), windows[last].0));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_title_styles() {
+        let title = "Terminal 1 - cargo build";
+        assert_eq!(
+            truncate_title(title, 14, ButtonTitleTruncation::Cut),
+            "Terminal 1 - c"
+        );
+        assert_eq!(
+            truncate_title(title, 14, ButtonTitleTruncation::Ellipsis),
+            "Terminal 1 ..."
+        );
+        assert_eq!(
+            truncate_title("short", 14, ButtonTitleTruncation::Ellipsis),
+            "short"
+        );
+        // Multi-byte titles are cut on character boundaries
+        assert_eq!(
+            truncate_title("ñandú ñandú", 4, ButtonTitleTruncation::Cut),
+            "ñand"
+        );
+    }
+
+    #[test]
+    fn test_layout_matches_default_format() {
+        let windows = [
+            (1, "Terminal 1", true, false),
+            (2, "Terminal 2", false, true),
+        ];
+        let layout = layout(&windows, &[], 10, 100, 14, ButtonTitleTruncation::Cut);
+        assert_eq!(layout.buttons.len(), 2);
+        assert_eq!((layout.buttons[0].x, layout.buttons[0].width), (10, 14));
+        assert_eq!((layout.buttons[1].x, layout.buttons[1].width), (25, 14));
+        assert_eq!(layout.window_at(10), Some(1));
+        assert_eq!(layout.window_at(24), None);
+        assert_eq!(layout.window_at(25), Some(2));
+        assert!(layout.scroll_left.is_none() && layout.scroll_right.is_none());
+    }
+
+    #[test]
+    fn test_layout_condenses_then_scrolls() {
+        let windows = [
+            (1, "Terminal 1", false, false),
+            (2, "Terminal 2", false, false),
+            (3, "Terminal 3", true, false),
+            (4, "Terminal 4", false, false),
+        ];
+
+        // Room for four condensed buttons
+        let condensed = layout(&windows, &[], 0, 40, 14, ButtonTitleTruncation::Cut);
+        assert_eq!(condensed.buttons.len(), 4);
+        assert!(condensed.buttons.iter().all(|b| b.x + b.width <= 40));
+
+        // Too narrow even when condensed: scroll to keep the focused window visible
+        let scrolled = layout(&windows, &[], 0, 20, 14, ButtonTitleTruncation::Cut);
+        assert!(scrolled.buttons.iter().any(|b| b.id == 3));
+        assert!(scrolled.buttons.iter().all(|b| b.x + b.width <= 20));
+        let (left_x, left_id) = scrolled.scroll_left.unwrap();
+        assert_eq!(scrolled.window_at(left_x), Some(left_id));
+        assert!(left_id < scrolled.buttons[0].id);
+    }
+
+    #[test]
+    fn test_duplicate_titles_get_suffixes() {
+        let windows = [
+            (1, "Terminal", true, false),
+            (2, "Terminal", false, false),
+            (3, "htop", false, false),
+            (4, "Terminal", false, false),
+        ];
+        let process = |id: u32| (id != 4).then(|| "vim".to_string());
+
+        let numbered = duplicate_suffixes(&windows, ButtonTitleDuplicates::Number, process);
+        assert_eq!(numbered, [" #1", " #2", "", " #3"]);
+        // Windows 1 and 2 both run vim, so their numbers stay
+        let by_process = duplicate_suffixes(&windows, ButtonTitleDuplicates::Process, process);
+        assert_eq!(by_process, [" (vim) #1", " (vim) #2", "", ""]);
+        let off = duplicate_suffixes(&windows, ButtonTitleDuplicates::Off, process);
+        assert!(off.iter().all(String::is_empty));
+
+        // Condensed titles keep their suffix; hit-testing follows the wider labels
+        let layout = layout(&windows, &numbered, 0, 100, 6, ButtonTitleTruncation::Cut);
+        let labels: Vec<&str> = layout.buttons.iter().map(|b| b.label.as_str()).collect();
+        assert_eq!(labels, ["Ter #1", "Ter #2", "htop", "Ter #3"]);
+        assert_eq!(layout.buttons[1].width, 10);
+        assert_eq!(layout.window_at(layout.buttons[3].x + 9), Some(4));
+    }
+}
@@ -0,0 +1,224 @@
+//! Expose overview: all windows shown side by side as tiles for quick selection
+//! Toggled with F9; pick a window by clicking its tile or pressing its number
+//! Tiles of windows with recent output carry an activity marker on their bottom border
+
+use super::manager::WindowManager;
+use super::number_overlay::extract_number_from_title;
+use crate::rendering::{Cell, Charset, Theme, VideoBuffer};
+
+/// Gap between tiles and around the tile area
+const TILE_GAP: u16 = 1;
+
+/// Compute tile rectangles (x, y, width, height) for `count` windows
+/// Tiles fill the desktop area between the top bar and the bottom bar in a near-square grid
+pub fn tile_layout(count: usize, cols: u16, rows: u16) -> Vec<(u16, u16, u16, u16)> {
+    if count == 0 {
+        return Vec::new();
+    }
+
+    let grid_cols = (count as f64).sqrt().ceil() as u16;
+    let grid_rows = (count as u16).div_ceil(grid_cols);
+
+    // Desktop area: row 1 (below top bar) to rows - 2 (above bottom bar)
+    let area_y = 1 + TILE_GAP;
+    let area_w = cols.saturating_sub(TILE_GAP);
+    let area_h = rows.saturating_sub(2 + TILE_GAP);
+
+    let tile_w = (area_w / grid_cols).saturating_sub(TILE_GAP).max(1);
+    let tile_h = (area_h / grid_rows).saturating_sub(TILE_GAP).max(1);
+
+    (0..count as u16)
+        .map(|i| {
+            let x = TILE_GAP + (i % grid_cols) * (tile_w + TILE_GAP);
+            let y = area_y + (i / grid_cols) * (tile_h + TILE_GAP);
+            (x, y, tile_w, tile_h)
+        })
+        .collect()
+}
+
+/// Find the window whose tile contains the given point
+pub fn window_at(
+    window_manager: &WindowManager,
+    cols: u16,
+    rows: u16,
+    x: u16,
+    y: u16,
+) -> Option<u32> {
+    let windows = window_manager.get_window_positions();
+    tile_layout(windows.len(), cols, rows)
+        .into_iter()
+        .zip(windows.iter())
+        .find(|((tx, ty, tw, th), _)| x >= *tx && x < tx + tw && y >= *ty && y < ty + th)
+        .map(|(_, (id, ..))| *id)
+}
+
+/// Render the overview over the desktop area
+pub fn render_expose(
+    buffer: &mut VideoBuffer,
+    window_manager: &WindowManager,
+    charset: &Charset,
+    theme: &Theme,
+    tint_strength: f32,
+) {
+    let (cols, rows) = buffer.dimensions();
+
+    // Cover the regular window layout with the desktop background
+    let background = Cell::new_unchecked(charset.background, theme.desktop_fg, theme.desktop_bg);
+    for y in 1..rows.saturating_sub(1) {
+        for x in 0..cols {
+            buffer.set(x, y, background);
+        }
+    }
+
+    let focused_id = window_manager.get_focused_window_id();
+    let windows = window_manager.get_window_positions();
+    let tiles = tile_layout(windows.len(), cols, rows);
+
+    for ((x, y, width, height), (id, .., title)) in tiles.into_iter().zip(windows.iter()) {
+        if width < 3 || height < 3 {
+            continue;
+        }
+
+        let is_focused = Some(*id) == focused_id;
+        let (border_fg, border_bg, title_fg, title_bg) = if is_focused {
+            (
+                theme.window_border_focused_fg,
+                theme.window_border_focused_bg,
+                theme.window_title_focused_fg,
+                theme.window_title_focused_bg,
+            )
+        } else {
+            (
+                theme.window_border_unfocused_fg,
+                theme.window_border_unfocused_bg,
+                theme.window_title_unfocused_fg,
+                theme.window_title_unfocused_bg,
+            )
+        };
+
+        // Frame
+        let right = x + width - 1;
+        let bottom = y + height - 1;
+        for dx in x..=right {
+            buffer.set(
+                dx,
+                y,
+                Cell::new_unchecked(charset.border_horizontal, border_fg, title_bg),
+            );
+            buffer.set(
+                dx,
+                bottom,
+                Cell::new_unchecked(charset.border_horizontal, border_fg, border_bg),
+            );
+        }
+        for dy in y..=bottom {
+            buffer.set(
+                x,
+                dy,
+                Cell::new_unchecked(charset.border_vertical, border_fg, border_bg),
+            );
+            buffer.set(
+                right,
+                dy,
+                Cell::new_unchecked(charset.border_vertical, border_fg, border_bg),
+            );
+        }
+        buffer.set(
+            x,
+            y,
+            Cell::new_unchecked(charset.border_top_left, border_fg, title_bg),
+        );
+        buffer.set(
+            right,
+            y,
+            Cell::new_unchecked(charset.border_top_right, border_fg, title_bg),
+        );
+        buffer.set(
+            x,
+            bottom,
+            Cell::new_unchecked(charset.border_bottom_left, border_fg, border_bg),
+        );
+        buffer.set(
+            right,
+            bottom,
+            Cell::new_unchecked(charset.border_bottom_right, border_fg, border_bg),
+        );
+
+        // Title with the selection number (same numbers as Alt+1-9)
+        let display_title = window_manager
+            .window_display_title(*id)
+            .unwrap_or_else(|| title.clone());
+        let label = match extract_number_from_title(title) {
+            Some(n) if (1..=9).contains(&n) => format!(" [{}] {} ", n, display_title),
+            _ => format!(" {} ", display_title),
+        };
+        for (i, ch) in label
+            .chars()
+            .take(width.saturating_sub(4) as usize)
+            .enumerate()
+        {
+            buffer.set(
+                x + 2 + i as u16,
+                y,
+                Cell::new_unchecked(ch, title_fg, title_bg),
+            );
+        }
+
+        // Activity marker on the bottom border while the window is producing output
+        if window_manager.window_is_active(*id) {
+            for (i, ch) in " active "
+                .chars()
+                .take(width.saturating_sub(4) as usize)
+                .enumerate()
+            {
+                buffer.set(
+                    x + 2 + i as u16,
+                    bottom,
+                    Cell::new_unchecked(ch, border_fg, border_bg),
+                );
+            }
+        }
+
+        // Live preview of the visible content (cropped to the tile)
+        window_manager.render_window_preview(
+            *id,
+            buffer,
+            (x + 1, y + 1, width - 2, height - 2),
+            theme,
+            tint_strength,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tile_layout_grid_shape() {
+        assert!(tile_layout(0, 80, 25).is_empty());
+
+        // Four windows: 2x2 grid
+        let tiles = tile_layout(4, 80, 25);
+        assert_eq!(tiles.len(), 4);
+        assert_eq!(tiles[0].1, tiles[1].1);
+        assert_eq!(tiles[0].0, tiles[2].0);
+        assert!(tiles[2].1 > tiles[0].1);
+
+        // Five windows: 3 columns, 2 rows
+        let tiles = tile_layout(5, 80, 25);
+        assert_eq!(tiles[0].1, tiles[2].1);
+        assert!(tiles[3].1 > tiles[0].1);
+    }
+
+    #[test]
+    fn test_tile_layout_stays_in_desktop_area() {
+        for count in 1..=12 {
+            for (x, y, w, h) in tile_layout(count, 80, 25) {
+                assert!(x + w <= 80);
+                assert!(y >= 1);
+                assert!(y + h <= 24);
+            }
+        }
+    }
+}
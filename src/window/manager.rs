@@ -1,10 +1,20 @@
 use super::base::ResizeEdge;
-use super::terminal_window::{MouseContentPosition, TerminalWindow};
+use super::button_bar::{self, ButtonBarLayout};
+use super::terminal_window::{MouseContentPosition, TerminalWindow, WindowStats};
 use crate::app::app_state::AutoScrollDirection;
+use crate::app::config_manager::{
+    AltScreenScroll, ButtonTitleDuplicates, ButtonTitleTruncation, CloseConfirmEnter,
+    CloseConfirmPolicy, EnterSends, FocusHighlight, MouseForwardMode, NewWindowPosition,
+    PasteGuard, ScrollbarMode, SmallScreenPolicy,
+};
 use crate::app::session::{self, SessionState, WindowSnapshot};
 use crate::rendering::{Charset, Theme, VideoBuffer};
-use crate::term_emu::ShellConfig;
+use crate::term_emu::{
+    ControlNotation, CursorShape, ForegroundSignal, OutputLog, PasteTransform, SharedOutputLog,
+    ShellConfig, trim_trailing_newline,
+};
 use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use crossterm::style::Color;
 use std::collections::HashMap;
 use std::io;
 use std::time::Instant;
@@ -26,6 +36,8 @@ pub enum PersistEvent {
     WindowClosed(u32),
     /// A window was created on the daemon side
     WindowCreated(u32),
+    /// The daemon signalled a window's foreground job
+    SignalSent(ForegroundSignal),
     /// An error occurred
     Error(String),
     /// The daemon connection was lost
@@ -55,6 +67,88 @@ pub struct WindowManager {
     // Shell configuration for new terminal windows
     shell_config: ShellConfig,
 
+    // Cursor shape applied to new windows until an application requests another
+    default_cursor_shape: CursorShape,
+
+    // Answerback string (ENQ response) applied to every window
+    answerback: String,
+
+    // Minimum contrast ratio for application colors (0.0 = off)
+    minimum_contrast: f32,
+
+    // Text cursor color applied to every window (None = invert the cell)
+    cursor_color: Option<Color>,
+
+    // Newest scrollback lines kept unpacked in every window (None = no compression)
+    scrollback_compression: Option<usize>,
+
+    // Unfocused windows let the desktop show through their blank cells
+    ghost_unfocused: bool,
+
+    // Title bars show how far windows are scrolled back
+    scroll_indicator: bool,
+
+    // The focused window draws its cursor even when the application hid it
+    force_cursor_visible: bool,
+
+    // Extra emphasis of the focused window and how much unfocused frames are muted
+    focus_highlight: FocusHighlight,
+    focus_dim_percent: u8,
+
+    // A maximized focused window lists every window as tabs in its title bar
+    tab_strip: bool,
+
+    // Width in cells of the resize hit area along window edges (1 = the border itself)
+    resize_grip_size: u16,
+
+    // Control characters are shown literally in new windows (None = interpreted)
+    control_notation: Option<ControlNotation>,
+
+    // Recently focused window IDs, most recent last (for focus_last_window)
+    focus_history: Vec<u32>,
+
+    // When window scrollbars are shown
+    scrollbar_mode: ScrollbarMode,
+
+    // Where new floating windows open
+    new_window_position: NewWindowPosition,
+
+    // Output log path (`{id}` = window ID) and rotation size; None = off
+    output_log: Option<(String, u64)>,
+    // Log shared by all windows when the path has no `{id}`
+    shared_output_log: Option<SharedOutputLog>,
+
+    // Scrollback lines saved per window in the session file
+    session_scrollback_lines: usize,
+    // Whether saved and restored session lines drop trailing blank cells
+    session_trim_blanks: bool,
+
+    // What Enter sends to applications that haven't set LNM
+    enter_sends: EnterSends,
+    // Which mouse events reach the focused terminal's application
+    mouse_forward_mode: MouseForwardMode,
+    // Shift+wheel scrolls term39's scrollback instead of reaching the application
+    shift_wheel_scrollback: bool,
+    // Clean-up applied to plain (non-bracketed) pastes
+    paste_transform: PasteTransform,
+
+    // What pasting into a window at a hidden (password) prompt does
+    paste_guard: PasteGuard,
+    // Which kinds of unsaved work make closing a window ask first
+    close_confirm_policy: CloseConfirmPolicy,
+    // Foreground programs that always make closing a window ask first
+    protected_processes: Vec<String>,
+    // PTY output parsed per window per frame (0 = unlimited)
+    max_bytes_per_frame: usize,
+    // Characters besides letters and digits that double-click word selection includes
+    word_chars: String,
+    // What the wheel does over a window showing the alternate screen
+    alt_screen_scroll: AltScreenScroll,
+    // Bottom bar button titles: maximum length and how longer titles are shortened
+    button_title_max_len: usize,
+    button_title_truncation: ButtonTitleTruncation,
+    button_title_duplicates: ButtonTitleDuplicates,
+
     // Pivot state for tiled window resizing
     pivot_dragging: Option<PivotDragState>,
     /// Current split ratio for horizontal division (left column width / total)
@@ -83,6 +177,9 @@ enum SnapZone {
 /// Snap threshold in pixels
 const SNAP_THRESHOLD: u16 = 25;
 
+/// Windows remembered for jumping back with focus_last_window
+const FOCUS_HISTORY_LEN: usize = 8;
+
 #[derive(Clone, Copy, Debug)]
 struct DragState {
     window_id: u32,
@@ -145,6 +242,39 @@ impl WindowManager {
             last_window_x: None,
             last_window_y: None,
             shell_config: ShellConfig::default(),
+            default_cursor_shape: CursorShape::Block,
+            answerback: String::new(),
+            minimum_contrast: 0.0,
+            cursor_color: None,
+            scrollback_compression: None,
+            ghost_unfocused: false,
+            scroll_indicator: true,
+            force_cursor_visible: false,
+            focus_highlight: FocusHighlight::None,
+            focus_dim_percent: 0,
+            tab_strip: false,
+            resize_grip_size: 1,
+            control_notation: None,
+            focus_history: Vec::new(),
+            scrollbar_mode: ScrollbarMode::default(),
+            new_window_position: NewWindowPosition::default(),
+            output_log: None,
+            shared_output_log: None,
+            session_scrollback_lines: session::MAX_LINES_PER_TERMINAL,
+            session_trim_blanks: true,
+            enter_sends: EnterSends::default(),
+            mouse_forward_mode: MouseForwardMode::default(),
+            shift_wheel_scrollback: true,
+            paste_transform: PasteTransform::default(),
+            paste_guard: PasteGuard::default(),
+            close_confirm_policy: CloseConfirmPolicy::default(),
+            protected_processes: Vec::new(),
+            max_bytes_per_frame: 0,
+            word_chars: "_-".to_string(),
+            alt_screen_scroll: AltScreenScroll::default(),
+            button_title_max_len: 14,
+            button_title_truncation: ButtonTitleTruncation::default(),
+            button_title_duplicates: ButtonTitleDuplicates::default(),
             pivot_dragging: None,
             h_split_ratio: 0.5,
             v_split_ratio: 0.5,
@@ -201,6 +331,288 @@ impl WindowManager {
         self.shell_config = shell_config;
     }
 
+    /// Set the default cursor shape for all current and future windows
+    pub fn set_default_cursor_shape(&mut self, shape: CursorShape) {
+        self.default_cursor_shape = shape;
+        for w in &mut self.windows {
+            w.set_default_cursor_shape(shape);
+        }
+    }
+
+    /// Set the answerback string for all current and future windows
+    pub fn set_answerback(&mut self, answerback: &str) {
+        self.answerback = answerback.to_string();
+        for w in &mut self.windows {
+            w.set_answerback(answerback);
+        }
+    }
+
+    /// Set the text cursor color for all current and future windows (None = inverted)
+    pub fn set_cursor_color(&mut self, color: Option<Color>) {
+        self.cursor_color = color;
+        for w in &mut self.windows {
+            w.set_cursor_color(color);
+        }
+    }
+
+    /// Compress old scrollback lines in all current and future windows, keeping the newest
+    /// `compress_after` unpacked (None = off)
+    pub fn set_scrollback_compression(&mut self, compress_after: Option<usize>) {
+        self.scrollback_compression = compress_after;
+        for w in &mut self.windows {
+            w.set_scrollback_compression(compress_after);
+        }
+    }
+
+    /// Draw the cursor of focused windows regardless of DECTCEM (current and future windows)
+    pub fn set_force_cursor_visible(&mut self, force: bool) {
+        self.force_cursor_visible = force;
+        for w in &mut self.windows {
+            w.set_force_cursor_visible(force);
+        }
+    }
+
+    /// Set the focus highlight and the muting of unfocused frames (current and future windows)
+    pub fn set_focus_highlight(&mut self, highlight: FocusHighlight, dim_percent: u8) {
+        self.focus_highlight = highlight;
+        self.focus_dim_percent = dim_percent.min(90);
+        for w in &mut self.windows {
+            w.set_focus_highlight(highlight, self.focus_dim_percent);
+        }
+    }
+
+    /// Show tabs for all windows in the title bar of a maximized focused window
+    pub fn set_tab_strip(&mut self, enabled: bool) {
+        self.tab_strip = enabled;
+    }
+
+    /// Row and span (start_x, end_x exclusive) of the tab strip, when it is shown: the
+    /// title area of the focused window while it is maximized and other windows exist
+    pub fn tab_strip_area(&self) -> Option<(u16, u16, u16)> {
+        if !self.tab_strip {
+            return None;
+        }
+        let focused = self.get_focused_window()?;
+        let w = &focused.window;
+        if !w.is_maximized || w.is_minimized || w.is_scratchpad {
+            return None;
+        }
+        let others = self
+            .windows
+            .iter()
+            .any(|other| other.id() != w.id && !other.window.is_scratchpad);
+        // Between the "[X] [+] [_] " buttons and the right border
+        (others && w.width > 20).then(|| (w.y, w.title_buttons_end(), w.x + w.width - 2))
+    }
+
+    /// Switch to a window picked from the tab strip, maximizing it so the strip stays
+    pub fn switch_to_tab(
+        &mut self,
+        window_id: u32,
+        buffer_width: u16,
+        buffer_height: u16,
+        gaps: bool,
+    ) {
+        if let Some(win) = self.get_window_by_id_mut(window_id)
+            && win.window.is_minimized
+        {
+            win.window.restore_from_minimize();
+        }
+        self.focus_window(window_id);
+        self.maximize_window(window_id, buffer_width, buffer_height, gaps);
+    }
+
+    /// Toggle drawing the focused window's cursor even when the application hid it
+    /// Returns the new state, or None if no window is focused
+    pub fn toggle_focused_force_cursor(&mut self) -> Option<bool> {
+        let win = self.get_focused_window_mut()?;
+        let force = !win.is_cursor_forced_visible();
+        win.set_force_cursor_visible(force);
+        Some(force)
+    }
+
+    /// Widen the area along window edges that grabs for resizing (1-4 cells)
+    pub fn set_resize_grip_size(&mut self, size: u16) {
+        self.resize_grip_size = size.clamp(1, 4);
+    }
+
+    /// Show or hide the scrolled-back indicator in title bars (current and future windows)
+    pub fn set_scroll_indicator(&mut self, show: bool) {
+        self.scroll_indicator = show;
+        for w in &mut self.windows {
+            w.set_scroll_indicator(show);
+        }
+    }
+
+    /// Let the desktop show through blank cells of unfocused windows (current and future)
+    pub fn set_ghost_unfocused(&mut self, ghost: bool) {
+        self.ghost_unfocused = ghost;
+        for w in &mut self.windows {
+            w.set_ghost_unfocused(ghost);
+        }
+    }
+
+    /// Show control characters literally in all current and future windows (None = off)
+    /// The notation is also the one used when toggling a window
+    pub fn set_control_notation(&mut self, notation: Option<ControlNotation>) {
+        self.control_notation = notation;
+        for w in &mut self.windows {
+            w.set_control_display(notation);
+        }
+    }
+
+    /// Set the minimum contrast ratio for all current and future windows
+    pub fn set_minimum_contrast(&mut self, ratio: f32) {
+        self.minimum_contrast = ratio;
+        for w in &mut self.windows {
+            w.set_minimum_contrast(ratio);
+        }
+    }
+
+    /// Set when scrollbars are shown for all current and future windows
+    pub fn set_scrollbar_mode(&mut self, mode: ScrollbarMode) {
+        self.scrollbar_mode = mode;
+        for w in &mut self.windows {
+            w.set_scrollbar_mode(mode);
+        }
+    }
+
+    /// Set where new floating windows open
+    pub fn set_new_window_position(&mut self, position: NewWindowPosition) {
+        self.new_window_position = position;
+    }
+
+    /// Mirror lines entering the scrollback to a plain-text file, for all current and
+    /// future windows (an empty path turns it off); `{id}` in the path gives each window
+    /// its own file, otherwise they share one
+    pub fn set_output_log(&mut self, path: &str, max_bytes: u64) {
+        let path = path.trim();
+        let path = match path.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir()
+                .map(|home| home.join(rest).to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.to_string()),
+            None => path.to_string(),
+        };
+        self.shared_output_log = (!path.is_empty() && !path.contains("{id}"))
+            .then(|| OutputLog::shared(&path, max_bytes));
+        self.output_log = (!path.is_empty()).then_some((path, max_bytes));
+        for i in 0..self.windows.len() {
+            let log = self.output_log_for(self.windows[i].id());
+            self.windows[i].set_output_log(log);
+        }
+    }
+
+    /// Output log for window `id` (None when logging is off)
+    fn output_log_for(&self, id: u32) -> Option<SharedOutputLog> {
+        if let Some(log) = &self.shared_output_log {
+            return Some(log.clone());
+        }
+        let (path, max_bytes) = self.output_log.as_ref()?;
+        Some(OutputLog::shared(
+            path.replace("{id}", &id.to_string()),
+            *max_bytes,
+        ))
+    }
+
+    /// Set how many scrollback lines per window are saved with the session
+    pub fn set_session_scrollback_lines(&mut self, lines: usize) {
+        self.session_scrollback_lines = lines;
+    }
+
+    /// Set whether trailing blank cells are left out of saved and restored session lines
+    pub fn set_session_trim_blanks(&mut self, trim: bool) {
+        self.session_trim_blanks = trim;
+    }
+
+    /// Set what the Enter key sends (applications in LNM always get CR LF)
+    pub fn set_enter_sends(&mut self, enter_sends: EnterSends) {
+        self.enter_sends = enter_sends;
+    }
+
+    /// What the Enter key sends, and whether the focused application set LNM (mode 20)
+    pub fn focused_enter_mode(&self) -> (EnterSends, bool) {
+        let lnm = self
+            .get_focused_window()
+            .is_some_and(|w| w.get_newline_mode());
+        (self.enter_sends, lnm)
+    }
+
+    /// Set which mouse events are forwarded to terminal applications
+    pub fn set_mouse_forward_mode(&mut self, mode: MouseForwardMode) {
+        self.mouse_forward_mode = mode;
+    }
+
+    /// Set whether Shift+wheel scrolls the scrollback while an app tracks the mouse
+    pub fn set_shift_wheel_scrollback(&mut self, enabled: bool) {
+        self.shift_wheel_scrollback = enabled;
+    }
+
+    /// Set the clean-up applied to plain (non-bracketed) pastes
+    pub fn set_paste_transform(&mut self, transform: PasteTransform) {
+        self.paste_transform = transform;
+    }
+
+    /// Set what pasting into a window at a hidden prompt does
+    pub fn set_paste_guard(&mut self, guard: PasteGuard) {
+        self.paste_guard = guard;
+    }
+
+    /// What pasting into a window at a hidden prompt does
+    pub fn paste_guard(&self) -> PasteGuard {
+        self.paste_guard
+    }
+
+    /// Set which kinds of unsaved work make closing a window ask first
+    pub fn set_close_confirm_policy(&mut self, policy: CloseConfirmPolicy) {
+        self.close_confirm_policy = policy;
+    }
+
+    /// Set the foreground programs that always make closing a window ask first
+    pub fn set_protected_processes(&mut self, names: Vec<String>) {
+        self.protected_processes = names;
+    }
+
+    /// Check if closing a window asks first: unsaved work per the policy, or a protected
+    /// program in the foreground
+    fn window_needs_close_confirmation(&self, window: &TerminalWindow) -> bool {
+        window.needs_close_confirmation(self.close_confirm_policy)
+            || window.runs_protected_process(&self.protected_processes)
+    }
+
+    /// Set how much PTY output each window parses per frame (0 = unlimited)
+    pub fn set_max_bytes_per_frame(&mut self, max_bytes: usize) {
+        self.max_bytes_per_frame = max_bytes;
+    }
+
+    /// Set which characters besides letters and digits count as word characters
+    pub fn set_word_chars(&mut self, word_chars: &str) {
+        self.word_chars = word_chars.to_string();
+    }
+
+    /// Set what the wheel does while a window shows the alternate screen
+    pub fn set_alt_screen_scroll(&mut self, mode: AltScreenScroll) {
+        self.alt_screen_scroll = mode;
+    }
+
+    /// Whether a window is at a hidden (password) prompt
+    pub fn is_window_at_hidden_prompt(&self, window_id: u32) -> bool {
+        self.get_window_by_id(window_id)
+            .is_some_and(|w| w.is_password_input())
+    }
+
+    /// Set the maximum bottom bar button title length, how longer titles are shortened and
+    /// how windows sharing a title are told apart
+    pub fn set_button_titles(
+        &mut self,
+        max_len: usize,
+        truncation: ButtonTitleTruncation,
+        duplicates: ButtonTitleDuplicates,
+    ) {
+        self.button_title_max_len = max_len;
+        self.button_title_truncation = truncation;
+        self.button_title_duplicates = duplicates;
+    }
+
     /// Get the current shell configuration
     #[allow(dead_code)]
     pub fn shell_config(&self) -> &ShellConfig {
@@ -260,6 +672,57 @@ impl WindowManager {
         }
     }
 
+    /// Position for a new floating window, following the configured placement
+    /// `pointer` is the last mouse position (UnderMouse falls back to the centre without it)
+    /// The result keeps the whole window on-screen between the top and bottom bars
+    pub fn next_window_position(
+        &self,
+        width: u16,
+        height: u16,
+        buffer_width: u16,
+        buffer_height: u16,
+        pointer: Option<(u16, u16)>,
+    ) -> (u16, u16) {
+        let center = (
+            buffer_width.saturating_sub(width) / 2,
+            buffer_height.saturating_sub(height) / 2,
+        );
+        let (x, y) = match (self.new_window_position, pointer) {
+            (NewWindowPosition::Cascade, _) => {
+                self.get_cascade_position(width, height, buffer_width, buffer_height)
+            }
+            (NewWindowPosition::Center, _) | (NewWindowPosition::UnderMouse, None) => center,
+            // Title bar centred under the pointer
+            (NewWindowPosition::UnderMouse, Some((px, py))) => (px.saturating_sub(width / 2), py),
+            (NewWindowPosition::Smart, _) => {
+                let occupied: Vec<(u16, u16, u16, u16)> = self
+                    .windows
+                    .iter()
+                    .filter(|w| !w.window.is_minimized)
+                    .map(|w| (w.window.x, w.window.y, w.window.width, w.window.height))
+                    .collect();
+                least_covered_position(&occupied, width, height, buffer_width, buffer_height)
+            }
+        };
+        clamp_window_position(x, y, width, height, buffer_width, buffer_height)
+    }
+
+    /// Apply the per-window settings configured on the manager to a newly created window
+    fn apply_window_defaults(&self, terminal_window: &mut TerminalWindow) {
+        terminal_window.set_default_cursor_shape(self.default_cursor_shape);
+        terminal_window.set_answerback(&self.answerback);
+        terminal_window.set_minimum_contrast(self.minimum_contrast);
+        terminal_window.set_cursor_color(self.cursor_color);
+        terminal_window.set_scrollback_compression(self.scrollback_compression);
+        terminal_window.set_ghost_unfocused(self.ghost_unfocused);
+        terminal_window.set_control_display(self.control_notation);
+        terminal_window.set_scroll_indicator(self.scroll_indicator);
+        terminal_window.set_force_cursor_visible(self.force_cursor_visible);
+        terminal_window.set_focus_highlight(self.focus_highlight, self.focus_dim_percent);
+        terminal_window.set_scrollbar_mode(self.scrollbar_mode);
+        terminal_window.set_output_log(self.output_log_for(terminal_window.id()));
+    }
+
     /// Create and add a new terminal window (returns window ID or error message)
     pub fn create_window(
         &mut self,
@@ -270,10 +733,38 @@ impl WindowManager {
         title: String,
         initial_command: Option<String>,
     ) -> Result<u32, String> {
+        self.create_window_in_dir(x, y, width, height, title, initial_command, None)
+    }
+
+    /// Create a new terminal window starting in `working_dir` (None uses the default start
+    /// directory, --cwd or default_cwd, and inherits term39's when there is none)
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_window_in_dir(
+        &mut self,
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+        title: String,
+        initial_command: Option<String>,
+        working_dir: Option<String>,
+    ) -> Result<u32, String> {
+        // Windows without their own command run the --exec command instead of the shell
+        let initial_command = initial_command.or_else(|| self.shell_config.exec_command.clone());
+        let working_dir = working_dir.or_else(|| self.shell_config.working_dir.clone());
+
         // In persist mode, route through daemon so PTYs survive client exit
         #[cfg(unix)]
         if self.persist_client.is_some() {
-            return self.create_window_via_daemon(x, y, width, height, title, initial_command);
+            return self.create_window_via_daemon(
+                x,
+                y,
+                width,
+                height,
+                title,
+                initial_command,
+                working_dir,
+            );
         }
 
         let id = self.next_id;
@@ -289,6 +780,7 @@ impl WindowManager {
         self.last_window_y = Some(y);
 
         // Create terminal window
+        let shell_config = self.shell_config.clone().with_working_dir(working_dir);
         match TerminalWindow::new(
             id,
             x,
@@ -297,14 +789,16 @@ impl WindowManager {
             height,
             title.clone(),
             initial_command.clone(),
-            &self.shell_config,
+            &shell_config,
         ) {
             Ok(mut terminal_window) => {
                 terminal_window.set_focused(true);
+                self.apply_window_defaults(&mut terminal_window);
                 let idx = self.windows.len();
                 self.windows.push(terminal_window);
                 self.window_index_cache.insert(id, idx);
-                self.focus = FocusState::Window(id);
+                self.set_focused_window(id);
+                self.keep_scratchpad_on_top();
                 Ok(id)
             }
             Err(e) => {
@@ -323,11 +817,7 @@ impl WindowManager {
     /// If `gaps` is true, adds spacing between windows and screen edges
     /// For 2-4 windows with gaps, uses current split ratios (for pivot support)
     pub fn auto_position_windows(&mut self, buffer_width: u16, buffer_height: u16, gaps: bool) {
-        let visible_count = self
-            .windows
-            .iter()
-            .filter(|w| !w.window.is_minimized)
-            .count();
+        let visible_count = self.windows.iter().filter(|w| w.window.in_layout()).count();
 
         if visible_count == 0 {
             return;
@@ -351,7 +841,7 @@ impl WindowManager {
         let mut visible_ids: Vec<u32> = self
             .windows
             .iter()
-            .filter(|w| !w.window.is_minimized)
+            .filter(|w| w.window.in_layout())
             .map(|w| w.id())
             .collect();
         visible_ids.sort();
@@ -546,6 +1036,16 @@ impl WindowManager {
         }
     }
 
+    /// Re-maximize maximized windows for a new screen size (e.g. after hiding the bars)
+    pub fn refit_maximized_windows(&mut self, buffer_width: u16, buffer_height: u16, gaps: bool) {
+        for win in &mut self.windows {
+            if win.window.is_maximized {
+                win.window.refit_maximize(buffer_width, buffer_height, gaps);
+                let _ = win.resize(win.window.width, win.window.height);
+            }
+        }
+    }
+
     /// Clamp all windows to fit within the new screen bounds
     /// This is used when the terminal is resized and auto-tiling is disabled
     pub fn clamp_windows_to_bounds(&mut self, buffer_width: u16, buffer_height: u16) {
@@ -591,6 +1091,50 @@ impl WindowManager {
         self.send_persist_geometry_all();
     }
 
+    /// Fit windows to a screen below the small screen threshold using the given policy
+    /// Clamp keeps the regular behavior; the other policies rearrange windows first
+    pub fn fit_windows_to_small_screen(
+        &mut self,
+        buffer_width: u16,
+        buffer_height: u16,
+        policy: SmallScreenPolicy,
+    ) {
+        let usable_height = buffer_height.saturating_sub(2); // -1 for top bar, -1 for button bar
+        let focused_id = self.get_focused_window_id();
+
+        match policy {
+            SmallScreenPolicy::Clamp => {}
+            SmallScreenPolicy::Minimize => {
+                // Minimize every window that no longer fits entirely, except the focused one
+                for win in &mut self.windows {
+                    let w = &win.window;
+                    let fits = w.x + w.width <= buffer_width
+                        && w.y >= 1
+                        && w.y + w.height <= 1 + usable_height;
+                    if !w.is_minimized && !fits && Some(w.id) != focused_id {
+                        win.window.minimize();
+                    }
+                }
+            }
+            SmallScreenPolicy::Stack => {
+                // Shrink windows to the screen and center them on top of each other
+                for win in &mut self.windows {
+                    if win.window.is_minimized {
+                        continue;
+                    }
+                    let width = win.window.width.min(buffer_width);
+                    let height = win.window.height.min(usable_height);
+                    win.window.width = width;
+                    win.window.height = height;
+                    win.window.x = (buffer_width - width) / 2;
+                    win.window.y = 1 + (usable_height - height) / 2;
+                }
+            }
+        }
+
+        self.clamp_windows_to_bounds(buffer_width, buffer_height);
+    }
+
     /// Bring window to front and focus it
     pub fn focus_window(&mut self, id: u32) {
         // Find window using cache
@@ -606,11 +1150,136 @@ impl WindowManager {
             // Focus this window
             window.set_focused(true);
             self.windows.push(window);
-            self.focus = FocusState::Window(id);
+            self.set_focused_window(id);
 
             // Rebuild cache since indices changed
             self.rebuild_cache();
+            self.keep_scratchpad_on_top();
+        }
+    }
+
+    /// Make window `id` the focus and record it in the focus history (focus_last_window)
+    fn set_focused_window(&mut self, id: u32) {
+        self.focus = FocusState::Window(id);
+        push_focus_history(&mut self.focus_history, id);
+    }
+
+    /// Focus the window that was focused before the current one, restoring it if minimized
+    /// Repeated calls switch back and forth between the two most recent windows
+    /// Returns false when there is no such window
+    pub fn focus_last_window(&mut self) -> bool {
+        let current = match self.focus {
+            FocusState::Window(id) => Some(id),
+            _ => None,
+        };
+        // Closed windows and a hidden scratchpad are skipped
+        let target = previous_in_history(&self.focus_history, current, |id| {
+            self.get_window_by_id(id)
+                .is_some_and(|w| !(w.window.is_scratchpad && w.window.is_minimized))
+        });
+        let Some(target) = target else {
+            return false;
+        };
+        if let Some(win) = self.get_window_by_id_mut(target)
+            && win.window.is_minimized
+        {
+            win.window.restore_from_minimize();
+        }
+        self.focus_window(target);
+        true
+    }
+
+    /// Move a visible scratchpad back to the top of the z-order
+    fn keep_scratchpad_on_top(&mut self) {
+        let Some(pos) = self
+            .windows
+            .iter()
+            .position(|w| w.window.is_scratchpad && !w.window.is_minimized)
+        else {
+            return;
+        };
+        if pos + 1 < self.windows.len() {
+            let scratchpad = self.windows.remove(pos);
+            self.windows.push(scratchpad);
+            self.rebuild_cache();
+        }
+    }
+
+    /// Show or hide the scratchpad, creating it (centered under the top bar) on first use
+    /// A shown scratchpad that isn't focused is focused instead of hidden
+    /// Returns whether the scratchpad is now shown
+    pub fn toggle_scratchpad(
+        &mut self,
+        buffer_width: u16,
+        buffer_height: u16,
+    ) -> Result<bool, String> {
+        let existing = self
+            .windows
+            .iter()
+            .find(|w| w.window.is_scratchpad)
+            .map(|w| (w.id(), w.window.is_minimized));
+
+        match existing {
+            None => {
+                let width = (buffer_width * 3 / 4).max(24);
+                let height = (buffer_height.saturating_sub(2) / 2).max(5);
+                let x = buffer_width.saturating_sub(width) / 2;
+                let id = self.create_window(x, 1, width, height, "Scratchpad".to_string(), None)?;
+                if let Some(win) = self.get_window_by_id_mut(id) {
+                    win.window.is_scratchpad = true;
+                }
+                Ok(true)
+            }
+            Some((id, false)) if self.focus == FocusState::Window(id) => {
+                if let Some(win) = self.get_window_by_id_mut(id) {
+                    win.window.minimize();
+                }
+                let next_window_id = self
+                    .windows
+                    .iter()
+                    .rev()
+                    .find(|w| !w.window.is_minimized)
+                    .map(|w| w.id());
+                match next_window_id {
+                    Some(next_id) => self.focus_window(next_id),
+                    None => self.focus_desktop(),
+                }
+                Ok(false)
+            }
+            Some((id, _)) => {
+                if let Some(win) = self.get_window_by_id_mut(id) {
+                    win.window.restore_from_minimize();
+                }
+                self.focus_window(id);
+                Ok(true)
+            }
+        }
+    }
+
+    /// Move a window to the bottom of the z-order without focusing it
+    /// Lowering the focused window focuses the next topmost window, as minimizing does
+    /// Returns false if the window doesn't exist
+    pub fn lower_window(&mut self, id: u32) -> bool {
+        let Some(pos) = self.get_window_index(id) else {
+            return false;
+        };
+        let window = self.windows.remove(pos);
+        self.windows.insert(0, window);
+        self.rebuild_cache();
+
+        if self.focus == FocusState::Window(id) {
+            let next_window_id = self
+                .windows
+                .iter()
+                .rev()
+                .find(|w| !w.window.is_minimized && w.id() != id)
+                .map(|w| w.id());
+            match next_window_id {
+                Some(next_id) => self.focus_window(next_id),
+                None => self.focus_desktop(),
+            }
         }
+        true
     }
 
     /// Focus the desktop (unfocus all windows)
@@ -721,6 +1390,7 @@ impl WindowManager {
     /// Handle mouse event
     /// Returns true if a window was closed (so caller can reposition)
     /// If `gaps` is true, maximize operations will respect gap settings
+    #[allow(clippy::collapsible_match)]
     pub fn handle_mouse_event(
         &mut self,
         buffer: &mut VideoBuffer,
@@ -816,11 +1486,11 @@ impl WindowManager {
                 false
             }
             MouseEventKind::ScrollUp => {
-                self.handle_scroll_up(x, y);
+                self.handle_scroll(x, y, true, event.modifiers);
                 false
             }
             MouseEventKind::ScrollDown => {
-                self.handle_scroll_down(x, y);
+                self.handle_scroll(x, y, false, event.modifiers);
                 false
             }
             _ => false,
@@ -835,6 +1505,16 @@ impl WindowManager {
         gaps: bool,
         auto_tiling: bool,
     ) -> bool {
+        // A tab of the maximized window's tab strip switches to that window
+        if let Some((tab_y, start_x, end_x)) = self.tab_strip_area()
+            && y == tab_y
+            && let Some(window_id) = self.button_bar_layout(start_x, end_x).window_at(x)
+        {
+            let (cols, rows) = buffer.dimensions();
+            self.switch_to_tab(window_id, cols, rows, gaps);
+            return false;
+        }
+
         // Find window at click position
         if let Some(window_id) = self.window_at(x, y) {
             // Extract all needed data from window before any mutable operations
@@ -843,11 +1523,13 @@ impl WindowManager {
                 let w = &tw.window;
                 (
                     tw.is_in_close_button(x, y),
-                    tw.is_dirty(),
+                    self.window_needs_close_confirmation(tw),
                     w.is_in_maximize_button(x, y),
                     w.is_in_minimize_button(x, y),
                     w.is_maximized,
-                    w.get_resize_edge(x, y),
+                    // The scrollbar keeps its clicks when a wide grip covers it
+                    w.get_resize_edge(x, y, self.resize_grip_size)
+                        .filter(|_| !tw.is_point_on_scrollbar(x, y)),
                     w.width,
                     w.height,
                     w.x,
@@ -861,7 +1543,7 @@ impl WindowManager {
 
             if let Some((
                 is_close_button,
-                is_dirty,
+                needs_confirmation,
                 is_maximize_button,
                 is_minimize_button,
                 is_maximized,
@@ -878,7 +1560,7 @@ impl WindowManager {
             {
                 // Check if clicking close button
                 if is_close_button {
-                    if is_dirty {
+                    if needs_confirmation {
                         // Show confirmation dialog
                         if let Some(window) = self.get_window_by_id_mut(window_id) {
                             window.show_close_confirmation();
@@ -1039,7 +1721,6 @@ impl WindowManager {
         false
     }
 
-    #[allow(clippy::collapsible_if)]
     fn handle_mouse_drag(
         &mut self,
         buffer: &mut VideoBuffer,
@@ -1230,25 +1911,63 @@ impl WindowManager {
         self.current_snap_zone = None;
     }
 
-    #[allow(clippy::collapsible_if)]
-    fn handle_scroll_up(&mut self, x: u16, y: u16) {
+    /// Wheel over a window: scroll its scrollback 3 lines, or on the alternate screen
+    /// (which has none) do what `alt_screen_scroll` says; Shift keeps the scrollback
+    fn handle_scroll(&mut self, x: u16, y: u16, up: bool, modifiers: KeyModifiers) {
         // Find window at position
-        if let Some(window_id) = self.window_at(x, y) {
-            if let Some(terminal_window) = self.get_window_by_id_mut(window_id) {
-                // Scroll up 3 lines
-                terminal_window.scroll_up(3);
+        let Some(window_id) = self.window_at(x, y) else {
+            return;
+        };
+        let Some(terminal_window) = self.get_window_by_id(window_id) else {
+            return;
+        };
+
+        let shift_held = self.shift_wheel_scrollback && modifiers.contains(KeyModifiers::SHIFT);
+        let action = if !shift_held && terminal_window.is_alt_screen_active() {
+            alt_screen_wheel(
+                self.alt_screen_scroll,
+                terminal_window.has_mouse_tracking_enabled(),
+            )
+        } else {
+            AltScreenWheel::Scrollback
+        };
+        match action {
+            AltScreenWheel::Scrollback => {}
+            AltScreenWheel::Ignore => return,
+            _ if self.is_window_input_locked(window_id) => return,
+            AltScreenWheel::ArrowKeys => {
+                let keys = wheel_arrow_keys(up, 3, terminal_window.get_application_cursor_keys());
+                #[cfg(unix)]
+                if self.persist_client.is_some() {
+                    self.send_persist_input(window_id, keys.as_bytes());
+                    return;
+                }
+                if let Some(terminal_window) = self.get_window_by_id_mut(window_id) {
+                    let _ = terminal_window.send_str(&keys);
+                }
+                return;
+            }
+            AltScreenWheel::MouseReport => {
+                let button = if up { 64 } else { 65 };
+                if let Some(terminal_window) = self.get_window_by_id_mut(window_id) {
+                    terminal_window.handle_mouse_for_terminal(x, y, button, 0, true);
+                    // Forward the buffered report to the daemon (Remote mode)
+                    #[cfg(unix)]
+                    if let Some(bytes) = terminal_window.drain_pending_remote_bytes() {
+                        self.send_persist_input(window_id, &bytes);
+                    }
+                }
+                return;
             }
         }
-    }
 
-    #[allow(clippy::collapsible_if)]
-    fn handle_scroll_down(&mut self, x: u16, y: u16) {
-        // Find window at position
-        if let Some(window_id) = self.window_at(x, y) {
-            if let Some(terminal_window) = self.get_window_by_id_mut(window_id) {
-                // Scroll down 3 lines
-                terminal_window.scroll_down(3);
-            }
+        let Some(terminal_window) = self.get_window_by_id_mut(window_id) else {
+            return;
+        };
+        if up {
+            terminal_window.scroll_up(3);
+        } else {
+            terminal_window.scroll_down(3);
         }
     }
 
@@ -1260,19 +1979,19 @@ impl WindowManager {
         buffer: &mut VideoBuffer,
         charset: &Charset,
         theme: &Theme,
-        tint_terminal: bool,
+        tint_strength: f32,
         keyboard_mode_active: bool,
     ) -> bool {
         let mut windows_to_close = Vec::new();
 
         for i in 0..self.windows.len() {
             // Process terminal output before rendering
-            if let Ok(false) = self.windows[i].process_output() {
+            if let Ok(false) = self.windows[i].process_output(self.max_bytes_per_frame) {
                 // Shell process has exited, mark for closure
                 windows_to_close.push(self.windows[i].id());
             }
 
-            self.windows[i].render(buffer, charset, theme, tint_terminal, keyboard_mode_active);
+            self.windows[i].render(buffer, charset, theme, tint_strength, keyboard_mode_active);
         }
 
         // Close windows whose shell processes have exited
@@ -1286,6 +2005,68 @@ impl WindowManager {
         any_closed
     }
 
+    /// Collect commands that finished in unfocused windows since the last call
+    /// Returns (window title, command name) pairs; finished commands in the focused window are dropped
+    pub fn take_finished_background_commands(&mut self) -> Vec<(String, String)> {
+        let focused_id = self.get_focused_window_id();
+        let mut finished = Vec::new();
+        for w in &mut self.windows {
+            if let Some(command) = w.take_finished_command() {
+                if Some(w.id()) != focused_id {
+                    finished.push((w.window.title.clone(), command));
+                }
+            }
+        }
+        finished
+    }
+
+    /// Whether any window rang the bell since the last call; with `mark` set, background
+    /// windows that rang get a bell marker on their bottom bar button until focused
+    pub fn take_bells(&mut self, mark: bool) -> bool {
+        let mut rang = false;
+        for w in &mut self.windows {
+            rang |= w.take_bell(mark);
+        }
+        rang
+    }
+
+    /// Whether a window has a bell marker (rang while in the background)
+    pub fn window_has_bell(&self, window_id: u32) -> bool {
+        self.get_window_by_id(window_id)
+            .is_some_and(|w| w.has_bell())
+    }
+
+    /// Whether a window produced output within the last couple of seconds
+    pub fn window_is_active(&self, window_id: u32) -> bool {
+        self.get_window_by_id(window_id)
+            .is_some_and(|w| w.is_active())
+    }
+
+    /// Render a read-only preview of a window's visible content into a rectangle
+    pub fn render_window_preview(
+        &self,
+        window_id: u32,
+        buffer: &mut VideoBuffer,
+        rect: (u16, u16, u16, u16),
+        theme: &Theme,
+        tint_strength: f32,
+    ) {
+        if let Some(w) = self.get_window_by_id(window_id) {
+            w.render_preview(buffer, rect, theme, tint_strength);
+        }
+    }
+
+    /// Check if a window is maximized
+    pub fn is_window_maximized(&self, window_id: u32) -> bool {
+        self.get_window_by_id(window_id)
+            .is_some_and(|w| w.window.is_maximized)
+    }
+
+    /// Get a window's title including its last known running process
+    pub fn window_display_title(&self, window_id: u32) -> Option<String> {
+        self.get_window_by_id(window_id).map(|w| w.display_title())
+    }
+
     /// Render snap preview overlay (if dragging and snap zone is active)
     pub fn render_snap_preview(&self, buffer: &mut VideoBuffer, charset: &Charset, theme: &Theme) {
         use crate::rendering::Cell;
@@ -1370,11 +2151,22 @@ impl WindowManager {
         None
     }
 
-    /// Get window positions for overlay rendering
+    /// Rectangles (x, y, width, height) of the windows on screen, bottom to top
+    pub fn visible_window_rects(&self) -> Vec<(u16, u16, u16, u16)> {
+        self.windows
+            .iter()
+            .filter(|w| !w.window.is_minimized)
+            .map(|w| (w.window.x, w.window.y, w.window.width, w.window.height))
+            .collect()
+    }
+
+    /// Get window positions for overlay rendering (the scratchpad is left out, as in the
+    /// button bar)
     /// Returns: (window_id, x, y, width, height, is_minimized, title)
     pub fn get_window_positions(&self) -> Vec<(u32, u16, u16, u16, u16, bool, String)> {
         self.windows
             .iter()
+            .filter(|w| !w.window.is_scratchpad)
             .map(|w| {
                 (
                     w.id(),
@@ -1412,69 +2204,77 @@ impl WindowManager {
         list
     }
 
+    /// Layout of the window buttons between `start_x` and `end_x` in the button bar
+    /// The scratchpad has no button
+    pub fn button_bar_layout(&self, start_x: u16, end_x: u16) -> ButtonBarLayout {
+        let mut windows = self.get_window_list();
+        windows.retain(|&(id, ..)| {
+            self.get_window_by_id(id)
+                .is_some_and(|w| !w.window.is_scratchpad)
+        });
+        let suffixes =
+            button_bar::duplicate_suffixes(&windows, self.button_title_duplicates, |id| {
+                self.get_window_by_id(id)
+                    .and_then(|w| w.cached_process_name().map(str::to_string))
+            });
+        button_bar::layout(
+            &windows,
+            &suffixes,
+            start_x,
+            end_x,
+            self.button_title_max_len,
+            self.button_title_truncation,
+        )
+    }
+
     /// Get window ID at button bar position (read-only, does not modify state)
-    /// offset_x: the starting x position for window buttons (after other UI elements)
+    /// start_x/end_x: the area for window buttons (between other UI elements)
     pub fn button_bar_get_window_at(
         &self,
         x: u16,
         bar_y: u16,
         click_y: u16,
-        offset_x: u16,
+        start_x: u16,
+        end_x: u16,
     ) -> Option<u32> {
         // Only process if clicking on the button bar row
         if click_y != bar_y {
             return None;
         }
 
-        // Get windows sorted by creation order (same as display order)
-        let mut sorted_windows: Vec<&TerminalWindow> = self.windows.iter().collect();
-        sorted_windows.sort_by_key(|w| w.id());
-
-        let mut current_x = offset_x; // Start at the offset position
-
-        for terminal_window in sorted_windows {
-            let window = &terminal_window.window;
-
-            // Button format: [ Title ]
-            // Max button width is 18 chars (including brackets and spaces)
-            let max_title_len = 14; // Leaves room for [ ] and spaces
-            let button_title = if window.title.len() > max_title_len {
-                &window.title[..max_title_len]
-            } else {
-                &window.title
-            };
-
-            let button_width = button_title.len() as u16 + 4; // "[ " + title + " ]"
-            let button_end = current_x + button_width;
-
-            // Check if click is within this button
-            if x >= current_x && x < button_end {
-                return Some(window.id);
-            }
-
-            // Move to next button position (with 1 space gap)
-            current_x = button_end + 1;
-        }
+        self.button_bar_layout(start_x, end_x).window_at(x)
+    }
 
-        None
+    /// Get the x position and width of a window's button in the button bar
+    pub fn button_bar_button_span(
+        &self,
+        window_id: u32,
+        start_x: u16,
+        end_x: u16,
+    ) -> Option<(u16, u16)> {
+        self.button_bar_layout(start_x, end_x)
+            .buttons
+            .into_iter()
+            .find(|button| button.id == window_id)
+            .map(|button| (button.x, button.width))
     }
 
     /// Handle click on button bar - returns window ID if clicked on a button
-    /// offset_x: the starting x position for window buttons (after other UI elements)
+    /// start_x/end_x: the area for window buttons (between other UI elements)
     pub fn button_bar_click(
         &mut self,
         x: u16,
         bar_y: u16,
         click_y: u16,
-        offset_x: u16,
+        start_x: u16,
+        end_x: u16,
     ) -> Option<u32> {
         // Use the read-only method to find the window
-        let clicked_window_id = self.button_bar_get_window_at(x, bar_y, click_y, offset_x);
+        let clicked_window_id = self.button_bar_get_window_at(x, bar_y, click_y, start_x, end_x);
 
         // Focus the clicked window if found
         if let Some(window_id) = clicked_window_id {
             // If the window is minimized, restore it first
-            #[allow(clippy::collapsible_if)]
             if let Some(win) = self.get_window_by_id_mut(window_id) {
                 if win.window.is_minimized {
                     win.window.restore_from_minimize();
@@ -1489,9 +2289,12 @@ impl WindowManager {
     }
 
     /// Send input to the focused terminal window
-    #[allow(clippy::collapsible_if)]
     pub fn send_to_focused(&mut self, s: &str) -> std::io::Result<()> {
         if let FocusState::Window(id) = self.focus {
+            if self.is_window_input_locked(id) {
+                return Ok(());
+            }
+
             // In persist mode, route input through daemon
             #[cfg(unix)]
             if self.persist_client.is_some() {
@@ -1507,9 +2310,12 @@ impl WindowManager {
     }
 
     /// Send a character to the focused terminal window
-    #[allow(clippy::collapsible_if)]
     pub fn send_char_to_focused(&mut self, c: char) -> std::io::Result<()> {
         if let FocusState::Window(id) = self.focus {
+            if self.is_window_input_locked(id) {
+                return Ok(());
+            }
+
             // In persist mode, route input through daemon
             #[cfg(unix)]
             if self.persist_client.is_some() {
@@ -1526,6 +2332,48 @@ impl WindowManager {
         Ok(())
     }
 
+    /// Check if a window has its input locked (keystrokes, pastes and mouse and focus reports are dropped)
+    fn is_window_input_locked(&self, window_id: u32) -> bool {
+        self.get_window_by_id(window_id)
+            .is_some_and(|w| w.is_input_locked())
+    }
+
+    /// Step the focused window's title bar color tag through the palette
+    /// Returns the new tag, or None if no window is focused
+    pub fn cycle_focused_tag(&mut self) -> Option<Option<Color>> {
+        let win = self.get_focused_window_mut()?;
+        win.window.cycle_tag();
+        Some(win.window.tag_color)
+    }
+
+    /// Set (or with None, clear) the focused window's title bar color tag
+    /// Returns false when no window is focused
+    pub fn set_focused_tag(&mut self, tag: Option<Color>) -> bool {
+        let Some(win) = self.get_focused_window_mut() else {
+            return false;
+        };
+        win.window.tag_color = tag;
+        true
+    }
+
+    /// Toggle literal display of control characters in the focused window
+    /// Returns true when they are now shown, or None if no window is focused
+    pub fn toggle_focused_control_display(&mut self) -> Option<bool> {
+        let notation = self.control_notation.unwrap_or(ControlNotation::Pictures);
+        Some(
+            self.get_focused_window_mut()?
+                .toggle_control_display(notation),
+        )
+    }
+
+    /// Toggle the input lock of the focused window
+    /// Returns the new lock state, or None if no window is focused
+    pub fn toggle_focused_input_lock(&mut self) -> Option<bool> {
+        let win = self.get_focused_window_mut()?;
+        win.toggle_input_lock();
+        Some(win.is_input_locked())
+    }
+
     /// Check if the focused window has mouse tracking enabled
     pub fn focused_has_mouse_tracking(&self) -> bool {
         if let FocusState::Window(id) = self.focus {
@@ -1536,6 +2384,24 @@ impl WindowManager {
         false
     }
 
+    /// Check if a wheel event should scroll term39's scrollback even though the
+    /// focused application tracks the mouse (Shift held and the option enabled)
+    pub fn wheel_overrides_mouse_tracking(&self, modifiers: KeyModifiers) -> bool {
+        self.shift_wheel_scrollback
+            && modifiers.contains(KeyModifiers::SHIFT)
+            && self.focused_receives_mouse()
+    }
+
+    /// Check if mouse events over the focused window go to its application
+    /// (depends on the mouse forward mode and the app's mouse tracking state)
+    pub fn focused_receives_mouse(&self) -> bool {
+        match self.mouse_forward_mode {
+            MouseForwardMode::Always => matches!(self.focus, FocusState::Window(_)),
+            MouseForwardMode::WhenAppRequests => self.focused_has_mouse_tracking(),
+            MouseForwardMode::Never => false,
+        }
+    }
+
     /// Check if the focused window has a close confirmation dialog active
     pub fn focused_has_close_confirmation(&self) -> bool {
         if let FocusState::Window(id) = self.focus {
@@ -1550,7 +2416,6 @@ impl WindowManager {
     /// Returns true if the event was consumed (forwarded to child process)
     /// button: 0=left, 1=middle, 2=right, 64=scroll up, 65=scroll down
     /// action: 0=press, 1=release, 2=drag/motion
-    #[allow(clippy::collapsible_if)]
     pub fn forward_mouse_to_focused(
         &mut self,
         screen_x: u16,
@@ -1558,10 +2423,11 @@ impl WindowManager {
         button: u8,
         action: u8,
     ) -> bool {
+        let force = self.mouse_forward_mode == MouseForwardMode::Always;
         if let FocusState::Window(id) = self.focus {
             if let Some(terminal_window) = self.get_window_by_id_mut(id) {
-                let consumed =
-                    terminal_window.handle_mouse_for_terminal(screen_x, screen_y, button, action);
+                let consumed = terminal_window
+                    .handle_mouse_for_terminal(screen_x, screen_y, button, action, force);
                 // Forward any buffered mouse bytes to daemon (Remote mode)
                 #[cfg(unix)]
                 if consumed {
@@ -1582,10 +2448,18 @@ impl WindowManager {
         // Collect pending resize notifications from remote windows
         #[cfg(unix)]
         let mut resize_notifications: Vec<(u32, u16, u16)> = Vec::new();
+        // Collect buffered bytes (e.g. focus events) from remote windows
+        #[cfg(unix)]
+        let mut pending_inputs: Vec<(u32, Vec<u8>)> = Vec::new();
 
         for terminal_window in &mut self.windows {
             let _ = terminal_window.flush_input();
 
+            #[cfg(unix)]
+            if let Some(bytes) = terminal_window.drain_pending_remote_bytes() {
+                pending_inputs.push((terminal_window.id(), bytes));
+            }
+
             // Check for pending resize on remote windows
             #[cfg(unix)]
             if terminal_window.is_remote() {
@@ -1602,6 +2476,11 @@ impl WindowManager {
         for (wid, cols, rows) in resize_notifications {
             self.send_persist_resize(wid, cols, rows);
         }
+
+        #[cfg(unix)]
+        for (id, bytes) in pending_inputs {
+            self.send_persist_input(id, &bytes);
+        }
     }
 
     // =========================================================================
@@ -1630,6 +2509,7 @@ impl WindowManager {
             );
 
             terminal_window.set_focused(false);
+            self.apply_window_defaults(&mut terminal_window);
             let idx = self.windows.len();
             self.windows.push(terminal_window);
             self.window_index_cache.insert(info.window_id, idx);
@@ -1643,7 +2523,8 @@ impl WindowManager {
         // Focus the last (top) window if any
         if let Some(last) = self.windows.last_mut() {
             last.set_focused(true);
-            self.focus = FocusState::Window(last.id());
+            let id = last.id();
+            self.set_focused_window(id);
         }
     }
 
@@ -1725,6 +2606,11 @@ impl WindowManager {
                 crate::persist::protocol::DaemonMsg::WindowCreated { window_id } => {
                     events.push(PersistEvent::WindowCreated(window_id));
                 }
+                crate::persist::protocol::DaemonMsg::SignalSent { signal, .. } => {
+                    if let Some(signal) = ForegroundSignal::from_raw(signal) {
+                        events.push(PersistEvent::SignalSent(signal));
+                    }
+                }
                 crate::persist::protocol::DaemonMsg::Error { message } => {
                     events.push(PersistEvent::Error(message));
                 }
@@ -1754,6 +2640,7 @@ impl WindowManager {
     /// Sends CreateWindow to daemon, waits for WindowCreated response,
     /// then creates a local Remote window with the daemon's window_id.
     #[cfg(unix)]
+    #[allow(clippy::too_many_arguments)]
     fn create_window_via_daemon(
         &mut self,
         x: u16,
@@ -1762,6 +2649,7 @@ impl WindowManager {
         height: u16,
         title: String,
         initial_command: Option<String>,
+        working_dir: Option<String>,
     ) -> Result<u32, String> {
         let client = match self.persist_client.as_mut() {
             Some(c) => c,
@@ -1770,7 +2658,15 @@ impl WindowManager {
 
         // Send request to daemon
         client
-            .request_create_window(x, y, width, height, title.clone(), initial_command)
+            .request_create_window(
+                x,
+                y,
+                width,
+                height,
+                title.clone(),
+                initial_command.clone(),
+                working_dir,
+            )
             .map_err(|e| format!("Failed to send create request: {}", e))?;
 
         // Wait for WindowCreated response (daemon processes synchronously)
@@ -1822,6 +2718,7 @@ impl WindowManager {
             title,
             daemon_window_id,
         );
+        terminal_window.set_launch_command(initial_command);
 
         // Unfocus all windows
         for w in &mut self.windows {
@@ -1829,10 +2726,12 @@ impl WindowManager {
         }
 
         terminal_window.set_focused(true);
+        self.apply_window_defaults(&mut terminal_window);
         let idx = self.windows.len();
         self.windows.push(terminal_window);
         self.window_index_cache.insert(daemon_window_id, idx);
-        self.focus = FocusState::Window(daemon_window_id);
+        self.set_focused_window(daemon_window_id);
+        self.keep_scratchpad_on_top();
 
         // Track position for cascading
         self.last_window_x = Some(x);
@@ -1896,6 +2795,45 @@ impl WindowManager {
         }
     }
 
+    /// Emulator metrics of every window (for the debug overlay)
+    pub fn window_stats(&self) -> Vec<WindowStats> {
+        self.windows.iter().map(|w| w.stats()).collect()
+    }
+
+    /// Clear the scrollback of the focused window
+    /// Returns true if a window was focused
+    pub fn clear_focused_scrollback(&mut self) -> bool {
+        if let Some(win) = self.get_focused_window_mut() {
+            win.clear_scrollback();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Send a signal to the foreground process group of the focused window
+    /// Returns false when the signal was only handed to the daemon (remote windows): the
+    /// outcome arrives later as PersistEvent::SignalSent or a daemon error
+    pub fn signal_focused(&mut self, signal: ForegroundSignal) -> Result<bool, String> {
+        let win = self
+            .get_focused_window()
+            .ok_or_else(|| "No window focused".to_string())?;
+        #[cfg(unix)]
+        if let Some(window_id) = win.remote_window_id() {
+            let client = self
+                .persist_client
+                .as_mut()
+                .ok_or_else(|| "Not connected to the session daemon".to_string())?;
+            return client
+                .send_signal_foreground(window_id, signal.as_raw())
+                .map(|()| false)
+                .map_err(|e| e.to_string());
+        }
+        win.signal_foreground(signal)
+            .map(|()| true)
+            .map_err(|e| e.to_string())
+    }
+
     /// Get application cursor keys mode (DECCKM) state for the focused window
     pub fn get_focused_application_cursor_keys(&self) -> bool {
         if let FocusState::Window(id) = self.focus {
@@ -1906,6 +2844,23 @@ impl WindowManager {
         false
     }
 
+    /// Kill a window's processes with SIGKILL and close it without any confirmation,
+    /// for a job that ignores the normal close. Returns true if the window was closed
+    pub fn force_close_window(&mut self, id: u32) -> bool {
+        let Some(pos) = self.get_window_index(id) else {
+            return false;
+        };
+        #[cfg(unix)]
+        if let Some(window_id) = self.windows[pos].remote_window_id() {
+            // The daemon kills the shell when it drops the window; the job goes first
+            if let Some(ref mut client) = self.persist_client {
+                let _ = client.send_signal_foreground(window_id, libc::SIGKILL);
+            }
+        }
+        self.windows[pos].force_kill();
+        self.close_window(id)
+    }
+
     /// Close window by ID
     /// Returns true if a window was actually closed
     ///
@@ -1945,7 +2900,7 @@ impl WindowManager {
 
                 if let Some(next_id) = next_focus {
                     // Focus the MRU non-minimized window
-                    self.focus = FocusState::Window(next_id);
+                    self.set_focused_window(next_id);
                     // Mark it as focused
                     if let Some(win) = self.get_window_by_id_mut(next_id) {
                         win.set_focused(true);
@@ -1967,9 +2922,10 @@ impl WindowManager {
         &mut self,
         window_id: u32,
         key: crossterm::event::KeyEvent,
+        enter: CloseConfirmEnter,
     ) -> Option<bool> {
         self.get_window_by_id_mut(window_id)
-            .and_then(|w| w.handle_close_confirmation_key(key))
+            .and_then(|w| w.handle_close_confirmation_key(key, enter))
     }
 
     /// Maximize window by ID
@@ -2001,9 +2957,18 @@ impl WindowManager {
             return;
         }
 
-        // Get sorted list of windows by creation order (ID)
-        let mut sorted_windows: Vec<u32> = self.windows.iter().map(|w| w.id()).collect();
+        // Get sorted list of windows by creation order (ID), a hidden scratchpad aside
+        let mut sorted_windows: Vec<u32> = self
+            .windows
+            .iter()
+            .filter(|w| !w.window.is_scratchpad || !w.window.is_minimized)
+            .map(|w| w.id())
+            .collect();
         sorted_windows.sort();
+        if sorted_windows.is_empty() {
+            self.focus_topbar();
+            return;
+        }
 
         match self.focus {
             FocusState::Desktop | FocusState::Topbar => {
@@ -2061,9 +3026,18 @@ impl WindowManager {
             return;
         }
 
-        // Get sorted list of windows by creation order (ID)
-        let mut sorted_windows: Vec<u32> = self.windows.iter().map(|w| w.id()).collect();
+        // Get sorted list of windows by creation order (ID), a hidden scratchpad aside
+        let mut sorted_windows: Vec<u32> = self
+            .windows
+            .iter()
+            .filter(|w| !w.window.is_scratchpad || !w.window.is_minimized)
+            .map(|w| w.id())
+            .collect();
         sorted_windows.sort();
+        if sorted_windows.is_empty() {
+            self.focus_topbar();
+            return;
+        }
 
         match self.focus {
             FocusState::Desktop => {
@@ -2106,8 +3080,29 @@ impl WindowManager {
 
     /// Paste text to a window
     pub fn paste_to_window(&mut self, window_id: u32, text: &str) -> std::io::Result<()> {
+        let transform = self.paste_transform;
+        if let Some(window) = self.get_window_by_id_mut(window_id) {
+            if window.is_input_locked() {
+                return Ok(());
+            }
+            window.paste_text(text, transform)?;
+        }
+        Ok(())
+    }
+
+    /// Paste text without running it: trailing line breaks are dropped
+    pub fn paste_literal_to_window(&mut self, window_id: u32, text: &str) -> std::io::Result<()> {
+        self.paste_to_window(window_id, trim_trailing_newline(text))
+    }
+
+    /// Paste text and run it: Enter is sent after the paste (outside any bracketed paste)
+    pub fn paste_and_run_in_window(&mut self, window_id: u32, text: &str) -> std::io::Result<()> {
+        self.paste_to_window(window_id, trim_trailing_newline(text))?;
         if let Some(window) = self.get_window_by_id_mut(window_id) {
-            window.paste_text(text)?;
+            if window.is_input_locked() {
+                return Ok(());
+            }
+            window.send_str("\r")?;
         }
         Ok(())
     }
@@ -2148,8 +3143,9 @@ impl WindowManager {
 
     /// Expand selection to word in a window
     pub fn expand_selection_to_word(&mut self, window_id: u32) {
-        if let Some(window) = self.get_window_by_id_mut(window_id) {
-            window.expand_selection_to_word();
+        let word_chars = &self.word_chars;
+        if let Some(window) = self.windows.iter_mut().find(|w| w.id() == window_id) {
+            window.expand_selection_to_word(word_chars);
         }
     }
 
@@ -2220,7 +3216,9 @@ impl WindowManager {
                 // Check drag/resize areas only for non-maximized windows
                 // (can't drag/resize maximized windows)
                 if !w.is_maximized {
-                    if terminal_window.is_in_title_bar(x, y) || w.get_resize_edge(x, y).is_some() {
+                    if terminal_window.is_in_title_bar(x, y)
+                        || w.get_resize_edge(x, y, self.resize_grip_size).is_some()
+                    {
                         return true;
                     }
                 }
@@ -2456,64 +3454,73 @@ impl WindowManager {
             FocusState::Desktop | FocusState::Topbar => return false,
         };
 
-        // Get current window center
-        let current_window = self.get_window_by_id(current_id);
-        let (cx, cy) = match current_window {
-            Some(w) => (
-                w.window.x + w.window.width / 2,
-                w.window.y + w.window.height / 2,
-            ),
-            None => return false,
-        };
+        if let Some(nearest_id) =
+            self.window_in_direction(current_id, direction, |w| !w.window.is_minimized)
+        {
+            self.focus_window(nearest_id);
+            return true;
+        }
 
-        // Find candidate windows in the specified direction
+        false
+    }
+
+    /// Nearest window matching `filter` in `direction` (see `nearest_in_direction`)
+    fn window_in_direction(
+        &self,
+        from_id: u32,
+        direction: u8,
+        filter: impl Fn(&TerminalWindow) -> bool,
+    ) -> Option<u32> {
+        let rect = |w: &TerminalWindow| (w.window.x, w.window.y, w.window.width, w.window.height);
+        let from = rect(self.get_window_by_id(from_id)?);
         let candidates: Vec<_> = self
             .windows
             .iter()
-            .filter(|w| w.id() != current_id && !w.window.is_minimized)
-            .filter_map(|w| {
-                let wx = w.window.x + w.window.width / 2;
-                let wy = w.window.y + w.window.height / 2;
-
-                // Check if window is in the right direction
-                let in_direction = match direction {
-                    0 => wx < cx, // left
-                    1 => wy > cy, // down
-                    2 => wy < cy, // up
-                    3 => wx > cx, // right
-                    _ => false,
-                };
-
-                if in_direction {
-                    // Calculate weighted distance (favor windows more aligned with direction)
-                    let dx = (wx as i32 - cx as i32).unsigned_abs();
-                    let dy = (wy as i32 - cy as i32).unsigned_abs();
-                    let distance = match direction {
-                        0 | 3 => dx + dy / 2, // horizontal: weight x more
-                        1 | 2 => dy + dx / 2, // vertical: weight y more
-                        _ => dx + dy,
-                    };
-                    Some((w.id(), distance))
-                } else {
-                    None
-                }
-            })
+            .filter(|w| w.id() != from_id && filter(w))
+            .map(|w| (w.id(), rect(w)))
             .collect();
+        nearest_in_direction(from, &candidates, direction)
+    }
 
-        // Find the nearest candidate
-        if let Some((nearest_id, _)) = candidates.into_iter().min_by_key(|(_, dist)| *dist) {
-            self.focus_window(nearest_id);
-            return true;
+    /// Move the focused auto-tiled window into the neighboring tile in `direction` (one of
+    /// the `mode_handlers::DIR_*` constants), swapping it with the window there and re-tiling
+    /// Returns false when the focused window is not tiled or has no tile that way
+    pub fn swap_focused_tile(
+        &mut self,
+        direction: u8,
+        buffer_width: u16,
+        buffer_height: u16,
+        gaps: bool,
+    ) -> bool {
+        let FocusState::Window(id) = self.focus else {
+            return false;
+        };
+        if !self.is_window_tiled_locked(id, true) {
+            return false;
         }
+        let Some(neighbor) =
+            self.window_in_direction(id, direction, |w| self.is_window_tiled_locked(w.id(), true))
+        else {
+            return false;
+        };
 
-        false
+        // Tiles are assigned in ID order, so trading IDs trades slots; the moved window
+        // now carries the neighbor's ID
+        self.swap_window_ids(id, neighbor);
+        self.rebuild_cache();
+        self.focus_window(neighbor);
+        self.auto_position_windows(buffer_width, buffer_height, gaps);
+        true
     }
 
-    /// Request to close the focused window, checking dirty state first
+    /// Request to close the focused window, checking for unsaved work first
     /// Returns true if a window was closed, false if confirmation dialog was shown or no window focused
     pub fn request_close_focused_window(&mut self) -> bool {
+        let needs_confirmation = self
+            .get_focused_window()
+            .is_some_and(|window| self.window_needs_close_confirmation(window));
         if let Some(window) = self.get_focused_window_mut() {
-            if window.is_dirty() {
+            if needs_confirmation {
                 // Show confirmation dialog
                 window.show_close_confirmation();
                 false
@@ -2551,6 +3558,54 @@ impl WindowManager {
         }
     }
 
+    /// Toggle the dense font (smaller glyphs, more rows/columns) on the focused window
+    /// `density` is the backend's dense cells per screen cell
+    /// Returns the new state (true = dense), or None if no window is focused
+    pub fn toggle_focused_window_dense(&mut self, density: (u16, u16)) -> Option<bool> {
+        let win = self.get_focused_window_mut()?;
+        let dense = win.content_density() == (1, 1);
+        let _ = win.set_content_density(if dense { density } else { (1, 1) });
+        Some(dense)
+    }
+
+    /// Toggle vertical-only maximize on the focused window (keeps x and width)
+    /// Returns true if the operation was performed
+    pub fn toggle_focused_window_maximize_vertical(
+        &mut self,
+        buffer_height: u16,
+        gaps: bool,
+    ) -> bool {
+        if let Some(win) = self.get_focused_window_mut() {
+            let wid = win.id();
+            win.window.toggle_maximize_vertical(buffer_height, gaps);
+            let _ = win.resize(win.window.width, win.window.height);
+            #[cfg(unix)]
+            self.send_persist_geometry_for_window(wid);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Toggle horizontal-only maximize on the focused window (keeps y and height)
+    /// Returns true if the operation was performed
+    pub fn toggle_focused_window_maximize_horizontal(
+        &mut self,
+        buffer_width: u16,
+        gaps: bool,
+    ) -> bool {
+        if let Some(win) = self.get_focused_window_mut() {
+            let wid = win.id();
+            win.window.toggle_maximize_horizontal(buffer_width, gaps);
+            let _ = win.resize(win.window.width, win.window.height);
+            #[cfg(unix)]
+            self.send_persist_geometry_for_window(wid);
+            true
+        } else {
+            false
+        }
+    }
+
     /// Toggle minimize on the focused window
     /// Returns true if the operation was performed
     pub fn toggle_focused_window_minimize(&mut self) -> bool {
@@ -2565,8 +3620,8 @@ impl WindowManager {
     /// Save current session to file
     pub fn save_session_to_file(&self) -> io::Result<()> {
         let path = session::get_session_path()?;
-        let state = self.create_session_state();
-        session::save_session(&state, &path)?;
+        let mut state = self.create_session_state();
+        session::save_session(&mut state, &path)?;
         Ok(())
     }
 
@@ -2589,7 +3644,8 @@ impl WindowManager {
         // Extract window snapshots (in z-order)
         for terminal_window in &self.windows {
             let window = &terminal_window.window;
-            let (terminal_lines, cursor) = terminal_window.get_terminal_content();
+            let (scrollback_lines, terminal_lines, cursor) = terminal_window
+                .get_terminal_content(self.session_scrollback_lines, self.session_trim_blanks);
             let (pre_max_x, pre_max_y, pre_max_w, pre_max_h) = window.get_pre_maximize_geometry();
 
             let snapshot = WindowSnapshot {
@@ -2602,6 +3658,13 @@ impl WindowManager {
                 is_focused: window.is_focused,
                 is_minimized: window.is_minimized,
                 is_maximized: window.is_maximized,
+                is_scratchpad: window.is_scratchpad,
+                input_locked: terminal_window.is_input_locked(),
+                tag_color: terminal_window
+                    .window
+                    .tag_color
+                    .and_then(super::base::tag_name)
+                    .map(str::to_string),
                 pre_maximize_x: pre_max_x,
                 pre_maximize_y: pre_max_y,
                 pre_maximize_width: pre_max_w,
@@ -2609,6 +3672,7 @@ impl WindowManager {
                 scroll_offset: terminal_window.get_scroll_offset(),
                 cursor,
                 terminal_lines,
+                scrollback_lines,
             };
 
             state.windows.push(snapshot);
@@ -2617,8 +3681,11 @@ impl WindowManager {
         state
     }
 
-    /// Restore session from file
-    pub fn restore_session_from_file(shell_config: ShellConfig) -> io::Result<Self> {
+    /// Restore session from file (`trim_blanks` drops trailing blank cells of restored lines)
+    pub fn restore_session_from_file(
+        shell_config: ShellConfig,
+        trim_blanks: bool,
+    ) -> io::Result<Self> {
         let path = session::get_session_path()?;
 
         // Try to load session
@@ -2643,13 +3710,19 @@ impl WindowManager {
                 snapshot.width,
                 snapshot.height,
                 snapshot.title.clone(),
-                None, // No initial command for restored windows
+                manager.shell_config.exec_command.clone(), // Restored windows rerun --exec, if any
                 &manager.shell_config,
             ) {
                 // Restore window state
                 terminal_window.set_focused(snapshot.is_focused);
                 terminal_window.window.is_minimized = snapshot.is_minimized;
                 terminal_window.window.is_maximized = snapshot.is_maximized;
+                terminal_window.window.is_scratchpad = snapshot.is_scratchpad;
+                terminal_window.set_input_locked(snapshot.input_locked);
+                terminal_window.window.tag_color = snapshot
+                    .tag_color
+                    .as_deref()
+                    .and_then(super::base::tag_color_by_name);
                 terminal_window.window.set_pre_maximize_geometry(
                     snapshot.pre_maximize_x,
                     snapshot.pre_maximize_y,
@@ -2661,7 +3734,12 @@ impl WindowManager {
                 terminal_window.set_scroll_offset(snapshot.scroll_offset);
 
                 // Restore terminal content
-                terminal_window.restore_terminal_content(snapshot.terminal_lines, &snapshot.cursor);
+                terminal_window.restore_terminal_content(
+                    snapshot.scrollback_lines,
+                    snapshot.terminal_lines,
+                    &snapshot.cursor,
+                    trim_blanks,
+                );
 
                 manager.windows.push(terminal_window);
             }
@@ -2683,12 +3761,9 @@ impl WindowManager {
     // Pivot Operations for Tiled Window Resizing
     // =========================================================================
 
-    /// Get visible (non-minimized) window count
+    /// Get visible (non-minimized) window count, the scratchpad aside
     fn visible_window_count(&self) -> usize {
-        self.windows
-            .iter()
-            .filter(|w| !w.window.is_minimized)
-            .count()
+        self.windows.iter().filter(|w| w.window.in_layout()).count()
     }
 
     /// Check if a window is in the locked tiled set (first 4 visible windows when auto-tiling)
@@ -2702,7 +3777,7 @@ impl WindowManager {
         let mut visible_ids: Vec<u32> = self
             .windows
             .iter()
-            .filter(|w| !w.window.is_minimized)
+            .filter(|w| w.window.in_layout())
             .map(|w| w.id())
             .collect();
         visible_ids.sort();
@@ -2858,7 +3933,7 @@ impl WindowManager {
         let mut visible_ids: Vec<u32> = self
             .windows
             .iter()
-            .filter(|w| !w.window.is_minimized)
+            .filter(|w| w.window.in_layout())
             .map(|w| w.id())
             .collect();
         visible_ids.sort();
@@ -2975,7 +4050,7 @@ impl WindowManager {
         let mut visible_ids: Vec<u32> = self
             .windows
             .iter()
-            .filter(|w| !w.window.is_minimized)
+            .filter(|w| w.window.in_layout())
             .map(|w| w.id())
             .collect();
         visible_ids.sort();
@@ -3065,3 +4140,469 @@ impl Default for WindowManager {
         Self::new()
     }
 }
+
+/// Clamp a window's top-left corner so it fits between the top bar (row 0) and the
+/// bottom bar (last row); windows larger than that area stick to the top-left
+pub(crate) fn clamp_window_position(
+    x: u16,
+    y: u16,
+    width: u16,
+    height: u16,
+    buffer_width: u16,
+    buffer_height: u16,
+) -> (u16, u16) {
+    let max_x = buffer_width.saturating_sub(width);
+    let max_y = buffer_height
+        .saturating_sub(1)
+        .saturating_sub(height)
+        .max(1);
+    (x.min(max_x), y.clamp(1, max_y))
+}
+
+/// Record `id` as the most recently focused window
+fn push_focus_history(history: &mut Vec<u32>, id: u32) {
+    history.retain(|&w| w != id);
+    history.push(id);
+    if history.len() > FOCUS_HISTORY_LEN {
+        history.remove(0);
+    }
+}
+
+/// Most recently focused window in `history` other than `current` that passes `usable`
+fn previous_in_history(
+    history: &[u32],
+    current: Option<u32>,
+    usable: impl Fn(u32) -> bool,
+) -> Option<u32> {
+    history
+        .iter()
+        .rev()
+        .copied()
+        .find(|&id| Some(id) != current && usable(id))
+}
+
+/// Top-left corner where a `width` x `height` window overlaps the `occupied` rectangles
+/// (x, y, width, height) least, preferring positions closer to the screen centre on ties
+/// Candidates are the screen edges and the sides of each occupied rectangle
+fn least_covered_position(
+    occupied: &[(u16, u16, u16, u16)],
+    width: u16,
+    height: u16,
+    buffer_width: u16,
+    buffer_height: u16,
+) -> (u16, u16) {
+    let max_x = buffer_width.saturating_sub(width);
+    let max_y = buffer_height
+        .saturating_sub(1)
+        .saturating_sub(height)
+        .max(1);
+    let center = (
+        max_x / 2,
+        (buffer_height.saturating_sub(height) / 2).clamp(1, max_y),
+    );
+
+    let mut xs = vec![0, center.0, max_x];
+    let mut ys = vec![1, center.1, max_y];
+    for &(ox, oy, ow, oh) in occupied {
+        xs.extend([ox.saturating_add(ow), ox.saturating_sub(width)]);
+        ys.extend([oy.saturating_add(oh), oy.saturating_sub(height)]);
+    }
+
+    let overlap = |x: u16, y: u16| -> u32 {
+        occupied
+            .iter()
+            .map(|&(ox, oy, ow, oh)| {
+                let w = (x + width).min(ox + ow).saturating_sub(x.max(ox)) as u32;
+                let h = (y + height).min(oy + oh).saturating_sub(y.max(oy)) as u32;
+                w * h
+            })
+            .sum()
+    };
+
+    let mut best = center;
+    let mut best_score = (overlap(center.0, center.1), 0);
+    for &x in &xs {
+        for &y in &ys {
+            let (x, y) = (x.min(max_x), y.clamp(1, max_y));
+            let distance = x.abs_diff(center.0) as u32 + y.abs_diff(center.1) as u32;
+            let score = (overlap(x, y), distance);
+            if score < best_score {
+                best = (x, y);
+                best_score = score;
+            }
+        }
+    }
+    best
+}
+
+/// What the wheel does over a window showing the alternate screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AltScreenWheel {
+    Scrollback,
+    ArrowKeys,
+    MouseReport,
+    Ignore,
+}
+
+/// Pick the `alt_screen_scroll` reaction; `mouse_tracking` when the app asked for mouse events
+fn alt_screen_wheel(mode: AltScreenScroll, mouse_tracking: bool) -> AltScreenWheel {
+    match mode {
+        AltScreenScroll::Scrollback => AltScreenWheel::Scrollback,
+        AltScreenScroll::SendArrows => AltScreenWheel::ArrowKeys,
+        AltScreenScroll::Forward if mouse_tracking => AltScreenWheel::MouseReport,
+        AltScreenScroll::Forward => AltScreenWheel::ArrowKeys,
+        AltScreenScroll::None => AltScreenWheel::Ignore,
+    }
+}
+
+/// Cursor key presses sent for `lines` wheel steps (SS3 form in application cursor mode)
+fn wheel_arrow_keys(up: bool, lines: usize, application_cursor_keys: bool) -> String {
+    let key = match (up, application_cursor_keys) {
+        (true, false) => "\x1b[A",
+        (false, false) => "\x1b[B",
+        (true, true) => "\x1bOA",
+        (false, true) => "\x1bOB",
+    };
+    key.repeat(lines)
+}
+
+/// Rectangle as (x, y, width, height)
+type Rect = (u16, u16, u16, u16);
+
+/// The candidate (id, rect) whose center is nearest to the center of `from` in `direction`
+/// (one of the `mode_handlers::DIR_*` constants), weighting distance along the direction more
+fn nearest_in_direction(from: Rect, candidates: &[(u32, Rect)], direction: u8) -> Option<u32> {
+    let center = |(x, y, width, height): Rect| ((x + width / 2) as i32, (y + height / 2) as i32);
+    let (cx, cy) = center(from);
+
+    candidates
+        .iter()
+        .filter_map(|&(id, rect)| {
+            let (wx, wy) = center(rect);
+
+            // Check if window is in the right direction
+            let in_direction = match direction {
+                0 => wx < cx, // left
+                1 => wy > cy, // down
+                2 => wy < cy, // up
+                3 => wx > cx, // right
+                _ => false,
+            };
+            if !in_direction {
+                return None;
+            }
+
+            // Calculate weighted distance (favor windows more aligned with direction)
+            let dx = (wx - cx).unsigned_abs();
+            let dy = (wy - cy).unsigned_abs();
+            let distance = match direction {
+                0 | 3 => dx + dy / 2, // horizontal: weight x more
+                1 | 2 => dy + dx / 2, // vertical: weight y more
+                _ => dx + dy,
+            };
+            Some((id, distance))
+        })
+        .min_by_key(|&(_, distance)| distance)
+        .map(|(id, _)| id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wheel_arrow_keys() {
+        assert_eq!(wheel_arrow_keys(true, 3, false), "\x1b[A\x1b[A\x1b[A");
+        assert_eq!(wheel_arrow_keys(false, 1, false), "\x1b[B");
+        // less and man turn on application cursor keys
+        assert_eq!(wheel_arrow_keys(false, 2, true), "\x1bOB\x1bOB");
+    }
+
+    #[test]
+    fn test_alt_screen_wheel_follows_mode() {
+        for tracking in [false, true] {
+            assert_eq!(
+                alt_screen_wheel(AltScreenScroll::Scrollback, tracking),
+                AltScreenWheel::Scrollback
+            );
+            assert_eq!(
+                alt_screen_wheel(AltScreenScroll::SendArrows, tracking),
+                AltScreenWheel::ArrowKeys
+            );
+            assert_eq!(
+                alt_screen_wheel(AltScreenScroll::None, tracking),
+                AltScreenWheel::Ignore
+            );
+        }
+        // Forward reports the wheel to apps that track the mouse, keys reach the others
+        assert_eq!(
+            alt_screen_wheel(AltScreenScroll::Forward, true),
+            AltScreenWheel::MouseReport
+        );
+        assert_eq!(
+            alt_screen_wheel(AltScreenScroll::Forward, false),
+            AltScreenWheel::ArrowKeys
+        );
+    }
+
+    #[test]
+    fn test_focus_history_ping_pongs_between_two_windows() {
+        let mut history = Vec::new();
+        for id in [1, 2, 3] {
+            push_focus_history(&mut history, id);
+        }
+        // At 3 the last window is 2; focusing it makes 3 the last one
+        assert_eq!(previous_in_history(&history, Some(3), |_| true), Some(2));
+        push_focus_history(&mut history, 2);
+        assert_eq!(previous_in_history(&history, Some(2), |_| true), Some(3));
+        assert_eq!(history, vec![1, 3, 2]);
+
+        // A closed window is skipped; from the desktop the current window counts
+        assert_eq!(
+            previous_in_history(&history, Some(2), |id| id != 3),
+            Some(1)
+        );
+        assert_eq!(previous_in_history(&history, None, |_| true), Some(2));
+
+        for id in 10..30 {
+            push_focus_history(&mut history, id);
+        }
+        assert_eq!(history.len(), FOCUS_HISTORY_LEN);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_focus_last_window_after_opening_two() {
+        let mut manager = WindowManager::new();
+        let first = manager
+            .create_window(0, 1, 30, 10, "One".to_string(), None)
+            .unwrap();
+        let second = manager
+            .create_window(10, 3, 30, 10, "Two".to_string(), None)
+            .unwrap();
+        assert_eq!(manager.get_focus(), FocusState::Window(second));
+
+        assert!(manager.focus_last_window());
+        assert_eq!(manager.get_focus(), FocusState::Window(first));
+        assert!(manager.focus_last_window());
+        assert_eq!(manager.get_focus(), FocusState::Window(second));
+
+        // Closing the focused window refocuses the other one, which stays in the history
+        assert!(manager.close_window(second));
+        assert_eq!(manager.get_focus(), FocusState::Window(first));
+        assert!(!manager.focus_last_window());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_tab_strip_needs_another_regular_window() {
+        let mut manager = WindowManager::new();
+        manager.set_tab_strip(true);
+        let first = manager
+            .create_window(0, 1, 30, 10, "One".to_string(), None)
+            .unwrap();
+        manager.maximize_window(first, 80, 24, false);
+        // A single window has nothing to switch to
+        assert_eq!(manager.tab_strip_area(), None);
+
+        // The scratchpad is no tab either
+        assert_eq!(manager.toggle_scratchpad(80, 24), Ok(true));
+        manager.focus_window(first);
+        assert_eq!(manager.tab_strip_area(), None);
+
+        manager
+            .create_window(5, 3, 30, 10, "Two".to_string(), None)
+            .unwrap();
+        manager.focus_window(first);
+        let w = &manager.get_window_by_id(first).unwrap().window;
+        let end = w.x + w.width - 2;
+        assert_eq!(
+            manager.tab_strip_area(),
+            Some((w.y, w.title_buttons_end(), end))
+        );
+        // The strip starts right after the minimize button
+        assert!(w.is_in_minimize_button(w.title_buttons_end() - 2, w.y));
+        assert!(!w.is_in_minimize_button(w.title_buttons_end() - 1, w.y));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_tab_strip_click_switches_and_keeps_maximized() {
+        let mut manager = WindowManager::new();
+        manager.set_tab_strip(true);
+        let first = manager
+            .create_window(0, 1, 30, 10, "One".to_string(), None)
+            .unwrap();
+        let second = manager
+            .create_window(5, 3, 30, 10, "Two".to_string(), None)
+            .unwrap();
+        manager.focus_window(first);
+        manager.maximize_window(first, 80, 24, false);
+
+        let (tab_y, start_x, end_x) = manager.tab_strip_area().unwrap();
+        let layout = manager.button_bar_layout(start_x, end_x);
+        let tab_x = (start_x..end_x)
+            .find(|&x| layout.window_at(x) == Some(second))
+            .unwrap();
+        let mut buffer = VideoBuffer::new(80, 24);
+        manager.handle_mouse_down(&mut buffer, tab_x, tab_y, false, false);
+
+        assert_eq!(manager.get_focus(), FocusState::Window(second));
+        assert!(
+            manager
+                .get_window_by_id(second)
+                .unwrap()
+                .window
+                .is_maximized
+        );
+        assert!(manager.tab_strip_area().is_some());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_toggle_scratchpad_stays_on_top() {
+        let mut manager = WindowManager::new();
+        let window = manager
+            .create_window(0, 1, 30, 10, "One".to_string(), None)
+            .unwrap();
+
+        // First use creates the scratchpad, focused and on top
+        assert_eq!(manager.toggle_scratchpad(80, 24), Ok(true));
+        let FocusState::Window(scratchpad) = manager.get_focus() else {
+            panic!("scratchpad not focused");
+        };
+        assert_ne!(scratchpad, window);
+        assert_eq!(manager.windows.last().unwrap().id(), scratchpad);
+
+        // Focusing another window keeps the shown scratchpad above it
+        manager.focus_window(window);
+        assert_eq!(manager.get_focus(), FocusState::Window(window));
+        assert_eq!(manager.windows.last().unwrap().id(), scratchpad);
+
+        // Unfocused, the toggle brings it to focus; focused, it hides it
+        assert_eq!(manager.toggle_scratchpad(80, 24), Ok(true));
+        assert_eq!(manager.get_focus(), FocusState::Window(scratchpad));
+        assert_eq!(manager.toggle_scratchpad(80, 24), Ok(false));
+        assert_eq!(manager.get_focus(), FocusState::Window(window));
+        assert!(
+            manager
+                .get_window_by_id(scratchpad)
+                .unwrap()
+                .window
+                .is_minimized
+        );
+
+        // Expose and the window number overlay never list it
+        let positions = manager.get_window_positions();
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].0, window);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_small_screen_policies() {
+        let open_two = || {
+            let mut manager = WindowManager::new();
+            let far = manager
+                .create_window(40, 10, 30, 10, "Far".to_string(), None)
+                .unwrap();
+            let focused = manager
+                .create_window(50, 12, 30, 10, "Focused".to_string(), None)
+                .unwrap();
+            (manager, far, focused)
+        };
+        let window =
+            |manager: &WindowManager, id| manager.get_window_by_id(id).unwrap().window.clone();
+
+        // Minimize: windows that don't fit go away, except the focused one
+        let (mut manager, far, focused) = open_two();
+        manager.fit_windows_to_small_screen(60, 20, SmallScreenPolicy::Minimize);
+        assert!(window(&manager, far).is_minimized);
+        assert!(!window(&manager, focused).is_minimized);
+
+        // Stack: every window is shrunk to the screen and centered
+        let (mut manager, far, focused) = open_two();
+        manager.fit_windows_to_small_screen(28, 8, SmallScreenPolicy::Stack);
+        for id in [far, focused] {
+            let w = window(&manager, id);
+            assert!(!w.is_minimized);
+            assert!(w.x + w.width <= 28 && w.y >= 1 && w.y + w.height <= 7);
+        }
+
+        // Clamp: windows stay open and are kept on the screen
+        let (mut manager, far, _) = open_two();
+        manager.fit_windows_to_small_screen(60, 20, SmallScreenPolicy::Clamp);
+        assert!(!window(&manager, far).is_minimized);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_lower_window_moves_focus_on() {
+        let mut manager = WindowManager::new();
+        let ids: Vec<u32> = (0..3)
+            .map(|i| {
+                manager
+                    .create_window(i * 5, 1, 30, 10, format!("W{}", i), None)
+                    .unwrap()
+            })
+            .collect();
+        let stack = |manager: &WindowManager| -> Vec<u32> {
+            manager.windows.iter().map(|w| w.id()).collect()
+        };
+
+        // Lowering the focused window sends it to the bottom and focuses the new top one
+        assert!(manager.lower_window(ids[2]));
+        assert_eq!(stack(&manager), [ids[2], ids[0], ids[1]]);
+        assert_eq!(manager.get_focus(), FocusState::Window(ids[1]));
+
+        // Lowering another window leaves the focus alone
+        assert!(manager.lower_window(ids[0]));
+        assert_eq!(stack(&manager), [ids[0], ids[2], ids[1]]);
+        assert_eq!(manager.get_focus(), FocusState::Window(ids[1]));
+        assert_eq!(manager.get_window_index(ids[1]), Some(2));
+
+        assert!(!manager.lower_window(999));
+    }
+
+    #[test]
+    fn test_nearest_window_in_direction() {
+        // Four tiles: 1 top-left, 2 bottom-left, 3 top-right, 4 bottom-right
+        let tiles = [
+            (1, (0, 1, 40, 11)),
+            (2, (0, 12, 40, 12)),
+            (3, (40, 1, 40, 11)),
+            (4, (40, 12, 40, 12)),
+        ];
+        let others = |id| -> Vec<_> { tiles.iter().copied().filter(|t| t.0 != id).collect() };
+        assert_eq!(nearest_in_direction(tiles[0].1, &others(1), 3), Some(3));
+        assert_eq!(nearest_in_direction(tiles[0].1, &others(1), 1), Some(2));
+        assert_eq!(nearest_in_direction(tiles[3].1, &others(4), 0), Some(2));
+        assert_eq!(nearest_in_direction(tiles[3].1, &others(4), 2), Some(3));
+        // Nothing further left of a left tile
+        assert_eq!(nearest_in_direction(tiles[0].1, &others(1), 0), None);
+    }
+
+    #[test]
+    fn test_window_position_is_clamped_on_screen() {
+        // Pointer near the bottom-right corner: the window is pulled back on-screen
+        assert_eq!(clamp_window_position(70, 20, 40, 10, 80, 24), (40, 13));
+        // Never over the top bar
+        assert_eq!(clamp_window_position(5, 0, 40, 10, 80, 24), (5, 1));
+        // Larger than the usable area: pinned below the top bar
+        assert_eq!(clamp_window_position(10, 10, 100, 30, 80, 24), (0, 1));
+    }
+
+    #[test]
+    fn test_smart_position_avoids_windows() {
+        // Empty desktop: centred
+        assert_eq!(least_covered_position(&[], 40, 10, 80, 24), (20, 7));
+
+        // A window covering the left half: the new one goes to the right half
+        let (x, _) = least_covered_position(&[(0, 1, 40, 22)], 40, 10, 80, 24);
+        assert_eq!(x, 40);
+
+        // Left half and top-right taken: right below the top-right window
+        let occupied = [(0, 1, 40, 22), (40, 1, 40, 11)];
+        assert_eq!(least_covered_position(&occupied, 40, 10, 80, 24), (40, 12));
+    }
+}
@@ -1,4 +1,8 @@
+pub mod animation;
 pub mod base;
+pub mod border_join;
+pub mod button_bar;
+pub mod expose;
 pub mod manager;
 pub mod mode_handlers;
 pub mod number_overlay;
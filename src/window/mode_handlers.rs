@@ -63,11 +63,17 @@ pub fn handle_window_mode_keyboard(
             top_y,
             profile,
         ),
-        WindowSubMode::Move => {
-            handle_move_mode(app_state, key_event, window_manager, cols, rows, top_y)
-        }
+        WindowSubMode::Move => handle_move_mode(
+            app_state,
+            app_config,
+            key_event,
+            window_manager,
+            cols,
+            rows,
+            top_y,
+        ),
         WindowSubMode::Resize(direction) => {
-            handle_resize_mode(app_state, key_event, window_manager, direction)
+            handle_resize_mode(app_state, app_config, key_event, window_manager, direction)
         }
     }
 }
@@ -176,13 +182,13 @@ fn handle_navigation_mode(
             true
         }
 
-        // Tab cycling
-        KeyCode::Tab if !has_shift => {
-            window_manager.cycle_to_next_window();
+        // Tab cycling (profile-based; previous first since Tab also matches with Shift held)
+        _ if matches_any(&profile.wm_cycle_previous, code, modifiers) => {
+            window_manager.cycle_to_previous_window();
             true
         }
-        KeyCode::BackTab | KeyCode::Tab if has_shift => {
-            window_manager.cycle_to_previous_window();
+        _ if matches_any(&profile.wm_cycle_next, code, modifiers) => {
+            window_manager.cycle_to_next_window();
             true
         }
 
@@ -224,6 +230,46 @@ fn handle_navigation_mode(
             true
         }
 
+        // Maximize height only / width only (profile-based)
+        _ if matches_any(&profile.wm_maximize_vertical, code, modifiers) => {
+            if !is_focused_window_locked(window_manager, app_state.auto_tiling_enabled) {
+                window_manager
+                    .toggle_focused_window_maximize_vertical(rows, app_config.tiling_gaps);
+            }
+            true
+        }
+        _ if matches_any(&profile.wm_maximize_horizontal, code, modifiers) => {
+            if !is_focused_window_locked(window_manager, app_state.auto_tiling_enabled) {
+                window_manager
+                    .toggle_focused_window_maximize_horizontal(cols, app_config.tiling_gaps);
+            }
+            true
+        }
+
+        // Dense font (framebuffer only): more rows/columns in the focused window
+        _ if matches_any(&profile.wm_toggle_dense_font, code, modifiers) => {
+            let message = match backend.dense_density() {
+                Some(density) => match window_manager.toggle_focused_window_dense(density) {
+                    Some(true) => Some("Dense font on"),
+                    Some(false) => Some("Dense font off"),
+                    None => None,
+                },
+                None => Some("Dense font needs framebuffer mode and a smaller dense font"),
+            };
+            if let Some(message) = message {
+                app_state.active_toast = Some(crate::ui::toast::Toast::new(message));
+            }
+            true
+        }
+
+        // Lower the focused window to the bottom of the stack (focus moves on)
+        _ if matches_any(&profile.wm_lower, code, modifiers) => {
+            if let Some(id) = window_manager.get_focused_window_id() {
+                window_manager.lower_window(id);
+            }
+            true
+        }
+
         // Minimize
         _ if matches_any(&profile.wm_minimize, code, modifiers) => {
             window_manager.toggle_focused_window_minimize();
@@ -273,68 +319,27 @@ fn handle_navigation_mode(
             true
         }
 
-        // Numpad-style snap positions (1-9)
+        // Numpad-style snap positions (1-9), laid out like the keypad
+        // Pressing 5 on a window that is already centered maximizes it (grid style)
         // Don't snap locked windows (auto-tiled first 4)
-        KeyCode::Char('1') => {
-            if !is_focused_window_locked(window_manager, app_state.auto_tiling_enabled) {
-                let (x, y, w, h) = SnapPosition::BottomLeft.calculate_rect(cols, rows, top_y);
-                window_manager.snap_focused_window(x, y, w, h);
-            }
-            true
-        }
-        KeyCode::Char('2') => {
-            if !is_focused_window_locked(window_manager, app_state.auto_tiling_enabled) {
-                let (x, y, w, h) = SnapPosition::BottomCenter.calculate_rect(cols, rows, top_y);
-                window_manager.snap_focused_window(x, y, w, h);
-            }
-            true
-        }
-        KeyCode::Char('3') => {
-            if !is_focused_window_locked(window_manager, app_state.auto_tiling_enabled) {
-                let (x, y, w, h) = SnapPosition::BottomRight.calculate_rect(cols, rows, top_y);
-                window_manager.snap_focused_window(x, y, w, h);
-            }
-            true
-        }
-        KeyCode::Char('4') => {
-            if !is_focused_window_locked(window_manager, app_state.auto_tiling_enabled) {
-                let (x, y, w, h) = SnapPosition::MiddleLeft.calculate_rect(cols, rows, top_y);
-                window_manager.snap_focused_window(x, y, w, h);
-            }
-            true
-        }
-        KeyCode::Char('5') => {
-            if !is_focused_window_locked(window_manager, app_state.auto_tiling_enabled) {
-                let (x, y, w, h) = SnapPosition::Center.calculate_rect(cols, rows, top_y);
-                window_manager.snap_focused_window(x, y, w, h);
-            }
-            true
-        }
-        KeyCode::Char('6') => {
-            if !is_focused_window_locked(window_manager, app_state.auto_tiling_enabled) {
-                let (x, y, w, h) = SnapPosition::MiddleRight.calculate_rect(cols, rows, top_y);
-                window_manager.snap_focused_window(x, y, w, h);
-            }
-            true
-        }
-        KeyCode::Char('7') => {
-            if !is_focused_window_locked(window_manager, app_state.auto_tiling_enabled) {
-                let (x, y, w, h) = SnapPosition::TopLeft.calculate_rect(cols, rows, top_y);
-                window_manager.snap_focused_window(x, y, w, h);
-            }
-            true
-        }
-        KeyCode::Char('8') => {
-            if !is_focused_window_locked(window_manager, app_state.auto_tiling_enabled) {
-                let (x, y, w, h) = SnapPosition::TopCenter.calculate_rect(cols, rows, top_y);
-                window_manager.snap_focused_window(x, y, w, h);
-            }
-            true
-        }
-        KeyCode::Char('9') => {
-            if !is_focused_window_locked(window_manager, app_state.auto_tiling_enabled) {
-                let (x, y, w, h) = SnapPosition::TopRight.calculate_rect(cols, rows, top_y);
-                window_manager.snap_focused_window(x, y, w, h);
+        KeyCode::Char(digit @ '1'..='9') => {
+            if !is_focused_window_locked(window_manager, app_state.auto_tiling_enabled)
+                && let Some(position) = SnapPosition::from_numpad(digit)
+            {
+                let (x, y, w, h) = position.calculate_rect(cols, rows, top_y);
+                let already_there = window_manager.get_focused_window().is_some_and(|win| {
+                    let win = &win.window;
+                    (win.x, win.y, win.width, win.height) == (x, y, w, h)
+                });
+                if position == SnapPosition::Center && already_there {
+                    window_manager.toggle_focused_window_maximize(
+                        cols,
+                        rows,
+                        app_config.tiling_gaps,
+                    );
+                } else {
+                    window_manager.snap_focused_window(x, y, w, h);
+                }
             }
             true
         }
@@ -353,6 +358,7 @@ fn handle_navigation_mode(
 /// Handle keyboard in Move sub-mode
 fn handle_move_mode(
     app_state: &mut AppState,
+    app_config: &AppConfig,
     key_event: KeyEvent,
     window_manager: &mut WindowManager,
     cols: u16,
@@ -360,26 +366,36 @@ fn handle_move_mode(
     top_y: u16,
 ) -> bool {
     // Check if focused window is locked (auto-tiled first 4)
-    // Locked windows cannot be moved, but allow exiting move mode
+    // Locked windows don't move freely: direction keys swap them into the neighboring tile
     if let Some(focused_id) = window_manager.get_focused_window_id() {
         if window_manager.is_window_tiled_locked(focused_id, app_state.auto_tiling_enabled) {
-            // Allow exiting move mode but block actual movement
-            match key_event.code {
+            let direction = match key_event.code {
+                KeyCode::Char('h' | 'H') | KeyCode::Left => Some(DIR_LEFT),
+                KeyCode::Char('j' | 'J') | KeyCode::Down => Some(DIR_DOWN),
+                KeyCode::Char('k' | 'K') | KeyCode::Up => Some(DIR_UP),
+                KeyCode::Char('l' | 'L') | KeyCode::Right => Some(DIR_RIGHT),
+                _ => None,
+            };
+            if let Some(direction) = direction {
+                window_manager.swap_focused_tile(direction, cols, rows, app_config.tiling_gaps);
+            } else if matches!(
+                key_event.code,
                 KeyCode::Enter
-                | KeyCode::Esc
-                | KeyCode::F(8)
-                | KeyCode::Char('m')
-                | KeyCode::Char('`') => {
-                    app_state.keyboard_mode.return_to_navigation();
-                    app_state.move_state.reset();
-                }
-                _ => {}
+                    | KeyCode::Esc
+                    | KeyCode::F(8)
+                    | KeyCode::Char('m')
+                    | KeyCode::Char('`')
+            ) {
+                app_state.keyboard_mode.return_to_navigation();
+                app_state.move_state.reset();
             }
             return true;
         }
     }
 
     let has_shift = key_event.modifiers.contains(KeyModifiers::SHIFT);
+    // Ctrl takes large steps (Shift already snaps/switches edges)
+    let large = key_event.modifiers.contains(KeyModifiers::CONTROL);
 
     match key_event.code {
         // Exit Move mode (Enter, Esc, F8, m)
@@ -417,22 +433,22 @@ fn handle_move_mode(
 
         // Incremental movement (with adaptive step)
         KeyCode::Char('h') | KeyCode::Left if !has_shift => {
-            let step = app_state.move_state.get_step() as i16;
+            let step = move_step(app_state, app_config, large);
             window_manager.move_focused_window_by(-step, 0, cols, rows, top_y);
             true
         }
         KeyCode::Char('j') | KeyCode::Down if !has_shift => {
-            let step = app_state.move_state.get_step() as i16;
+            let step = move_step(app_state, app_config, large);
             window_manager.move_focused_window_by(0, step, cols, rows, top_y);
             true
         }
         KeyCode::Char('k') | KeyCode::Up if !has_shift => {
-            let step = app_state.move_state.get_step() as i16;
+            let step = move_step(app_state, app_config, large);
             window_manager.move_focused_window_by(0, -step, cols, rows, top_y);
             true
         }
         KeyCode::Char('l') | KeyCode::Right if !has_shift => {
-            let step = app_state.move_state.get_step() as i16;
+            let step = move_step(app_state, app_config, large);
             window_manager.move_focused_window_by(step, 0, cols, rows, top_y);
             true
         }
@@ -499,6 +515,7 @@ fn handle_move_mode(
 /// Shift modifier controls which edge is resized (left/top vs right/bottom)
 fn handle_resize_mode(
     app_state: &mut AppState,
+    app_config: &AppConfig,
     key_event: KeyEvent,
     window_manager: &mut WindowManager,
     _resize_direction: ResizeDirection, // Kept for API compatibility
@@ -524,6 +541,8 @@ fn handle_resize_mode(
     }
 
     let has_shift = key_event.modifiers.contains(KeyModifiers::SHIFT);
+    // Ctrl takes large steps (Shift already snaps/switches edges)
+    let large = key_event.modifiers.contains(KeyModifiers::CONTROL);
 
     match key_event.code {
         // Exit Resize mode (Enter, Esc, F8, r)
@@ -565,52 +584,52 @@ fn handle_resize_mode(
 
         // h/Left = shrink width from right edge
         KeyCode::Char('h') | KeyCode::Left if !has_shift => {
-            let step = app_state.resize_state.get_step() as i16;
+            let step = resize_step(app_state, app_config, large);
             window_manager.resize_focused_window_by(-step, 0);
             true
         }
         // Shift+H = grow width from left edge
         KeyCode::Char('H') | KeyCode::Left if has_shift => {
-            let step = app_state.resize_state.get_step() as i16;
+            let step = resize_step(app_state, app_config, large);
             window_manager.resize_focused_window_from_left(step);
             true
         }
 
         // l/Right = grow width from right edge
         KeyCode::Char('l') | KeyCode::Right if !has_shift => {
-            let step = app_state.resize_state.get_step() as i16;
+            let step = resize_step(app_state, app_config, large);
             window_manager.resize_focused_window_by(step, 0);
             true
         }
         // Shift+L = shrink width from left edge
         KeyCode::Char('L') | KeyCode::Right if has_shift => {
-            let step = app_state.resize_state.get_step() as i16;
+            let step = resize_step(app_state, app_config, large);
             window_manager.resize_focused_window_from_left(-step);
             true
         }
 
         // k/Up = shrink height from bottom edge
         KeyCode::Char('k') | KeyCode::Up if !has_shift => {
-            let step = app_state.resize_state.get_step() as i16;
+            let step = resize_step(app_state, app_config, large);
             window_manager.resize_focused_window_by(0, -step);
             true
         }
         // Shift+K = grow height from top edge
         KeyCode::Char('K') | KeyCode::Up if has_shift => {
-            let step = app_state.resize_state.get_step() as i16;
+            let step = resize_step(app_state, app_config, large);
             window_manager.resize_focused_window_from_top(step);
             true
         }
 
         // j/Down = grow height from bottom edge
         KeyCode::Char('j') | KeyCode::Down if !has_shift => {
-            let step = app_state.resize_state.get_step() as i16;
+            let step = resize_step(app_state, app_config, large);
             window_manager.resize_focused_window_by(0, step);
             true
         }
         // Shift+J = shrink height from top edge
         KeyCode::Char('J') | KeyCode::Down if has_shift => {
-            let step = app_state.resize_state.get_step() as i16;
+            let step = resize_step(app_state, app_config, large);
             window_manager.resize_focused_window_from_top(-step);
             true
         }
@@ -620,6 +639,32 @@ fn handle_resize_mode(
     }
 }
 
+/// Move step for one key press: adaptive speed times `keyboard_move_step`,
+/// and times `keyboard_large_step_multiplier` when `large`
+fn move_step(app_state: &mut AppState, app_config: &AppConfig, large: bool) -> i16 {
+    let multiplier = if large {
+        app_config.keyboard_large_step_multiplier
+    } else {
+        1
+    };
+    app_state
+        .move_state
+        .get_scaled_step(app_config.keyboard_move_step, multiplier)
+}
+
+/// Resize step for one key press (see move_step); the window manager keeps windows
+/// at their minimum size however large the step
+fn resize_step(app_state: &mut AppState, app_config: &AppConfig, large: bool) -> i16 {
+    let multiplier = if large {
+        app_config.keyboard_large_step_multiplier
+    } else {
+        1
+    };
+    app_state
+        .resize_state
+        .get_scaled_step(app_config.keyboard_resize_step, multiplier)
+}
+
 /// Show Window Mode help overlay with all keybindings
 pub fn show_winmode_help_window(app_state: &mut AppState, cols: u16, rows: u16) {
     let help_message = "\
@@ -646,7 +691,7 @@ Press {Y}`{W} or {Y}F8{W} to toggle Window Mode
 {C}NUMPAD POSITIONS (1-9){W}
 
 {Y}7{W} {Y}8{W} {Y}9{W}       Top-left, Top-center, Top-right
-{Y}4{W} {Y}5{W} {Y}6{W}       Middle-left, Center, Middle-right
+{Y}4{W} {Y}5{W} {Y}6{W}       Middle-left, Center (again: maximize), Middle-right
 {Y}1{W} {Y}2{W} {Y}3{W}       Bottom-left, Bottom-center, Bottom-right
 
 {C}WINDOW ACTIONS{W}
@@ -656,13 +701,18 @@ Press {Y}`{W} or {Y}F8{W} to toggle Window Mode
 {Y}m{W}           Enter Move mode
 {Y}r{W}           Enter Resize mode
 {Y}z{W}/{Y}+{W}/{Y}Space{W}   Toggle maximize
+{Y}v{W}/{Y}V{W}         Maximize height/width only
 {Y}-{W}/{Y}_{W}         Toggle minimize
 {Y}x{W}/{Y}q{W}         Close focused window
 {Y}a{W}           Toggle auto-tiling
+{Y}d{W}           Toggle dense font (framebuffer)
+{Y}b{W}           Lower window (send to back)
 
 {C}MOVE MODE (after 'm'){W}
 
 {Y}h/j/k/l{W}     Move window (adaptive speed)
+                Swap with neighbor tile (auto-tiled)
+{Y}Ctrl{W}+arrows   Move in large steps
 {Y}Shift+H/J/K/L{W} Snap to edge
 {Y}Enter{W}/{Y}Esc{W}/{Y}m{W} Exit Move mode
 
@@ -671,6 +721,7 @@ Press {Y}`{W} or {Y}F8{W} to toggle Window Mode
 {Y}h{W}/{Y}l{W}         Shrink/Grow width
 {Y}k{W}/{Y}j{W}         Shrink/Grow height
 {Y}Shift{W}       Invert direction
+{Y}Ctrl{W}        Large steps
 {Y}Enter{W}/{Y}Esc{W}/{Y}r{W} Exit Resize mode
 
 {C}EXIT WINDOW MODE{W}
@@ -104,7 +104,7 @@ const MIN_WIDTH_FOR_ASCII: u16 = 12;
 const MIN_HEIGHT_FOR_ASCII: u16 = 10;
 
 /// Extract number from window title (e.g., "Terminal 3" -> Some(3))
-fn extract_number_from_title(title: &str) -> Option<usize> {
+pub(crate) fn extract_number_from_title(title: &str) -> Option<usize> {
     // Look for "Terminal N" pattern - extract the number after "Terminal "
     if let Some(rest) = title.strip_prefix("Terminal ") {
         // Take only digits before any other content (e.g., "3 [ > bash ]" -> "3")
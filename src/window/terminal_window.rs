@@ -1,8 +1,15 @@
 use super::base::Window;
 use crate::app::app_state::AutoScrollDirection;
-use crate::rendering::{Cell, Charset, CharsetMode, Theme, VideoBuffer};
+use crate::app::config_manager::{
+    CloseConfirmEnter, CloseConfirmPolicy, FocusHighlight, ScrollbarMode,
+};
+use crate::rendering::{
+    Cell, Charset, CharsetMode, DENSE_PLACEHOLDER, DenseRegion, Theme, VideoBuffer,
+    adjust_fg_for_contrast, calculate_contrast_ratio, calculate_luminance,
+};
 use crate::term_emu::{
-    Color as TermColor, NamedColor, Position, Selection, SelectionType, ShellConfig, TerminalCell,
+    Color as TermColor, ControlNotation, CursorShape, ForegroundSignal, NamedColor, PasteTransform,
+    Position, Selection, SelectionType, SharedOutputLog, ShellConfig, TerminalCell,
     TerminalEmulator, TerminalGrid, TerminalRenderer,
 };
 use crate::ui::prompt::{Prompt, PromptAction, PromptButton, PromptType, TextAlign};
@@ -65,15 +72,55 @@ pub enum EmulatorMode {
     },
 }
 
+/// Per-window emulator metrics shown by the debug overlay
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowStats {
+    pub id: u32,
+    pub cols: usize,
+    pub rows: usize,
+    pub scrollback_len: usize,
+    pub bytes_processed: u64,
+}
+
+/// Decide whether closing asks first; the process lookup only runs when it matters
+fn close_needs_confirmation(
+    policy: CloseConfirmPolicy,
+    has_typed: bool,
+    has_running_process: impl FnOnce() -> bool,
+) -> bool {
+    (policy.if_typed && has_typed) || (policy.if_process_running && has_running_process())
+}
+
+/// Whether `process` is one of the `protected` names (case-insensitive, ignoring a
+/// directory and the `-` of a login shell on either side)
+fn is_protected_process(process: &str, protected: &[String]) -> bool {
+    let bare = |name: &str| {
+        let name = name.trim();
+        let name = name.rsplit('/').next().unwrap_or(name);
+        name.strip_prefix('-').unwrap_or(name).to_lowercase()
+    };
+    let process = bare(process);
+    !process.is_empty() && protected.iter().any(|name| bare(name) == process)
+}
+
 /// A window containing a terminal emulator
 pub struct TerminalWindow {
     pub window: Window,
     mode: EmulatorMode,
     scroll_offset: usize,         // For scrollback navigation
     selection: Option<Selection>, // Current text selection
+    /// Grid's discarded-scrollback count when the selection was last re-anchored
+    scrollback_discarded_seen: u64,
     // Cached foreground process name to avoid spawning ps every frame
     cached_process_name: Option<String>,
     process_name_last_update: Instant,
+    // Name of a foreground command that just returned to the shell (not yet reported)
+    finished_command: Option<String>,
+    // The application rang the bell while the window was in the background
+    bell: bool,
+    // Grid generation seen by the last output check, and when new output last arrived
+    activity_generation: u64,
+    last_activity: Option<Instant>,
     // Close confirmation state
     pub(crate) pending_close_confirmation: Option<CloseConfirmation>,
     // Track user input (for dirty state detection)
@@ -86,6 +133,28 @@ pub struct TerminalWindow {
     pending_remote_bytes: Vec<u8>,
     /// Pending resize notification for daemon (cols, rows)
     pending_resize: Option<(u16, u16)>,
+    /// When true, keyboard input and pastes are dropped (output still flows)
+    input_locked: bool,
+    /// Terminal cells per screen cell (columns, rows); above (1, 1) the content uses a
+    /// smaller font in framebuffer mode while the frame keeps the global metrics
+    content_density: (u16, u16),
+    /// Command the window was launched with (None for a plain shell)
+    launch_command: Option<String>,
+    /// Minimum fg/bg contrast ratio for application colors (0.0 = off)
+    minimum_contrast: f32,
+    /// Configured text cursor color (None = invert the cell under the cursor)
+    cursor_color: Option<Color>,
+    /// When unfocused, draw blank cells as the desktop pattern (`ghost_unfocused_windows`)
+    ghost_unfocused: bool,
+    /// Show how far the view is scrolled back in the title bar (`scroll_indicator`)
+    scroll_indicator: bool,
+    /// Draw the cursor while focused even when the application hid it (DECTCEM)
+    force_cursor_visible: bool,
+    /// Extra emphasis of the focused window (`focus_highlight`, `focus_dim_percent`)
+    focus_highlight: FocusHighlight,
+    focus_dim_percent: u8,
+    /// Plain-text log receiving lines as they scroll into the scrollback (None = off)
+    output_log: Option<SharedOutputLog>,
 }
 
 /// Mouse tracking state - all flags retrieved with a single mutex lock
@@ -101,6 +170,34 @@ struct MouseTrackingState {
     urxvt_mode: bool,
 }
 
+/// How long a window counts as active after its last output
+const ACTIVITY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Known interactive shells (the foreground process while no command is running)
+const SHELL_NAMES: &[&str] = &[
+    "sh",
+    "bash",
+    "zsh",
+    "fish",
+    "dash",
+    "ksh",
+    "mksh",
+    "tcsh",
+    "csh",
+    "nu",
+    "elvish",
+    "xonsh",
+    "pwsh",
+    "cmd.exe",
+    "powershell.exe",
+];
+
+/// Check if a process name is an interactive shell (login shells are prefixed with '-')
+fn is_shell_process(name: &str) -> bool {
+    let name = name.trim_start_matches('-');
+    SHELL_NAMES.contains(&name)
+}
+
 impl TerminalWindow {
     /// Create a new terminal window with a local PTY (standalone mode)
     #[allow(clippy::too_many_arguments)]
@@ -114,14 +211,15 @@ impl TerminalWindow {
         initial_command: Option<String>,
         shell_config: &ShellConfig,
     ) -> std::io::Result<Self> {
-        // Calculate content area (excluding 2-char borders and title bar)
-        let content_width = width.saturating_sub(4).max(1); // -2 left, -2 right
-        let content_height = height.saturating_sub(2).max(1); // -1 title, -1 bottom
-
         let window = Window::new(id, x, y, width, height, title);
 
+        // Calculate content area (excluding borders and title bar)
+        let content_width = window.content_width().max(1);
+        let content_height = height.saturating_sub(2).max(1); // -1 title, -1 bottom
+
         // Parse initial_command into program + args for direct execution
         let parsed_command = initial_command.as_ref().map(|cmd| Self::parse_command(cmd));
+        let launch_command = initial_command;
 
         let emulator = TerminalEmulator::new(
             content_width as usize,
@@ -136,14 +234,30 @@ impl TerminalWindow {
             mode: EmulatorMode::Local(emulator),
             scroll_offset: 0,
             selection: None,
+            scrollback_discarded_seen: 0,
             cached_process_name: None,
             process_name_last_update: Instant::now(),
+            finished_command: None,
+            bell: false,
+            activity_generation: 0,
+            last_activity: None,
             pending_close_confirmation: None,
             created_at: Instant::now(),
             has_user_input: false,
             last_rendered_generation: 0,
             pending_remote_bytes: Vec::new(),
             pending_resize: None,
+            input_locked: false,
+            content_density: (1, 1),
+            launch_command,
+            minimum_contrast: 0.0,
+            ghost_unfocused: false,
+            scroll_indicator: true,
+            force_cursor_visible: false,
+            focus_highlight: FocusHighlight::None,
+            focus_dim_percent: 0,
+            cursor_color: None,
+            output_log: None,
         })
     }
 
@@ -159,10 +273,9 @@ impl TerminalWindow {
         title: String,
         daemon_window_id: u32,
     ) -> Self {
-        let content_width = width.saturating_sub(4).max(1);
-        let content_height = height.saturating_sub(2).max(1);
-
         let window = Window::new(id, x, y, width, height, title);
+        let content_width = window.content_width().max(1);
+        let content_height = height.saturating_sub(2).max(1);
         let renderer = TerminalRenderer::new(content_width as usize, content_height as usize, 1000);
 
         Self {
@@ -173,14 +286,30 @@ impl TerminalWindow {
             },
             scroll_offset: 0,
             selection: None,
+            scrollback_discarded_seen: 0,
             cached_process_name: None,
             process_name_last_update: Instant::now(),
+            finished_command: None,
+            bell: false,
+            activity_generation: 0,
+            last_activity: None,
             pending_close_confirmation: None,
             created_at: Instant::now(),
             has_user_input: false,
             last_rendered_generation: 0,
             pending_remote_bytes: Vec::new(),
             pending_resize: None,
+            input_locked: false,
+            content_density: (1, 1),
+            launch_command: None,
+            minimum_contrast: 0.0,
+            ghost_unfocused: false,
+            scroll_indicator: true,
+            force_cursor_visible: false,
+            focus_highlight: FocusHighlight::None,
+            focus_dim_percent: 0,
+            cursor_color: None,
+            output_log: None,
         }
     }
 
@@ -212,6 +341,26 @@ impl TerminalWindow {
         if let EmulatorMode::Remote { renderer, .. } = &mut self.mode {
             renderer.feed_output(data);
         }
+        self.sync_selection_with_scrollback();
+        self.write_output_log();
+        self.track_activity();
+    }
+
+    /// Emulator metrics for the debug overlay
+    pub fn stats(&self) -> WindowStats {
+        let grid_arc = self.grid_arc();
+        let grid = grid_arc.lock().unwrap();
+        let bytes_processed = match &self.mode {
+            EmulatorMode::Local(emu) => emu.bytes_processed(),
+            EmulatorMode::Remote { renderer, .. } => renderer.bytes_processed(),
+        };
+        WindowStats {
+            id: self.window.id,
+            cols: grid.cols(),
+            rows: grid.rows(),
+            scrollback_len: grid.scrollback_len(),
+            bytes_processed,
+        }
     }
 
     /// Drain any pending bytes buffered from Remote mode (mouse events, etc.)
@@ -268,11 +417,78 @@ impl TerminalWindow {
     }
 
     /// Process terminal output (call this regularly in the event loop)
+    /// Parses up to about `max_bytes` of queued output (0 = no limit)
     /// In Remote mode, returns Ok(true) (output is fed externally via feed_remote_output)
-    pub fn process_output(&mut self) -> std::io::Result<bool> {
-        match &mut self.mode {
-            EmulatorMode::Local(emu) => emu.process_output(),
+    pub fn process_output(&mut self, max_bytes: usize) -> std::io::Result<bool> {
+        let alive = match &mut self.mode {
+            EmulatorMode::Local(emu) => emu.process_output(max_bytes),
             EmulatorMode::Remote { .. } => Ok(true), // Always alive; output fed externally
+        };
+        self.sync_selection_with_scrollback();
+        self.write_output_log();
+        self.track_activity();
+        self.update_foreground_process();
+        alive
+    }
+
+    /// Note when the window's content last changed (the expose activity marker)
+    fn track_activity(&mut self) {
+        let generation = self.grid_arc().lock().unwrap().generation();
+        if generation != self.activity_generation {
+            self.activity_generation = generation;
+            self.last_activity = Some(Instant::now());
+        }
+    }
+
+    /// Whether the window produced output within the last couple of seconds
+    pub fn is_active(&self) -> bool {
+        self.last_activity
+            .is_some_and(|at| at.elapsed() < ACTIVITY_TIMEOUT)
+    }
+
+    /// Attach (or with None, detach) the plain-text output log
+    pub fn set_output_log(&mut self, log: Option<SharedOutputLog>) {
+        self.grid_arc()
+            .lock()
+            .unwrap()
+            .set_capture_committed_lines(log.is_some());
+        self.output_log = log;
+    }
+
+    /// Append the lines committed to the scrollback since the last frame to the output log
+    fn write_output_log(&mut self) {
+        let Some(log) = &self.output_log else {
+            return;
+        };
+        let lines = self.grid_arc().lock().unwrap().take_committed_lines();
+        if !lines.is_empty() {
+            // A failing log (disk full, removed directory) must never disturb the window
+            let _ = log.lock().unwrap().append(&lines);
+        }
+    }
+
+    /// Keep the selection on its content when old scrollback lines are discarded
+    /// (selection rows are absolute, so they shift when the scrollback is trimmed),
+    /// and the scrollback view within the lines that are left
+    fn sync_selection_with_scrollback(&mut self) {
+        let (discarded, scrollback_len) = {
+            let grid = self.grid_arc();
+            let grid = grid.lock().unwrap();
+            (grid.scrollback_discarded(), grid.scrollback_len())
+        };
+        // The scrollback may have shrunk under the view (ESC[3J)
+        self.scroll_offset = self.scroll_offset.min(scrollback_len);
+        let delta = discarded.saturating_sub(self.scrollback_discarded_seen);
+        self.scrollback_discarded_seen = discarded;
+        if delta == 0 {
+            return;
+        }
+
+        if let Some(selection) = &mut self.selection {
+            let lines = delta.min(u16::MAX as u64) as u16;
+            if !selection.shift_up(lines) {
+                self.selection = None;
+            }
         }
     }
 
@@ -316,9 +532,8 @@ impl TerminalWindow {
         self.window.width = new_width;
         self.window.height = new_height;
 
-        // Calculate new content dimensions (accounting for 2-char borders)
-        let content_width = new_width.saturating_sub(4).max(1); // -2 left, -2 right
-        let content_height = new_height.saturating_sub(2).max(1); // -1 title, -1 bottom
+        // Calculate new terminal grid dimensions (content area scaled by density)
+        let (content_width, content_height) = self.grid_size();
 
         // Invalidate render cache since window dimensions changed
         self.invalidate_render_cache();
@@ -333,6 +548,28 @@ impl TerminalWindow {
         }
     }
 
+    /// Terminal grid size (cols, rows): the content area (inside the 2-char borders and
+    /// title bar) multiplied by the content density
+    fn grid_size(&self) -> (u16, u16) {
+        let (density_x, density_y) = self.content_density;
+        let content_width = self.window.content_width().max(1);
+        let content_height = self.window.height.saturating_sub(2).max(1); // -1 title, -1 bottom
+        (content_width * density_x, content_height * density_y)
+    }
+
+    /// Terminal cells per screen cell (columns, rows); (1, 1) is the regular font
+    pub fn content_density(&self) -> (u16, u16) {
+        self.content_density
+    }
+
+    /// Change the content density and resize the terminal grid to match
+    pub fn set_content_density(&mut self, density: (u16, u16)) -> std::io::Result<()> {
+        self.content_density = (density.0.max(1), density.1.max(1));
+        // Selection is in grid coordinates which no longer match
+        self.selection = None;
+        self.resize(self.window.width, self.window.height)
+    }
+
     /// Invalidate the render cache to force re-rendering on next frame
     /// Call this when window state changes that affect rendering (resize, focus, selection)
     #[inline]
@@ -362,6 +599,123 @@ impl TerminalWindow {
         self.scroll_offset = 0;
     }
 
+    /// Clear the scrollback buffer without touching the visible screen or the shell
+    pub fn clear_scrollback(&mut self) {
+        let grid = self.grid_arc();
+        grid.lock().unwrap().clear_scrollback();
+        self.scroll_offset = 0;
+        // Selection uses absolute rows which shift when scrollback is dropped
+        self.selection = None;
+        self.invalidate_render_cache();
+    }
+
+    /// Set the cursor shape used when the application has not requested one
+    pub fn set_default_cursor_shape(&mut self, shape: CursorShape) {
+        let grid = self.grid_arc();
+        grid.lock().unwrap().set_default_cursor_shape(shape);
+        self.invalidate_render_cache();
+    }
+
+    /// Set the string sent back to the application when it sends ENQ
+    pub fn set_answerback(&mut self, answerback: &str) {
+        let grid = self.grid_arc();
+        grid.lock().unwrap().set_answerback(answerback);
+    }
+
+    /// Set the minimum contrast ratio application colors are adjusted to (0.0 = off)
+    pub fn set_minimum_contrast(&mut self, ratio: f32) {
+        self.minimum_contrast = ratio;
+        self.invalidate_render_cache();
+    }
+
+    /// Show control characters and escape sequences literally (None = interpret them)
+    pub fn set_control_display(&mut self, notation: Option<ControlNotation>) {
+        let grid = self.grid_arc();
+        grid.lock().unwrap().set_control_display(notation);
+    }
+
+    /// Switch between interpreting the output and showing it literally in `notation`
+    /// Returns true when control characters are now shown
+    pub fn toggle_control_display(&mut self, notation: ControlNotation) -> bool {
+        let grid = self.grid_arc();
+        let mut grid = grid.lock().unwrap();
+        let show = grid.control_notation().is_none();
+        grid.set_control_display(show.then_some(notation));
+        show
+    }
+
+    /// Keep only the newest `compress_after` scrollback lines unpacked (None = no compression)
+    pub fn set_scrollback_compression(&mut self, compress_after: Option<usize>) {
+        let grid = self.grid_arc();
+        grid.lock()
+            .unwrap()
+            .set_scrollback_compression(compress_after);
+    }
+
+    /// Set the configured text cursor color (None = invert the cell under the cursor)
+    pub fn set_cursor_color(&mut self, color: Option<Color>) {
+        self.cursor_color = color;
+        self.invalidate_render_cache();
+    }
+
+    /// Let the desktop show through blank cells while the window is unfocused
+    pub fn set_ghost_unfocused(&mut self, ghost: bool) {
+        self.ghost_unfocused = ghost;
+        self.invalidate_render_cache();
+    }
+
+    /// Show or hide the scrolled-back indicator in the title bar
+    pub fn set_scroll_indicator(&mut self, show: bool) {
+        self.scroll_indicator = show;
+    }
+
+    /// Draw the cursor while focused regardless of the application's `ESC [ ? 25 l`
+    pub fn set_force_cursor_visible(&mut self, force: bool) {
+        self.force_cursor_visible = force;
+        self.invalidate_render_cache();
+    }
+
+    pub fn is_cursor_forced_visible(&self) -> bool {
+        self.force_cursor_visible
+    }
+
+    /// Set how the window stands out when focused, or is muted when not
+    pub fn set_focus_highlight(&mut self, highlight: FocusHighlight, dim_percent: u8) {
+        self.focus_highlight = highlight;
+        self.focus_dim_percent = dim_percent;
+        self.invalidate_render_cache();
+    }
+
+    /// Set when the scrollbar is shown; resizes the terminal when the content width changes
+    pub fn set_scrollbar_mode(&mut self, mode: ScrollbarMode) {
+        if self.window.scrollbar_mode == mode {
+            return;
+        }
+        let old_width = self.window.content_width();
+        self.window.scrollbar_mode = mode;
+        if self.window.content_width() != old_width {
+            let _ = self.resize(self.window.width, self.window.height);
+        } else {
+            self.invalidate_render_cache();
+        }
+    }
+
+    /// Check if input is locked for this window
+    pub fn is_input_locked(&self) -> bool {
+        self.input_locked
+    }
+
+    /// Set the input lock state (for session restoration)
+    pub fn set_input_locked(&mut self, locked: bool) {
+        self.input_locked = locked;
+    }
+
+    /// Toggle the input lock: when locked, keystrokes, pastes, mouse reports and
+    /// focus reports are dropped
+    pub fn toggle_input_lock(&mut self) {
+        self.input_locked = !self.input_locked;
+    }
+
     /// Render the terminal window
     /// If keyboard_mode_active is true and window is focused, uses keyboard mode colors
     pub fn render(
@@ -369,11 +723,14 @@ impl TerminalWindow {
         buffer: &mut VideoBuffer,
         charset: &Charset,
         theme: &Theme,
-        tint_terminal: bool,
+        tint_strength: f32,
         keyboard_mode_active: bool,
     ) {
-        // Get dynamic title with cached process name
-        let dynamic_title = self.get_dynamic_title_cached();
+        // Get dynamic title with cached process name (prefixed with a lock glyph when locked)
+        let mut dynamic_title = self.get_dynamic_title_cached();
+        if self.input_locked {
+            dynamic_title = format!("{} {}", charset.icon_lock, dynamic_title);
+        }
 
         // Render the window frame and title bar with dynamic title
         self.window.render_with_title(
@@ -383,26 +740,116 @@ impl TerminalWindow {
             Some(&dynamic_title),
             keyboard_mode_active,
         );
+        self.render_scroll_indicator(buffer, charset, theme);
 
         // Acquire grid lock once for both content and scrollbar rendering
         let grid_arc = self.grid_arc();
         let grid = grid_arc.lock().unwrap();
 
-        // Render the terminal content
-        self.render_terminal_content_with_grid(buffer, theme, tint_terminal, &grid);
+        // Render the terminal content, blank cells of a ghosted unfocused window showing
+        // the desktop pattern
+        let ghost = (self.ghost_unfocused && !self.window.is_focused)
+            .then(|| Cell::new_unchecked(charset.background, theme.desktop_fg, theme.desktop_bg));
+        self.render_terminal_content_with_grid(buffer, theme, tint_strength, ghost, &grid);
 
         // Render the scrollbar
         self.render_scrollbar_with_grid(buffer, charset, theme, &grid);
 
+        self.window.render_focus_highlight(
+            buffer,
+            theme,
+            self.focus_highlight,
+            self.focus_dim_percent,
+            keyboard_mode_active,
+        );
+
         // Render close confirmation on top of window content (if active)
         self.render_close_confirmation(buffer, charset, theme);
     }
 
+    /// While scrolled back, show the lines below the view at the right of the title bar,
+    /// e.g. `[↑ 120]` (`[^120]` with the ASCII charset)
+    fn render_scroll_indicator(&self, buffer: &mut VideoBuffer, charset: &Charset, theme: &Theme) {
+        if !self.scroll_indicator || self.scroll_offset == 0 {
+            return;
+        }
+        let label = match charset.mode {
+            CharsetMode::Ascii => format!("[^{}]", self.scroll_offset),
+            CharsetMode::Unicode | CharsetMode::UnicodeSingleLine => {
+                format!("[\u{2191} {}]", self.scroll_offset)
+            }
+        };
+        let len = label.chars().count() as u16;
+
+        // Right-aligned with a space before the 2-char right border, never over the buttons
+        let win = &self.window;
+        let buttons_end = win.title_buttons_end();
+        let Some(start) = (win.x + win.width).checked_sub(len + 3) else {
+            return;
+        };
+        if start <= buttons_end {
+            return;
+        }
+
+        // Separate it from a long title with spaces in the title bar colors
+        for x in [start - 1, start + len] {
+            if let Some(&cell) = buffer.get(x, win.y) {
+                buffer.set(
+                    x,
+                    win.y,
+                    Cell {
+                        character: ' ',
+                        ..cell
+                    },
+                );
+            }
+        }
+        for (i, ch) in label.chars().enumerate() {
+            buffer.set(
+                start + i as u16,
+                win.y,
+                Cell::new_unchecked(ch, theme.prompt_warning_fg, theme.prompt_warning_bg),
+            );
+        }
+    }
+
+    /// Render the visible screen into a sub-rectangle (read-only, cropped to fit)
+    /// Used by the expose overview for window thumbnails
+    pub fn render_preview(
+        &self,
+        buffer: &mut VideoBuffer,
+        rect: (u16, u16, u16, u16),
+        theme: &Theme,
+        tint_strength: f32,
+    ) {
+        let (x, y, width, height) = rect;
+        let grid_arc = self.grid_arc();
+        let grid = grid_arc.lock().unwrap();
+
+        for row in 0..height {
+            for col in 0..width {
+                let cell = match grid.get_render_cell(col as usize, row as usize) {
+                    Some(term_cell) => convert_terminal_cell(
+                        term_cell,
+                        theme,
+                        tint_strength,
+                        self.minimum_contrast,
+                    ),
+                    None => {
+                        Cell::new_unchecked(' ', theme.window_content_fg, theme.window_content_bg)
+                    }
+                };
+                buffer.set(x + col, y + row, cell);
+            }
+        }
+    }
+
     fn render_terminal_content_with_grid(
         &self,
         buffer: &mut VideoBuffer,
         theme: &Theme,
-        tint_terminal: bool,
+        tint_strength: f32,
+        ghost: Option<Cell>,
         grid: &MutexGuard<'_, TerminalGrid>,
     ) {
         if self.window.is_minimized {
@@ -412,29 +859,47 @@ impl TerminalWindow {
         // Content area starts after 2-char left border and title bar
         let content_x = self.window.x + 2; // After 2-char left border
         let content_y = self.window.y + 1; // After title bar
-        let content_width = self.window.width.saturating_sub(4); // -2 left, -2 right
+        let content_width = self.window.content_width();
         let content_height = self.window.height.saturating_sub(2); // -1 title, -1 bottom
 
         let scrollback_len = grid.scrollback_len();
         let visible_rows = grid.rows();
 
+        // Dense windows render into their own grid which the backend rasterizes with a
+        // smaller font; the screen cells underneath only hold placeholders
+        let (density_x, density_y) = self.content_density;
+        let is_dense = self.content_density != (1, 1);
+        let grid_width = content_width * density_x;
+        let grid_height = content_height * density_y;
+        let mut dense_cells = Vec::new();
+        if is_dense {
+            dense_cells.reserve(grid_width as usize * grid_height as usize);
+        }
+        // The desktop pattern is drawn in screen cells, so dense content stays opaque
+        let ghost = ghost.filter(|_| !is_dense);
+
         // Render terminal grid cells
-        for row in 0..content_height {
-            for col in 0..content_width {
+        for row in 0..grid_height {
+            let row_idx = row as usize;
+            // Line index when scrolled back, and the scrollback line it falls on (fetched
+            // once per row, since compressed lines are unpacked on every read)
+            let total_lines = scrollback_len + visible_rows;
+            let line_idx = total_lines.saturating_sub(self.scroll_offset + visible_rows) + row_idx;
+            let scrollback_line = if self.scroll_offset > 0 && line_idx < scrollback_len {
+                grid.get_scrollback_line(line_idx)
+            } else {
+                None
+            };
+
+            for col in 0..grid_width {
                 let grid_col = col as usize;
-                let row_idx = row as usize;
 
                 // Calculate which line to display based on scroll offset
                 let term_cell = if self.scroll_offset > 0 {
                     // We're scrolled back, need to fetch from scrollback or visible rows
-                    let total_lines = scrollback_len + visible_rows;
-                    let line_idx =
-                        total_lines.saturating_sub(self.scroll_offset + visible_rows) + row_idx;
-
                     if line_idx < scrollback_len {
                         // Fetch from scrollback
-                        grid.get_scrollback_line(line_idx)
-                            .and_then(|line| line.get(grid_col))
+                        scrollback_line.as_ref().and_then(|line| line.get(grid_col))
                     } else {
                         // Fetch from visible rows
                         let visible_row = line_idx - scrollback_len;
@@ -447,8 +912,12 @@ impl TerminalWindow {
                 };
 
                 // Render the cell
-                let mut cell = if let Some(term_cell) = term_cell {
-                    convert_terminal_cell(term_cell, theme, tint_terminal)
+                let mut cell = if let Some(ghost) = ghost
+                    && term_cell.is_none_or(is_blank_cell)
+                {
+                    ghost
+                } else if let Some(term_cell) = term_cell {
+                    convert_terminal_cell(term_cell, theme, tint_strength, self.minimum_contrast)
                 } else {
                     // Grid doesn't have data for this cell (window is larger than grid)
                     // Use default terminal background to maintain visual consistency
@@ -467,7 +936,11 @@ impl TerminalWindow {
                     }
                 }
 
-                buffer.set(content_x + col, content_y + row, cell);
+                if is_dense {
+                    dense_cells.push(cell);
+                } else {
+                    buffer.set(content_x + col, content_y + row, cell);
+                }
             }
         }
 
@@ -475,43 +948,53 @@ impl TerminalWindow {
         // Applications like Claude hide the cursor to draw their own
         // Use get_render_cursor to respect synchronized output snapshot
         let render_cursor = grid.get_render_cursor();
-        if render_cursor.visible && self.scroll_offset == 0 {
-            let cursor_x = content_x + render_cursor.x as u16;
-            let cursor_y = content_y + render_cursor.y as u16;
+        // An OSC 12 color from the application wins over the configured one
+        let cursor_color = grid
+            .cursor_color()
+            .map(|(r, g, b)| Color::Rgb { r, g, b })
+            .or(self.cursor_color);
+        let cursor_visible =
+            render_cursor.visible || (self.force_cursor_visible && self.window.is_focused);
+        if cursor_visible && self.scroll_offset == 0 {
+            let cursor_col = render_cursor.x as u16;
+            let cursor_row = render_cursor.y as u16;
 
             // Check if cursor is within window bounds
-            if cursor_x < content_x + content_width && cursor_y < content_y + content_height {
-                // Get the current cell at cursor position
-                if let Some(current_cell) = buffer.get(cursor_x, cursor_y) {
-                    // Create cursor based on cursor shape
-                    let cursor_cell = match render_cursor.shape {
-                        crate::term_emu::CursorShape::Block => {
-                            // For block cursor, show as inverted colors
-                            if current_cell.character == ' ' || current_cell.character == '\0' {
-                                // For empty space, show a solid block using the foreground color
-                                // This makes the cursor visible as a colored block
-                                Cell::new('█', current_cell.fg_color, current_cell.bg_color)
-                            } else {
-                                // For text, invert the colors (swap fg and bg)
-                                Cell::new(
-                                    current_cell.character,
-                                    current_cell.bg_color, // Use bg as fg (inverted)
-                                    current_cell.fg_color, // Use fg as bg (inverted)
-                                )
-                            }
-                        }
-                        crate::term_emu::CursorShape::Underline => {
-                            // For underline cursor, show underscore in foreground color
-                            Cell::new('_', current_cell.fg_color, current_cell.bg_color)
-                        }
-                        crate::term_emu::CursorShape::Bar => {
-                            // For bar cursor, show vertical bar in foreground color
-                            Cell::new('│', current_cell.fg_color, current_cell.bg_color)
-                        }
-                    };
-                    buffer.set(cursor_x, cursor_y, cursor_cell);
+            if cursor_col < grid_width && cursor_row < grid_height {
+                if is_dense {
+                    let idx = cursor_row as usize * grid_width as usize + cursor_col as usize;
+                    if let Some(current_cell) = dense_cells.get_mut(idx) {
+                        *current_cell =
+                            cursor_cell(current_cell, render_cursor.shape, cursor_color);
+                    }
+                } else {
+                    let cursor_x = content_x + cursor_col;
+                    let cursor_y = content_y + cursor_row;
+                    // Get the current cell at cursor position
+                    if let Some(current_cell) = buffer.get(cursor_x, cursor_y) {
+                        let cell = cursor_cell(current_cell, render_cursor.shape, cursor_color);
+                        buffer.set(cursor_x, cursor_y, cell);
+                    }
+                }
+            }
+        }
+
+        if is_dense {
+            let placeholder = Cell::new_unchecked(
+                DENSE_PLACEHOLDER,
+                theme.window_content_fg,
+                theme.window_content_bg,
+            );
+            for row in 0..content_height {
+                for col in 0..content_width {
+                    buffer.set(content_x + col, content_y + row, placeholder);
                 }
             }
+            buffer.add_dense_region(DenseRegion {
+                rect: (content_x, content_y, content_width, content_height),
+                density: self.content_density,
+                cells: dense_cells,
+            });
         }
     }
 
@@ -528,9 +1011,11 @@ impl TerminalWindow {
 
         let scrollback_len = grid.scrollback_len();
 
-        // Only show scrollbar if there's scrollback content
-        if scrollback_len == 0 {
-            return;
+        // Auto shows the scrollbar only once there's scrollback content
+        match self.window.scrollbar_mode {
+            ScrollbarMode::Never => return,
+            ScrollbarMode::Auto if scrollback_len == 0 => return,
+            _ => {}
         }
 
         let scrollbar_x = self.window.x + self.window.width - 2; // Inner char of 2-char right border
@@ -540,7 +1025,8 @@ impl TerminalWindow {
         let visible_rows = grid.rows();
         let total_lines = scrollback_len + visible_rows;
         let (thumb_start, thumb_end) = if total_lines <= visible_rows {
-            (0, 0)
+            // Nothing to scroll (scrollbar forced on): the thumb fills the track
+            (track_start, track_end)
         } else {
             let track_height = (track_end - track_start) as usize;
             let thumb_size = ((visible_rows as f64 / total_lines as f64) * track_height as f64)
@@ -604,8 +1090,30 @@ impl TerminalWindow {
     pub fn set_focused(&mut self, focused: bool) {
         if self.window.is_focused != focused {
             self.window.is_focused = focused;
+            if focused {
+                self.bell = false;
+            }
             // Invalidate render cache since cursor visibility changes with focus
             self.invalidate_render_cache();
+            self.report_focus_change(focused);
+        }
+    }
+
+    /// Send a focus in/out event (CSI I / CSI O) if the app enabled focus reporting (?1004)
+    fn report_focus_change(&mut self, focused: bool) {
+        let enabled = self.grid_arc().lock().unwrap().focus_event_mode;
+        if !enabled || self.input_locked {
+            return;
+        }
+        let seq: &[u8] = if focused { b"\x1b[I" } else { b"\x1b[O" };
+        match &mut self.mode {
+            EmulatorMode::Local(emu) => {
+                let _ = emu.write_input(seq);
+            }
+            EmulatorMode::Remote { .. } => {
+                // Buffer bytes for WindowManager to forward to daemon
+                self.pending_remote_bytes.extend_from_slice(seq);
+            }
         }
     }
 
@@ -633,15 +1141,31 @@ impl TerminalWindow {
         self.window.is_in_close_button(x, y)
     }
 
-    /// Check if window has unsaved work (user input or non-shell process running)
-    /// Ignores shell processes and common shell helpers
-    pub fn is_dirty(&self) -> bool {
-        // Check if user has typed anything (after initial 1 second grace period)
-        if self.has_user_input {
-            return true;
-        }
+    /// Check if closing the window should ask first, given which kinds of unsaved work
+    /// (typed input, a running process) the policy cares about
+    pub fn needs_close_confirmation(&self, policy: CloseConfirmPolicy) -> bool {
+        close_needs_confirmation(policy, self.has_typed_input(), || {
+            self.has_running_process()
+        })
+    }
+
+    /// Check if the foreground process is one of the `protected_process_names` (an ssh or
+    /// database session, an editor), which always asks before closing
+    pub fn runs_protected_process(&self, protected: &[String]) -> bool {
+        !protected.is_empty()
+            && self
+                .get_foreground_process_name()
+                .is_some_and(|name| is_protected_process(&name, protected))
+    }
+
+    /// Check if the user has typed anything (after the initial 1 second grace period)
+    pub fn has_typed_input(&self) -> bool {
+        self.has_user_input
+    }
 
-        // Check if there's a non-shell process running
+    /// Check if a non-shell process is running in the foreground
+    /// Ignores shell processes and common shell helpers
+    pub fn has_running_process(&self) -> bool {
         if let Some(process_name) = self.get_foreground_process_name() {
             // List of shell processes and common shell-related tools to ignore
             let ignore_list = [
@@ -700,7 +1224,7 @@ impl TerminalWindow {
         // Calculate content area for centering the dialog
         let content_x = self.window.x + 2;
         let content_y = self.window.y + 1;
-        let content_width = self.window.width.saturating_sub(4);
+        let content_width = self.window.content_width();
         let content_height = self.window.height.saturating_sub(2);
 
         self.pending_close_confirmation = Some(CloseConfirmation::new(
@@ -765,7 +1289,7 @@ impl TerminalWindow {
 
     /// Check if a point is on the scrollbar
     pub fn is_point_on_scrollbar(&self, x: u16, y: u16) -> bool {
-        if self.window.is_minimized {
+        if self.window.is_minimized || self.window.scrollbar_mode == ScrollbarMode::Never {
             return false;
         }
         let scrollbar_x = self.window.x + self.window.width - 2; // Inner char of 2-char right border
@@ -820,7 +1344,7 @@ impl TerminalWindow {
     fn screen_to_grid_pos(&self, screen_x: u16, screen_y: u16) -> Option<Position> {
         let content_x = self.window.x + 2; // After 2-char left border
         let content_y = self.window.y + 1; // After title bar
-        let content_width = self.window.width.saturating_sub(4); // -2 left, -2 right
+        let content_width = self.window.content_width();
         let content_height = self.window.height.saturating_sub(2); // -1 title, -1 bottom
 
         // Check if coordinates are within content area
@@ -832,8 +1356,10 @@ impl TerminalWindow {
             return None;
         }
 
-        let col = screen_x - content_x;
-        let row = screen_y - content_y;
+        // Dense windows map each screen cell to the top-left grid cell it covers
+        let (density_x, density_y) = self.content_density;
+        let col = (screen_x - content_x) * density_x;
+        let row = (screen_y - content_y) * density_y;
 
         Some(Position::new(col, row))
     }
@@ -860,9 +1386,7 @@ impl TerminalWindow {
         let abs_row = absolute_row as usize;
         if abs_row < scrollback_len {
             // Position is in scrollback buffer
-            grid.get_scrollback_line(abs_row)
-                .and_then(|line| line.get(col as usize))
-                .map(|cell| cell.c)
+            grid.get_scrollback_char(abs_row, col as usize)
         } else {
             // Position is in visible grid
             let visible_row = abs_row - scrollback_len;
@@ -870,11 +1394,26 @@ impl TerminalWindow {
         }
     }
 
+    /// Cell count of the line at an absolute row (scrollback lines keep their own width)
+    fn line_width_at_absolute(
+        grid: &MutexGuard<'_, TerminalGrid>,
+        absolute_row: u16,
+        scrollback_len: usize,
+    ) -> u16 {
+        let abs_row = absolute_row as usize;
+        let width = if abs_row < scrollback_len {
+            grid.get_scrollback_width(abs_row).unwrap_or(0)
+        } else {
+            grid.cols()
+        };
+        width.min(u16::MAX as usize) as u16
+    }
+
     /// Check if a screen position is above, below, or inside the content area
     pub fn get_mouse_content_position(&self, screen_x: u16, screen_y: u16) -> MouseContentPosition {
         let content_x = self.window.x + 2; // After 2-char left border
         let content_y = self.window.y + 1; // After title bar
-        let content_width = self.window.width.saturating_sub(4); // -2 left, -2 right
+        let content_width = self.window.content_width();
         let content_height = self.window.height.saturating_sub(2); // -1 title, -1 bottom
 
         // Check if within horizontal bounds (within window width)
@@ -902,10 +1441,8 @@ impl TerminalWindow {
             g.scrollback_len()
         };
 
+        let (content_width, content_height) = self.grid_size();
         if let Some(selection) = &mut self.selection {
-            let content_height = self.window.height.saturating_sub(2);
-            let content_width = self.window.width.saturating_sub(4);
-
             let (viewport_row, col) = match direction {
                 AutoScrollDirection::Up => (0, 0), // Top-left for scrolling up
                 AutoScrollDirection::Down => (
@@ -923,6 +1460,7 @@ impl TerminalWindow {
 
     /// Start a new selection (uses absolute coordinates for scroll-aware selection)
     pub fn start_selection(&mut self, screen_x: u16, screen_y: u16, selection_type: SelectionType) {
+        self.sync_selection_with_scrollback();
         if let Some(pos) = self.screen_to_grid_pos(screen_x, screen_y) {
             // Convert viewport row to absolute row
             let grid = self.grid_arc();
@@ -976,14 +1514,15 @@ impl TerminalWindow {
     }
 
     /// Expand selection to word boundaries (handles absolute coordinates)
-    pub fn expand_selection_to_word(&mut self) {
+    /// `word_chars` are the characters besides letters and digits that belong to words
+    pub fn expand_selection_to_word(&mut self, word_chars: &str) {
         // Get grid before borrowing selection to avoid borrow conflict
         let grid_arc = self.grid_arc();
         let grid = grid_arc.lock().unwrap();
         let scrollback_len = grid.scrollback_len();
 
         if let Some(selection) = &mut self.selection {
-            selection.expand_to_word(|pos| {
+            selection.expand_to_word(word_chars, |pos| {
                 // Selection uses absolute coordinates, so use absolute-aware cell access
                 Self::get_cell_at_absolute(&grid, pos.col, pos.row, scrollback_len)
             });
@@ -992,16 +1531,16 @@ impl TerminalWindow {
 
     /// Expand selection to line
     pub fn expand_selection_to_line(&mut self) {
+        let (content_width, _) = self.grid_size();
         if let Some(selection) = &mut self.selection {
-            let content_width = self.window.width.saturating_sub(4); // -2 left, -2 right
             selection.expand_to_line(content_width);
         }
     }
 
     /// Select all content in the terminal (uses absolute coordinates)
     pub fn select_all(&mut self) {
-        let content_width = self.window.width.saturating_sub(4); // -2 left, -2 right
-        let content_height = self.window.height.saturating_sub(2); // -1 title, -1 bottom border
+        self.sync_selection_with_scrollback();
+        let (content_width, content_height) = self.grid_size();
 
         // Get scrollback length for absolute coordinate calculation
         let grid = self.grid_arc();
@@ -1064,9 +1603,19 @@ impl TerminalWindow {
             }
             _ => {
                 // Linear selection (character, word, line)
+                // Lines are read to their full length rather than the content width, so
+                // text beyond the viewport (scrollback from a wider window) is copied too.
+                // A selection reaching the right edge takes the rest of its last line.
+                let (content_width, _) = self.grid_size();
+                let last_col = if end.col + 1 >= content_width {
+                    Self::line_width_at_absolute(&grid, end.row, scrollback_len).max(end.col + 1)
+                        - 1
+                } else {
+                    end.col
+                };
                 if start.row == end.row {
                     // Single line
-                    for col in start.col..=end.col {
+                    for col in start.col..=last_col {
                         if let Some(c) =
                             Self::get_cell_at_absolute(&grid, col, start.row, scrollback_len)
                         {
@@ -1076,8 +1625,8 @@ impl TerminalWindow {
                 } else {
                     // Multiple lines
                     // First line (from start.col to end of line)
-                    let content_width = self.window.width.saturating_sub(4); // -2 left, -2 right
-                    for col in start.col..content_width {
+                    let width = Self::line_width_at_absolute(&grid, start.row, scrollback_len);
+                    for col in start.col..width {
                         if let Some(c) =
                             Self::get_cell_at_absolute(&grid, col, start.row, scrollback_len)
                         {
@@ -1088,7 +1637,8 @@ impl TerminalWindow {
 
                     // Middle lines (full lines)
                     for row in (start.row + 1)..end.row {
-                        for col in 0..content_width {
+                        let width = Self::line_width_at_absolute(&grid, row, scrollback_len);
+                        for col in 0..width {
                             if let Some(c) =
                                 Self::get_cell_at_absolute(&grid, col, row, scrollback_len)
                             {
@@ -1099,7 +1649,7 @@ impl TerminalWindow {
                     }
 
                     // Last line (from start to end.col)
-                    for col in 0..=end.col {
+                    for col in 0..=last_col {
                         if let Some(c) =
                             Self::get_cell_at_absolute(&grid, col, end.row, scrollback_len)
                         {
@@ -1121,13 +1671,22 @@ impl TerminalWindow {
 
     /// Paste text to terminal (with bracketed paste mode support)
     /// In Remote mode, this is a no-op (WindowManager routes via daemon)
-    pub fn paste_text(&mut self, text: &str) -> std::io::Result<()> {
+    pub fn paste_text(&mut self, text: &str, transform: PasteTransform) -> std::io::Result<()> {
         match &mut self.mode {
-            EmulatorMode::Local(emu) => emu.send_paste(text),
+            EmulatorMode::Local(emu) => emu.send_paste(text, transform),
             EmulatorMode::Remote { .. } => Ok(()),
         }
     }
 
+    /// Whether the shell is reading hidden input such as a password (never for Remote windows,
+    /// whose PTY lives in the daemon)
+    pub fn is_password_input(&self) -> bool {
+        match &self.mode {
+            EmulatorMode::Local(emu) => emu.is_password_input(),
+            EmulatorMode::Remote { .. } => false,
+        }
+    }
+
     /// Check if there's an active selection
     pub fn has_selection(&self) -> bool {
         self.selection.is_some()
@@ -1138,7 +1697,7 @@ impl TerminalWindow {
     pub fn get_content_bounds(&self) -> (u16, u16, u16, u16) {
         let content_x = self.window.x + 2; // After 2-char left border
         let content_y = self.window.y + 1; // After title bar
-        let content_width = self.window.width.saturating_sub(4); // -2 left, -2 right
+        let content_width = self.window.content_width();
         let content_height = self.window.height.saturating_sub(2); // -1 title, -1 bottom
         (content_x, content_y, content_width, content_height)
     }
@@ -1149,17 +1708,23 @@ impl TerminalWindow {
     }
 
     /// Extract terminal content for session persistence
+    /// Returns (scrollback, visible lines, cursor); scrollback keeps the last `max_scrollback` lines
+    /// and `trim_blanks` leaves out trailing blank cells
     pub fn get_terminal_content(
         &self,
+        max_scrollback: usize,
+        trim_blanks: bool,
     ) -> (
+        Vec<crate::app::session::SerializableTerminalLine>,
         Vec<crate::app::session::SerializableTerminalLine>,
         crate::app::session::SerializableCursor,
     ) {
         match &self.mode {
-            EmulatorMode::Local(emu) => emu.get_terminal_content(),
+            EmulatorMode::Local(emu) => emu.get_terminal_content(max_scrollback, trim_blanks),
             EmulatorMode::Remote { .. } => {
                 // Remote mode: return empty content (daemon owns the data)
                 (
+                    Vec::new(),
                     Vec::new(),
                     crate::app::session::SerializableCursor {
                         x: 0,
@@ -1172,14 +1737,16 @@ impl TerminalWindow {
         }
     }
 
-    /// Restore terminal content from session
+    /// Restore terminal content from session (scrollback first, then the visible screen)
     pub fn restore_terminal_content(
         &mut self,
+        scrollback: Vec<crate::app::session::SerializableTerminalLine>,
         lines: Vec<crate::app::session::SerializableTerminalLine>,
         cursor: &crate::app::session::SerializableCursor,
+        trim_blanks: bool,
     ) {
         if let EmulatorMode::Local(emu) = &mut self.mode {
-            emu.restore_terminal_content(lines, cursor);
+            emu.restore_terminal_content(scrollback, lines, cursor, trim_blanks);
         }
     }
 
@@ -1191,24 +1758,102 @@ impl TerminalWindow {
         }
     }
 
-    /// Get the cached foreground process name, updating cache every 500ms
-    /// This avoids spawning ps processes every frame (60fps = 60 times/second)
-    fn get_foreground_process_name_cached(&mut self) -> Option<String> {
+    /// Send a signal to the foreground process group of a local PTY
+    /// (remote windows are signalled by the daemon, see `WindowManager::signal_focused`)
+    pub fn signal_foreground(&self, signal: ForegroundSignal) -> std::io::Result<()> {
+        match &self.mode {
+            EmulatorMode::Local(emu) => emu.signal_foreground(signal),
+            EmulatorMode::Remote { .. } => Err(std::io::Error::other(
+                "remote window: signal must go through the daemon",
+            )),
+        }
+    }
+
+    /// SIGKILL the processes of a local PTY (remote windows are killed by the daemon,
+    /// see `WindowManager::force_close_window`)
+    pub fn force_kill(&mut self) {
+        if let EmulatorMode::Local(emu) = &mut self.mode {
+            emu.force_kill();
+        }
+    }
+
+    /// Command the window was launched with (None for a plain shell)
+    pub fn launch_command(&self) -> Option<&str> {
+        self.launch_command.as_deref()
+    }
+
+    /// Record the command a remote window was launched with
+    #[cfg_attr(not(unix), allow(dead_code))]
+    pub fn set_launch_command(&mut self, command: Option<String>) {
+        self.launch_command = command;
+    }
+
+    /// Current directory of the shell: the last OSC 7 report, else the process
+    /// working directory (local PTYs on Linux only)
+    pub fn working_directory(&self) -> Option<String> {
+        let reported = self
+            .grid_arc()
+            .lock()
+            .unwrap()
+            .working_directory()
+            .map(str::to_string);
+        reported.or_else(|| match &self.mode {
+            EmulatorMode::Local(emu) => emu.get_working_directory(),
+            EmulatorMode::Remote { .. } => None,
+        })
+    }
+
+    /// Get the cached foreground process name (refreshed by process_output)
+    fn get_foreground_process_name_cached(&self) -> Option<String> {
+        self.cached_process_name.clone()
+    }
+
+    /// Refresh the cached foreground process name every 500ms and note a command that finished
+    /// This avoids spawning ps processes every frame (60fps = 60 times/second), and runs
+    /// with the PTY output so hidden and minimized windows notice finished commands too
+    fn update_foreground_process(&mut self) {
         use std::time::Duration;
 
         // Update cache every 500ms (2 times per second instead of 60)
         let elapsed = self.process_name_last_update.elapsed();
         if elapsed >= Duration::from_millis(500) || self.cached_process_name.is_none() {
+            let previous = self.cached_process_name.take();
             self.cached_process_name = self.get_foreground_process_name();
             self.process_name_last_update = Instant::now();
+
+            // A command returning to the shell means it has finished
+            if let (Some(prev), Some(current)) = (previous, &self.cached_process_name) {
+                if !is_shell_process(&prev) && is_shell_process(current) {
+                    self.finished_command = Some(prev);
+                }
+            }
         }
+    }
 
-        self.cached_process_name.clone()
+    /// Whether the application rang the bell since the last call, marking the window
+    /// when it is in the background and `mark` is set (cleared on focus)
+    pub fn take_bell(&mut self, mark: bool) -> bool {
+        let grid = self.grid_arc();
+        let rang = grid.lock().unwrap().take_bell();
+        if rang && mark && !self.window.is_focused {
+            self.bell = true;
+        }
+        rang
+    }
+
+    /// Whether a bell rang in the background since the window was last focused
+    pub fn has_bell(&self) -> bool {
+        self.bell
+    }
+
+    /// Take the name of a foreground command that finished since the last call
+    pub fn take_finished_command(&mut self) -> Option<String> {
+        self.finished_command.take()
     }
 
     /// Get the dynamic title including the running process name (with caching)
     /// Format: "Terminal N [ > process ]" where > is a running indicator
-    fn get_dynamic_title_cached(&mut self) -> String {
+    fn get_dynamic_title_cached(&self) -> String {
         if let Some(process_name) = self.get_foreground_process_name_cached() {
             // Use '>' as an ASCII-compatible "running" indicator with spacing
             format!("{} [ > {} ]", self.window.title, process_name)
@@ -1217,6 +1862,19 @@ impl TerminalWindow {
         }
     }
 
+    /// Last known foreground program, the shell itself at the prompt (no process lookup)
+    pub fn cached_process_name(&self) -> Option<&str> {
+        self.cached_process_name.as_deref()
+    }
+
+    /// Get the title with the last known running process (no process lookup)
+    pub fn display_title(&self) -> String {
+        match &self.cached_process_name {
+            Some(process_name) => format!("{} [ > {} ]", self.window.title, process_name),
+            None => self.window.title.clone(),
+        }
+    }
+
     /// Get the dynamic title including the running process name
     /// Format: "Terminal N [ > process ]" where > is a running indicator
     #[allow(dead_code)]
@@ -1249,6 +1907,13 @@ impl TerminalWindow {
         }
     }
 
+    /// Whether the application switched to the alternate screen (no scrollback there)
+    pub fn is_alt_screen_active(&self) -> bool {
+        let grid = self.grid_arc();
+        let grid = grid.lock().unwrap();
+        grid.is_alt_screen()
+    }
+
     /// Get application cursor keys mode state (DECCKM)
     pub fn get_application_cursor_keys(&self) -> bool {
         let grid = self.grid_arc();
@@ -1256,12 +1921,32 @@ impl TerminalWindow {
         grid.application_cursor_keys
     }
 
+    /// Get Line Feed/New Line Mode state (LNM, mode 20): Enter sends CR LF
+    pub fn get_newline_mode(&self) -> bool {
+        let grid = self.grid_arc();
+        let grid = grid.lock().unwrap();
+        grid.lnm_mode
+    }
+
     /// Handle keyboard input for close confirmation dialog
+    /// `enter` selects what Enter does; 'y' always closes and 'n' always cancels
     /// Returns Some(true) if should close, Some(false) if canceled, None if not handled
-    pub fn handle_close_confirmation_key(&mut self, key: KeyEvent) -> Option<bool> {
+    pub fn handle_close_confirmation_key(
+        &mut self,
+        key: KeyEvent,
+        enter: CloseConfirmEnter,
+    ) -> Option<bool> {
         let confirmation = self.pending_close_confirmation.as_mut()?;
 
         match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                self.pending_close_confirmation = None;
+                Some(true) // Close
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                self.pending_close_confirmation = None;
+                Some(false) // Cancel
+            }
             KeyCode::Left | KeyCode::Char('h') => {
                 confirmation.prompt.select_previous_button();
                 None // Just update UI, don't trigger action
@@ -1271,9 +1956,16 @@ impl TerminalWindow {
                 None // Just update UI, don't trigger action
             }
             KeyCode::Enter => {
-                let action = confirmation.prompt.get_selected_action();
+                let should_close = match enter {
+                    CloseConfirmEnter::Selected => matches!(
+                        confirmation.prompt.get_selected_action(),
+                        Some(PromptAction::Confirm)
+                    ),
+                    CloseConfirmEnter::Cancel => false,
+                    CloseConfirmEnter::Ignore => return None,
+                };
                 self.pending_close_confirmation = None;
-                Some(matches!(action, Some(PromptAction::Confirm)))
+                Some(should_close)
             }
             KeyCode::Esc | KeyCode::Char('q') => {
                 self.pending_close_confirmation = None;
@@ -1336,9 +2028,8 @@ impl TerminalWindow {
         // Content area starts after 2-char left border and title bar
         let content_x = self.window.x + 2;
         let content_y = self.window.y + 1;
-        // Content area ends before 2-char right border and bottom border
-        // But we need to account for the scrollbar on the right side
-        let content_width = self.window.width.saturating_sub(5); // -2 left, -2 right, -1 scrollbar
+        // Content area ends before the right border (and scrollbar) and bottom border
+        let content_width = self.window.content_width();
         let content_height = self.window.height.saturating_sub(2); // -1 title, -1 bottom
 
         // Check if point is within content area
@@ -1347,8 +2038,9 @@ impl TerminalWindow {
             && screen_y >= content_y
             && screen_y < content_y + content_height
         {
-            let term_x = screen_x - content_x;
-            let term_y = screen_y - content_y;
+            let (density_x, density_y) = self.content_density;
+            let term_x = (screen_x - content_x) * density_x;
+            let term_y = (screen_y - content_y) * density_y;
             Some((term_x, term_y))
         } else {
             None
@@ -1449,6 +2141,8 @@ impl TerminalWindow {
     }
 
     /// Handle a mouse event from the parent application
+    /// With `force`, clicks and wheel are reported even if the app didn't enable tracking
+    /// (motion is still only sent when the app asked for it)
     /// Returns true if the event was consumed (forwarded to terminal)
     pub fn handle_mouse_for_terminal(
         &mut self,
@@ -1456,9 +2150,11 @@ impl TerminalWindow {
         screen_y: u16,
         button: u8,
         action: u8,
+        force: bool,
     ) -> bool {
         // Get all mouse tracking state with a single mutex lock
-        let state = self.get_mouse_tracking_state();
+        let mut state = self.get_mouse_tracking_state();
+        state.tracking_enabled |= force;
 
         // Check if this terminal wants mouse events
         if !state.tracking_enabled {
@@ -1470,6 +2166,11 @@ impl TerminalWindow {
             return false;
         }
 
+        // A locked window swallows the report instead of leaving it to selection
+        if self.input_locked {
+            return true;
+        }
+
         // Convert to terminal coordinates
         if let Some((term_x, term_y)) = self.screen_to_terminal_coords(screen_x, screen_y) {
             // Send the event using pre-fetched state (no additional lock needed)
@@ -1512,8 +2213,71 @@ fn write_u16_to_buf(buf: &mut [u8], value: u16) -> usize {
     len
 }
 
+/// Build the cursor cell drawn over `current` for the given cursor shape
+/// With a cursor `color` the cursor is painted in it over the cell's background instead of
+/// inverting the cell, so it stays visible whatever the content colors are
+fn cursor_cell(current: &Cell, shape: CursorShape, color: Option<Color>) -> Cell {
+    if let Some(color) = color {
+        return match shape {
+            CursorShape::Block if current.character == ' ' || current.character == '\0' => {
+                Cell::new('█', color, current.bg_color)
+            }
+            CursorShape::Block => {
+                // Black or white text, whichever reads on the cursor color
+                let text = if calculate_luminance(&color) > 0.5 {
+                    Color::Black
+                } else {
+                    Color::White
+                };
+                Cell::new(current.character, text, color)
+            }
+            CursorShape::Underline => Cell::new('_', color, current.bg_color),
+            CursorShape::Bar => Cell::new('│', color, current.bg_color),
+        };
+    }
+    match shape {
+        CursorShape::Block => {
+            // For block cursor, show as inverted colors
+            if current.character == ' ' || current.character == '\0' {
+                // For empty space, show a solid block using the foreground color
+                // This makes the cursor visible as a colored block
+                Cell::new('█', current.fg_color, current.bg_color)
+            } else {
+                // For text, invert the colors (swap fg and bg)
+                Cell::new(
+                    current.character,
+                    current.bg_color, // Use bg as fg (inverted)
+                    current.fg_color, // Use fg as bg (inverted)
+                )
+            }
+        }
+        CursorShape::Underline => {
+            // For underline cursor, show underscore in foreground color
+            Cell::new('_', current.fg_color, current.bg_color)
+        }
+        CursorShape::Bar => {
+            // For bar cursor, show vertical bar in foreground color
+            Cell::new('│', current.fg_color, current.bg_color)
+        }
+    }
+}
+
+/// An empty cell on the default background, which a ghosted window leaves see-through
+fn is_blank_cell(cell: &TerminalCell) -> bool {
+    cell.c == ' '
+        && cell.bg == TermColor::Default
+        && !cell.attrs.reverse
+        && cell.combining.is_none()
+}
+
 /// Convert a terminal cell to a video buffer cell
-fn convert_terminal_cell(term_cell: &TerminalCell, theme: &Theme, tint_terminal: bool) -> Cell {
+/// `minimum_contrast` (0.0 = off) adjusts unreadable foregrounds after tinting
+fn convert_terminal_cell(
+    term_cell: &TerminalCell,
+    theme: &Theme,
+    tint_strength: f32,
+    minimum_contrast: f32,
+) -> Cell {
     let mut fg = convert_fg_color(&term_cell.fg);
     let mut bg = convert_bg_color(&term_cell.bg);
 
@@ -1522,10 +2286,14 @@ fn convert_terminal_cell(term_cell: &TerminalCell, theme: &Theme, tint_terminal:
         std::mem::swap(&mut fg, &mut bg);
     }
 
-    // Apply theme-based tinting if enabled
-    if tint_terminal {
-        fg = apply_theme_tint(fg, theme, true);
-        bg = apply_theme_tint(bg, theme, false);
+    // Apply theme-based tinting if enabled (0.0 = off, 1.0 = full theme mapping)
+    if tint_strength > 0.0 {
+        fg = blend_toward_tint(fg, apply_theme_tint(fg, theme, true), tint_strength);
+        bg = blend_toward_tint(bg, apply_theme_tint(bg, theme, false), tint_strength);
+    }
+
+    if minimum_contrast > 1.0 {
+        fg = apply_minimum_contrast(fg, bg, minimum_contrast);
     }
 
     // Use unchecked cell creation - theme tints are pre-designed with contrast in mind
@@ -1695,6 +2463,76 @@ fn apply_theme_tint(color: Color, theme: &Theme, is_foreground: bool) -> Color {
     }
 }
 
+/// Move a color toward its theme-tinted version by `strength` (0.0 = original, 1.0 = tinted)
+/// RGB colors are interpolated; named and indexed colors snap to the nearest named color
+fn blend_toward_tint(original: Color, tinted: Color, strength: f32) -> Color {
+    if strength >= 1.0 || original == tinted {
+        return tinted;
+    }
+
+    let blended = blend_with_theme_color(tinted, original, strength);
+    match original {
+        Color::Rgb { .. } => blended,
+        _ => nearest_named_color(blended),
+    }
+}
+
+/// Lighten or darken `fg` until it reaches `min_ratio` against `bg`
+/// Named and indexed colors stay named when the nearest named color is still readable
+fn apply_minimum_contrast(fg: Color, bg: Color, min_ratio: f32) -> Color {
+    if calculate_contrast_ratio(&fg, &bg) >= min_ratio {
+        return fg;
+    }
+
+    let adjusted = adjust_fg_for_contrast(fg, bg, min_ratio);
+    match fg {
+        Color::Rgb { .. } => adjusted,
+        _ => {
+            let named = nearest_named_color(adjusted);
+            if calculate_contrast_ratio(&named, &bg) >= min_ratio {
+                named
+            } else {
+                adjusted
+            }
+        }
+    }
+}
+
+/// Find the named ANSI color closest to the given color
+fn nearest_named_color(color: Color) -> Color {
+    const NAMED: [Color; 16] = [
+        Color::Black,
+        Color::DarkGrey,
+        Color::Red,
+        Color::DarkRed,
+        Color::Green,
+        Color::DarkGreen,
+        Color::Yellow,
+        Color::DarkYellow,
+        Color::Blue,
+        Color::DarkBlue,
+        Color::Magenta,
+        Color::DarkMagenta,
+        Color::Cyan,
+        Color::DarkCyan,
+        Color::White,
+        Color::Grey,
+    ];
+
+    let (r, g, b) = color_to_rgb(color);
+    let distance = |candidate: &Color| {
+        let (cr, cg, cb) = color_to_rgb(*candidate);
+        let dr = r as i32 - cr as i32;
+        let dg = g as i32 - cg as i32;
+        let db = b as i32 - cb as i32;
+        dr * dr + dg * dg + db * db
+    };
+    NAMED
+        .into_iter()
+        .min_by_key(distance)
+        .unwrap_or(Color::Black)
+}
+
 /// Darken a color by a factor (0.0 = black, 1.0 = original)
 fn darken_color(color: Color, factor: f32) -> Color {
     match color {
@@ -1788,3 +2626,426 @@ fn ansi_to_rgb(idx: u8) -> (u8, u8, u8) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Remote-mode window (no PTY) with a 20x4 content area
+    fn test_window() -> TerminalWindow {
+        TerminalWindow::new_remote(1, 0, 0, 24, 6, "Terminal 1".to_string(), 1)
+    }
+
+    fn feed_lines(win: &mut TerminalWindow, range: std::ops::Range<usize>) {
+        for i in range {
+            win.feed_remote_output(format!("line {}\r\n", i).as_bytes());
+        }
+    }
+
+    /// Select the first two content rows (screen rows 1-2, content starts at x=2)
+    fn select_top_rows(win: &mut TerminalWindow) {
+        win.start_selection(2, 1, SelectionType::Character);
+        win.update_selection(8, 2);
+        win.complete_selection();
+    }
+
+    #[test]
+    fn test_selection_text_unchanged_after_scrolling() {
+        let mut win = test_window();
+        feed_lines(&mut win, 0..20);
+        win.scroll_up(5);
+        select_top_rows(&mut win);
+        let copied = win.get_selected_text().unwrap();
+
+        win.scroll_up(3);
+        assert_eq!(win.get_selected_text().unwrap(), copied);
+        win.scroll_to_bottom();
+        assert_eq!(win.get_selected_text().unwrap(), copied);
+    }
+
+    #[test]
+    fn test_selection_text_unchanged_after_new_output() {
+        let mut win = test_window();
+        feed_lines(&mut win, 0..10);
+        select_top_rows(&mut win);
+        let copied = win.get_selected_text().unwrap();
+
+        // Selected lines scroll out of the viewport into the scrollback
+        feed_lines(&mut win, 10..30);
+        assert_eq!(win.get_selected_text().unwrap(), copied);
+    }
+
+    #[test]
+    fn test_selection_follows_content_when_scrollback_is_trimmed() {
+        let mut win = test_window();
+        feed_lines(&mut win, 0..1010);
+        win.scroll_up(20);
+        select_top_rows(&mut win);
+        let copied = win.get_selected_text().unwrap();
+
+        // Scrollback is full: every new line discards the oldest one
+        feed_lines(&mut win, 1010..1015);
+        assert_eq!(win.get_selected_text().unwrap(), copied);
+    }
+
+    #[test]
+    fn test_selection_copies_lines_wider_than_window() {
+        let mut win = TerminalWindow::new_remote(1, 0, 0, 44, 6, "Terminal 1".to_string(), 1);
+        let long = "0123456789abcdefghijklmnopqrstuvwxyzABCD";
+        win.feed_remote_output(format!("{}\r\nnext\r\n", long).as_bytes());
+        feed_lines(&mut win, 0..4);
+        // Shrink to a 20-column content area, the long line is now in the scrollback
+        win.resize(24, 6).unwrap();
+        win.scroll_up(6);
+
+        // Drag from the start of the long line to the right edge, and across both lines
+        win.start_selection(2, 1, SelectionType::Character);
+        win.update_selection(21, 1);
+        win.complete_selection();
+        assert_eq!(win.get_selected_text().unwrap(), long);
+
+        win.start_selection(2, 1, SelectionType::Character);
+        win.update_selection(5, 2);
+        win.complete_selection();
+        assert_eq!(win.get_selected_text().unwrap(), format!("{}\nnext", long));
+    }
+
+    #[test]
+    fn test_bell_marks_background_window_until_focused() {
+        let mut win = test_window();
+        win.set_focused(true);
+        win.feed_remote_output(b"\x07");
+        assert!(win.take_bell(true));
+        assert!(!win.has_bell());
+
+        win.set_focused(false);
+        // Without marking, the bell is only reported
+        win.feed_remote_output(b"done\x07");
+        assert!(win.take_bell(false));
+        assert!(!win.has_bell());
+        win.feed_remote_output(b"\x07");
+        assert!(win.take_bell(true));
+        assert!(win.has_bell());
+        assert!(!win.take_bell(true));
+        assert!(win.has_bell());
+
+        win.set_focused(true);
+        assert!(!win.has_bell());
+    }
+
+    #[test]
+    fn test_blend_toward_tint() {
+        let original = Color::Rgb { r: 200, g: 0, b: 0 };
+        let tinted = Color::Rgb { r: 0, g: 0, b: 100 };
+        assert_eq!(blend_toward_tint(original, tinted, 1.0), tinted);
+        assert_eq!(
+            blend_toward_tint(original, tinted, 0.5),
+            Color::Rgb {
+                r: 100,
+                g: 0,
+                b: 50
+            }
+        );
+
+        // Named colors stay named: a weak tint keeps the original color
+        assert_eq!(
+            blend_toward_tint(Color::DarkRed, Color::Blue, 0.1),
+            Color::DarkRed
+        );
+        assert_eq!(
+            blend_toward_tint(Color::DarkRed, Color::Blue, 0.9),
+            Color::Blue
+        );
+    }
+
+    #[test]
+    fn test_tint_strength_zero_keeps_native_colors() {
+        let theme = Theme::classic();
+        let term_cell = TerminalCell {
+            bg: TermColor::Named(NamedColor::Red),
+            ..Default::default()
+        };
+        let cell = convert_terminal_cell(&term_cell, &theme, 0.0, 0.0);
+        assert_eq!(cell.bg_color, Color::DarkRed);
+    }
+
+    #[test]
+    fn test_minimum_contrast_lightens_dim_text() {
+        let theme = Theme::classic();
+        // SGR 90 (bright black) on the default background
+        let term_cell = TerminalCell {
+            fg: TermColor::Named(NamedColor::BrightBlack),
+            ..Default::default()
+        };
+
+        let off = convert_terminal_cell(&term_cell, &theme, 0.0, 0.0);
+        assert_eq!(off.fg_color, Color::DarkGrey);
+
+        let on = convert_terminal_cell(&term_cell, &theme, 0.0, 4.5);
+        assert!(calculate_contrast_ratio(&on.fg_color, &on.bg_color) >= 4.5);
+        assert_eq!(on.bg_color, off.bg_color);
+    }
+
+    #[test]
+    fn test_focus_events_only_when_enabled() {
+        let mut win = test_window();
+        win.set_focused(true);
+        win.set_focused(false);
+        assert_eq!(win.drain_pending_remote_bytes(), None);
+
+        win.feed_remote_output(b"\x1b[?1004h");
+        win.set_focused(true);
+        win.set_focused(true); // No change, no event
+        win.set_focused(false);
+        assert_eq!(
+            win.drain_pending_remote_bytes(),
+            Some(b"\x1b[I\x1b[O".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_output_marks_window_active() {
+        let mut win = test_window();
+        assert!(!win.is_active());
+        win.feed_remote_output(b"building...\r\n");
+        assert!(win.is_active());
+    }
+
+    #[test]
+    fn test_erase_saved_lines_returns_view_to_bottom() {
+        let mut win = test_window();
+        feed_lines(&mut win, 0..20);
+        win.scroll_up(12);
+        assert_eq!(win.get_scroll_offset(), 12);
+
+        win.feed_remote_output(b"\x1b[3J");
+        assert_eq!(win.get_scroll_offset(), 0);
+    }
+
+    #[test]
+    fn test_input_lock_drops_mouse_and_focus_reports() {
+        let mut win = test_window();
+        win.feed_remote_output(b"\x1b[?1000h\x1b[?1004h");
+        win.toggle_input_lock();
+        assert!(win.handle_mouse_for_terminal(3, 2, 0, 0, false));
+        win.set_focused(true);
+        win.set_focused(false);
+        assert_eq!(win.drain_pending_remote_bytes(), None);
+
+        win.toggle_input_lock();
+        assert!(win.handle_mouse_for_terminal(3, 2, 0, 0, false));
+        assert!(win.drain_pending_remote_bytes().is_some());
+    }
+
+    #[test]
+    fn test_scroll_indicator_in_title_bar() {
+        let mut win = test_window();
+        feed_lines(&mut win, 0..20);
+        let theme = Theme::classic();
+        let title = |win: &mut TerminalWindow, charset: &Charset| {
+            let mut buffer = VideoBuffer::new(30, 10);
+            win.render(&mut buffer, charset, &theme, 0.0, false);
+            // Right border starts at x = 22
+            (0..22)
+                .map(|x| buffer.get(x, 0).unwrap().character)
+                .collect::<String>()
+        };
+
+        assert!(!title(&mut win, &Charset::unicode()).contains('\u{2191}'));
+        win.scroll_up(12);
+        assert!(title(&mut win, &Charset::unicode()).ends_with(" [\u{2191} 12] "));
+        assert!(title(&mut win, &Charset::ascii()).ends_with(" [^12] "));
+
+        win.set_scroll_indicator(false);
+        assert!(!title(&mut win, &Charset::ascii()).contains("^12"));
+        win.set_scroll_indicator(true);
+        win.scroll_to_bottom();
+        assert!(!title(&mut win, &Charset::ascii()).contains("^"));
+    }
+
+    #[test]
+    fn test_forced_cursor_ignores_hidden_cursor_when_focused() {
+        let mut win = test_window();
+        win.set_focused(true);
+        win.feed_remote_output(b"ab\x1b[?25l");
+        let theme = Theme::classic();
+        // Cursor after "ab": content starts at (2, 1)
+        let cursor_cell = |win: &mut TerminalWindow| {
+            let mut buffer = VideoBuffer::new(30, 10);
+            win.render(&mut buffer, &Charset::unicode(), &theme, 0.0, false);
+            *buffer.get(4, 1).unwrap()
+        };
+        let hidden = cursor_cell(&mut win);
+
+        win.set_force_cursor_visible(true);
+        assert_ne!(cursor_cell(&mut win), hidden);
+        // Unfocused windows follow the application again
+        win.set_focused(false);
+        assert_eq!(cursor_cell(&mut win), hidden);
+    }
+
+    #[test]
+    fn test_ghost_window_shows_desktop_in_blank_cells() {
+        let mut win = test_window();
+        win.set_ghost_unfocused(true);
+        win.feed_remote_output(b"ab \x1b[44m \x1b[0m");
+        let charset = Charset::unicode();
+        let theme = Theme::classic();
+        let render = |win: &mut TerminalWindow| {
+            let mut buffer = VideoBuffer::new(30, 10);
+            win.render(&mut buffer, &charset, &theme, 0.0, false);
+            // Content starts at (2, 1)
+            (2..6)
+                .map(|x| *buffer.get(x, 1).unwrap())
+                .collect::<Vec<Cell>>()
+        };
+
+        let cells = render(&mut win);
+        assert_eq!(cells[0].character, 'a');
+        assert_eq!(cells[2].character, charset.background);
+        assert_eq!(cells[2].bg_color, theme.desktop_bg);
+        // A colored space is not blank
+        assert_eq!(cells[3].character, ' ');
+
+        win.set_focused(true);
+        assert_eq!(render(&mut win)[2].character, ' ');
+    }
+
+    #[test]
+    fn test_dense_content_renders_into_region() {
+        let mut win = test_window();
+        win.set_content_density((1, 2)).unwrap();
+        // 20x4 content cells hold a 20x8 terminal grid
+        assert_eq!(win.take_pending_resize(), Some((20, 8)));
+        assert_eq!(win.screen_to_terminal_coords(3, 2), Some((1, 2)));
+
+        win.feed_remote_output(b"top\r\nsecond");
+        let theme = Theme::classic();
+        let mut buffer = VideoBuffer::new(30, 10);
+        let grid_arc = win.grid_arc();
+        let grid = grid_arc.lock().unwrap();
+        win.render_terminal_content_with_grid(&mut buffer, &theme, 0.0, None, &grid);
+
+        let region = &buffer.dense_regions()[0];
+        assert_eq!(region.rect, (2, 1, 20, 4));
+        assert_eq!(region.get(0, 0).unwrap().character, 't');
+        assert_eq!(region.get(0, 1).unwrap().character, 's');
+        assert_eq!(buffer.get(2, 1).unwrap().character, DENSE_PLACEHOLDER);
+    }
+
+    #[test]
+    fn test_close_confirmation_policy() {
+        let policy = |if_process_running, if_typed| CloseConfirmPolicy {
+            if_process_running,
+            if_typed,
+        };
+        for (typed, running) in [(false, false), (true, false), (false, true), (true, true)] {
+            // Both checks on (default): any unsaved work asks
+            assert_eq!(
+                close_needs_confirmation(CloseConfirmPolicy::default(), typed, || running),
+                typed || running
+            );
+            assert_eq!(
+                close_needs_confirmation(policy(true, false), typed, || running),
+                running
+            );
+            assert_eq!(
+                close_needs_confirmation(policy(false, true), typed, || running),
+                typed
+            );
+            assert!(!close_needs_confirmation(
+                policy(false, false),
+                typed,
+                || running
+            ));
+        }
+        // The process lookup is skipped when typed input already decides
+        assert!(close_needs_confirmation(policy(true, true), true, || {
+            panic!("process looked up")
+        }));
+
+        let mut win = test_window();
+        assert!(!win.needs_close_confirmation(CloseConfirmPolicy::default()));
+        win.has_user_input = true;
+        assert!(win.needs_close_confirmation(CloseConfirmPolicy::default()));
+        assert!(!win.needs_close_confirmation(policy(true, false)));
+    }
+
+    #[test]
+    fn test_protected_process_names() {
+        let protected = vec!["ssh".to_string(), "PSQL".to_string(), "-zsh".to_string()];
+        assert!(is_protected_process("ssh", &protected));
+        assert!(is_protected_process("psql", &protected));
+        assert!(is_protected_process("/usr/bin/SSH", &protected));
+        // Login shells show up with a leading '-'
+        assert!(is_protected_process("-zsh", &protected));
+        assert!(is_protected_process("zsh", &protected));
+        assert!(!is_protected_process("sshd", &protected));
+        assert!(!is_protected_process("vim", &protected));
+        assert!(!is_protected_process("", &["".to_string()]));
+        assert!(!test_window().runs_protected_process(&protected));
+    }
+
+    #[test]
+    fn test_close_confirmation_keys() {
+        let key = |code| KeyEvent::from(code);
+        let mut win = test_window();
+
+        // Default: Enter activates the preselected Cancel button
+        win.show_close_confirmation();
+        let enter = key(KeyCode::Enter);
+        assert_eq!(
+            win.handle_close_confirmation_key(enter, CloseConfirmEnter::Selected),
+            Some(false)
+        );
+
+        // Tab moves to Close, but Enter still cancels in Cancel mode
+        win.show_close_confirmation();
+        assert_eq!(
+            win.handle_close_confirmation_key(key(KeyCode::Tab), CloseConfirmEnter::Cancel),
+            None
+        );
+        assert_eq!(
+            win.handle_close_confirmation_key(enter, CloseConfirmEnter::Cancel),
+            Some(false)
+        );
+
+        // Ignore mode: Enter does nothing, 'y' closes
+        win.show_close_confirmation();
+        assert_eq!(
+            win.handle_close_confirmation_key(enter, CloseConfirmEnter::Ignore),
+            None
+        );
+        assert!(win.has_close_confirmation());
+        assert_eq!(
+            win.handle_close_confirmation_key(key(KeyCode::Char('y')), CloseConfirmEnter::Ignore),
+            Some(true)
+        );
+
+        win.show_close_confirmation();
+        assert_eq!(
+            win.handle_close_confirmation_key(key(KeyCode::Char('n')), CloseConfirmEnter::Selected),
+            Some(false)
+        );
+        assert!(!win.has_close_confirmation());
+    }
+
+    #[test]
+    fn test_scrollbar_never_reclaims_its_column() {
+        let mut win = test_window();
+        assert_eq!(win.grid_size(), (20, 4));
+        assert!(win.is_point_on_scrollbar(22, 2));
+        assert_eq!(win.screen_to_terminal_coords(22, 2), None);
+
+        win.set_scrollbar_mode(ScrollbarMode::Never);
+        assert_eq!(win.grid_size(), (21, 4));
+        assert_eq!(win.pending_resize, Some((21, 4)));
+        assert!(!win.is_point_on_scrollbar(22, 2));
+        assert_eq!(win.screen_to_terminal_coords(22, 2), Some((20, 1)));
+
+        // Always keeps the Auto layout
+        win.set_scrollbar_mode(ScrollbarMode::Always);
+        assert_eq!(win.grid_size(), (20, 4));
+        assert!(win.is_point_on_scrollbar(22, 2));
+    }
+}